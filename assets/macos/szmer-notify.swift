@@ -0,0 +1,95 @@
+// Notification helper built into Szmer.app by `szmer bundle install`
+//
+// Posts one notification through UNUserNotificationCenter, in the thread
+// and category of its kind, so reminders, escalations and summaries group
+// apart in Notification Center. notify-rust can't set either.
+//
+// Usage: szmer-notify <kind> <wait seconds> <title> <body> <sound> [<action>=<label>...]
+//
+// The actions are the buttons of reminder notifications. Prints "sent"
+// once the notification is up, then the action clicked within the wait,
+// "default" for a click on the notification itself.
+
+import Foundation
+import UserNotifications
+
+let kinds = ["reminder", "escalation", "summary"]
+let arguments = CommandLine.arguments
+
+func fail(_ message: String) -> Never {
+    FileHandle.standardError.write(Data((message + "\n").utf8))
+    exit(1)
+}
+
+guard arguments.count >= 6, kinds.contains(arguments[1]), let wait = Double(arguments[2]) else {
+    fail("usage: szmer-notify <kind> <wait seconds> <title> <body> <sound> [<action>=<label>...]")
+}
+let kind = arguments[1]
+let actions = arguments.dropFirst(6).compactMap { argument -> UNNotificationAction? in
+    let parts = argument.split(separator: "=", maxSplits: 1)
+    guard parts.count == 2 else { return nil }
+    return UNNotificationAction(identifier: String(parts[0]), title: String(parts[1]), options: [])
+}
+
+final class Delegate: NSObject, UNUserNotificationCenterDelegate {
+    // Show it even though the app counts as being in front while it runs
+    func userNotificationCenter(
+        _ center: UNUserNotificationCenter,
+        willPresent notification: UNNotification,
+        withCompletionHandler completionHandler: @escaping (UNNotificationPresentationOptions) -> Void
+    ) {
+        completionHandler([.banner, .list, .sound])
+    }
+
+    func userNotificationCenter(
+        _ center: UNUserNotificationCenter,
+        didReceive response: UNNotificationResponse,
+        withCompletionHandler completionHandler: @escaping () -> Void
+    ) {
+        let action = response.actionIdentifier
+        print(action == UNNotificationDefaultActionIdentifier ? "default" : action)
+        fflush(stdout)
+        completionHandler()
+        exit(0)
+    }
+}
+
+let center = UNUserNotificationCenter.current()
+let delegate = Delegate()
+center.delegate = delegate
+// Only reminders have buttons, the other kinds are for reading
+center.setNotificationCategories(Set(kinds.map { name in
+    UNNotificationCategory(
+        identifier: "szmer.\(name)",
+        actions: name == "reminder" ? actions : [],
+        intentIdentifiers: [],
+        options: []
+    )
+}))
+
+center.requestAuthorization(options: [.alert, .sound]) { granted, error in
+    guard granted else {
+        fail("Notifications are turned off for Szmer: \(error?.localizedDescription ?? "not allowed")")
+    }
+
+    let content = UNMutableNotificationContent()
+    content.title = arguments[3]
+    content.body = arguments[4]
+    content.threadIdentifier = "szmer.\(kind)"
+    content.categoryIdentifier = "szmer.\(kind)"
+    if !arguments[5].isEmpty {
+        content.sound = UNNotificationSound(named: UNNotificationSoundName(arguments[5]))
+    }
+
+    let request = UNNotificationRequest(identifier: UUID().uuidString, content: content, trigger: nil)
+    center.add(request) { error in
+        if let error = error {
+            fail(error.localizedDescription)
+        }
+        print("sent")
+        fflush(stdout)
+    }
+}
+
+DispatchQueue.main.asyncAfter(deadline: .now() + wait) { exit(0) }
+RunLoop.main.run()
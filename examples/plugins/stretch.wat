@@ -0,0 +1,71 @@
+;; Example szmer WASM plugin, a gate and a tip provider
+;;
+;; szmer compiles the text format itself, so this file installs as is:
+;;
+;;   szmer plugin install examples/plugins/stretch.wat
+;;   szmer config set plugins.enabled stretch
+;;
+;; The gate holds reminders back when the interval is under 5 minutes, the
+;; tip provider always suggests the same stretch.
+(module
+  (memory (export "memory") 1)
+
+  ;; Replies live at the start of memory, requests are allocated after them
+  ;; describe
+  (data (i32.const 0) "{\"roles\":[\"gate\",\"tip\"],\"description\":\"Stretching tips, and no reminders more often than every 5 minutes\"}")
+  ;; closed
+  (data (i32.const 106) "{\"open\":false,\"detail\":\"reminders every few minutes leave no time to work\"}")
+  ;; open
+  (data (i32.const 181) "{\"open\":true,\"detail\":\"the interval leaves time to work\"}")
+  ;; tip
+  (data (i32.const 238) "{\"tip\":\"Stand up, lace your fingers and reach for the ceiling for ten seconds.\"}")
+
+  (global $next (mut i32) (i32.const 1024))
+
+  ;; A bump allocator is enough, every call gets a fresh instance
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (local.get $ptr) (local.get $len)))
+    (local.get $ptr))
+
+  ;; Where a reply is, as szmer expects it: ptr << 32 | len
+  (func $reply (param $ptr i32) (param $len i32) (result i64)
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+      (i64.extend_i32_u (local.get $len))))
+
+  ;; The number after the first colon of {"interval_seconds":3600}
+  (func $interval (param $ptr i32) (param $len i32) (result i32)
+    (local $end i32)
+    (local $char i32)
+    (local $digits i32)
+    (local $seconds i32)
+    (local.set $end (i32.add (local.get $ptr) (local.get $len)))
+    (block $done
+      (loop $scan
+        (br_if $done (i32.ge_u (local.get $ptr) (local.get $end)))
+        (local.set $char (i32.load8_u (local.get $ptr)))
+        (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+        (if (local.get $digits)
+          (then
+            (br_if $done (i32.ge_u (i32.sub (local.get $char) (i32.const 48)) (i32.const 10)))
+            (local.set $seconds
+              (i32.add
+                (i32.mul (local.get $seconds) (i32.const 10))
+                (i32.sub (local.get $char) (i32.const 48)))))
+          (else
+            (local.set $digits (i32.eq (local.get $char) (i32.const 58)))))
+        (br $scan)))
+    (local.get $seconds))
+
+  (func (export "describe") (param i32 i32) (result i64)
+    (call $reply (i32.const 0) (i32.const 106)))
+
+  (func (export "gate") (param $ptr i32) (param $len i32) (result i64)
+    (if (result i64) (i32.lt_u (call $interval (local.get $ptr) (local.get $len)) (i32.const 300))
+      (then (call $reply (i32.const 106) (i32.const 75)))
+      (else (call $reply (i32.const 181) (i32.const 57)))))
+
+  (func (export "tip") (param i32 i32) (result i64)
+    (call $reply (i32.const 238) (i32.const 80))))
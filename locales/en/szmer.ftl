@@ -0,0 +1,198 @@
+# English messages, the fallback for every other locale.
+# Keep ids in sync with the other locales/<locale>/szmer.ftl files.
+
+## Reminders
+
+notification-summary = Time for a Break!
+notification-open-link = Open
+notification-snooze = Snooze 5m
+notification-skip = Skip
+notification-taken = Break taken
+overlay-postpone = Postpone
+overlay-break-in = Break in { $time }
+follow-up-summary = That wasn't a break
+follow-up-body = You went straight back to work. A few minutes away from the screen still count.
+
+# Titles and tip phrasings for notification.tone, picked at random
+tone-gentle-summary-1 = Maybe a little break?
+tone-gentle-summary-2 = Time to be kind to yourself
+tone-gentle-summary-3 = A gentle reminder to pause
+tone-gentle-tip-1 = Whenever you're ready: { $tip }
+tone-gentle-tip-2 = { $tip } Take your time.
+tone-gentle-tip-3 = No rush. { $tip }
+tone-drill-sergeant-summary-1 = BREAK TIME, SOLDIER!
+tone-drill-sergeant-summary-2 = ON YOUR FEET!
+tone-drill-sergeant-summary-3 = HANDS OFF THE KEYBOARD!
+tone-drill-sergeant-tip-1 = { $tip } That's an order!
+tone-drill-sergeant-tip-2 = Listen up! { $tip }
+tone-drill-sergeant-tip-3 = { $tip } Move, move, move!
+tone-humorous-summary-1 = Your chair needs a break from you
+tone-humorous-summary-2 = Error 418: human needs a break
+tone-humorous-summary-3 = The keyboard filed a complaint
+tone-humorous-tip-1 = { $tip } Your future self says thanks.
+tone-humorous-tip-2 = Plot twist: { $tip }
+tone-humorous-tip-3 = { $tip } The bug will still be there when you're back.
+
+tip-walk = Stand up and walk around your office for 2-3 minutes.
+tip-water = Drink a glass of water to stay hydrated.
+tip-shoulder-rolls = Do 10 shoulder rolls to release tension.
+tip-look-far = Look at something far away for 20 seconds to rest your eyes.
+tip-breathe = Take 5 deep breaths to reduce stress and increase oxygen flow.
+tip-stretch-arms = Stretch your arms above your head and hold for 10 seconds.
+tip-neck = Do 10 neck stretches - gently tilt your head side to side.
+tip-squats = Stand up and do 10 squats to get your blood flowing.
+tip-wrists = Roll your wrists and ankles to improve circulation.
+tip-snack = Walk to get a healthy snack or refill your water bottle.
+tip-twist = Stretch your back by doing a seated twist in your chair.
+tip-shake = Stand up and shake out your arms and legs.
+tip-face = Close your eyes and relax your facial muscles for 30 seconds.
+tip-fresh-air = Open a window or step outside for fresh air.
+tip-temples = Massage your temples to relieve tension headaches.
+tip-posture = Straighten your posture and adjust your chair height.
+tip-screen-height = Sit back in your chair and bring the top of your screen to eye level.
+tip-tea = Make yourself a cup of tea and drink it slowly.
+tip-arm-circles = Do 10 arm circles forward and backward.
+
+## Durations
+
+duration-seconds = { $count ->
+    [one] { $count } second
+   *[other] { $count } seconds
+}
+duration-minutes = { $count ->
+    [one] { $count } minute
+   *[other] { $count } minutes
+}
+duration-hours = { $count ->
+    [one] { $count } hour
+   *[other] { $count } hours
+}
+duration-days = { $count ->
+    [one] { $count } day
+   *[other] { $count } days
+}
+duration-less-than-a-minute = less than a minute
+time-until = in { $duration }
+time-every = every { $duration }
+time-very-soon = very soon
+
+## Install
+
+install-interval-set = ✓ Break interval set to { $interval }
+install-reminders-every = You will receive break reminders { $every }.
+
+## Status
+
+status-title = Szmer Status
+status-daemon = Daemon
+status-scheduler = Scheduler
+status-uptime = Uptime
+status-state = State
+status-interval = Interval
+status-days = Days
+status-holidays = Holidays
+status-pomodoro = Pomodoro
+status-work-hours = Work hours
+status-clock = Clock
+status-profiles = Profiles
+status-sound = Sound
+status-status = Status
+status-next-break = Next break
+status-gates = Gates
+status-today = Today
+status-compliance = Breaks taken
+status-running = ✓ Running
+status-not-installed = ✗ Not installed
+status-not-running = ⚠ Installed but not running
+status-check-failed = ✗ Error checking status
+status-scheduler-duplicate = ⚠ Also installed, reminders will be sent twice
+status-keep-daemon-hint = Run 'szmer uninstall' to keep only the daemon.
+status-install-hint = Run 'szmer install' to set up break reminders.
+status-default-sound = (system default)
+status-paused = ⏸ Paused
+status-paused-until = ⏸ Paused until { $time }, resumes { $time_until }
+status-active = ▶ Active
+status-snoozed = ⏰ Snoozed until { $time }
+status-days-off-today = { $days } (no reminders today)
+status-holidays-today = { $name } (no reminders today)
+status-holidays-unknown = { $region } (no calendar yet, run 'szmer holidays update')
+status-pomodoro-work = Work session { $session } of { $sessions }, break at { $time }
+status-pomodoro-break = Short break until { $time }
+status-pomodoro-long-break = Long break until { $time }
+status-work-hours-inside = { $hours } (working now)
+status-work-hours-outside = { $hours } (reminders resume { $time_until }, { $time })
+status-clock-wall = Wall clock (follows clock changes)
+status-clock-monotonic = Monotonic (ignores clock changes)
+status-clock-monotonic-unsupported = Monotonic, but { $scheduler } only has wall-clock timers
+status-next-break-at = { $time_until } ({ $time })
+status-next-break-unknown = Every { $interval } (no notification sent yet)
+status-today-breaks = { $count ->
+    [one] { $count } break
+   *[other] { $count } breaks
+}
+status-today-commits = { $count ->
+    [one] { $count } commit
+   *[other] { $count } commits
+}
+
+status-compliance-today = { $taken } of { $reminders } today ({ $rate }%)
+status-compliance-week = { $taken } of { $reminders } this week ({ $rate }%)
+
+## Stop and resume
+
+stop-stopped = ✓ Break reminders stopped.
+stop-stopped-until = ✓ Break reminders stopped until { $time }, they resume on their own.
+stop-already = Break reminders are already stopped.
+resume-hint = Run 'szmer resume' to start receiving reminders again.
+resume-resumed = ✓ Break reminders resumed.
+resume-already = Break reminders are already running.
+resume-time-off = Reminders were off for { $duration }, since { $since }.
+resume-last-break = Last break taken: { $time }
+resume-keep-interval = Keep reminders coming { $every }?
+
+## Pomodoro
+
+pomodoro-started = ✓ Pomodoro started: work for { $work }, then take a break for { $short }. Every { $every }th break lasts { $long }.
+pomodoro-stopped = ✓ Pomodoro stopped, reminders come { $every } again.
+pomodoro-not-running = Pomodoro isn't running.
+pomodoro-break-summary = Break time: { $duration }
+pomodoro-long-break-summary = Long break: { $duration }
+pomodoro-work-summary = Break over, back to work
+pomodoro-work-body = Next break at { $time }.
+
+## Break activities
+
+break-left = { $time } left
+breathing-inhale = Breathe in
+breathing-hold = Hold
+breathing-exhale = Breathe out
+
+## Errors
+
+error-not-installed = Break reminder is not installed
+error-already-installed = Break reminder is already installed
+error-already-installed-at = Break reminder is already installed at { $path }
+error-daemon-not-running = szmer daemon is not running
+error-daemon-already-running = Another szmer daemon is already running
+error-daemon-already-running-pid = Another szmer daemon is already running (PID { $pid })
+error-scheduler-conflict-daemon = szmer daemon is running (PID { $pid }), installing the scheduler would send every reminder twice
+error-scheduler-conflict-scheduler = The break reminder scheduler is installed, the daemon would send every reminder twice
+error-unsupported = { $feature } is not supported on this platform
+error-unknown-config-key = Unknown configuration key: '{ $key }'
+error-invalid-config = { $path } has { $count ->
+    [one] a value
+   *[other] { $count } values
+} szmer can't use:
+error-notification-skipped = Skipping notification: { $reason }
+
+hint-label = hint
+hint-install = Run 'szmer install' first.
+hint-reinstall = Run 'szmer uninstall' first if you want to reinstall.
+hint-start-daemon = Start it with 'szmer daemon'.
+hint-daemon-running = Check it with 'szmer status' or stop it before starting another.
+hint-stop-daemon = Stop the daemon before installing the scheduler.
+hint-uninstall-scheduler = Run 'szmer uninstall' first to use the daemon instead of the scheduler.
+hint-config-keys = Run 'szmer config keys' to list the keys and what they take.
+hint-config-show = Run 'szmer config show' to see the current settings.
+hint-config-validate = Fix each value as suggested, then check the file with 'szmer config validate'.
+hint-doctor = Run 'szmer doctor' to check the environment szmer runs in.
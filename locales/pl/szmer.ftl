@@ -0,0 +1,224 @@
+# Polskie komunikaty. Brakujące wpisy są brane z locales/en/szmer.ftl.
+
+## Przypomnienia
+
+notification-summary = Czas na przerwę!
+notification-open-link = Otwórz
+notification-snooze = Odłóż o 5 min
+notification-skip = Pomiń
+notification-taken = Przerwa zrobiona
+overlay-postpone = Odłóż
+overlay-break-in = Przerwa za { $time }
+follow-up-summary = To nie była przerwa
+follow-up-body = Praca ruszyła od razu po przypomnieniu. Kilka minut z dala od ekranu wciąż się liczy.
+
+tone-gentle-summary-1 = Może krótka przerwa?
+tone-gentle-summary-2 = Czas zadbać o siebie
+tone-gentle-summary-3 = Delikatne przypomnienie o przerwie
+tone-gentle-tip-1 = Kiedy zechcesz: { $tip }
+tone-gentle-tip-2 = { $tip } Bez pośpiechu.
+tone-gentle-tip-3 = Spokojnie. { $tip }
+tone-drill-sergeant-summary-1 = PRZERWA, ŻOŁNIERZU!
+tone-drill-sergeant-summary-2 = WSTAWAĆ!
+tone-drill-sergeant-summary-3 = RĘCE PRECZ OD KLAWIATURY!
+tone-drill-sergeant-tip-1 = { $tip } To rozkaz!
+tone-drill-sergeant-tip-2 = Słuchać! { $tip }
+tone-drill-sergeant-tip-3 = { $tip } Ruszać się!
+tone-humorous-summary-1 = Twoje krzesło potrzebuje od ciebie przerwy
+tone-humorous-summary-2 = Błąd 418: człowiek potrzebuje przerwy
+tone-humorous-summary-3 = Klawiatura złożyła skargę
+tone-humorous-tip-1 = { $tip } Twoje przyszłe ja dziękuje.
+tone-humorous-tip-2 = Zwrot akcji: { $tip }
+tone-humorous-tip-3 = { $tip } Błąd poczeka, aż wrócisz.
+
+tip-walk = Wstań i przejdź się po biurze przez 2-3 minuty.
+tip-water = Wypij szklankę wody, żeby się nawodnić.
+tip-shoulder-rolls = Zrób 10 krążeń barkami, żeby rozluźnić napięcie.
+tip-look-far = Popatrz przez 20 sekund na coś odległego, żeby dać odpocząć oczom.
+tip-breathe = Weź 5 głębokich oddechów, żeby zmniejszyć stres i dotlenić organizm.
+tip-stretch-arms = Wyciągnij ręce nad głowę i przytrzymaj przez 10 sekund.
+tip-neck = Zrób 10 skłonów szyi - delikatnie przechylaj głowę na boki.
+tip-squats = Wstań i zrób 10 przysiadów, żeby pobudzić krążenie.
+tip-wrists = Pokręć nadgarstkami i kostkami, żeby poprawić krążenie.
+tip-snack = Przejdź się po zdrową przekąskę albo dolej wody do butelki.
+tip-twist = Rozciągnij plecy, robiąc skręt tułowia na krześle.
+tip-shake = Wstań i potrząśnij rękami i nogami.
+tip-face = Zamknij oczy i rozluźnij mięśnie twarzy na 30 sekund.
+tip-fresh-air = Otwórz okno albo wyjdź na świeże powietrze.
+tip-temples = Pomasuj skronie, żeby złagodzić napięciowy ból głowy.
+tip-posture = Wyprostuj się i dopasuj wysokość krzesła.
+tip-screen-height = Oprzyj się o oparcie krzesła i ustaw ekran tak, żeby jego górna krawędź była na wysokości oczu.
+tip-tea = Zrób sobie herbatę i wypij ją powoli.
+tip-arm-circles = Zrób 10 krążeń ramion do przodu i do tyłu.
+
+## Czas
+
+# $case to "accusative" po "za" i "co": 1 minuta, ale za 1 minutę
+duration-seconds = { $case ->
+    [accusative] { $count ->
+        [one] { $count } sekundę
+        [few] { $count } sekundy
+       *[many] { $count } sekund
+    }
+   *[nominative] { $count ->
+        [one] { $count } sekunda
+        [few] { $count } sekundy
+       *[many] { $count } sekund
+    }
+}
+duration-minutes = { $case ->
+    [accusative] { $count ->
+        [one] { $count } minutę
+        [few] { $count } minuty
+       *[many] { $count } minut
+    }
+   *[nominative] { $count ->
+        [one] { $count } minuta
+        [few] { $count } minuty
+       *[many] { $count } minut
+    }
+}
+duration-hours = { $case ->
+    [accusative] { $count ->
+        [one] { $count } godzinę
+        [few] { $count } godziny
+       *[many] { $count } godzin
+    }
+   *[nominative] { $count ->
+        [one] { $count } godzina
+        [few] { $count } godziny
+       *[many] { $count } godzin
+    }
+}
+duration-days = { $count ->
+    [one] { $count } dzień
+   *[other] { $count } dni
+}
+duration-less-than-a-minute = mniej niż minuta
+time-until = za { $duration }
+time-every = co { $duration }
+time-very-soon = za chwilę
+
+## Instalacja
+
+install-interval-set = ✓ Odstęp między przerwami: { $interval }
+install-reminders-every = Przypomnienia o przerwie będą się pojawiać { $every }.
+
+## Status
+
+status-title = Status szmera
+status-daemon = Demon
+status-scheduler = Harmonogram
+status-uptime = Działa od
+status-state = Stan
+status-interval = Odstęp
+status-days = Dni
+status-holidays = Święta
+status-pomodoro = Pomodoro
+status-work-hours = Godz. pracy
+status-clock = Zegar
+status-profiles = Profile
+status-sound = Dźwięk
+status-status = Status
+status-next-break = Przerwa
+status-gates = Bramki
+status-today = Dzisiaj
+status-compliance = Zrobione
+status-running = ✓ Działa
+status-not-installed = ✗ Nie zainstalowano
+status-not-running = ⚠ Zainstalowany, ale nie działa
+status-check-failed = ✗ Błąd sprawdzania stanu
+status-scheduler-duplicate = ⚠ Też zainstalowany, przypomnienia przyjdą podwójnie
+status-keep-daemon-hint = Uruchom 'szmer uninstall', żeby zostawić tylko demona.
+status-install-hint = Uruchom 'szmer install', żeby skonfigurować przypomnienia o przerwach.
+status-default-sound = (domyślny systemowy)
+status-paused = ⏸ Wstrzymane
+status-paused-until = ⏸ Wstrzymane do { $time }, wznowienie { $time_until }
+status-active = ▶ Aktywne
+status-snoozed = ⏰ Odłożone do { $time }
+status-days-off-today = { $days } (dziś bez przypomnień)
+status-holidays-today = { $name } (dziś bez przypomnień)
+status-holidays-unknown = { $region } (brak kalendarza, uruchom 'szmer holidays update')
+status-pomodoro-work = Sesja pracy { $session } z { $sessions }, przerwa o { $time }
+status-pomodoro-break = Krótka przerwa do { $time }
+status-pomodoro-long-break = Długa przerwa do { $time }
+status-work-hours-inside = { $hours } (teraz w pracy)
+status-work-hours-outside = { $hours } (przypomnienia wrócą { $time_until }, { $time })
+status-clock-wall = Ścienny (idzie za zmianami zegara)
+status-clock-monotonic = Monotoniczny (pomija zmiany zegara)
+status-clock-monotonic-unsupported = Monotoniczny, ale { $scheduler } ma tylko zegar ścienny
+status-next-break-at = { $time_until } ({ $time })
+status-next-break-unknown = Co { $interval } (jeszcze nie wysłano przypomnienia)
+status-today-breaks = { $count ->
+    [one] { $count } przerwa
+    [few] { $count } przerwy
+   *[many] { $count } przerw
+}
+status-today-commits = { $count ->
+    [one] { $count } commit
+    [few] { $count } commity
+   *[many] { $count } commitów
+}
+
+status-compliance-today = { $taken } z { $reminders } dziś ({ $rate }%)
+status-compliance-week = { $taken } z { $reminders } w tym tygodniu ({ $rate }%)
+
+## Wstrzymywanie i wznawianie
+
+stop-stopped = ✓ Przypomnienia o przerwach wstrzymane.
+stop-stopped-until = ✓ Przypomnienia o przerwach wstrzymane do { $time }, wrócą same.
+stop-already = Przypomnienia o przerwach są już wstrzymane.
+resume-hint = Uruchom 'szmer resume', żeby znów dostawać przypomnienia.
+resume-resumed = ✓ Przypomnienia o przerwach wznowione.
+resume-already = Przypomnienia o przerwach już działają.
+resume-time-off = Przypomnienia były wyłączone przez { $duration }, od { $since }.
+resume-last-break = Ostatnia przerwa: { $time }
+resume-keep-interval = Zostawić przypomnienia { $every }?
+
+## Pomodoro
+
+pomodoro-started = ✓ Pomodoro włączone: { $work } pracy, potem { $short } przerwy. Co { $every }. przerwa trwa { $long }.
+pomodoro-stopped = ✓ Pomodoro wyłączone, przypomnienia znów przychodzą { $every }.
+pomodoro-not-running = Pomodoro nie jest włączone.
+pomodoro-break-summary = Czas na przerwę: { $duration }
+pomodoro-long-break-summary = Długa przerwa: { $duration }
+pomodoro-work-summary = Koniec przerwy, wracamy do pracy
+pomodoro-work-body = Następna przerwa o { $time }.
+
+## Aktywności w przerwie
+
+break-left = Zostało { $time }
+breathing-inhale = Wdech
+breathing-hold = Zatrzymanie
+breathing-exhale = Wydech
+
+## Błędy
+
+error-not-installed = Przypominacz o przerwach nie jest zainstalowany
+error-already-installed = Przypominacz o przerwach jest już zainstalowany
+error-already-installed-at = Przypominacz o przerwach jest już zainstalowany w { $path }
+error-daemon-not-running = Demon szmera nie działa
+error-daemon-already-running = Inny demon szmera już działa
+error-daemon-already-running-pid = Inny demon szmera już działa (PID { $pid })
+error-scheduler-conflict-daemon = Demon szmera działa (PID { $pid }), instalacja harmonogramu podwoiłaby każde przypomnienie
+error-scheduler-conflict-scheduler = Harmonogram przypomnień jest zainstalowany, demon podwoiłby każde przypomnienie
+error-unsupported = { $feature } nie jest obsługiwane na tej platformie
+error-unknown-config-key = Nieznany klucz konfiguracji: '{ $key }'
+error-invalid-config = { $path } { $count ->
+    [one] ma wartość, której
+    [few] ma { $count } wartości, których
+   *[many] ma { $count } wartości, których
+} szmer nie może użyć:
+error-notification-skipped = Pomijam przypomnienie: { $reason }
+
+hint-label = wskazówka
+hint-install = Najpierw uruchom 'szmer install'.
+hint-reinstall = Uruchom najpierw 'szmer uninstall', jeśli chcesz zainstalować ponownie.
+hint-start-daemon = Uruchom go poleceniem 'szmer daemon'.
+hint-daemon-running = Sprawdź go poleceniem 'szmer status' albo zatrzymaj przed uruchomieniem kolejnego.
+hint-stop-daemon = Zatrzymaj demona przed instalacją harmonogramu.
+hint-uninstall-scheduler = Uruchom najpierw 'szmer uninstall', żeby używać demona zamiast harmonogramu.
+hint-config-keys = Uruchom 'szmer config keys', żeby zobaczyć dostępne klucze i ich wartości.
+hint-config-show = Uruchom 'szmer config show', żeby zobaczyć bieżące ustawienia.
+hint-config-validate = Popraw każdą wartość zgodnie z podpowiedzią, a potem sprawdź plik przez 'szmer config validate'.
+hint-doctor = Uruchom 'szmer doctor', żeby sprawdzić środowisko, w którym działa szmer.
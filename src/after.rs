@@ -0,0 +1,108 @@
+use crate::clock::Clock;
+use crate::config::Config;
+use crate::control::{self, Request, Response};
+use crate::reminder::{self, Outcome};
+use crate::timestamp;
+use chrono::{DateTime, Local};
+use std::process::Command;
+
+/// Run `command`, then suggest a break if it's been long enough since the last one
+///
+/// The suggestion goes through the daemon when it's running, so its timer
+/// restarts, and otherwise through the same pause and gate checks as
+/// `szmer notify`. Messages go to stderr to keep the command's output
+/// clean. Returns the command's exit code.
+///
+/// # Arguments
+/// * `command` - Program and its arguments
+/// * `min_work_minutes` - Time since the last reminder that warrants a break
+///   (defaults to half the reminder interval)
+pub fn run(
+    clock: &dyn Clock,
+    command: &[String],
+    min_work_minutes: Option<u64>,
+) -> Result<i32, Box<dyn std::error::Error>> {
+    let (program, args) = command.split_first().ok_or("No command given")?;
+
+    let started = clock.now();
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    let code = status.code().unwrap_or(1);
+
+    let config = Config::load()?;
+    let min_work_seconds = min_work_minutes
+        .map(|minutes| minutes * 60)
+        .unwrap_or(config.interval_seconds / 2);
+    let last_reminder = timestamp::get_last_notification()?;
+
+    if !is_break_due(last_reminder, started, clock.now(), min_work_seconds) {
+        return Ok(code);
+    }
+
+    if let Some(response) = control::send(&Request::TriggerBreak)? {
+        match response {
+            Response::Ok { message } | Response::Error { message } => eprintln!("{message}"),
+            Response::Status(_) => {}
+        }
+        return Ok(code);
+    }
+
+    match reminder::remind(&config, None)? {
+        Outcome::Sent => eprintln!("✓ {program} finished, break reminder sent."),
+        Outcome::BackToWork => eprintln!("✓ {program} finished, pomodoro break is over."),
+        Outcome::Paused
+        | Outcome::Snoozed(_)
+        | Outcome::Waiting(_)
+        | Outcome::Away(_)
+        | Outcome::Back(_) => {}
+        Outcome::Skipped(reason) => eprintln!("Not suggesting a break: {reason}"),
+    }
+
+    Ok(code)
+}
+
+/// Whether enough time passed since the last reminder to suggest a break
+///
+/// Without any reminder on record, work is counted from when the command
+/// started.
+fn is_break_due(
+    last_reminder: Option<DateTime<Local>>,
+    started: DateTime<Local>,
+    now: DateTime<Local>,
+    min_work_seconds: u64,
+) -> bool {
+    let since = last_reminder.unwrap_or(started);
+    (now - since).num_seconds() >= min_work_seconds as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_is_break_due() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let last_reminder = clock.now();
+        clock.advance(chrono::Duration::minutes(20));
+        let started = clock.now();
+        clock.advance(chrono::Duration::minutes(15));
+
+        assert!(is_break_due(
+            Some(last_reminder),
+            started,
+            clock.now(),
+            30 * 60
+        ));
+        assert!(!is_break_due(
+            Some(last_reminder),
+            started,
+            clock.now(),
+            40 * 60
+        ));
+        // A long build on its own counts as work
+        assert!(is_break_due(None, started, clock.now(), 15 * 60));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::clock::Clock;
+use crate::config::Config;
+use crate::error::SzmerError;
+use crate::notification::{Kind, NotifierChain, Reminder};
+use crate::timestamp;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+const STATE_FILENAME: &str = "failures.json";
+
+/// Failed runs in a row before szmer asks for attention
+const FAILURES_BEFORE_ALERT: u32 = 3;
+
+/// How often the same trouble is brought up, by notification and on the terminal
+const ALERT_EVERY_HOURS: i64 = 24;
+
+/// Background `szmer notify` runs that failed in a row
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Failures {
+    count: u32,
+    since: Option<DateTime<Local>>,
+    last_error: String,
+    notified_at: Option<DateTime<Local>>,
+    announced_at: Option<DateTime<Local>>,
+}
+
+impl Failures {
+    /// Count a failure, returning whether it's time to notify about them
+    fn fail(&mut self, now: DateTime<Local>, error: String) -> bool {
+        self.count += 1;
+        self.since.get_or_insert(now);
+        self.last_error = error;
+        self.count >= FAILURES_BEFORE_ALERT && is_due(self.notified_at, now)
+    }
+
+    fn summary(&self) -> String {
+        let since = self
+            .since
+            .map(|since| format!(" since {}", since.format("%a %H:%M")))
+            .unwrap_or_default();
+        format!(
+            "Reminders failed {} times in a row{since}: {}",
+            self.count, self.last_error
+        )
+    }
+}
+
+fn is_due(last: Option<DateTime<Local>>, now: DateTime<Local>) -> bool {
+    last.is_none_or(|last| now - last >= Duration::hours(ALERT_EVERY_HOURS))
+}
+
+/// Keep count of how a background `szmer notify` went
+///
+/// Nobody watches scheduled runs, so after a few failures in a row szmer
+/// sends one "needs attention" notification a day. That may fail too when
+/// notifications are what's broken, so `announce` also brings it up on the
+/// terminal. Skipped reminders are neither a failure nor a success.
+pub fn record(clock: &dyn Clock, result: &Result<(), Box<dyn std::error::Error>>) {
+    let outcome = match result {
+        Ok(()) => remove(),
+        Err(e) if is_skipped(e.as_ref()) => return,
+        Err(e) => record_failure(clock.now(), e.to_string()),
+    };
+    if let Err(e) = outcome {
+        eprintln!("Warning: Failed to keep count of failed reminders: {e}");
+    }
+}
+
+fn is_skipped(error: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        error.downcast_ref(),
+        Some(SzmerError::NotificationSkipped { .. })
+    )
+}
+
+fn record_failure(now: DateTime<Local>, error: String) -> Result<(), Box<dyn std::error::Error>> {
+    let mut failures = load();
+    if failures.fail(now, error) {
+        let reminder = Reminder {
+            summary: "szmer needs attention".to_string(),
+            body: format!(
+                "{}. Run 'szmer doctor' to look into it.",
+                failures.summary()
+            ),
+            sound: None,
+            link: None,
+            kind: Kind::Escalation,
+        };
+        // The config may be what's broken
+        let config = Config::load().unwrap_or_default();
+        if NotifierChain::from_config(&config).send(&reminder).is_ok() {
+            failures.notified_at = Some(now);
+        }
+    }
+    save(&failures)
+}
+
+/// Tell the user about failing background runs, at most once a day
+pub fn announce(clock: &dyn Clock) {
+    if !std::io::stderr().is_terminal() {
+        return;
+    }
+    let mut failures = load();
+    let now = clock.now();
+    if failures.count < FAILURES_BEFORE_ALERT || !is_due(failures.announced_at, now) {
+        return;
+    }
+
+    eprintln!("⚠ szmer needs attention. {}", failures.summary());
+    eprintln!("  Run 'szmer doctor' to look into it.\n");
+
+    failures.announced_at = Some(now);
+    let _ = save(&failures);
+}
+
+fn load() -> Failures {
+    state_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(failures: &Failures) -> Result<(), Box<dyn std::error::Error>> {
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(failures)?)?;
+    Ok(())
+}
+
+fn remove() -> Result<(), Box<dyn std::error::Error>> {
+    match fs::remove_file(state_path()?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn state_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(STATE_FILENAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_alert_after_repeated_failures_once_a_day() {
+        let at = |time: &str| MockClock::at(time).now();
+        let mut failures = Failures::default();
+
+        assert!(!failures.fail(at("2025-01-06 09:00:00"), "first".to_string()));
+        assert!(!failures.fail(at("2025-01-06 10:00:00"), "second".to_string()));
+        assert!(failures.fail(at("2025-01-06 11:00:00"), "third".to_string()));
+        assert_eq!(
+            failures.summary(),
+            "Reminders failed 3 times in a row since Mon 09:00: third"
+        );
+
+        failures.notified_at = Some(at("2025-01-06 11:00:00"));
+        assert!(!failures.fail(at("2025-01-06 12:00:00"), "again".to_string()));
+        assert!(failures.fail(at("2025-01-07 11:00:00"), "again".to_string()));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::clock::Clock;
+use crate::command::SystemCommandRunner;
+use crate::idle;
+use crate::timestamp;
+use chrono::{DateTime, Duration, Local};
+use std::fs;
+use std::path::PathBuf;
+
+const STATE_FILENAME: &str = "away_since";
+
+/// Shortest `away.after`, longer than a break so one doesn't count as away
+pub const MIN_AFTER_SECONDS: u64 = 15 * 60;
+
+/// Whether the user is at the machine, as of a reminder check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Presence {
+    /// At the machine, or there's no telling
+    Here,
+    /// No input since then, for longer than `away.after`
+    Away(DateTime<Local>),
+    /// Input again after being away since then
+    Back(DateTime<Local>),
+}
+
+/// Check for the user having left the machine
+///
+/// Going without input for `after_seconds` counts as away. With `remember`
+/// the away state is kept in the cache directory, so the first check with
+/// the user back reports `Back` once and the interval can start over.
+pub fn check(
+    clock: &dyn Clock,
+    runner: &dyn SystemCommandRunner,
+    after_seconds: u64,
+    remember: bool,
+) -> Presence {
+    let Some(idle) = idle::idle_seconds(runner) else {
+        return Presence::Here;
+    };
+    let now = clock.now();
+    let away_since = if remember { load() } else { None };
+    let presence = presence(now, idle, after_seconds, away_since);
+
+    if remember {
+        let saved = match presence {
+            Presence::Away(since) if away_since.is_none() => save(since),
+            Presence::Back(_) => remove(),
+            _ => Ok(()),
+        };
+        if let Err(e) = saved {
+            eprintln!("Warning: Failed to keep the away state: {e}");
+        }
+    }
+    presence
+}
+
+fn presence(
+    now: DateTime<Local>,
+    idle_seconds: u64,
+    after_seconds: u64,
+    away_since: Option<DateTime<Local>>,
+) -> Presence {
+    if idle_seconds >= after_seconds {
+        let last_input = now - Duration::seconds(idle_seconds as i64);
+        Presence::Away(away_since.unwrap_or(last_input))
+    } else {
+        away_since.map_or(Presence::Here, Presence::Back)
+    }
+}
+
+fn load() -> Option<DateTime<Local>> {
+    let content = fs::read_to_string(state_path().ok()?).ok()?;
+    DateTime::from_timestamp(content.trim().parse().ok()?, 0).map(|at| at.with_timezone(&Local))
+}
+
+fn save(since: DateTime<Local>) -> Result<(), Box<dyn std::error::Error>> {
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", since.timestamp()))?;
+    Ok(())
+}
+
+fn remove() -> Result<(), Box<dyn std::error::Error>> {
+    match fs::remove_file(state_path()?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn state_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(STATE_FILENAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_away_after_long_idle_until_input() {
+        let now = MockClock::at("2025-01-06 23:00:00").now();
+        let two_hours = 2 * 60 * 60;
+
+        assert_eq!(presence(now, 600, two_hours, None), Presence::Here);
+        // Away from the last input on
+        assert_eq!(
+            presence(now, 3 * 60 * 60, two_hours, None),
+            Presence::Away(MockClock::at("2025-01-06 20:00:00").now())
+        );
+
+        let since = MockClock::at("2025-01-06 18:00:00").now();
+        assert_eq!(
+            presence(now, 9 * 60 * 60, two_hours, Some(since)),
+            Presence::Away(since)
+        );
+        assert_eq!(
+            presence(now, 30, two_hours, Some(since)),
+            Presence::Back(since)
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::command::{run_checked, ProcessRunner};
+use crate::config::Config;
+use crate::timestamp;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Cache file holding every reminder time, the source of stats and streaks
+const HISTORY_FILE: &str = "last_notification";
+
+/// Cache file holding how reminders were answered, see `szmer respond`
+const ANSWERS_FILE: &str = "break_answers";
+
+/// Bundle the config, plugins, reminder history and cached tips into a tarball
+///
+/// Paths inside the archive are relative to `$HOME`, so it can be restored
+/// on a machine with a different user name. Daemon state, snoozes and
+/// other short-lived files are left out.
+pub fn create(file: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let home = home_dir()?;
+    let members = members(&home)?;
+    if members.is_empty() {
+        return Err("Nothing to back up yet, szmer has no config or history".into());
+    }
+
+    let file = absolute(file)?;
+    let mut args = vec!["-czf", file.to_str().ok_or("Invalid backup path")?, "-C"];
+    args.push(home.to_str().ok_or("Invalid home directory")?);
+    args.extend(members.iter().map(String::as_str));
+    run_checked(&ProcessRunner, "tar", &args, "Failed to create backup")?;
+
+    println!("✓ Backed up {} to {}", members.join(", "), file.display());
+    Ok(())
+}
+
+/// Unpack a backup made by `create` over the current state
+///
+/// The archive is listed first and refused if anything in it would land
+/// outside szmer's own directories. Existing state is only replaced with
+/// `force`.
+pub fn restore(file: &Path, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let home = home_dir()?;
+    let file = file.to_str().ok_or("Invalid backup path")?;
+
+    let listing = run_checked(
+        &ProcessRunner,
+        "tar",
+        &["-tzf", file],
+        "Failed to read backup",
+    )?;
+    check_members(&listing.stdout)?;
+
+    if Config::find_config_file()?.is_some() && !force {
+        return Err(
+            "szmer is already configured here. Pass --force to replace the config and history"
+                .into(),
+        );
+    }
+
+    run_checked(
+        &ProcessRunner,
+        "tar",
+        &[
+            "-xzf",
+            file,
+            "-C",
+            home.to_str().ok_or("Invalid home directory")?,
+        ],
+        "Failed to restore backup",
+    )?;
+
+    println!("✓ Restored config and history from {file}");
+    println!("The scheduler isn't part of the backup; run 'szmer install' if needed.");
+    Ok(())
+}
+
+fn home_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(PathBuf::from(env::var("HOME")?))
+}
+
+/// `path` relative to the current directory made absolute, since tar runs with `-C`
+fn absolute(path: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir()?.join(path)
+    })
+}
+
+/// Paths to archive, relative to `home`, that exist right now
+fn members(home: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+
+    if let Some(config_dir) = Config::get_config_path()?.parent() {
+        paths.push(config_dir.to_path_buf());
+    }
+
+    let cache_dir = timestamp::get_cache_dir()?;
+    paths.push(cache_dir.join(HISTORY_FILE));
+    paths.push(cache_dir.join(ANSWERS_FILE));
+    if let Ok(entries) = fs::read_dir(&cache_dir) {
+        let mut tips: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| is_tips_cache(path))
+            .collect();
+        tips.sort();
+        paths.extend(tips);
+    }
+
+    Ok(paths
+        .iter()
+        .filter(|path| path.exists())
+        .filter_map(|path| path.strip_prefix(home).ok())
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect())
+}
+
+/// Cached tips from a URL source, see `tips::UrlTips`
+fn is_tips_cache(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("tips-") && name.ends_with(".txt"))
+}
+
+/// Reject archives with paths outside szmer's config and cache directories
+fn check_members(listing: &str) -> Result<(), Box<dyn std::error::Error>> {
+    for member in listing
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        let path = Path::new(member);
+        let inside = [".config/szmer", ".cache/szmer"]
+            .iter()
+            .any(|dir| path.starts_with(dir));
+        let escapes = path
+            .components()
+            .any(|component| matches!(component, std::path::Component::ParentDir));
+        if !inside || escapes || path.is_absolute() {
+            return Err(format!("Not a szmer backup: it contains {member}").into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_members_accepts_only_szmer_paths() {
+        let listing = ".config/szmer/\n.config/szmer/config.json\n.cache/szmer/last_notification\n";
+        assert!(check_members(listing).is_ok());
+
+        assert!(check_members(".bashrc\n").is_err());
+        assert!(check_members(".config/szmer/../../.bashrc\n").is_err());
+        assert!(check_members("/etc/passwd\n").is_err());
+    }
+
+    #[test]
+    fn test_is_tips_cache() {
+        assert!(is_tips_cache(Path::new("/c/tips-00ab.txt")));
+        assert!(!is_tips_cache(Path::new("/c/last_notification")));
+    }
+}
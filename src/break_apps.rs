@@ -0,0 +1,257 @@
+use crate::command::SystemCommandRunner;
+use crate::{gsettings, session, time};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+/// Another break reminder that would notify alongside szmer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakApp {
+    Stretchly,
+    SafeEyes,
+    TimeOut,
+    GnomeWellbeing,
+    GnomeBreakTimer,
+}
+
+impl fmt::Display for BreakApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BreakApp::Stretchly => "Stretchly",
+            BreakApp::SafeEyes => "Safe Eyes",
+            BreakApp::TimeOut => "Time Out",
+            BreakApp::GnomeWellbeing => "GNOME break reminders",
+            BreakApp::GnomeBreakTimer => "GNOME Break Timer",
+        })
+    }
+}
+
+/// A break reminder found running or switched on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detected {
+    pub app: BreakApp,
+    /// What it reminds about, e.g. GNOME's "movement, eyesight"
+    pub detail: Option<String>,
+    /// Its break interval, when its settings could be read
+    pub interval_seconds: Option<u64>,
+}
+
+impl fmt::Display for Detected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.app)?;
+        match (&self.detail, self.interval_seconds) {
+            (Some(detail), Some(seconds)) => {
+                write!(f, " ({detail}, every {})", time::format_interval(seconds))
+            }
+            (Some(detail), None) => write!(f, " ({detail})"),
+            (None, Some(seconds)) => write!(f, " (every {})", time::format_interval(seconds)),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Other break reminders that are running, or switched on for GNOME's own
+///
+/// Stretchly's and Safe Eyes' intervals are read from their config files,
+/// GNOME's from its movement break settings. Time Out keeps its settings in
+/// a format szmer doesn't read, so only its process is detected.
+pub fn detect(runner: &dyn SystemCommandRunner) -> Vec<Detected> {
+    let mut found = Vec::new();
+
+    if is_running(runner, &["-i", "-x", "stretchly"]) {
+        found.push(Detected {
+            app: BreakApp::Stretchly,
+            detail: None,
+            interval_seconds: read_config(stretchly_config_path())
+                .and_then(|json| stretchly_interval(&json)),
+        });
+    }
+
+    if is_running(runner, &["-f", "safeeyes"]) {
+        found.push(Detected {
+            app: BreakApp::SafeEyes,
+            detail: None,
+            interval_seconds: read_config(home_path(".config/safeeyes/safeeyes.json"))
+                .and_then(|json| safe_eyes_interval(&json)),
+        });
+    }
+
+    if cfg!(target_os = "macos") && is_running(runner, &["-x", "Time Out"]) {
+        found.push(Detected {
+            app: BreakApp::TimeOut,
+            detail: None,
+            interval_seconds: None,
+        });
+    }
+
+    let gnome_breaks = session::gnome_break_reminders(runner);
+    if !gnome_breaks.is_empty() {
+        let interval_seconds = gnome_breaks
+            .iter()
+            .any(|name| name == "movement")
+            .then(|| gnome_movement_interval(runner))
+            .flatten();
+        found.push(Detected {
+            app: BreakApp::GnomeWellbeing,
+            detail: Some(gnome_breaks.join(", ")),
+            interval_seconds,
+        });
+    }
+
+    if session::is_gnome_break_timer_running(runner) {
+        found.push(Detected {
+            app: BreakApp::GnomeBreakTimer,
+            detail: None,
+            interval_seconds: None,
+        });
+    }
+
+    found
+}
+
+fn is_running(runner: &dyn SystemCommandRunner, pgrep_args: &[&str]) -> bool {
+    runner
+        .run("pgrep", pgrep_args)
+        .is_ok_and(|output| output.success)
+}
+
+fn home_path(relative: &str) -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(relative))
+}
+
+fn read_config(path: Option<PathBuf>) -> Option<String> {
+    fs::read_to_string(path?).ok()
+}
+
+fn stretchly_config_path() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        home_path("Library/Application Support/Stretchly/config.json")
+    } else {
+        home_path(".config/Stretchly/config.json")
+    }
+}
+
+/// Time between Stretchly's long breaks, or its mini breaks when those are off
+///
+/// A long break replaces every `breakInterval + 1`th mini break, which
+/// come every `microbreakInterval` milliseconds.
+///
+/// # Examples
+///
+/// ```
+/// let json = r#"{"microbreakInterval": 600000, "breakInterval": 2, "break": true}"#;
+/// assert_eq!(stretchly_interval(json), Some(1800));
+/// ```
+fn stretchly_interval(json: &str) -> Option<u64> {
+    let config: serde_json::Value = serde_json::from_str(json).ok()?;
+    let microbreak_seconds = config.get("microbreakInterval")?.as_u64()? / 1000;
+    let long_breaks = config.get("break").and_then(|v| v.as_bool()) != Some(false);
+    if !long_breaks {
+        return Some(microbreak_seconds);
+    }
+    let every = config.get("breakInterval")?.as_u64()? + 1;
+    Some(microbreak_seconds * every)
+}
+
+/// Time between Safe Eyes' long breaks, stored in minutes
+fn safe_eyes_interval(json: &str) -> Option<u64> {
+    let config: serde_json::Value = serde_json::from_str(json).ok()?;
+    Some(config.get("long_break_interval")?.as_u64()? * 60)
+}
+
+/// Interval of GNOME's movement breaks
+fn gnome_movement_interval(runner: &dyn SystemCommandRunner) -> Option<u64> {
+    let output = runner
+        .run(
+            "gsettings",
+            &[
+                "get",
+                &format!("{}.movement", session::GNOME_BREAKS_SCHEMA),
+                "interval-seconds",
+            ],
+        )
+        .ok()?;
+    output.success.then_some(())?;
+    parse_uint(&output.stdout)
+}
+
+/// Number in GVariant text form, e.g. `uint32 1800`
+fn parse_uint(value: &str) -> Option<u64> {
+    value.split_whitespace().last()?.parse().ok()
+}
+
+/// Turn off what szmer can turn off itself, GNOME's break reminders
+///
+/// Other apps can only be quit by the user, so for those the steps are
+/// printed instead.
+pub fn turn_off(
+    runner: &dyn SystemCommandRunner,
+    found: &[Detected],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for detected in found {
+        match detected.app {
+            BreakApp::GnomeWellbeing => {
+                gsettings::set(
+                    runner,
+                    session::GNOME_BREAKS_SCHEMA,
+                    "selected-breaks",
+                    &gsettings::string_list(&[]),
+                )?;
+                println!("✓ Turned off GNOME's break reminders");
+            }
+            app => println!("Quit {app} and turn off its autostart in its settings."),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+
+    #[test]
+    fn test_intervals_from_other_apps_settings() {
+        let stretchly = r#"{"microbreakInterval": 600000, "breakInterval": 2, "break": true}"#;
+        assert_eq!(stretchly_interval(stretchly), Some(1800));
+        let mini_only = r#"{"microbreakInterval": 1200000, "breakInterval": 2, "break": false}"#;
+        assert_eq!(stretchly_interval(mini_only), Some(1200));
+
+        assert_eq!(
+            safe_eyes_interval(r#"{"short_break_interval": 15, "long_break_interval": 75}"#),
+            Some(4500)
+        );
+        assert_eq!(safe_eyes_interval("not json"), None);
+    }
+
+    #[test]
+    fn test_detect_gnome_wellbeing_with_interval() {
+        let runner = RecordingRunner::default()
+            .reply(
+                "gsettings get org.gnome.desktop.break-reminders selected-breaks",
+                true,
+                "['movement', 'eyesight']\n",
+            )
+            .reply(
+                "gsettings get org.gnome.desktop.break-reminders.movement interval-seconds",
+                true,
+                "uint32 1800\n",
+            );
+
+        let found = detect(&runner);
+        assert_eq!(
+            found,
+            vec![Detected {
+                app: BreakApp::GnomeWellbeing,
+                detail: Some("movement, eyesight".to_string()),
+                interval_seconds: Some(1800),
+            }]
+        );
+        assert_eq!(
+            found[0].to_string(),
+            "GNOME break reminders (movement, eyesight, every 30 minutes)"
+        );
+    }
+}
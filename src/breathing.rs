@@ -0,0 +1,272 @@
+use crate::i18n::{tr, tr_with};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Rows from the middle of the circle to its edge at a full breath
+const MAX_RADIUS: u32 = 5;
+
+/// How often the circle is redrawn
+const FRAME: Duration = Duration::from_millis(125);
+
+/// Longest any one step may last, in seconds
+const MAX_STEP_SECONDS: u32 = 30;
+
+/// Seconds to breathe in, hold and breathe out, written e.g. `4-7-8`
+///
+/// Stored as its string form.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Pattern {
+    pub inhale: u32,
+    pub hold: u32,
+    pub exhale: u32,
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        Self {
+            inhale: 4,
+            hold: 7,
+            exhale: 8,
+        }
+    }
+}
+
+impl Pattern {
+    fn cycle_seconds(&self) -> u32 {
+        self.inhale + self.hold + self.exhale
+    }
+
+    /// The step `elapsed` seconds into the exercise falls in, how full the
+    /// lungs are from 0 to 1, and the whole seconds left of the step
+    fn at(&self, elapsed: f64) -> (Step, f64, u32) {
+        let t = elapsed % f64::from(self.cycle_seconds());
+        let (inhale, hold, exhale) = (
+            f64::from(self.inhale),
+            f64::from(self.hold),
+            f64::from(self.exhale),
+        );
+        let left = |end: f64| (end - t).ceil() as u32;
+
+        if t < inhale {
+            (Step::Inhale, t / inhale, left(inhale))
+        } else if t < inhale + hold {
+            (Step::Hold, 1.0, left(inhale + hold))
+        } else {
+            let out = t - inhale - hold;
+            (
+                Step::Exhale,
+                1.0 - out / exhale,
+                left(inhale + hold + exhale),
+            )
+        }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid breathing pattern: '{value}'. Use inhale-hold-exhale seconds, e.g. 4-7-8"
+            )
+        };
+        let steps: Vec<u32> = value
+            .trim()
+            .split('-')
+            .map(|step| step.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        let [inhale, hold, exhale] = steps[..] else {
+            return Err(invalid());
+        };
+
+        if inhale == 0 || exhale == 0 {
+            return Err("Breathing in and out take at least a second each".to_string());
+        }
+        if [inhale, hold, exhale]
+            .iter()
+            .any(|step| *step > MAX_STEP_SECONDS)
+        {
+            return Err(format!(
+                "Each step of a breathing pattern lasts at most {MAX_STEP_SECONDS} seconds"
+            ));
+        }
+        Ok(Self {
+            inhale,
+            hold,
+            exhale,
+        })
+    }
+}
+
+impl TryFrom<String> for Pattern {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Pattern> for String {
+    fn from(pattern: Pattern) -> Self {
+        pattern.to_string()
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.inhale, self.hold, self.exhale)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Inhale,
+    Hold,
+    Exhale,
+}
+
+impl Step {
+    fn label(self) -> String {
+        tr(match self {
+            Self::Inhale => "breathing-inhale",
+            Self::Hold => "breathing-hold",
+            Self::Exhale => "breathing-exhale",
+        })
+    }
+}
+
+/// Guide breathing in the terminal for `seconds`, with a circle that grows
+/// while breathing in and shrinks while breathing out
+///
+/// When `still`, for reduced motion, there's no circle and no seconds
+/// ticking, only the step to take and the minutes left.
+pub fn run(pattern: Pattern, seconds: u64, still: bool) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let started = Instant::now();
+    let mut drawn: Vec<String> = Vec::new();
+
+    while started.elapsed().as_secs() < seconds {
+        let frame = frame(pattern, started.elapsed().as_secs_f64(), seconds, still);
+
+        if frame != drawn {
+            // Draw over the last frame
+            if !drawn.is_empty() {
+                write!(stdout, "\x1b[{}A", drawn.len())?;
+            }
+            for line in &frame {
+                write!(stdout, "\r\x1b[2K{line}\n")?;
+            }
+            stdout.flush()?;
+            drawn = frame;
+        }
+
+        thread::sleep(FRAME);
+    }
+    Ok(())
+}
+
+/// The lines shown `elapsed` seconds into an exercise of `seconds`
+fn frame(pattern: Pattern, elapsed: f64, seconds: u64, still: bool) -> Vec<String> {
+    let (step, fullness, step_left) = pattern.at(elapsed);
+    let left = seconds - elapsed as u64;
+
+    if still {
+        let minutes = left.div_ceil(60);
+        return vec![
+            step.label(),
+            tr_with("break-left", &[("time", format!("{minutes}:00").into())]),
+        ];
+    }
+
+    let mut frame = circle(1 + (fullness * f64::from(MAX_RADIUS - 1)).round() as u32);
+    frame.push(String::new());
+    frame.push(format!("{} {step_left}", step.label()));
+    frame.push(tr_with(
+        "break-left",
+        &[("time", format!("{}:{:02}", left / 60, left % 60).into())],
+    ));
+    frame
+}
+
+/// A filled circle `radius` rows from the middle to the edge, padded to
+/// the size of the largest one so frames line up
+///
+/// Terminal cells are about twice as tall as they're wide, so each row is
+/// twice as wide as the circle is tall.
+fn circle(radius: u32) -> Vec<String> {
+    let max = MAX_RADIUS as i32;
+    let radius = f64::from(radius.min(MAX_RADIUS)) + 0.5;
+
+    (-max..=max)
+        .map(|y| {
+            (-2 * max..=2 * max)
+                .map(|x| {
+                    let (x, y) = (f64::from(x) / 2.0, f64::from(y));
+                    if x * x + y * y <= radius * radius {
+                        '●'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_steps_through_a_cycle() {
+        let pattern: Pattern = "4-2-4".parse().unwrap();
+        assert_eq!(pattern.to_string(), "4-2-4");
+
+        assert_eq!(pattern.at(0.0), (Step::Inhale, 0.0, 4));
+        assert_eq!(pattern.at(2.0), (Step::Inhale, 0.5, 2));
+        assert_eq!(pattern.at(4.5), (Step::Hold, 1.0, 2));
+        assert_eq!(pattern.at(7.0), (Step::Exhale, 0.75, 3));
+        // The next cycle starts over
+        assert_eq!(pattern.at(10.0), (Step::Inhale, 0.0, 4));
+
+        assert!("4-0-4".parse::<Pattern>().is_ok());
+        assert!("0-4-4".parse::<Pattern>().is_err());
+        assert!("4-7".parse::<Pattern>().is_err());
+        assert!("4-60-8".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn test_still_frames_only_change_with_the_step() {
+        let pattern: Pattern = "4-2-4".parse().unwrap();
+
+        let breathing_in = frame(pattern, 0.0, 120, true);
+        assert_eq!(breathing_in.len(), 2);
+        assert_eq!(breathing_in[0], Step::Inhale.label());
+        assert_eq!(frame(pattern, 3.5, 120, true), breathing_in);
+        assert_ne!(frame(pattern, 4.5, 120, true), breathing_in);
+
+        // Moving frames count every second down
+        assert_ne!(
+            frame(pattern, 0.0, 120, false),
+            frame(pattern, 1.0, 120, false)
+        );
+    }
+
+    #[test]
+    fn test_circle_grows_within_a_steady_frame() {
+        let small = circle(1);
+        let large = circle(MAX_RADIUS);
+
+        assert_eq!(small.len(), large.len());
+        assert_eq!(small.iter().filter(|row| !row.is_empty()).count(), 3);
+        assert!(large.iter().all(|row| !row.is_empty()));
+    }
+}
@@ -0,0 +1,273 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(target_os = "macos")]
+use crate::command::{run_checked, ProcessRunner};
+#[cfg(target_os = "macos")]
+use crate::{config::Config, schedule};
+#[cfg(target_os = "macos")]
+use std::{env, fs};
+
+/// Bundle identifier macOS uses to attribute szmer's notifications
+#[cfg(target_os = "macos")]
+pub const BUNDLE_ID: &str = "com.michalczmiel.szmer";
+#[cfg(target_os = "macos")]
+const BUNDLE_NAME: &str = "Szmer.app";
+#[cfg(target_os = "macos")]
+const EXECUTABLE_NAME: &str = "szmer";
+#[cfg(target_os = "macos")]
+const ICON_FILENAME: &str = "szmer.icns";
+
+/// Helper posting notifications with a thread and category per kind, built
+/// next to the executable
+#[cfg(target_os = "macos")]
+const NOTIFY_HELPER_NAME: &str = "szmer-notify";
+#[cfg(target_os = "macos")]
+const NOTIFY_HELPER_SOURCE: &str = include_str!("../assets/macos/szmer-notify.swift");
+
+/// Wrap the binary in a minimal signed app bundle
+///
+/// macOS attributes notifications to an application bundle. Running from a
+/// bundle gives szmer a stable identity, an icon, and its own entry in the
+/// notification settings. With the Xcode command line tools the bundle also
+/// gets the notification helper, which groups notifications by kind. If the
+/// scheduler is installed it is updated to run the bundled binary.
+///
+/// # Arguments
+/// * `icon` - Optional `.icns` file to use as the application icon
+/// * `identity` - Code signing identity (defaults to ad-hoc signing)
+#[cfg(target_os = "macos")]
+pub fn install(
+    icon: Option<&Path>,
+    identity: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let bundle_path = get_bundle_path()?;
+    let contents = bundle_path.join("Contents");
+    let executable_dir = contents.join("MacOS");
+    let resources_dir = contents.join("Resources");
+
+    fs::create_dir_all(&executable_dir)?;
+    fs::create_dir_all(&resources_dir)?;
+
+    let source = env::current_exe()?.canonicalize()?;
+    let executable = executable_dir.join(EXECUTABLE_NAME);
+    if source != executable {
+        fs::copy(&source, &executable)?;
+    }
+
+    let icon_file = match icon {
+        Some(icon) => {
+            fs::copy(icon, resources_dir.join(ICON_FILENAME))?;
+            Some(ICON_FILENAME)
+        }
+        None => None,
+    };
+
+    fs::write(contents.join("Info.plist"), generate_info_plist(icon_file))?;
+
+    if let Err(e) = build_notify_helper(&executable_dir.join(NOTIFY_HELPER_NAME)) {
+        eprintln!("Warning: {e}");
+        eprintln!(
+            "  Notifications won't be grouped by kind. Install the Xcode command line tools \
+             (xcode-select --install) and run 'szmer bundle install' again."
+        );
+    }
+
+    sign_bundle(&bundle_path, identity.unwrap_or("-"))?;
+
+    println!("✓ Created app bundle at: {}", bundle_path.display());
+
+    if schedule::is_installed() {
+        let executable_path = executable
+            .to_str()
+            .ok_or("Failed to convert bundle path to string")?;
+        let config = Config::load()?;
+        schedule::reload(executable_path, config.timer_seconds())?;
+        println!("✓ Scheduler updated to run from the app bundle");
+    }
+
+    println!("\nNotification settings are available in System Settings → Notifications → Szmer");
+
+    Ok(())
+}
+
+/// Remove the app bundle
+#[cfg(target_os = "macos")]
+pub fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle_path = get_bundle_path()?;
+
+    if !bundle_path.exists() {
+        println!("App bundle is not currently installed.");
+        return Ok(());
+    }
+
+    if schedule::is_installed() {
+        return Err(
+            "The scheduler may be running from the app bundle. Run 'szmer uninstall' first.".into(),
+        );
+    }
+
+    fs::remove_dir_all(&bundle_path)?;
+    println!("✓ Removed app bundle at: {}", bundle_path.display());
+
+    Ok(())
+}
+
+/// Check if the app bundle is installed
+pub fn is_installed() -> bool {
+    get_bundle_path().ok().is_some_and(|p| p.exists())
+}
+
+/// Send notifications as the app bundle when running from it
+///
+/// Otherwise macOS attributes them to a stand-in application. The identity
+/// can only be set once per process, so later calls do nothing.
+#[cfg(target_os = "macos")]
+pub fn claim_notifications() {
+    static CLAIM: std::sync::Once = std::sync::Once::new();
+    CLAIM.call_once(|| {
+        if !is_running_from_bundle() {
+            return;
+        }
+        if let Err(e) = notify_rust::set_application(BUNDLE_ID) {
+            eprintln!("Warning: Failed to send notifications as {BUNDLE_ID}: {e}");
+        }
+    });
+}
+
+/// Whether this process runs the binary inside the installed app bundle
+#[cfg(target_os = "macos")]
+fn is_running_from_bundle() -> bool {
+    let exe = env::current_exe().and_then(|exe| exe.canonicalize());
+    let bundle = get_bundle_path().ok().and_then(|p| p.canonicalize().ok());
+    match (exe, bundle) {
+        (Ok(exe), Some(bundle)) => exe.starts_with(bundle),
+        _ => false,
+    }
+}
+
+/// The notification helper, when this process runs from a bundle that has it
+///
+/// The helper has to run from the bundle for macOS to tell whose
+/// notifications it posts.
+#[cfg(target_os = "macos")]
+pub fn notify_helper() -> Option<PathBuf> {
+    let helper = env::current_exe()
+        .ok()?
+        .canonicalize()
+        .ok()?
+        .with_file_name(NOTIFY_HELPER_NAME);
+    (is_running_from_bundle() && helper.exists()).then_some(helper)
+}
+
+/// Compile the notification helper to `path` with the Swift compiler
+#[cfg(target_os = "macos")]
+fn build_notify_helper(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let source = env::temp_dir().join(format!("{NOTIFY_HELPER_NAME}-{}.swift", std::process::id()));
+    fs::write(&source, NOTIFY_HELPER_SOURCE)?;
+    let result = run_checked(
+        &ProcessRunner,
+        "xcrun",
+        &[
+            "swiftc",
+            "-O",
+            "-o",
+            &path.to_string_lossy(),
+            &source.to_string_lossy(),
+        ],
+        "Failed to build the notification helper",
+    );
+    let _ = fs::remove_file(&source);
+    result?;
+    Ok(())
+}
+
+/// Path to the executable inside the installed app bundle
+#[cfg(target_os = "macos")]
+pub fn executable_path() -> Option<PathBuf> {
+    get_bundle_path()
+        .ok()
+        .map(|p| p.join("Contents").join("MacOS").join(EXECUTABLE_NAME))
+        .filter(|p| p.exists())
+}
+
+#[cfg(target_os = "macos")]
+fn get_bundle_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let home = env::var("HOME")?;
+    Ok(PathBuf::from(home).join("Applications").join(BUNDLE_NAME))
+}
+
+#[cfg(target_os = "macos")]
+fn generate_info_plist(icon_file: Option<&str>) -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let icon_entry = icon_file
+        .map(|icon| format!("    <key>CFBundleIconFile</key>\n    <string>{icon}</string>\n"))
+        .unwrap_or_default();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>{BUNDLE_ID}</string>
+    <key>CFBundleName</key>
+    <string>Szmer</string>
+    <key>CFBundleExecutable</key>
+    <string>{EXECUTABLE_NAME}</string>
+    <key>CFBundlePackageType</key>
+    <string>APPL</string>
+    <key>CFBundleShortVersionString</key>
+    <string>{version}</string>
+    <key>CFBundleVersion</key>
+    <string>{version}</string>
+{icon_entry}    <key>LSUIElement</key>
+    <true/>
+</dict>
+</plist>
+"#
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn sign_bundle(bundle_path: &Path, identity: &str) -> Result<(), Box<dyn std::error::Error>> {
+    run_checked(
+        &ProcessRunner,
+        "codesign",
+        &[
+            "--force",
+            "--deep",
+            "--sign",
+            identity,
+            &bundle_path.to_string_lossy(),
+        ],
+        "Failed to sign app bundle",
+    )?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn install(
+    _icon: Option<&Path>,
+    _identity: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(crate::error::SzmerError::Unsupported {
+        feature: "App bundles",
+    }
+    .into())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
+    Err(crate::error::SzmerError::Unsupported {
+        feature: "App bundles",
+    }
+    .into())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn get_bundle_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Err(crate::error::SzmerError::Unsupported {
+        feature: "App bundles",
+    }
+    .into())
+}
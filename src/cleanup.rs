@@ -0,0 +1,119 @@
+use crate::config::Config;
+use crate::{schedule, timestamp};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the launchd agents send output, see `schedule.rs`
+pub const LOG_FILES: &[&str] = &[
+    "/tmp/szmer.log",
+    "/tmp/szmer.err",
+    "/tmp/szmer-daemon.log",
+    "/tmp/szmer-daemon.err",
+];
+
+/// What a file szmer leaves behind is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Config,
+    Cache,
+    Log,
+    Scheduler,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Kind::Config => "Config",
+            Kind::Cache => "Cache",
+            Kind::Log => "Log",
+            Kind::Scheduler => "Scheduler",
+        })
+    }
+}
+
+/// A file or directory of szmer's still on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leftover {
+    pub kind: Kind,
+    pub path: PathBuf,
+}
+
+/// Everything szmer wrote that is still there
+///
+/// Scheduler files show up when uninstalling couldn't remove them, e.g.
+/// because systemd refused to disable the timer.
+pub fn leftovers() -> Vec<Leftover> {
+    let mut candidates: Vec<(Kind, PathBuf)> = schedule::installed_files()
+        .into_iter()
+        .map(|path| (Kind::Scheduler, path))
+        .collect();
+    if let Some(dir) = Config::get_config_path()
+        .ok()
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+    {
+        candidates.push((Kind::Config, dir));
+    }
+    if let Ok(dir) = timestamp::get_cache_dir() {
+        candidates.push((Kind::Cache, dir));
+    }
+    candidates.extend(
+        LOG_FILES
+            .iter()
+            .map(|path| (Kind::Log, PathBuf::from(path))),
+    );
+
+    existing(candidates)
+}
+
+fn existing(candidates: Vec<(Kind, PathBuf)>) -> Vec<Leftover> {
+    candidates
+        .into_iter()
+        .filter(|(_, path)| path.exists())
+        .map(|(kind, path)| Leftover { kind, path })
+        .collect()
+}
+
+/// Delete `leftovers`, directories with everything in them
+///
+/// Keeps going past failures so one stubborn file doesn't keep the rest,
+/// and returns the ones that couldn't be removed with the reason.
+pub fn remove(leftovers: &[Leftover]) -> Vec<(&Leftover, std::io::Error)> {
+    leftovers
+        .iter()
+        .filter_map(|leftover| {
+            let removed = if leftover.path.is_dir() {
+                fs::remove_dir_all(&leftover.path)
+            } else {
+                fs::remove_file(&leftover.path)
+            };
+            removed.err().map(|e| (leftover, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_existing_files_and_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("szmer");
+        fs::create_dir_all(cache.join("plugins")).unwrap();
+        fs::write(cache.join("last_notification"), "").unwrap();
+        let log = dir.path().join("szmer.log");
+        fs::write(&log, "").unwrap();
+
+        let found = existing(vec![
+            (Kind::Cache, cache.clone()),
+            (Kind::Log, log.clone()),
+            (Kind::Log, dir.path().join("szmer.err")),
+        ]);
+        assert_eq!(found.len(), 2);
+
+        assert!(remove(&found).is_empty());
+        assert!(!cache.exists());
+        assert!(!log.exists());
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Local};
+
+/// Source of the current wall-clock time
+///
+/// Time-dependent logic takes a `Clock` instead of calling `Local::now()`
+/// directly, so tests can pin and advance time deterministically.
+pub trait Clock {
+    fn now(&self) -> DateTime<Local>;
+}
+
+/// The real system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[cfg(test)]
+#[derive(Debug)]
+pub struct MockClock {
+    now: std::cell::Cell<DateTime<Local>>,
+}
+
+#[cfg(test)]
+impl MockClock {
+    /// Create a clock stopped at the given local time, e.g. "2025-01-06 09:00:00"
+    pub fn at(time: &str) -> Self {
+        let naive = chrono::NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S")
+            .expect("valid mock time");
+        let now = naive
+            .and_local_timezone(Local)
+            .earliest()
+            .expect("mock time exists in the local timezone");
+        Self {
+            now: std::cell::Cell::new(now),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, duration: chrono::Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Local> {
+        self.now.get()
+    }
+}
+
+/// For a test to keep moving a clock it handed over
+#[cfg(test)]
+impl Clock for std::rc::Rc<MockClock> {
+    fn now(&self) -> DateTime<Local> {
+        self.now.get()
+    }
+}
@@ -0,0 +1,219 @@
+use crate::error::SzmerError;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Captured result of running an external program
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Runs external tools such as launchctl, systemctl, crontab and timew
+///
+/// Code that shells out takes a runner instead of using
+/// `std::process::Command` directly, so tests can record the calls and
+/// script the replies without touching the system.
+pub trait SystemCommandRunner {
+    /// Run `program` with `args` and capture its output
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput>;
+
+    /// Like `run`, writing `input` to the program's stdin
+    fn run_with_input(
+        &self,
+        program: &str,
+        args: &[&str],
+        input: &str,
+    ) -> io::Result<CommandOutput>;
+}
+
+/// Runs commands for real
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessRunner;
+
+impl SystemCommandRunner for ProcessRunner {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        let output = Command::new(program).args(args).output()?;
+        Ok(to_command_output(output))
+    }
+
+    fn run_with_input(
+        &self,
+        program: &str,
+        args: &[&str],
+        input: &str,
+    ) -> io::Result<CommandOutput> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // Programs may exit without reading their input, that's not an error
+            match stdin.write_all(input.as_bytes()) {
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+                _ => {}
+            }
+        }
+
+        Ok(to_command_output(child.wait_with_output()?))
+    }
+}
+
+fn to_command_output(output: std::process::Output) -> CommandOutput {
+    CommandOutput {
+        success: output.status.success(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    }
+}
+
+/// Run a command and turn a non-zero exit into an error
+///
+/// # Arguments
+/// * `runner` - Runner executing the command
+/// * `program` - Program to run
+/// * `args` - Arguments to pass
+/// * `error_prefix` - Context prepended to the program's stderr on failure
+pub fn run_checked(
+    runner: &dyn SystemCommandRunner,
+    program: &str,
+    args: &[&str],
+    error_prefix: &str,
+) -> Result<CommandOutput, Box<dyn std::error::Error>> {
+    let output = runner.run(program, args)?;
+
+    if !output.success {
+        return Err(SzmerError::CommandFailed {
+            context: error_prefix.to_string(),
+            stderr: output.stderr,
+        }
+        .into());
+    }
+
+    Ok(output)
+}
+
+/// Run `program` with `args` after `seconds`, in the background
+///
+/// A detached `sleep` does the waiting in its own session, so the
+/// command still runs after szmer and the terminal that started it exit.
+pub fn spawn_after(
+    seconds: u64,
+    program: &str,
+    args: &[&str],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut command = Command::new("sh");
+    command
+        .args([
+            "-c",
+            r#"sleep "$0" && exec "$@""#,
+            &seconds.to_string(),
+            program,
+        ])
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: setsid is async-signal-safe and touches no parent state
+        unsafe {
+            command.pre_exec(|| {
+                libc::setsid();
+                Ok(())
+            });
+        }
+    }
+
+    command
+        .spawn()
+        .map_err(|e| format!("Failed to schedule {program}: {e}"))?;
+    Ok(())
+}
+
+/// Runner that records every call and answers from a script, for tests
+///
+/// Commands without a scripted reply fail as if the program wasn't found.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct RecordingRunner {
+    replies: std::collections::HashMap<String, CommandOutput>,
+    calls: std::cell::RefCell<Vec<String>>,
+}
+
+#[cfg(test)]
+impl RecordingRunner {
+    /// Script the reply for a command line such as "systemctl --user is-active szmer.timer"
+    pub fn reply(mut self, command_line: &str, success: bool, stdout: &str) -> Self {
+        self.replies.insert(
+            command_line.to_string(),
+            CommandOutput {
+                success,
+                stdout: stdout.to_string(),
+                stderr: String::new(),
+            },
+        );
+        self
+    }
+
+    /// Every command line run so far, in order
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+impl SystemCommandRunner for RecordingRunner {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        let command_line = std::iter::once(program)
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.calls.borrow_mut().push(command_line.clone());
+
+        self.replies
+            .get(&command_line)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, command_line))
+    }
+
+    fn run_with_input(
+        &self,
+        program: &str,
+        args: &[&str],
+        input: &str,
+    ) -> io::Result<CommandOutput> {
+        let output = self.run(program, args)?;
+        if let Some(last) = self.calls.borrow_mut().last_mut() {
+            last.push_str(&format!(" <<< {input}"));
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_checked_reports_stderr() {
+        let runner = RecordingRunner::default().reply("false", false, "");
+        let error = run_checked(&runner, "false", &[], "It failed").unwrap_err();
+        assert!(error.to_string().starts_with("It failed"));
+    }
+
+    #[test]
+    fn test_recording_runner_records_calls() {
+        let runner = RecordingRunner::default().reply("echo hi", true, "hi\n");
+        let output = runner.run("echo", &["hi"]).unwrap();
+        assert_eq!(output.stdout, "hi\n");
+        assert!(runner.run("missing", &[]).is_err());
+        assert_eq!(runner.calls(), ["echo hi", "missing"]);
+    }
+}
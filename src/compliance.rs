@@ -0,0 +1,119 @@
+use crate::clock::Clock;
+use crate::time::{start_of_today, start_of_week};
+use crate::timestamp::{self, Answer, AnswerRecord};
+use chrono::{DateTime, Local};
+
+/// How the reminders sent in a period were answered
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Compliance {
+    pub reminders: usize,
+    pub taken: usize,
+    pub skipped: usize,
+    pub snoozed: usize,
+}
+
+impl Compliance {
+    /// Count the reminders and answers recorded at or after `since`
+    pub fn since(
+        reminders: &[DateTime<Local>],
+        answers: &[AnswerRecord],
+        since: DateTime<Local>,
+    ) -> Self {
+        let count = |wanted: Answer| {
+            answers
+                .iter()
+                .filter(|(at, answer)| *at >= since && *answer == wanted)
+                .count()
+        };
+
+        Self {
+            reminders: reminders.iter().filter(|at| **at >= since).count(),
+            taken: count(Answer::Taken),
+            skipped: count(Answer::Skip),
+            snoozed: count(Answer::Snooze),
+        }
+    }
+
+    /// Share of reminders that ended in a break, in percent
+    ///
+    /// `None` without reminders. Breaks taken without a reminder, through
+    /// `szmer took-break`, can't push it past 100.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let compliance = Compliance { reminders: 4, taken: 3, ..Default::default() };
+    /// assert_eq!(compliance.rate(), Some(75));
+    /// ```
+    pub fn rate(&self) -> Option<usize> {
+        (self.reminders > 0).then(|| (self.taken * 100 / self.reminders).min(100))
+    }
+
+    /// Counts and rate, for `--json` output
+    pub fn to_json(self) -> serde_json::Value {
+        serde_json::json!({
+            "reminders": self.reminders,
+            "taken": self.taken,
+            "skipped": self.skipped,
+            "snoozed": self.snoozed,
+            "rate": self.rate(),
+        })
+    }
+
+    /// Whether anything was answered, so a rate means something
+    pub fn has_answers(&self) -> bool {
+        self.taken + self.skipped + self.snoozed > 0
+    }
+}
+
+/// Compliance for today and for the week so far, from the cached history
+pub fn today_and_week(
+    clock: &dyn Clock,
+) -> Result<(Compliance, Compliance), Box<dyn std::error::Error>> {
+    let reminders = timestamp::notification_history()?;
+    let answers = timestamp::answer_history()?;
+
+    Ok((
+        Compliance::since(&reminders, &answers, start_of_today(clock)),
+        Compliance::since(&reminders, &answers, start_of_week(clock)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_compliance_since() {
+        // A Wednesday
+        let clock = MockClock::at("2025-01-08 12:00:00");
+        let monday = clock.now() - chrono::Duration::days(2);
+        let today = clock.now();
+        let reminders = [monday, monday, today, today];
+        let answers = [
+            (monday, Answer::Taken),
+            (monday, Answer::Skip),
+            (today, Answer::Taken),
+            (today, Answer::Snooze),
+        ];
+
+        let day = Compliance::since(&reminders, &answers, start_of_today(&clock));
+        assert_eq!(
+            day,
+            Compliance {
+                reminders: 2,
+                taken: 1,
+                skipped: 0,
+                snoozed: 1,
+            }
+        );
+        assert_eq!(day.rate(), Some(50));
+
+        let week = Compliance::since(&reminders, &answers, start_of_week(&clock));
+        assert_eq!((week.reminders, week.taken, week.rate()), (4, 2, Some(50)));
+
+        assert_eq!(Compliance::default().rate(), None);
+        assert!(!Compliance::default().has_answers());
+    }
+}
@@ -1,9 +1,57 @@
+use crate::breathing;
+use crate::error::SzmerError;
+use crate::i18n;
+use crate::pomodoro;
+use crate::time::{format_days, format_interval};
+use crate::timestamp;
+use crate::tips::TipCategory;
+use crate::toml_file;
+use chrono::{DateTime, Datelike, Days, Local, NaiveTime, Weekday};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 const CONFIG_DIR: &str = ".config/szmer";
-const CONFIG_FILE: &str = "config.json";
+const CONFIG_FILE: &str = "config.toml";
+/// Where the config lived before it moved to TOML, still read until the next save
+const LEGACY_CONFIG_FILE: &str = "config.json";
+/// Where pausing is kept when the config file is read-only, e.g. a Nix store link
+const PAUSE_STATE_FILE: &str = "pause.json";
+/// Config keys that hold the pause rather than a setting
+const PAUSE_KEYS: [&str; 3] = ["paused", "paused_until", "paused_since"];
+
+/// Version of the config format this szmer writes, see `MIGRATIONS`
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Steps upgrading a config file, the one at index `n` from version `n` to `n + 1`
+///
+/// Each step gets the file as parsed, before serde reads it, so it can
+/// move renamed keys and reshape sections. Add a step and bump
+/// `CONFIG_VERSION` with every change older files can't be read as they are.
+const MIGRATIONS: [fn(&mut serde_json::Map<String, serde_json::Value>); CONFIG_VERSION as usize] = [
+    // Files from before versioning need no changes
+    |_| {},
+];
+
+/// Top of a newly written config file
+const CONFIG_HEADER: &str = "\
+# szmer settings, changed with `szmer config set` or by hand.
+# Comments on their own lines are kept when szmer saves the file.
+";
+
+/// Break intervals the install wizard offers out of the box, in seconds
+pub const BUILTIN_INTERVAL_PRESETS: &[(&str, u64)] = &[
+    ("eye-saver", 20 * 60),
+    ("pomodoro", 25 * 60),
+    ("answer-to-everything", 42 * 60),
+    ("standard-hour", 60 * 60),
+    ("deep-work", 90 * 60),
+    ("the-numbers", 108 * 60),
+];
 
 /// Configuration for Timewarrior integration
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -13,63 +61,2690 @@ pub struct TimewarriorConfig {
     pub enabled: bool,
 }
 
+/// Situations that hold reminders back
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct SuppressConfig {
+    /// App names or window classes, matched as case-insensitive globs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apps: Vec<String>,
+    /// Wi-Fi networks used for presenting, matched as case-insensitive globs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub networks: Vec<String>,
+    /// Whether to hold reminders back while the screen is shared
+    #[serde(default)]
+    pub screen_sharing: bool,
+    /// macOS Focus names that turn reminders off, matched as globs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub focus_modes: Vec<String>,
+}
+
+impl SuppressConfig {
+    pub fn is_empty(&self) -> bool {
+        self.apps.is_empty() && self.focus_modes.is_empty() && !self.is_presenting_enabled()
+    }
+
+    /// Whether any presentation check is configured
+    pub fn is_presenting_enabled(&self) -> bool {
+        !self.networks.is_empty() || self.screen_sharing
+    }
+}
+
+/// When reminders should bypass the desktop and go to the terminal
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadlessMode {
+    /// Use terminal delivery only in a text console or SSH session
+    #[default]
+    Auto,
+    /// Always deliver reminders to the terminal
+    Always,
+    /// Always use desktop notifications
+    Never,
+}
+
+impl FromStr for HeadlessMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!(
+                "Invalid headless mode: '{value}'. Use 'auto', 'always' or 'never'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HeadlessMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        })
+    }
+}
+
+/// Voice reminders are written in
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Tone {
+    /// Soft suggestions
+    Gentle,
+    /// The plain title and tip
+    #[default]
+    Neutral,
+    /// Short, shouted orders
+    DrillSergeant,
+    /// Jokes around the tip
+    Humorous,
+}
+
+impl FromStr for Tone {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "gentle" => Ok(Self::Gentle),
+            "neutral" => Ok(Self::Neutral),
+            "drill-sergeant" => Ok(Self::DrillSergeant),
+            "humorous" => Ok(Self::Humorous),
+            _ => Err(format!(
+                "Invalid tone: '{value}'. Use 'gentle', 'neutral', 'drill-sergeant' or 'humorous'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Tone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Gentle => "gentle",
+            Self::Neutral => "neutral",
+            Self::DrillSergeant => "drill-sergeant",
+            Self::Humorous => "humorous",
+        })
+    }
+}
+
+/// What a desktop reminder looks like
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Style {
+    /// A regular desktop notification
+    #[default]
+    Notification,
+    /// A large window counting the break down, see overlay.rs
+    Overlay,
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "notification" => Ok(Self::Notification),
+            "overlay" => Ok(Self::Overlay),
+            _ => Err(format!(
+                "Invalid style: '{value}'. Use 'notification' or 'overlay'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Notification => "notification",
+            Self::Overlay => "overlay",
+        })
+    }
+}
+
+/// Preset colors and sizes of the break overlay
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlayTheme {
+    /// Large white text on a dark window
+    #[default]
+    Dark,
+    /// Large dark text on a light window
+    Light,
+    /// Small grey text and only the progress bar counting down
+    Minimal,
+    /// Larger white text on black, with GTK's high contrast theme
+    #[serde(rename = "high-contrast")]
+    HighContrast,
+}
+
+impl FromStr for OverlayTheme {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            "minimal" => Ok(Self::Minimal),
+            "high-contrast" => Ok(Self::HighContrast),
+            _ => Err(format!(
+                "Invalid overlay theme: '{value}'. Use 'dark', 'light', 'minimal' or 'high-contrast'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OverlayTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::Minimal => "minimal",
+            Self::HighContrast => "high-contrast",
+        })
+    }
+}
+
+/// How the overlay shows the time left of the break
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Countdown {
+    /// Minutes and seconds after the tip, next to the progress bar
+    Clock,
+    /// The progress bar alone
+    Bar,
+}
+
+impl FromStr for Countdown {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "clock" => Ok(Self::Clock),
+            "bar" => Ok(Self::Bar),
+            _ => Err(format!(
+                "Invalid countdown: '{value}'. Use 'clock' or 'bar'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Countdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Clock => "clock",
+            Self::Bar => "bar",
+        })
+    }
+}
+
+/// Whether the overlay and the breathing exercise move
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Motion {
+    /// Reduced when the desktop asks apps to cut down on animation
+    #[default]
+    Auto,
+    /// Count down every second and animate the breathing circle
+    Full,
+    /// Count down in whole minutes and breathe with words only
+    Reduced,
+}
+
+impl FromStr for Motion {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "full" => Ok(Self::Full),
+            "reduced" => Ok(Self::Reduced),
+            _ => Err(format!(
+                "Invalid motion: '{value}'. Use 'auto', 'full' or 'reduced'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Motion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Full => "full",
+            Self::Reduced => "reduced",
+        })
+    }
+}
+
+/// Font sizes the overlay text takes, in points
+pub const OVERLAY_FONT_SIZES: RangeInclusive<u32> = 8..=96;
+
+/// Whether `value` is a `#rgb` or `#rrggbb` color
+pub fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// How reminders are delivered when running headless
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminalMethod {
+    /// Ring the terminal bell and print the reminder
+    #[default]
+    Bell,
+    /// Broadcast the reminder to all terminals with `wall`
+    Wall,
+    /// Append the reminder to a named pipe
+    Pipe,
+}
+
+impl FromStr for TerminalMethod {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "bell" => Ok(Self::Bell),
+            "wall" => Ok(Self::Wall),
+            "pipe" => Ok(Self::Pipe),
+            _ => Err(format!(
+                "Invalid terminal method: '{value}'. Use 'bell', 'wall' or 'pipe'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TerminalMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Bell => "bell",
+            Self::Wall => "wall",
+            Self::Pipe => "pipe",
+        })
+    }
+}
+
+/// A channel break reminders can be delivered through
+///
+/// Stored as its string form, e.g. `"desktop"` or `"plugin:slack"`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum NotifierKind {
+    /// Native desktop notification
+    Desktop,
+    /// Terminal delivery using the headless settings
+    Terminal,
+    /// HTTP POST to a webhook URL
+    Webhook,
+    /// Read the reminder aloud
+    Speech,
+    /// Push to an ntfy topic, e.g. to a phone
+    Ntfy,
+    /// A notifier plugin, by name
+    Plugin(String),
+}
+
+impl FromStr for NotifierKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if let Some(name) = value
+            .strip_prefix("plugin:")
+            .filter(|name| !name.is_empty())
+        {
+            return Ok(Self::Plugin(name.to_string()));
+        }
+
+        match value.to_lowercase().as_str() {
+            "desktop" => Ok(Self::Desktop),
+            "terminal" => Ok(Self::Terminal),
+            "webhook" => Ok(Self::Webhook),
+            "speech" => Ok(Self::Speech),
+            "ntfy" => Ok(Self::Ntfy),
+            _ => Err(format!(
+                "Invalid notifier: '{value}'. Use 'desktop', 'terminal', 'webhook', 'speech', 'ntfy' or 'plugin:<name>'"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for NotifierKind {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<NotifierKind> for String {
+    fn from(kind: NotifierKind) -> Self {
+        kind.to_string()
+    }
+}
+
+impl fmt::Display for NotifierKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Desktop => f.write_str("desktop"),
+            Self::Terminal => f.write_str("terminal"),
+            Self::Webhook => f.write_str("webhook"),
+            Self::Speech => f.write_str("speech"),
+            Self::Ntfy => f.write_str("ntfy"),
+            Self::Plugin(name) => write!(f, "plugin:{name}"),
+        }
+    }
+}
+
+/// Which channels deliver reminders, tried in order until one succeeds
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeliveryConfig {
+    /// Notifiers to try, the first one that succeeds wins
+    #[serde(default = "default_chain")]
+    pub chain: Vec<NotifierKind>,
+    /// URL the webhook notifier posts reminders to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// How long gates may take before the reminder is sent without them
+    #[serde(default = "default_gate_deadline")]
+    pub gate_deadline_seconds: u64,
+}
+
+fn default_chain() -> Vec<NotifierKind> {
+    vec![NotifierKind::Desktop]
+}
+
+fn default_gate_deadline() -> u64 {
+    5
+}
+
+impl Default for DeliveryConfig {
+    fn default() -> Self {
+        Self {
+            chain: default_chain(),
+            webhook_url: None,
+            gate_deadline_seconds: default_gate_deadline(),
+        }
+    }
+}
+
+/// How urgently ntfy delivers a push, from `min` to `urgent`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NtfyPriority {
+    Min,
+    Low,
+    #[default]
+    Default,
+    High,
+    Urgent,
+}
+
+impl NtfyPriority {
+    const ALL: [Self; 5] = [
+        Self::Min,
+        Self::Low,
+        Self::Default,
+        Self::High,
+        Self::Urgent,
+    ];
+
+    /// The number ntfy takes, 1 for `min` through 5 for `urgent`
+    pub fn number(self) -> u8 {
+        self as u8 + 1
+    }
+
+    /// One level more urgent, `urgent` stays as it is
+    pub fn raised(self) -> Self {
+        Self::ALL[(self as usize + 1).min(Self::ALL.len() - 1)]
+    }
+
+    /// One level less urgent, `min` stays as it is
+    pub fn lowered(self) -> Self {
+        Self::ALL[(self as usize).saturating_sub(1)]
+    }
+}
+
+impl FromStr for NtfyPriority {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "min" => Ok(Self::Min),
+            "low" => Ok(Self::Low),
+            "default" => Ok(Self::Default),
+            "high" => Ok(Self::High),
+            "urgent" => Ok(Self::Urgent),
+            _ => Err(format!(
+                "Invalid priority: '{value}'. Use 'min', 'low', 'default', 'high' or 'urgent'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for NtfyPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Min => "min",
+            Self::Low => "low",
+            Self::Default => "default",
+            Self::High => "high",
+            Self::Urgent => "urgent",
+        })
+    }
+}
+
+/// Push notifications through ntfy.sh or a self-hosted ntfy server
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct NtfyConfig {
+    /// Server to publish to, ntfy.sh when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    /// Topic the phone is subscribed to, off when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// Access token for a protected topic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Priority of break reminders, escalations go a level up and summaries a level down
+    #[serde(default)]
+    pub priority: NtfyPriority,
+    /// Also push reminders another channel delivered
+    #[serde(default)]
+    pub mirror: bool,
+}
+
+impl NtfyConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Where reminder tips come from
+///
+/// Stored as its string form: `"builtin"`, `"file:<path>"`,
+/// `"command:<shell command>"` or an `https://` URL.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum TipSource {
+    /// The wellness tips that ship with szmer
+    #[default]
+    Builtin,
+    /// A local file with one tip per line
+    File(PathBuf),
+    /// A shell command whose output is the tip
+    Command(String),
+    /// A remote list with one tip per line, cached for offline use
+    Url(String),
+}
+
+impl TipSource {
+    pub fn is_builtin(&self) -> bool {
+        *self == Self::Builtin
+    }
+}
+
+impl FromStr for TipSource {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("builtin") {
+            return Ok(Self::Builtin);
+        }
+        if let Some(path) = value.strip_prefix("file:").filter(|path| !path.is_empty()) {
+            return Ok(Self::File(PathBuf::from(path)));
+        }
+        if let Some(command) = value
+            .strip_prefix("command:")
+            .filter(|command| !command.trim().is_empty())
+        {
+            return Ok(Self::Command(command.to_string()));
+        }
+        if value.starts_with("https://") || value.starts_with("http://") {
+            return Ok(Self::Url(value.to_string()));
+        }
+
+        Err(format!(
+            "Invalid tip source: '{value}'. Use 'builtin', 'file:<path>', 'command:<command>' or an https:// URL"
+        ))
+    }
+}
+
+impl TryFrom<String> for TipSource {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TipSource> for String {
+    fn from(source: TipSource) -> Self {
+        source.to_string()
+    }
+}
+
+impl fmt::Display for TipSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Builtin => f.write_str("builtin"),
+            Self::File(path) => write!(f, "file:{}", path.display()),
+            Self::Command(command) => write!(f, "command:{command}"),
+            Self::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+/// Turning other audio down while a reminder sounds
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DuckingConfig {
+    /// How much to lower the volume, as a percentage of its level (0 = off)
+    #[serde(default)]
+    pub percent: u8,
+    /// Keep the volume down for the whole break, not just the sound
+    #[serde(default)]
+    pub during_break: bool,
+}
+
+impl DuckingConfig {
+    pub fn is_empty(&self) -> bool {
+        self.percent == 0
+    }
+}
+
+/// A nudge for a break that didn't happen, the user kept working through it
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct FollowUpConfig {
+    /// How long after the break was ignored the nudge comes, off when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_seconds: Option<u64>,
+}
+
+impl FollowUpConfig {
+    pub fn is_empty(&self) -> bool {
+        self.delay_seconds.is_none()
+    }
+}
+
+/// Look of the break overlay and the grace period before it, see overlay.rs
+///
+/// Unset look values come from the theme.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OverlayConfig {
+    /// Preset the other values start from
+    #[serde(default)]
+    pub theme: OverlayTheme,
+    /// Text color as `#rrggbb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+    /// Text size in points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<u32>,
+    /// How the time left is shown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub countdown: Option<Countdown>,
+    /// Whether the countdowns and the breathing exercise move
+    #[serde(default)]
+    pub motion: Motion,
+    /// How long a small countdown runs before the overlay, 0 for none
+    #[serde(default = "default_grace")]
+    pub grace_seconds: u64,
+    /// Postpones allowed a day, unlimited when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_postpones: Option<u32>,
+}
+
+fn default_grace() -> u64 {
+    30
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            theme: OverlayTheme::default(),
+            text_color: None,
+            font_size: None,
+            countdown: None,
+            motion: Motion::default(),
+            grace_seconds: default_grace(),
+            max_postpones: None,
+        }
+    }
+}
+
+impl OverlayConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Holding reminders back while nobody is at the machine
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct AwayConfig {
+    /// How long without input counts as away, off when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_seconds: Option<u64>,
+}
+
+impl AwayConfig {
+    pub fn is_empty(&self) -> bool {
+        self.after_seconds.is_none()
+    }
+}
+
+/// A reminder on its own schedule next to the main one, e.g. eye micro-breaks
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BreakProfile {
+    /// Name used by `szmer notify --profile` and in scheduler file names
+    pub name: String,
+    /// How often the reminder goes out, in seconds
+    pub interval_seconds: u64,
+    /// Only show tips of this category
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<TipCategory>,
+    /// Sound for this profile, the main sound when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sound: Option<String>,
+}
+
+impl fmt::Display for BreakProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "every {}", format_interval(self.interval_seconds))?;
+        if let Some(category) = self.category {
+            write!(f, ", {} tips", category.as_str())?;
+        }
+        if let Some(sound) = &self.sound {
+            write!(f, ", sound {sound}")?;
+        }
+        Ok(())
+    }
+}
+
+/// When reminders are sent
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleConfig {
+    /// Weekdays reminders go out on, every day when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+    /// Time of day reminders go out in, any time when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_hours: Option<WorkHours>,
+    /// Which clock intervals are measured on
+    #[serde(default, skip_serializing_if = "TimerClock::is_wall")]
+    pub clock: TimerClock,
+    /// Region whose public holidays go without reminders, `auto` for the locale's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holidays: Option<String>,
+}
+
+impl ScheduleConfig {
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+            && self.work_hours.is_none()
+            && self.clock.is_wall()
+            && self.holidays.is_none()
+    }
+
+    /// Whether reminders go out on `day`
+    pub fn is_reminder_day(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    /// Whether reminders may go out at `now`, on a reminder day within work hours
+    pub fn allows(&self, now: DateTime<Local>) -> bool {
+        self.is_reminder_day(now.weekday())
+            && self
+                .work_hours
+                .is_none_or(|hours| hours.contains(now.time()))
+    }
+
+    /// When reminders may go out again, `None` when they may right now
+    ///
+    /// That's the start of work hours, or midnight without them, on the
+    /// next reminder day.
+    pub fn resumes_at(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        if self.allows(now) {
+            return None;
+        }
+
+        let start = self.work_hours.map_or(NaiveTime::MIN, |hours| hours.start);
+        (0..=7)
+            .filter_map(|offset| now.date_naive().checked_add_days(Days::new(offset)))
+            .filter(|day| self.is_reminder_day(day.weekday()))
+            .filter_map(|day| day.and_time(start).and_local_timezone(Local).earliest())
+            .find(|at| *at > now)
+    }
+}
+
+/// Clock the time between reminders and the length of breaks run on
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerClock {
+    /// Time of day, so a changed clock or an NTP correction moves reminders
+    #[default]
+    Wall,
+    /// Time elapsed, which clock changes don't touch
+    Monotonic,
+}
+
+impl TimerClock {
+    pub fn is_wall(&self) -> bool {
+        *self == Self::Wall
+    }
+}
+
+impl FromStr for TimerClock {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "wall" => Ok(Self::Wall),
+            "monotonic" => Ok(Self::Monotonic),
+            _ => Err(format!(
+                "Invalid clock: '{value}'. Use 'wall' or 'monotonic'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TimerClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Wall => "wall",
+            Self::Monotonic => "monotonic",
+        })
+    }
+}
+
+/// Part of the day reminders go out in, e.g. 09:00 to 17:30
+///
+/// An end before the start spans midnight, for night shifts.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct WorkHours {
+    #[serde(with = "clock_time")]
+    pub start: NaiveTime,
+    #[serde(with = "clock_time")]
+    pub end: NaiveTime,
+}
+
+impl WorkHours {
+    /// Whether `time` falls within the hours, the end excluded
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl FromStr for WorkHours {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid work hours: '{value}'. Use e.g. 09:00-17:30");
+        let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        let (Some(start), Some(end)) = (parse(start), parse(end)) else {
+            return Err(invalid());
+        };
+        if start == end {
+            return Err("Work hours must end at a different time than they start".to_string());
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl fmt::Display for WorkHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// Times of day as "HH:MM" in the config file
+mod clock_time {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.format("%H:%M"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&value, "%H:%M").map_err(serde::de::Error::custom)
+    }
+}
+
+/// `schedule.work_hours` for messages, "any time" when unset
+pub fn describe_work_hours(hours: Option<WorkHours>) -> String {
+    hours.map_or_else(|| "any time".to_string(), |hours| hours.to_string())
+}
+
+/// How insistent a desktop notification is, honored on Linux and the BSDs
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Urgency {
+    Low,
+    #[default]
+    Normal,
+    /// Shown over full-screen apps and, on most desktops, kept until dismissed
+    Critical,
+}
+
+impl FromStr for Urgency {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "critical" => Ok(Self::Critical),
+            _ => Err(format!(
+                "Invalid urgency: '{value}'. Use 'low', 'normal' or 'critical'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Urgency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::Critical => "critical",
+        })
+    }
+}
+
+/// How reminders read
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct NotificationConfig {
+    /// Phrasing of titles and tips
+    #[serde(default)]
+    pub tone: Tone,
+    /// Notification or break overlay, for the desktop channel
+    #[serde(default)]
+    pub style: Style,
+    /// Also read break reminders aloud, whichever channel delivered them
+    #[serde(default)]
+    pub speak: bool,
+    /// How long desktop notifications stay on screen
+    #[serde(default = "default_notification_timeout")]
+    pub timeout_seconds: u64,
+    /// Urgency of desktop notifications
+    #[serde(default)]
+    pub urgency: Urgency,
+    /// Keep desktop notifications until dismissed, whatever the timeout
+    #[serde(default)]
+    pub sticky: bool,
+}
+
+fn default_notification_timeout() -> u64 {
+    5
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            tone: Tone::default(),
+            style: Style::default(),
+            speak: false,
+            timeout_seconds: default_notification_timeout(),
+            urgency: Urgency::default(),
+            sticky: false,
+        }
+    }
+}
+
+impl NotificationConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// What `szmer break` has you do while the break lasts
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BreakActivity {
+    /// Count the break down
+    #[default]
+    None,
+    /// A guided breathing exercise
+    Breathing,
+}
+
+impl FromStr for BreakActivity {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "breathing" => Ok(Self::Breathing),
+            _ => Err(format!(
+                "Invalid break activity: '{value}'. Use 'none' or 'breathing'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for BreakActivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Breathing => "breathing",
+        })
+    }
+}
+
+/// The break `szmer break` walks through
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BreakConfig {
+    #[serde(default)]
+    pub activity: BreakActivity,
+    /// Timing of the breathing activity
+    #[serde(default)]
+    pub breathing: breathing::Pattern,
+}
+
+impl BreakConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Read-only sharing of the daily break summary
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ShareConfig {
+    /// Secret part of the share URL, sharing is off without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl ShareConfig {
+    pub fn is_empty(&self) -> bool {
+        self.token.is_none()
+    }
+}
+
+/// Token tools like Home Assistant send to `POST /notify` and `/pause`
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ApiConfig {
+    /// Bearer token for the trigger endpoints, they're off without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl ApiConfig {
+    pub fn is_empty(&self) -> bool {
+        self.token.is_none()
+    }
+}
+
+/// Work sessions and breaks of fixed length instead of a reminder interval
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PomodoroConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl PomodoroConfig {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled
+    }
+}
+
+/// Activity szmer reads from other tools
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct IntegrationsConfig {
+    /// Git repositories whose commits are counted as work
+    #[serde(default, skip_serializing_if = "GitConfig::is_empty")]
+    pub git: GitConfig,
+}
+
+impl IntegrationsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.git.is_empty()
+    }
+}
+
+/// Git repositories to sample commit activity from
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct GitConfig {
+    /// Paths of the repositories
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<PathBuf>,
+}
+
+impl GitConfig {
+    pub fn is_empty(&self) -> bool {
+        self.repos.is_empty()
+    }
+}
+
+/// Terminal-only delivery settings for machines without a desktop
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HeadlessConfig {
+    /// When to use terminal delivery instead of desktop notifications
+    #[serde(default)]
+    pub mode: HeadlessMode,
+    /// How reminders are delivered to the terminal
+    #[serde(default)]
+    pub method: TerminalMethod,
+    /// Named pipe reminders are appended to when using the pipe method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipe_path: Option<PathBuf>,
+    /// Whether to also print reminders in terminals logged in over SSH
+    #[serde(default)]
+    pub mirror_ssh: bool,
+}
+
 /// Main application configuration
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
+    /// Format of the file, upgraded on load when older, see `CONFIG_VERSION`
+    #[serde(default)]
+    pub version: u32,
     /// Notification sound name (None = system default)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notification_sound: Option<String>,
     /// Whether notifications are paused
     #[serde(default)]
     pub paused: bool,
+    /// When a pause from `szmer stop --for` or `--until` ends on its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused_until: Option<DateTime<Local>>,
+    /// When reminders were paused, for the summary `szmer resume` prints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused_since: Option<DateTime<Local>>,
     /// Break reminder interval in seconds
     #[serde(default = "default_interval")]
     pub interval_seconds: u64,
+    /// How long a break lasts after a reminder, in seconds
+    #[serde(default = "default_break_duration")]
+    pub break_duration_seconds: u64,
+    /// What to do during a break started with `szmer break`
+    #[serde(
+        rename = "break",
+        default,
+        skip_serializing_if = "BreakConfig::is_empty"
+    )]
+    pub breaks: BreakConfig,
+    /// When reminders go out
+    #[serde(default, skip_serializing_if = "ScheduleConfig::is_empty")]
+    pub schedule: ScheduleConfig,
+    /// How reminders read
+    #[serde(default, skip_serializing_if = "NotificationConfig::is_empty")]
+    pub notification: NotificationConfig,
+    /// Language of messages and tips, e.g. `pl`, the environment's when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
     /// Timewarrior integration settings
     #[serde(default)]
     pub timewarrior: TimewarriorConfig,
+    /// Terminal-only delivery settings
+    #[serde(default)]
+    pub headless: HeadlessConfig,
+    /// Notification channels and their fallback order
+    #[serde(default)]
+    pub delivery: DeliveryConfig,
+    /// Push notifications to a phone through ntfy
+    #[serde(default, skip_serializing_if = "NtfyConfig::is_empty")]
+    pub ntfy: NtfyConfig,
+    /// Names of the plugins szmer may run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<String>,
+    /// Where reminder tips come from
+    #[serde(default, skip_serializing_if = "TipSource::is_builtin")]
+    pub tips: TipSource,
+    /// Tips of your own, used with the built-in source along with `tips.txt`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_tips: Vec<String>,
+    /// Show only custom tips, rather than mixing them into the built-in ones
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub replace_builtin_tips: bool,
+    /// How often built-in tips of each category come up, 1 when unset, 0 for never
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tip_weights: BTreeMap<TipCategory, u32>,
+    /// Situations that hold reminders back
+    #[serde(default, skip_serializing_if = "SuppressConfig::is_empty")]
+    pub suppress: SuppressConfig,
+    /// Read-only share link for `szmer serve --public`
+    #[serde(default, skip_serializing_if = "ShareConfig::is_empty")]
+    pub share: ShareConfig,
+    /// Trigger endpoints of `szmer serve` for other tools
+    #[serde(default, skip_serializing_if = "ApiConfig::is_empty")]
+    pub api: ApiConfig,
+    /// Turning other audio down while a reminder sounds
+    #[serde(default, skip_serializing_if = "DuckingConfig::is_empty")]
+    pub ducking: DuckingConfig,
+    /// Following up on ignored breaks
+    #[serde(default, skip_serializing_if = "FollowUpConfig::is_empty")]
+    pub follow_up: FollowUpConfig,
+    /// Holding reminders back while away from the machine
+    #[serde(default, skip_serializing_if = "AwayConfig::is_empty")]
+    pub away: AwayConfig,
+    /// Look of the break overlay
+    #[serde(default, skip_serializing_if = "OverlayConfig::is_empty")]
+    pub overlay: OverlayConfig,
+    /// Links reminders open, by tip category or `default`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, String>,
+    /// Activity read from other tools
+    #[serde(default, skip_serializing_if = "IntegrationsConfig::is_empty")]
+    pub integrations: IntegrationsConfig,
+    /// Extra reminders with their own interval, tips and sound
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<BreakProfile>,
+    /// Named break intervals in seconds, added to or replacing the built-in ones
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub interval_presets: BTreeMap<String, u64>,
+    /// Pomodoro cycles, started with `szmer pomodoro`
+    #[serde(default, skip_serializing_if = "PomodoroConfig::is_empty")]
+    pub pomodoro: PomodoroConfig,
+}
+
+/// A key `szmer config set` takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ConfigKey {
+    pub name: &'static str,
+    /// What the value looks like
+    pub value: &'static str,
+    pub about: &'static str,
+}
+
+const fn key(name: &'static str, value: &'static str, about: &'static str) -> ConfigKey {
+    ConfigKey { name, value, about }
+}
+
+/// Every key `szmer config set` and `szmer config unset` take, for `szmer config keys`
+pub const CONFIG_KEYS: &[ConfigKey] = &[
+    key(
+        "interval",
+        "30m, 1h or preset:<name>",
+        "How often reminders go out",
+    ),
+    key(
+        "interval_presets.<name>",
+        "50m, empty to remove",
+        "A named interval",
+    ),
+    key("break_duration", "5m", "How long a break lasts"),
+    key(
+        "break.activity",
+        "none or breathing",
+        "What szmer break has you do",
+    ),
+    key(
+        "break.breathing",
+        "4-7-8",
+        "Seconds to breathe in, hold and breathe out",
+    ),
+    key("sound", "a sound name, or default", "Sound reminders play"),
+    key("paused", "true or false", "Hold reminders until resumed"),
+    key(
+        "locale",
+        "en, pl or system",
+        "Language of messages and tips",
+    ),
+    key(
+        "notification.tone",
+        "gentle, neutral, drill-sergeant or humorous",
+        "How reminders read",
+    ),
+    key(
+        "notification.style",
+        "notification or overlay",
+        "How reminders show",
+    ),
+    key(
+        "notification.speak",
+        "true or false",
+        "Also read reminders aloud",
+    ),
+    key(
+        "notification.timeout",
+        "10s",
+        "How long notifications stay on screen",
+    ),
+    key(
+        "notification.urgency",
+        "low, normal or critical",
+        "Urgency of notifications on Linux and the BSDs",
+    ),
+    key(
+        "notification.sticky",
+        "true or false",
+        "Keep notifications until dismissed",
+    ),
+    key("schedule.days", "mon-fri", "Days reminders go out on"),
+    key(
+        "schedule.work_hours",
+        "09:00-17:30 or off",
+        "Time of day reminders go out",
+    ),
+    key(
+        "schedule.clock",
+        "wall or monotonic",
+        "What the interval is counted on",
+    ),
+    key(
+        "schedule.holidays",
+        "pl, auto or off",
+        "Skip public holidays of a region",
+    ),
+    key(
+        "timewarrior.enabled",
+        "true or false",
+        "Skip reminders while not tracking time",
+    ),
+    key(
+        "headless.mode",
+        "auto, always or never",
+        "When to deliver to the terminal",
+    ),
+    key(
+        "headless.method",
+        "bell, wall or pipe",
+        "How terminal reminders are delivered",
+    ),
+    key(
+        "headless.pipe",
+        "a path",
+        "Named pipe for headless.method pipe",
+    ),
+    key(
+        "headless.mirror_ssh",
+        "true or false",
+        "Also print reminders in SSH sessions",
+    ),
+    key(
+        "delivery.chain",
+        "desktop,terminal",
+        "Channels to try, in order",
+    ),
+    key(
+        "delivery.webhook",
+        "a URL",
+        "Where the webhook channel posts",
+    ),
+    key(
+        "delivery.deadline",
+        "seconds",
+        "How long gates get to answer",
+    ),
+    key("ntfy.topic", "a topic", "Topic the ntfy channel pushes to"),
+    key(
+        "ntfy.server",
+        "a URL",
+        "Self-hosted ntfy server, ntfy.sh by default",
+    ),
+    key(
+        "ntfy.token",
+        "a token",
+        "Access token for a protected topic",
+    ),
+    key(
+        "ntfy.priority",
+        "min, low, default, high or urgent",
+        "Priority of pushed reminders",
+    ),
+    key(
+        "ntfy.mirror",
+        "true or false",
+        "Also push reminders another channel delivered",
+    ),
+    key("plugins.enabled", "focus,tips", "Plugins szmer may run"),
+    key(
+        "tips.source",
+        "builtin, file:, command: or a URL",
+        "Where tips come from",
+    ),
+    key(
+        "tips.replace_builtin",
+        "true or false",
+        "Show only your own tips, not the built-in ones",
+    ),
+    key(
+        "tips.weights.<category>",
+        "a number, 0 for none",
+        "How often tips of a category come up",
+    ),
+    key(
+        "suppress.apps",
+        "zoom.us,Keynote",
+        "Hold reminders while these apps are in front",
+    ),
+    key(
+        "suppress.networks",
+        "a list of networks",
+        "Hold reminders on these networks",
+    ),
+    key(
+        "suppress.screen_sharing",
+        "true or false",
+        "Hold reminders while the screen is shared",
+    ),
+    key(
+        "suppress.focus_modes",
+        "a list of Focus modes",
+        "Turn reminders off during these",
+    ),
+    key(
+        "integrations.git.repos",
+        "a list of paths",
+        "Repositories to count commits in",
+    ),
+    key(
+        "ducking.percent",
+        "0 to 100",
+        "How far other audio is lowered",
+    ),
+    key(
+        "ducking.during_break",
+        "true or false",
+        "Keep audio lowered for the whole break",
+    ),
+    key(
+        "follow_up.delay",
+        "2m or off",
+        "Remind again when a break is ignored",
+    ),
+    key(
+        "away.after",
+        "2h or off",
+        "Hold reminders back after this long without input",
+    ),
+    key(
+        "overlay.theme",
+        "dark, light, minimal or high-contrast",
+        "Colors and sizes of the break overlay",
+    ),
+    key(
+        "overlay.text_color",
+        "a color like #ffcc00",
+        "Overlay text color",
+    ),
+    key(
+        "overlay.font_size",
+        "8 to 96",
+        "Overlay text size in points",
+    ),
+    key(
+        "overlay.countdown",
+        "clock or bar",
+        "How the overlay shows the time left",
+    ),
+    key(
+        "overlay.motion",
+        "auto, full or reduced",
+        "Animation in the overlay and the breathing exercise",
+    ),
+    key(
+        "overlay.grace",
+        "30s or off",
+        "Countdown to postpone from before the overlay",
+    ),
+    key(
+        "overlay.max_postpones",
+        "a number or off",
+        "Postpones allowed a day",
+    ),
+    key(
+        "pomodoro.enabled",
+        "true or false",
+        "Run pomodoro cycles instead of the interval",
+    ),
+    key("links.default", "a URL", "Link reminders open"),
+    key("links.movement", "a URL", "Link for movement tips"),
+    key("links.stretching", "a URL", "Link for stretching tips"),
+    key("links.eyes", "a URL", "Link for eye tips"),
+    key("links.breathing", "a URL", "Link for breathing tips"),
+    key("links.posture", "a URL", "Link for posture tips"),
+    key("links.hydration", "a URL", "Link for hydration tips"),
+    key("links.refresh", "a URL", "Link for refresh tips"),
+];
+
+/// Whether `szmer config set` takes `key`, presets by any name
+pub fn is_config_key(key: &str) -> bool {
+    key.starts_with("interval_presets.")
+        || key
+            .strip_prefix("tips.weights.")
+            .is_some_and(|category| TipCategory::from_name(category).is_some())
+        || CONFIG_KEYS.iter().any(|k| k.name == key)
+}
+
+/// Intervals szmer accepts, from a minute to a day
+///
+/// The schedulers count in whole minutes at best.
+pub const INTERVAL_SECONDS: RangeInclusive<u64> = 60..=86_400;
+
+/// A config value szmer can't use, with the way to fix it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Problem {
+    /// Key as `szmer config set` takes it
+    pub key: String,
+    pub message: String,
+    /// Command or edit that fixes it
+    pub fix: String,
+}
+
+impl Problem {
+    pub fn new(key: impl Into<String>, message: String, fix: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            message,
+            fix: fix.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}. Fix: {}", self.key, self.message, self.fix)
+    }
+}
+
+/// Bring a parsed config file up to `CONFIG_VERSION`, returning the version it had
+fn migrate(value: &mut serde_json::Value) -> Result<u32, String> {
+    let Some(root) = value.as_object_mut() else {
+        return Ok(CONFIG_VERSION);
+    };
+    let version = match root.get("version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| format!("version: {version} is not a config version"))?,
+    };
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "The config is version {version}, written by a newer szmer. This one reads up to version {CONFIG_VERSION}"
+        ));
+    }
+
+    for step in &MIGRATIONS[version as usize..] {
+        step(root);
+    }
+    root.insert("version".to_string(), CONFIG_VERSION.into());
+    Ok(version)
+}
+
+/// Whether migrating `original` only stamped the version, nothing worth rewriting the file for
+/// The pause kept next to a read-only config file
+#[derive(Serialize, Deserialize)]
+struct PauseState {
+    paused: bool,
+    paused_until: Option<DateTime<Local>>,
+    paused_since: Option<DateTime<Local>>,
+}
+
+/// Whether szmer can't write the file, following symlinks
+fn is_read_only(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().readonly())
+}
+
+fn is_only_restamped(original: &serde_json::Value, migrated: &serde_json::Value) -> bool {
+    let mut restamped = original.clone();
+    if let (Some(restamped), Some(version)) = (restamped.as_object_mut(), migrated.get("version")) {
+        restamped.insert("version".to_string(), version.clone());
+    }
+    restamped == *migrated
+}
+
+/// Take out values serde would reject with an error that names no key
+///
+/// They are left to their defaults, so `szmer config set` can still load
+/// the file to put a good value in.
+fn take_unparsable(value: &mut serde_json::Value) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let Some(root) = value.as_object_mut() else {
+        return problems;
+    };
+
+    if let Some(interval) = root.get("interval_seconds").filter(|v| !v.is_u64()) {
+        problems.push(Problem::new(
+            "interval",
+            format!("{interval} is not a number of seconds"),
+            "szmer config set interval 30m",
+        ));
+        root.remove("interval_seconds");
+    }
+
+    let schedule = root.get_mut("schedule").and_then(|s| s.as_object_mut());
+    if let Some(schedule) = schedule {
+        if let Some(problem) = schedule.get("work_hours").and_then(work_hours_problem) {
+            problems.push(problem);
+            schedule.remove("work_hours");
+        }
+    }
+
+    problems
+}
+
+fn work_hours_problem(hours: &serde_json::Value) -> Option<Problem> {
+    let parses = |field: &str| {
+        hours[field]
+            .as_str()
+            .is_some_and(|time| NaiveTime::parse_from_str(time, "%H:%M").is_ok())
+    };
+    if hours.is_null() || (parses("start") && parses("end")) {
+        return None;
+    }
+
+    let written = match hours.as_str() {
+        Some(text) => format!("'{text}'"),
+        None => hours.to_string(),
+    };
+    Some(Problem::new(
+        "schedule.work_hours",
+        format!("{written} is not a start and end time like 09:00 and 17:30"),
+        "szmer config set schedule.work_hours 09:00-17:30",
+    ))
+}
+
+/// `follow_up.delay` or `away.after` for messages, "off" when unset
+pub fn describe_optional_duration(delay_seconds: Option<u64>) -> String {
+    delay_seconds.map_or_else(|| "off".to_string(), format_interval)
+}
+
+/// `tips.weights` for messages, e.g. "eyes 3, movement 0", "even" when unset
+pub fn describe_tip_weights(weights: &BTreeMap<TipCategory, u32>) -> String {
+    if weights.is_empty() {
+        return "even".to_string();
+    }
+    weights
+        .iter()
+        .map(|(category, weight)| format!("{} {weight}", category.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `schedule.days` for messages, "every day" when unrestricted
+pub fn describe_days(days: &[Weekday]) -> String {
+    if days.is_empty() {
+        "every day".to_string()
+    } else {
+        format_days(days)
+    }
 }
 
 fn default_interval() -> u64 {
     3600 // 1 hour default
 }
 
+fn default_break_duration() -> u64 {
+    300 // 5 minutes default
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             notification_sound: None,
             paused: false,
+            paused_until: None,
+            paused_since: None,
             interval_seconds: default_interval(),
+            break_duration_seconds: default_break_duration(),
+            schedule: ScheduleConfig::default(),
+            notification: NotificationConfig::default(),
+            locale: None,
             timewarrior: TimewarriorConfig::default(),
+            headless: HeadlessConfig::default(),
+            delivery: DeliveryConfig::default(),
+            plugins: Vec::new(),
+            tips: TipSource::default(),
+            custom_tips: Vec::new(),
+            replace_builtin_tips: false,
+            tip_weights: BTreeMap::new(),
+            ntfy: NtfyConfig::default(),
+            suppress: SuppressConfig::default(),
+            share: ShareConfig::default(),
+            api: ApiConfig::default(),
+            breaks: BreakConfig::default(),
+            ducking: DuckingConfig::default(),
+            follow_up: FollowUpConfig::default(),
+            away: AwayConfig::default(),
+            overlay: OverlayConfig::default(),
+            links: BTreeMap::new(),
+            integrations: IntegrationsConfig::default(),
+            profiles: Vec::new(),
+            interval_presets: BTreeMap::new(),
+            pomodoro: PomodoroConfig::default(),
         }
     }
 }
 
 impl Config {
+    /// Built-in presets, then the user's own, with user values winning by name
+    pub fn interval_presets(&self) -> Vec<(String, u64)> {
+        let mut presets: Vec<(String, u64)> = BUILTIN_INTERVAL_PRESETS
+            .iter()
+            .map(|(name, seconds)| {
+                let seconds = self.interval_presets.get(*name).unwrap_or(seconds);
+                (name.to_string(), *seconds)
+            })
+            .collect();
+        presets.extend(
+            self.interval_presets
+                .iter()
+                .filter(|(name, _)| !BUILTIN_INTERVAL_PRESETS.iter().any(|(b, _)| b == name))
+                .map(|(name, seconds)| (name.clone(), *seconds)),
+        );
+        presets
+    }
+
+    /// How often the scheduler or the daemon checks in
+    ///
+    /// That's the break interval, or in pomodoro mode a step short enough
+    /// to end each work session and break on time.
+    pub fn timer_seconds(&self) -> u64 {
+        if self.pomodoro.enabled {
+            pomodoro::TICK_SECONDS
+        } else {
+            self.interval_seconds
+        }
+    }
+
+    /// The break profile called `name`
+    pub fn profile(&self, name: &str) -> Result<&BreakProfile, String> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .ok_or_else(|| format!("Unknown break profile '{name}'"))
+    }
+
+    /// Seconds of the preset called `name`
+    pub fn interval_preset(&self, name: &str) -> Result<u64, String> {
+        let presets = self.interval_presets();
+        presets
+            .iter()
+            .find(|(preset, _)| preset == name)
+            .map(|(_, seconds)| *seconds)
+            .ok_or_else(|| {
+                let names: Vec<&str> = presets.iter().map(|(name, _)| name.as_str()).collect();
+                format!("Unknown preset '{name}'. Available: {}", names.join(", "))
+            })
+    }
+
+    /// Load the config, failing with every problem in it at once
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_path = Self::get_config_path()?;
+        let (config, problems) = Self::load_checked()?;
+        if problems.is_empty() {
+            return Ok(config);
+        }
+
+        Err(SzmerError::InvalidConfig {
+            path: Self::find_config_file()?.unwrap_or_default(),
+            problems: problems.iter().map(ToString::to_string).collect(),
+        }
+        .into())
+    }
+
+    /// `locale` from the config file, read on its own
+    ///
+    /// Messages are translated before the config is loaded and while it's
+    /// being loaded, so this skips migrations and checks.
+    pub fn configured_locale() -> Option<String> {
+        let path = Self::find_config_file().ok()??;
+        let content = fs::read_to_string(&path).ok()?;
+        let value: serde_json::Value = if path.ends_with(LEGACY_CONFIG_FILE) {
+            serde_json::from_str(&content).ok()?
+        } else {
+            toml_file::parse(&content).ok()?
+        };
+        value.get("locale")?.as_str().map(str::to_string)
+    }
+
+    /// Load the config along with what's wrong with it
+    ///
+    /// Values that can't be read at all are left at their defaults, others
+    /// are kept as they are.
+    pub fn load_checked() -> Result<(Self, Vec<Problem>), Box<dyn std::error::Error>> {
+        let Some(config_path) = Self::find_config_file()? else {
+            return Ok((Self::default(), Vec::new()));
+        };
+
+        let content = fs::read_to_string(&config_path)?;
+        let mut value = if config_path.ends_with(LEGACY_CONFIG_FILE) {
+            serde_json::from_str(&content)?
+        } else {
+            toml_file::parse(&content).map_err(|e| format!("{}: {e}", config_path.display()))?
+        };
+        let original = value.clone();
+        let from_version =
+            migrate(&mut value).map_err(|e| format!("{}: {e}", config_path.display()))?;
+        let migrated = !is_only_restamped(&original, &value);
+
+        let mut problems = take_unparsable(&mut value);
+        let mut config: Self =
+            serde_json::from_value(value).map_err(|e| format!("{}: {e}", config_path.display()))?;
+        let read_only = is_read_only(&config_path);
+        if read_only {
+            config.load_pause_state();
+        }
+        config.expire_pause(Local::now());
+
+        problems.extend(config.problems());
+        // Rewriting a file with problems would lose the values that couldn't be read
+        if migrated && problems.is_empty() && !read_only {
+            let backup_path = config_path.with_extension(format!(
+                "{}.v{from_version}.bak",
+                config_path
+                    .extension()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            ));
+            fs::write(&backup_path, &content)?;
+            config.save()?;
+            eprintln!(
+                "Upgraded settings to version {CONFIG_VERSION}, the old file is kept as {}",
+                backup_path.display()
+            );
+        }
+        Ok((config, problems))
+    }
+
+    /// Values that parse but szmer can't work with
+    pub fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let range = format!(
+            "from {} to {}",
+            format_interval(*INTERVAL_SECONDS.start()),
+            format_interval(*INTERVAL_SECONDS.end())
+        );
 
-        if !config_path.exists() {
-            return Ok(Self::default());
+        if !INTERVAL_SECONDS.contains(&self.interval_seconds) {
+            problems.push(Problem::new(
+                "interval",
+                format!(
+                    "{} seconds is out of range, it must be {range}",
+                    self.interval_seconds
+                ),
+                "szmer config set interval 30m",
+            ));
         }
+        for profile in &self.profiles {
+            if !INTERVAL_SECONDS.contains(&profile.interval_seconds) {
+                problems.push(Problem::new(
+                    format!("profiles.{}", profile.name),
+                    format!(
+                        "{} seconds is out of range, it must be {range}",
+                        profile.interval_seconds
+                    ),
+                    format!("szmer profile add {} --every 20m", profile.name),
+                ));
+            }
+        }
+        if let Some(locale) = self.locale.as_deref().filter(|l| !i18n::is_available(l)) {
+            problems.push(Problem::new(
+                "locale",
+                format!(
+                    "There are no translations for {locale}, only for {}",
+                    i18n::available()
+                ),
+                "szmer config unset locale",
+            ));
+        }
+        if let Some(color) = self
+            .overlay
+            .text_color
+            .as_deref()
+            .filter(|color| !is_hex_color(color))
+        {
+            problems.push(Problem::new(
+                "overlay.text_color",
+                format!("'{color}' is not a color like #ffcc00"),
+                "szmer config set overlay.text_color '#ffffff'",
+            ));
+        }
+        if let Some(size) = self
+            .overlay
+            .font_size
+            .filter(|size| !OVERLAY_FONT_SIZES.contains(size))
+        {
+            problems.push(Problem::new(
+                "overlay.font_size",
+                format!(
+                    "{size}pt is out of range, it must be from {} to {}",
+                    OVERLAY_FONT_SIZES.start(),
+                    OVERLAY_FONT_SIZES.end()
+                ),
+                "szmer config set overlay.font_size 28",
+            ));
+        }
+        if self.ducking.percent > 100 {
+            problems.push(Problem::new(
+                "ducking.percent",
+                format!("{}% is more than all of the volume", self.ducking.percent),
+                "szmer config set ducking.percent 50",
+            ));
+        }
+
+        problems
+    }
+
+    /// Put a `szmer config set` key back to its default, `false` for unknown keys
+    ///
+    /// A preset or a link goes away entirely, there's no default to go back to.
+    pub fn unset(&mut self, key: &str) -> bool {
+        let default = Self::default();
+        match key {
+            "interval" => self.interval_seconds = default.interval_seconds,
+            "break_duration" => self.break_duration_seconds = default.break_duration_seconds,
+            "break.activity" => self.breaks.activity = default.breaks.activity,
+            "break.breathing" => self.breaks.breathing = default.breaks.breathing,
+            "sound" => self.notification_sound = default.notification_sound,
+            "paused" => self.resume(),
+            "locale" => self.locale = default.locale,
+            "notification.tone" => self.notification.tone = default.notification.tone,
+            "notification.style" => self.notification.style = default.notification.style,
+            "notification.speak" => self.notification.speak = default.notification.speak,
+            "notification.timeout" => {
+                self.notification.timeout_seconds = default.notification.timeout_seconds
+            }
+            "notification.urgency" => self.notification.urgency = default.notification.urgency,
+            "notification.sticky" => self.notification.sticky = default.notification.sticky,
+            "schedule.days" => self.schedule.days = default.schedule.days,
+            "schedule.work_hours" => self.schedule.work_hours = default.schedule.work_hours,
+            "schedule.clock" => self.schedule.clock = default.schedule.clock,
+            "schedule.holidays" => self.schedule.holidays = default.schedule.holidays,
+            "timewarrior.enabled" => self.timewarrior.enabled = default.timewarrior.enabled,
+            "headless.mode" => self.headless.mode = default.headless.mode,
+            "headless.method" => self.headless.method = default.headless.method,
+            "headless.pipe" => self.headless.pipe_path = default.headless.pipe_path,
+            "headless.mirror_ssh" => self.headless.mirror_ssh = default.headless.mirror_ssh,
+            "delivery.chain" => self.delivery.chain = default.delivery.chain,
+            "delivery.webhook" => self.delivery.webhook_url = default.delivery.webhook_url,
+            "delivery.deadline" => {
+                self.delivery.gate_deadline_seconds = default.delivery.gate_deadline_seconds
+            }
+            "ntfy.topic" => self.ntfy.topic = default.ntfy.topic,
+            "ntfy.server" => self.ntfy.server = default.ntfy.server,
+            "ntfy.token" => self.ntfy.token = default.ntfy.token,
+            "ntfy.priority" => self.ntfy.priority = default.ntfy.priority,
+            "ntfy.mirror" => self.ntfy.mirror = default.ntfy.mirror,
+            "plugins.enabled" => self.plugins = default.plugins,
+            "tips.source" => self.tips = default.tips,
+            "tips.replace_builtin" => self.replace_builtin_tips = default.replace_builtin_tips,
+            "suppress.apps" => self.suppress.apps = default.suppress.apps,
+            "suppress.networks" => self.suppress.networks = default.suppress.networks,
+            "suppress.screen_sharing" => {
+                self.suppress.screen_sharing = default.suppress.screen_sharing
+            }
+            "suppress.focus_modes" => self.suppress.focus_modes = default.suppress.focus_modes,
+            "integrations.git.repos" => {
+                self.integrations.git.repos = default.integrations.git.repos
+            }
+            "ducking.percent" => self.ducking.percent = default.ducking.percent,
+            "ducking.during_break" => self.ducking.during_break = default.ducking.during_break,
+            "follow_up.delay" => self.follow_up.delay_seconds = default.follow_up.delay_seconds,
+            "away.after" => self.away.after_seconds = default.away.after_seconds,
+            "overlay.theme" => self.overlay.theme = default.overlay.theme,
+            "overlay.text_color" => self.overlay.text_color = default.overlay.text_color,
+            "overlay.font_size" => self.overlay.font_size = default.overlay.font_size,
+            "overlay.countdown" => self.overlay.countdown = default.overlay.countdown,
+            "overlay.motion" => self.overlay.motion = default.overlay.motion,
+            "overlay.grace" => self.overlay.grace_seconds = default.overlay.grace_seconds,
+            "overlay.max_postpones" => self.overlay.max_postpones = default.overlay.max_postpones,
+            "pomodoro.enabled" => self.pomodoro.enabled = default.pomodoro.enabled,
+            _ => match key.split_once('.') {
+                Some(("interval_presets", name)) => {
+                    self.interval_presets.remove(name);
+                }
+                Some(("links", category)) if is_config_key(key) => {
+                    self.links.remove(category);
+                }
+                Some(("tips", weight)) if weight.starts_with("weights.") => {
+                    let name = &weight["weights.".len()..];
+                    self.tip_weights
+                        .retain(|category, _| category.as_str() != name);
+                }
+                _ => return false,
+            },
+        }
+        true
+    }
+
+    /// Pause reminders, until `until` if set
+    ///
+    /// Pausing again only changes when the pause ends, not when it began.
+    pub fn pause(&mut self, until: Option<DateTime<Local>>, now: DateTime<Local>) {
+        if !self.paused {
+            self.paused_since = Some(now);
+        }
+        self.paused = true;
+        self.paused_until = until;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.paused_until = None;
+        self.paused_since = None;
+    }
 
-        let content = fs::read_to_string(config_path)?;
-        Ok(serde_json::from_str(&content)?)
+    /// Lift a pause whose `paused_until` has passed
+    ///
+    /// Done on load, so a timed pause ends without `szmer resume` for the
+    /// scheduler and the daemon alike. The file catches up on the next save.
+    fn expire_pause(&mut self, now: DateTime<Local>) {
+        if self.paused_until.is_some_and(|until| until <= now) {
+            self.resume();
+        }
     }
 
+    /// Write the config as TOML, keeping comments already in the file
+    ///
+    /// A `config.json` from before the switch to TOML is renamed to
+    /// `config.json.bak` once its settings are saved to `config.toml`.
+    ///
+    /// A read-only file, like one home-manager links from the Nix store,
+    /// is left alone. Pausing then goes to a state file of its own, and
+    /// changing anything else is an error.
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let config_path = Self::get_config_path()?;
+        if is_read_only(&config_path) {
+            return self.save_pause_state(&config_path);
+        }
 
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        fs::write(config_path, serde_json::to_string_pretty(self)?)?;
+        let previous =
+            fs::read_to_string(&config_path).unwrap_or_else(|_| CONFIG_HEADER.to_string());
+        let content = toml_file::to_string(&serde_json::to_value(self)?, &previous)?;
+        fs::write(&config_path, content)?;
+
+        let legacy_path = config_path.with_file_name(LEGACY_CONFIG_FILE);
+        if legacy_path.exists() {
+            let backup_path = legacy_path.with_extension("json.bak");
+            fs::rename(&legacy_path, &backup_path)?;
+            eprintln!(
+                "Moved settings to {}, the old file is kept as {}",
+                config_path.display(),
+                backup_path.display()
+            );
+        }
+        Ok(())
+    }
+
+    fn pause_state_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(timestamp::get_cache_dir()?.join(PAUSE_STATE_FILE))
+    }
+
+    /// Take the pause from the state file, if there is one
+    fn load_pause_state(&mut self) {
+        let Some(state) = Self::pause_state_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<PauseState>(&content).ok())
+        else {
+            return;
+        };
+        self.paused = state.paused;
+        self.paused_until = state.paused_until;
+        self.paused_since = state.paused_since;
+    }
+
+    fn save_pause_state(&self, config_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let (file, _) = Self::load_checked()?;
+        if self.settings()? != file.settings()? {
+            return Err(format!(
+                "{} is read-only, e.g. managed by Nix. Change settings there instead",
+                config_path.display()
+            )
+            .into());
+        }
+
+        let state = PauseState {
+            paused: self.paused,
+            paused_until: self.paused_until,
+            paused_since: self.paused_since,
+        };
+        let path = Self::pause_state_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&state)?)?;
         Ok(())
     }
 
-    fn get_config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    /// Every setting, leaving out the pause
+    fn settings(&self) -> Result<serde_json::Value, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(map) = value.as_object_mut() {
+            for key in PAUSE_KEYS {
+                map.remove(key);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Replace the config file with the defaults, returning where the old one is kept
+    ///
+    /// A fresh file is written rather than saving over the old one, which
+    /// would keep its comments.
+    pub fn reset() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config_path = Self::find_config_file()?.ok_or("There's no config file to reset")?;
+        let backup_path = config_path.with_extension(format!(
+            "{}.reset.bak",
+            config_path
+                .extension()
+                .unwrap_or_default()
+                .to_string_lossy()
+        ));
+        fs::rename(&config_path, &backup_path)?;
+        Self::default().save()?;
+        Ok(backup_path)
+    }
+
+    pub fn get_config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let home = std::env::var("HOME")?;
         Ok(PathBuf::from(home).join(CONFIG_DIR).join(CONFIG_FILE))
     }
+
+    /// `config.toml` and the `config.json` it replaced, in the order they're read
+    pub fn config_files() -> Result<[PathBuf; 2], Box<dyn std::error::Error>> {
+        let config_path = Self::get_config_path()?;
+        let legacy_path = config_path.with_file_name(LEGACY_CONFIG_FILE);
+        Ok([config_path, legacy_path])
+    }
+
+    /// The file settings are read from: `config.toml`, or a `config.json`
+    /// that hasn't been moved yet, `None` before the first save
+    pub fn find_config_file() -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+        Ok(Self::config_files()?.into_iter().find(|path| path.exists()))
+    }
+
+    /// Describe the settings that differ from `previous`, one line per change
+    ///
+    /// Used by the daemon to log what a hot reload applied.
+    pub fn changes_from(&self, previous: &Config) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if self.interval_seconds != previous.interval_seconds {
+            changes.push(format!(
+                "interval: {} → {}",
+                format_interval(previous.interval_seconds),
+                format_interval(self.interval_seconds)
+            ));
+        }
+        if self.break_duration_seconds != previous.break_duration_seconds {
+            changes.push(format!(
+                "break duration: {} → {}",
+                format_interval(previous.break_duration_seconds),
+                format_interval(self.break_duration_seconds)
+            ));
+        }
+        if self.notification_sound != previous.notification_sound {
+            let sound = |s: &Option<String>| s.clone().unwrap_or_else(|| "default".to_string());
+            changes.push(format!(
+                "sound: {} → {}",
+                sound(&previous.notification_sound),
+                sound(&self.notification_sound)
+            ));
+        }
+        if self.paused != previous.paused {
+            changes.push(format!("paused: {} → {}", previous.paused, self.paused));
+        }
+        if self.paused_until != previous.paused_until {
+            let until = |until: Option<DateTime<Local>>| {
+                until.map_or_else(
+                    || "none".to_string(),
+                    |until| until.format("%a %H:%M").to_string(),
+                )
+            };
+            changes.push(format!(
+                "paused_until: {} → {}",
+                until(previous.paused_until),
+                until(self.paused_until)
+            ));
+        }
+        if self.timewarrior.enabled != previous.timewarrior.enabled {
+            changes.push(format!(
+                "timewarrior.enabled: {} → {}",
+                previous.timewarrior.enabled, self.timewarrior.enabled
+            ));
+        }
+        if self.headless.mode != previous.headless.mode {
+            changes.push(format!(
+                "headless.mode: {} → {}",
+                previous.headless.mode, self.headless.mode
+            ));
+        }
+        if self.headless.method != previous.headless.method {
+            changes.push(format!(
+                "headless.method: {} → {}",
+                previous.headless.method, self.headless.method
+            ));
+        }
+        if self.headless.pipe_path != previous.headless.pipe_path {
+            changes.push("headless.pipe changed".to_string());
+        }
+        if self.headless.mirror_ssh != previous.headless.mirror_ssh {
+            changes.push(format!(
+                "headless.mirror_ssh: {} → {}",
+                previous.headless.mirror_ssh, self.headless.mirror_ssh
+            ));
+        }
+        if self.delivery.chain != previous.delivery.chain {
+            let chain = |chain: &[NotifierKind]| {
+                chain
+                    .iter()
+                    .map(NotifierKind::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            };
+            changes.push(format!(
+                "delivery.chain: {} → {}",
+                chain(&previous.delivery.chain),
+                chain(&self.delivery.chain)
+            ));
+        }
+        if self.delivery.webhook_url != previous.delivery.webhook_url {
+            changes.push("delivery.webhook changed".to_string());
+        }
+        if self.delivery.gate_deadline_seconds != previous.delivery.gate_deadline_seconds {
+            changes.push(format!(
+                "delivery.deadline: {}s → {}s",
+                previous.delivery.gate_deadline_seconds, self.delivery.gate_deadline_seconds
+            ));
+        }
+        if self.ntfy.topic != previous.ntfy.topic {
+            changes.push("ntfy.topic changed".to_string());
+        }
+        if self.ntfy.server != previous.ntfy.server {
+            changes.push("ntfy.server changed".to_string());
+        }
+        if self.ntfy.token != previous.ntfy.token {
+            changes.push("ntfy.token changed".to_string());
+        }
+        if self.ntfy.priority != previous.ntfy.priority {
+            changes.push(format!(
+                "ntfy.priority: {} → {}",
+                previous.ntfy.priority, self.ntfy.priority
+            ));
+        }
+        if self.ntfy.mirror != previous.ntfy.mirror {
+            changes.push(format!(
+                "ntfy.mirror: {} → {}",
+                previous.ntfy.mirror, self.ntfy.mirror
+            ));
+        }
+        if self.suppress.apps != previous.suppress.apps {
+            changes.push(format!(
+                "suppress.apps: [{}] → [{}]",
+                previous.suppress.apps.join(", "),
+                self.suppress.apps.join(", ")
+            ));
+        }
+        if self.suppress.networks != previous.suppress.networks {
+            changes.push(format!(
+                "suppress.networks: [{}] → [{}]",
+                previous.suppress.networks.join(", "),
+                self.suppress.networks.join(", ")
+            ));
+        }
+        if self.suppress.focus_modes != previous.suppress.focus_modes {
+            changes.push(format!(
+                "suppress.focus_modes: [{}] → [{}]",
+                previous.suppress.focus_modes.join(", "),
+                self.suppress.focus_modes.join(", ")
+            ));
+        }
+        if self.suppress.screen_sharing != previous.suppress.screen_sharing {
+            changes.push(format!(
+                "suppress.screen_sharing: {} → {}",
+                previous.suppress.screen_sharing, self.suppress.screen_sharing
+            ));
+        }
+        if self.schedule.days != previous.schedule.days {
+            changes.push(format!(
+                "schedule.days: {} → {}",
+                describe_days(&previous.schedule.days),
+                describe_days(&self.schedule.days)
+            ));
+        }
+        if self.schedule.work_hours != previous.schedule.work_hours {
+            changes.push(format!(
+                "schedule.work_hours: {} → {}",
+                describe_work_hours(previous.schedule.work_hours),
+                describe_work_hours(self.schedule.work_hours)
+            ));
+        }
+        if self.schedule.clock != previous.schedule.clock {
+            changes.push(format!(
+                "schedule.clock: {} → {}",
+                previous.schedule.clock, self.schedule.clock
+            ));
+        }
+        if self.schedule.holidays != previous.schedule.holidays {
+            changes.push(format!(
+                "schedule.holidays: {} → {}",
+                previous.schedule.holidays.as_deref().unwrap_or("off"),
+                self.schedule.holidays.as_deref().unwrap_or("off")
+            ));
+        }
+        if self.locale != previous.locale {
+            // Messages are translated once per process
+            changes.push(format!(
+                "locale: {} → {}, takes effect after a restart",
+                previous.locale.as_deref().unwrap_or("system"),
+                self.locale.as_deref().unwrap_or("system")
+            ));
+        }
+        if self.notification.tone != previous.notification.tone {
+            changes.push(format!(
+                "notification.tone: {} → {}",
+                previous.notification.tone, self.notification.tone
+            ));
+        }
+        if self.notification.style != previous.notification.style {
+            changes.push(format!(
+                "notification.style: {} → {}",
+                previous.notification.style, self.notification.style
+            ));
+        }
+        if self.notification.speak != previous.notification.speak {
+            changes.push(format!(
+                "notification.speak: {} → {}",
+                previous.notification.speak, self.notification.speak
+            ));
+        }
+        if self.notification.timeout_seconds != previous.notification.timeout_seconds {
+            changes.push(format!(
+                "notification.timeout: {}s → {}s",
+                previous.notification.timeout_seconds, self.notification.timeout_seconds
+            ));
+        }
+        if self.notification.urgency != previous.notification.urgency {
+            changes.push(format!(
+                "notification.urgency: {} → {}",
+                previous.notification.urgency, self.notification.urgency
+            ));
+        }
+        if self.notification.sticky != previous.notification.sticky {
+            changes.push(format!(
+                "notification.sticky: {} → {}",
+                previous.notification.sticky, self.notification.sticky
+            ));
+        }
+        if self.tips != previous.tips {
+            changes.push(format!("tips.source: {} → {}", previous.tips, self.tips));
+        }
+        if self.custom_tips != previous.custom_tips {
+            changes.push(format!(
+                "custom_tips: {} → {} tips",
+                previous.custom_tips.len(),
+                self.custom_tips.len()
+            ));
+        }
+        if self.replace_builtin_tips != previous.replace_builtin_tips {
+            changes.push(format!(
+                "tips.replace_builtin: {} → {}",
+                previous.replace_builtin_tips, self.replace_builtin_tips
+            ));
+        }
+        if self.tip_weights != previous.tip_weights {
+            changes.push(format!(
+                "tips.weights: {} → {}",
+                describe_tip_weights(&previous.tip_weights),
+                describe_tip_weights(&self.tip_weights)
+            ));
+        }
+        if self.integrations.git.repos != previous.integrations.git.repos {
+            changes.push(format!(
+                "integrations.git.repos: {} → {} repos",
+                previous.integrations.git.repos.len(),
+                self.integrations.git.repos.len()
+            ));
+        }
+        if self.ducking != previous.ducking {
+            changes.push(format!(
+                "ducking: {}% → {}%",
+                previous.ducking.percent, self.ducking.percent
+            ));
+        }
+        if self.follow_up != previous.follow_up {
+            changes.push(format!(
+                "follow_up.delay: {} → {}",
+                describe_optional_duration(previous.follow_up.delay_seconds),
+                describe_optional_duration(self.follow_up.delay_seconds)
+            ));
+        }
+        if self.away != previous.away {
+            changes.push(format!(
+                "away.after: {} → {}",
+                describe_optional_duration(previous.away.after_seconds),
+                describe_optional_duration(self.away.after_seconds)
+            ));
+        }
+        if self.overlay != previous.overlay {
+            changes.push("overlay changed".to_string());
+        }
+        if self.links != previous.links {
+            changes.push("links changed".to_string());
+        }
+        if self.plugins != previous.plugins {
+            changes.push(format!(
+                "plugins.enabled: {} → {}",
+                previous.plugins.join(","),
+                self.plugins.join(",")
+            ));
+        }
+        if self.breaks != previous.breaks {
+            changes.push(format!(
+                "break: {} ({}) → {} ({})",
+                previous.breaks.activity,
+                previous.breaks.breathing,
+                self.breaks.activity,
+                self.breaks.breathing
+            ));
+        }
+        if self.pomodoro.enabled != previous.pomodoro.enabled {
+            changes.push(format!(
+                "pomodoro.enabled: {} → {}",
+                previous.pomodoro.enabled, self.pomodoro.enabled
+            ));
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_changes_from_identical_config() {
+        let config = Config::default();
+        assert!(config.changes_from(&Config::default()).is_empty());
+    }
+
+    #[test]
+    fn test_changes_from_lists_each_change() {
+        let previous = Config::default();
+        let config = Config {
+            interval_seconds: 1500,
+            notification_sound: Some("Glass".to_string()),
+            timewarrior: TimewarriorConfig { enabled: true },
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.changes_from(&previous),
+            vec![
+                "interval: 1 hour → 25 minutes",
+                "sound: default → Glass",
+                "timewarrior.enabled: false → true",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notifier_kind_round_trips_plugins() {
+        let chain: Vec<NotifierKind> =
+            serde_json::from_str(r#"["desktop","plugin:slack"]"#).unwrap();
+        assert_eq!(
+            chain,
+            [
+                NotifierKind::Desktop,
+                NotifierKind::Plugin("slack".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&chain).unwrap(),
+            r#"["desktop","plugin:slack"]"#
+        );
+        assert!("plugin:".parse::<NotifierKind>().is_err());
+    }
+
+    #[test]
+    fn test_is_reminder_day() {
+        let mut schedule = ScheduleConfig::default();
+        assert!(schedule.is_reminder_day(Weekday::Sun));
+
+        schedule.days = vec![Weekday::Mon, Weekday::Fri];
+        assert!(schedule.is_reminder_day(Weekday::Fri));
+        assert!(!schedule.is_reminder_day(Weekday::Sun));
+    }
+
+    #[test]
+    fn test_timed_pause_expires() {
+        let at = |time: &str| MockClock::at(time).now();
+        let mut config = Config::default();
+        config.pause(None, at("2025-01-06 13:00:00"));
+        // Setting an end keeps when the pause began
+        config.pause(Some(at("2025-01-06 15:00:00")), at("2025-01-06 14:00:00"));
+        assert_eq!(config.paused_since, Some(at("2025-01-06 13:00:00")));
+
+        config.expire_pause(at("2025-01-06 14:59:00"));
+        assert!(config.paused);
+
+        config.expire_pause(at("2025-01-06 15:00:00"));
+        assert!(!config.paused);
+        assert_eq!(config.paused_until, None);
+        assert_eq!(config.paused_since, None);
+    }
+
+    #[test]
+    fn test_settings_leave_out_the_pause() {
+        let mut paused = Config::default();
+        paused.pause(None, MockClock::at("2025-01-06 13:00:00").now());
+        assert_eq!(
+            paused.settings().unwrap(),
+            Config::default().settings().unwrap()
+        );
+
+        paused.interval_seconds = 600;
+        assert_ne!(
+            paused.settings().unwrap(),
+            Config::default().settings().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_read_only_follows_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.toml");
+        fs::write(&file, "").unwrap();
+        assert!(!is_read_only(&file));
+
+        let mut permissions = fs::metadata(&file).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&file, permissions).unwrap();
+        let link = dir.path().join("link.toml");
+        std::os::unix::fs::symlink(&file, &link).unwrap();
+        assert!(is_read_only(&link));
+        assert!(!is_read_only(&dir.path().join("missing.toml")));
+    }
+
+    #[test]
+    fn test_work_hours_and_when_reminders_resume() {
+        let at = |time: &str| MockClock::at(time).now();
+        let schedule = ScheduleConfig {
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            work_hours: Some("09:00-17:30".parse().unwrap()),
+            ..ScheduleConfig::default()
+        };
+
+        // 2025-01-06 is a Monday
+        assert!(schedule.allows(at("2025-01-06 09:00:00")));
+        assert!(!schedule.allows(at("2025-01-06 17:30:00")));
+        assert_eq!(schedule.resumes_at(at("2025-01-06 12:00:00")), None);
+        assert_eq!(
+            schedule.resumes_at(at("2025-01-06 07:15:00")),
+            Some(at("2025-01-06 09:00:00"))
+        );
+        // Friday evening waits for Monday morning
+        assert_eq!(
+            schedule.resumes_at(at("2025-01-10 18:00:00")),
+            Some(at("2025-01-13 09:00:00"))
+        );
+
+        let night: WorkHours = "22:00-06:00".parse().unwrap();
+        assert!(night.contains(NaiveTime::from_hms_opt(2, 0, 0).unwrap()));
+        assert!(!night.contains(NaiveTime::from_hms_opt(12, 0, 0).unwrap()));
+        assert_eq!(night.to_string(), "22:00-06:00");
+        assert!("9-5".parse::<WorkHours>().is_err());
+    }
+
+    #[test]
+    fn test_tone_round_trips() {
+        for value in ["gentle", "neutral", "drill-sergeant", "humorous"] {
+            assert_eq!(value.parse::<Tone>().unwrap().to_string(), value);
+        }
+        assert!("grumpy".parse::<Tone>().is_err());
+    }
+
+    #[test]
+    fn test_notification_keeps_the_five_second_timeout_by_default() {
+        let notification: NotificationConfig =
+            serde_json::from_str(r#"{"tone":"gentle","urgency":"critical"}"#).unwrap();
+        assert_eq!(notification.timeout_seconds, 5);
+        assert_eq!(notification.urgency, Urgency::Critical);
+        assert!(!notification.sticky);
+        assert!(NotificationConfig::default().is_empty());
+        assert_eq!("Critical".parse::<Urgency>(), Ok(Urgency::Critical));
+    }
+
+    #[test]
+    fn test_tip_source_round_trips() {
+        for value in [
+            "builtin",
+            "file:/etc/szmer/tips.txt",
+            "command:fortune -s",
+            "https://intranet.example.com/tips.txt",
+        ] {
+            let source: TipSource = value.parse().unwrap();
+            assert_eq!(source.to_string(), value);
+        }
+        assert_eq!(
+            "command:fortune -s".parse(),
+            Ok(TipSource::Command("fortune -s".to_string()))
+        );
+        assert!("file:".parse::<TipSource>().is_err());
+        assert!("tips.txt".parse::<TipSource>().is_err());
+    }
+
+    #[test]
+    fn test_interval_presets_merge_user_presets() {
+        let mut config = Config::default();
+        config
+            .interval_presets
+            .insert("deep-work".to_string(), 120 * 60);
+        config
+            .interval_presets
+            .insert("meetings".to_string(), 50 * 60);
+
+        let presets = config.interval_presets();
+        assert_eq!(presets.len(), BUILTIN_INTERVAL_PRESETS.len() + 1);
+        assert_eq!(presets.last(), Some(&("meetings".to_string(), 3000)));
+        assert_eq!(config.interval_preset("deep-work"), Ok(7200));
+        assert_eq!(config.interval_preset("pomodoro"), Ok(1500));
+        assert!(config.interval_preset("nap").is_err());
+    }
+
+    #[test]
+    fn test_unset_restores_the_default_or_drops_the_entry() {
+        let mut config = Config {
+            interval_seconds: 3600,
+            interval_presets: BTreeMap::from([("deep-work".to_string(), 7200)]),
+            links: BTreeMap::from([("eyes".to_string(), "https://example.com".to_string())]),
+            ..Config::default()
+        };
+        config.ducking.percent = 40;
+
+        assert!(config.unset("interval"));
+        assert!(config.unset("ducking.percent"));
+        assert!(config.unset("interval_presets.deep-work"));
+        assert!(config.unset("links.eyes"));
+        config.tip_weights.insert(TipCategory::Eyes, 3);
+        assert!(config.unset("tips.weights.eyes"));
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::to_value(Config::default()).unwrap()
+        );
+
+        assert!(!config.unset("links.nope"));
+        assert!(!config.unset("version"));
+        for key in CONFIG_KEYS {
+            assert!(config.unset(key.name), "{}", key.name);
+        }
+    }
+
+    #[test]
+    fn test_migrate_stamps_the_version_and_refuses_newer_files() {
+        let mut value = serde_json::json!({ "interval_seconds": 1500 });
+        let original = value.clone();
+        assert_eq!(migrate(&mut value), Ok(0));
+        assert_eq!(value["version"], CONFIG_VERSION);
+        // Nothing moved, so the file isn't rewritten just for the stamp
+        assert!(is_only_restamped(&original, &value));
+
+        let mut newer = serde_json::json!({ "version": CONFIG_VERSION + 1 });
+        assert!(migrate(&mut newer).is_err());
+    }
+
+    fn notifier_kind() -> impl Strategy<Value = NotifierKind> {
+        prop_oneof![
+            Just(NotifierKind::Desktop),
+            Just(NotifierKind::Terminal),
+            Just(NotifierKind::Webhook),
+            Just(NotifierKind::Speech),
+            Just(NotifierKind::Ntfy),
+            "[a-z0-9_-]{1,16}".prop_map(NotifierKind::Plugin),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_notifier_kind_display_round_trips(kind in notifier_kind()) {
+            prop_assert_eq!(kind.to_string().parse::<NotifierKind>(), Ok(kind));
+        }
+
+        #[test]
+        fn test_config_round_trips(
+            interval_seconds in 60u64..86_400,
+            break_duration_seconds in 0u64..3600,
+            paused: bool,
+            chain in prop::collection::vec(notifier_kind(), 1..4),
+        ) {
+            let config = Config {
+                interval_seconds,
+                break_duration_seconds,
+                paused,
+                delivery: DeliveryConfig { chain, ..DeliveryConfig::default() },
+                ..Config::default()
+            };
+
+            let json = serde_json::to_string(&config).unwrap();
+            let loaded: Config = serde_json::from_str(&json).unwrap();
+            prop_assert!(loaded.changes_from(&config).is_empty());
+            prop_assert_eq!(&loaded.delivery.chain, &config.delivery.chain);
+
+            let toml = toml_file::to_string(&serde_json::to_value(&config).unwrap(), "").unwrap();
+            let loaded: Config = serde_json::from_value(toml_file::parse(&toml).unwrap()).unwrap();
+            prop_assert!(loaded.changes_from(&config).is_empty());
+            prop_assert_eq!(loaded.delivery.chain, config.delivery.chain);
+        }
+    }
 }
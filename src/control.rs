@@ -0,0 +1,341 @@
+#[cfg(unix)]
+use crate::config::{Config, DeliveryConfig};
+use crate::events::{Bus, Record};
+use crate::lifecycle::BreakState;
+use crate::reminder::Gate;
+use crate::timestamp;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+#[cfg(unix)]
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::sync::mpsc;
+#[cfg(unix)]
+use std::time::Duration;
+#[cfg(unix)]
+use std::{fs, thread};
+
+const SOCKET_FILENAME: &str = "szmer.sock";
+
+/// How long a reply may take on top of the gates the daemon runs first
+#[cfg(unix)]
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Environment variable pointing the CLI at a different daemon socket,
+/// e.g. one forwarded from another machine with `ssh -L`
+const SOCKET_ENV: &str = "SZMER_SOCKET";
+
+/// Request sent to the daemon over the control socket, one JSON object per line
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Request {
+    /// Pause reminders, until the given time if there is one
+    Stop {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        until: Option<DateTime<Local>>,
+    },
+    /// Resume reminders
+    Resume,
+    /// Pause reminders if running, resume them if paused
+    TogglePause,
+    /// Re-read the configuration file
+    Reload,
+    /// The system is about to sleep
+    Sleep,
+    /// The system woke up, restart the interval
+    Wake,
+    /// Save state and exit
+    Shutdown,
+    /// Delay the next reminder
+    Snooze { seconds: u64 },
+    /// Send a break reminder right now
+    TriggerBreak,
+    /// Report the daemon's current state
+    Status,
+    /// Replay recent events, and keep streaming new ones when following
+    Events {
+        #[serde(default)]
+        follow: bool,
+    },
+}
+
+/// Response sent back by the daemon, one JSON object per line
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Response {
+    /// The request was applied
+    Ok { message: String },
+    /// Current daemon state
+    Status(DaemonStatus),
+    /// The request could not be applied
+    Error { message: String },
+}
+
+/// Snapshot of the daemon state returned for status requests
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    /// Whether reminders are paused
+    pub paused: bool,
+    /// When a timed pause ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused_until: Option<DateTime<Local>>,
+    /// Break reminder interval in seconds
+    pub interval_seconds: u64,
+    /// When the next reminder is due
+    pub next_fire: Option<DateTime<Local>>,
+    /// When the daemon started
+    #[serde(default)]
+    pub started_at: Option<DateTime<Local>>,
+    /// Current verdict of each gate a reminder has to pass
+    #[serde(default)]
+    pub gates: Vec<Gate>,
+    /// How often the daemon has woken up since it started
+    #[serde(default)]
+    pub wakeups: WakeupStats,
+    /// Where the user is in the break cycle
+    #[serde(default)]
+    pub state: Option<BreakState>,
+}
+
+/// Wakeup counters, to verify the daemon stays idle between reminders
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WakeupStats {
+    /// Wakeups caused by the daemon's own timer
+    pub timer: u64,
+    /// Wakeups caused by control requests, signals and system events
+    pub requests: u64,
+}
+
+/// A control request together with the channel the daemon replies on
+pub type Envelope = (Request, Sender<Response>);
+
+/// Path to the daemon's control socket, `$SZMER_SOCKET` when set
+///
+/// Defaults to the per-user runtime directory, so each user on a shared
+/// machine talks to their own daemon.
+pub fn socket_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match socket_override() {
+        Some(path) => Ok(path),
+        None => Ok(timestamp::get_runtime_dir()?.join(SOCKET_FILENAME)),
+    }
+}
+
+/// Whether `$SZMER_SOCKET` points the CLI at a daemon other than the local one
+pub fn is_overridden() -> bool {
+    socket_override().is_some()
+}
+
+fn socket_override() -> Option<PathBuf> {
+    std::env::var_os(SOCKET_ENV)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Connect to the daemon, `Ok(None)` when no daemon is listening
+///
+/// An explicit `$SZMER_SOCKET` that nobody listens on is an error, so
+/// commands aimed at a remote daemon never silently apply locally.
+#[cfg(unix)]
+fn connect() -> Result<Option<UnixStream>, Box<dyn std::error::Error>> {
+    let path = socket_path()?;
+
+    match UnixStream::connect(&path) {
+        Ok(stream) => Ok(Some(stream)),
+        Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::ConnectionRefused) => {
+            if socket_override().is_some() {
+                return Err(format!(
+                    "No szmer daemon is listening on {} (from ${SOCKET_ENV})",
+                    path.display()
+                )
+                .into());
+            }
+            Ok(None)
+        }
+        Err(e) => Err(format!("Failed to connect to daemon: {e}").into()),
+    }
+}
+
+/// Send a request to the running daemon
+///
+/// Returns `Ok(None)` when no daemon is listening, so callers can fall back
+/// to editing the configuration directly. Breaks and status checks wait on
+/// the gates, and any request may queue behind a reminder going out, so the
+/// wait allows for the gate deadline.
+#[cfg(unix)]
+pub fn send(request: &Request) -> Result<Option<Response>, Box<dyn std::error::Error>> {
+    let Some(mut stream) = connect()? else {
+        return Ok(None);
+    };
+
+    let gate_deadline = Config::load()
+        .map(|config| config.delivery.gate_deadline_seconds)
+        .unwrap_or_else(|_| DeliveryConfig::default().gate_deadline_seconds);
+    stream.set_read_timeout(Some(
+        REPLY_TIMEOUT.saturating_add(Duration::from_secs(gate_deadline)),
+    ))?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+/// Print the daemon's event stream, one JSON object per line
+///
+/// Returns `Ok(false)` when no daemon is listening. With `follow` this
+/// blocks until the daemon goes away.
+#[cfg(unix)]
+pub fn stream_events(
+    follow: bool,
+    mut on_record: impl FnMut(&str),
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let Some(mut stream) = connect()? else {
+        return Ok(false);
+    };
+
+    writeln!(
+        stream,
+        "{}",
+        serde_json::to_string(&Request::Events { follow })?
+    )?;
+
+    for line in BufReader::new(stream).lines() {
+        on_record(&line?);
+    }
+
+    Ok(true)
+}
+
+/// Bind the control socket and forward incoming requests to the daemon loop
+///
+/// Event stream requests are served straight from `bus` without involving
+/// the daemon loop. Fails if another daemon is already listening on the socket.
+#[cfg(unix)]
+pub fn spawn_server(events: Sender<Envelope>, bus: Bus) -> Result<(), Box<dyn std::error::Error>> {
+    let path = socket_path()?;
+
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(crate::error::SzmerError::DaemonAlreadyRunning { pid: None }.into());
+        }
+        // Left behind by a daemon that didn't shut down cleanly
+        fs::remove_file(&path)?;
+    }
+
+    match path.parent() {
+        // A custom location is the user's call, only our own directory is locked down
+        Some(parent) if is_overridden() => fs::create_dir_all(parent)?,
+        Some(parent) => timestamp::create_private_dir(parent)?,
+        None => {}
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    // Only the owner may send commands, whatever the umask says
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let events = events.clone();
+            let bus = bus.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &events, &bus) {
+                    eprintln!("Warning: Control connection failed: {e}");
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn handle_connection(
+    stream: UnixStream,
+    events: &Sender<Envelope>,
+    bus: &Bus,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let response = match serde_json::from_str::<Request>(&line) {
+        Ok(Request::Events { follow }) => return stream_to(writer, bus, follow),
+        Ok(request) => {
+            let (reply_tx, reply_rx) = mpsc::channel();
+            events.send((request, reply_tx))?;
+            reply_rx.recv()?
+        }
+        Err(e) => Response::Error {
+            message: format!("Invalid request: {e}"),
+        },
+    };
+
+    writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+    Ok(())
+}
+
+/// Write recent events, then keep writing new ones until the client leaves
+#[cfg(unix)]
+fn stream_to(
+    mut writer: UnixStream,
+    bus: &Bus,
+    follow: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let write = |writer: &mut UnixStream, record: &Record| -> std::io::Result<()> {
+        writeln!(writer, "{}", serde_json::to_string(record)?)
+    };
+
+    if !follow {
+        for record in bus.history() {
+            write(&mut writer, &record)?;
+        }
+        return Ok(());
+    }
+
+    let (history, receiver) = bus.subscribe();
+    for record in &history {
+        write(&mut writer, record)?;
+    }
+    for record in receiver {
+        if write(&mut writer, &record).is_err() {
+            // The client disconnected, dropping the receiver unsubscribes it
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn stream_events(
+    _follow: bool,
+    _on_record: impl FnMut(&str),
+) -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(false)
+}
+
+#[cfg(not(unix))]
+pub fn send(_request: &Request) -> Result<Option<Response>, Box<dyn std::error::Error>> {
+    Ok(None)
+}
+
+#[cfg(not(unix))]
+pub fn spawn_server(
+    _events: Sender<Envelope>,
+    _bus: Bus,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(crate::error::SzmerError::Unsupported {
+        feature: "Daemon mode",
+    }
+    .into())
+}
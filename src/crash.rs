@@ -0,0 +1,195 @@
+use crate::timestamp;
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::io::IsTerminal;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const CRASH_DIR: &str = "crash";
+
+/// Remembers the newest report the user was already told about
+const ANNOUNCED_FILE: &str = ".announced";
+
+const ISSUES_URL: &str = "https://github.com/michalczmiel/szmer/issues";
+
+/// How many recent log lines a report includes
+const LOG_LINES: usize = 50;
+
+static RECENT_LOG: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Write a crash report whenever szmer panics, on any thread
+///
+/// The report stays on this machine in `~/.cache/szmer/crash/`; nothing is
+/// sent anywhere. The default panic message is still printed.
+pub fn install_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+
+        match write_report(info) {
+            Ok(path) => eprintln!("Crash report saved to {}", path.display()),
+            Err(e) => eprintln!("Failed to save crash report: {e}"),
+        }
+    }));
+}
+
+/// Keep a log line around so a crash report can show what led up to it
+pub fn remember(line: &str) {
+    if let Ok(mut log) = RECENT_LOG.lock() {
+        push_bounded(&mut log, line);
+    }
+}
+
+fn push_bounded(log: &mut VecDeque<String>, line: &str) {
+    if log.len() == LOG_LINES {
+        log.pop_front();
+    }
+    log.push_back(line.to_string());
+}
+
+/// Tell the user about crash reports written since they were last told
+///
+/// Only shown on a terminal, so reports from scheduled runs are mentioned
+/// the next time the user runs szmer themselves.
+pub fn announce_new_reports() {
+    if !std::io::stderr().is_terminal() {
+        return;
+    }
+    let Ok(dir) = crash_dir() else {
+        return;
+    };
+
+    let announced = fs::read_to_string(dir.join(ANNOUNCED_FILE)).unwrap_or_default();
+    let Some(newest) = report_names(&dir)
+        .into_iter()
+        .filter(|name| name.as_str() > announced.trim())
+        .max()
+    else {
+        return;
+    };
+
+    eprintln!("⚠ szmer crashed recently, a report was saved to:");
+    eprintln!("  {}", dir.join(&newest).display());
+    eprintln!("  Please attach it to an issue at {ISSUES_URL}");
+    eprintln!("  The report was redacted, but check it before sharing.\n");
+
+    let _ = fs::write(dir.join(ANNOUNCED_FILE), newest);
+}
+
+fn crash_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(CRASH_DIR))
+}
+
+/// Report file names, which sort by the time they were written
+fn report_names(dir: &std::path::Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with("crash-") && name.ends_with(".txt"))
+        .collect()
+}
+
+fn write_report(info: &PanicHookInfo) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = crash_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    let now = Local::now();
+    let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+
+    let thread = std::thread::current();
+    let recent_log = RECENT_LOG
+        .lock()
+        .map(|log| log.iter().cloned().collect::<Vec<_>>().join("\n"))
+        .unwrap_or_default();
+
+    let report = format!(
+        "szmer {version} crash report\n\
+         Time:     {time}\n\
+         Platform: {os} ({arch})\n\
+         Command:  {command}\n\
+         Thread:   {thread}\n\
+         \n\
+         {info}\n\
+         \n\
+         Backtrace:\n{backtrace}\n\
+         \n\
+         Recent log:\n{recent_log}\n",
+        version = env!("CARGO_PKG_VERSION"),
+        time = now.to_rfc3339(),
+        os = env::consts::OS,
+        arch = env::consts::ARCH,
+        command = env::args().collect::<Vec<_>>().join(" "),
+        thread = thread.name().unwrap_or("unnamed"),
+        backtrace = Backtrace::force_capture(),
+    );
+
+    fs::write(&path, redact(&report, env::var("HOME").ok().as_deref()))?;
+    Ok(path)
+}
+
+/// Strip personal details from a report before it's written
+///
+/// The home directory becomes `~` (hiding the user name) and URLs, which
+/// may carry webhook tokens, are replaced.
+///
+/// # Examples
+///
+/// ```
+/// let text = "/home/jane/.config szmer config set delivery.webhook https://hooks/abc";
+/// assert_eq!(redact(text, Some("/home/jane")), "~/.config szmer config set delivery.webhook <url>");
+/// ```
+pub fn redact(text: &str, home: Option<&str>) -> String {
+    let text = match home.filter(|home| home.len() > 1) {
+        Some(home) => text.replace(home, "~"),
+        None => text.to_string(),
+    };
+
+    text.split_inclusive(char::is_whitespace)
+        .map(|word| {
+            let trimmed = word.trim_end();
+            if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
+                format!("<url>{}", &word[trimmed.len()..])
+            } else {
+                word.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_home_and_urls() {
+        let text = "Command:  /home/jane/bin/szmer config set delivery.webhook https://hooks.example.com/T0/secret\nat /home/jane/src/main.rs";
+        assert_eq!(
+            redact(text, Some("/home/jane")),
+            "Command:  ~/bin/szmer config set delivery.webhook <url>\nat ~/src/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_redact_ignores_root_home() {
+        assert_eq!(redact("/usr/bin/szmer", Some("/")), "/usr/bin/szmer");
+    }
+
+    #[test]
+    fn test_log_keeps_recent_lines() {
+        let mut log = VecDeque::new();
+        for i in 0..LOG_LINES + 5 {
+            push_bounded(&mut log, &format!("line {i}"));
+        }
+        assert_eq!(log.len(), LOG_LINES);
+        assert_eq!(log.front().map(String::as_str), Some("line 5"));
+        assert_eq!(log.back().map(String::as_str), Some("line 54"));
+    }
+}
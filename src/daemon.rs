@@ -0,0 +1,992 @@
+use crate::clock::{Clock, SystemClock};
+use crate::command::ProcessRunner;
+use crate::config::Config;
+use crate::control::{self, DaemonStatus, Envelope, Request, Response, WakeupStats};
+use crate::error::SzmerError;
+use crate::events::{Bus, Event};
+use crate::i18n::tr;
+use crate::lifecycle::{BreakState, Transition};
+use crate::notification::{Kind, NotifierChain, Reminder};
+use crate::reminder::{self, Outcome};
+use crate::time::{format_interval, format_time_of_day, MAX_DURATION_SECONDS};
+use crate::{crash, dbus, idle, pidfile, pomodoro, power, schedule, signals, timestamp, watcher};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Wall-clock time passing this much faster than the monotonic clock means
+/// the machine was asleep
+const SLEEP_DETECTION_THRESHOLD: Duration = Duration::from_secs(120);
+
+/// Minimum spacing between timer wakeups
+///
+/// Deadlines falling within this window of each other are handled in one
+/// wakeup, so the timer wakes the daemon at most once per minute.
+const TIMER_LEEWAY: Duration = Duration::from_secs(60);
+
+/// How far into a break input means the user kept working
+///
+/// The check runs right at the end of this window rather than with the
+/// timer leeway, so the idle time it reads covers the break so far.
+const IGNORED_BREAK_WINDOW: Duration = Duration::from_secs(30);
+
+/// How often to look whether the user is back while away, see `away.after`
+const AWAY_CHECK: Duration = Duration::from_secs(60);
+
+const STATE_FILENAME: &str = "daemon_state.json";
+
+/// What the daemon saves on shutdown so a restart picks up where it left off
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedState {
+    next_fire: DateTime<Local>,
+}
+
+/// Run break reminders in a long-lived process with its own timer
+///
+/// This is an alternative to the launchd/systemd scheduler. The daemon
+/// listens on a control socket so `stop`, `resume` and `status` apply
+/// immediately instead of at the next tick, and reacts to `SIGUSR1`
+/// (break now), `SIGUSR2` (toggle pause) and `SIGHUP` (reload config).
+///
+/// Only one daemon may run at a time, guarded by a locked pidfile, and it
+/// refuses to start while the system scheduler is installed. On `SIGTERM`
+/// it saves the countdown so a restart resumes it.
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    if schedule::is_installed() {
+        return Err(SzmerError::SchedulerConflictsWithDaemon { pid: None }.into());
+    }
+
+    // Held until the daemon exits
+    let _pid_lock = pidfile::acquire()?;
+
+    let (events_tx, events_rx) = mpsc::channel::<Envelope>();
+    let bus = Bus::default();
+    control::spawn_server(events_tx.clone(), bus.clone())?;
+    signals::spawn_handler(events_tx.clone())?;
+
+    // Kept alive for the lifetime of the daemon, dropping it stops watching
+    let _config_watcher = match watcher::watch_config(events_tx.clone()) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            log(&format!("Warning: Config changes won't apply live: {e}"));
+            None
+        }
+    };
+
+    if let Err(e) = power::spawn_watcher(events_tx.clone()) {
+        log(&format!("Sleep notifications unavailable: {e}"));
+    }
+    idle::watch();
+
+    let dbus = match dbus::Service::start(events_tx) {
+        Ok(service) => Some(service),
+        Err(e) => {
+            log(&format!("D-Bus service unavailable: {e}"));
+            None
+        }
+    };
+
+    let mut daemon = Daemon::new(Config::load()?, dbus, bus, Box::new(SystemClock));
+    log(&format!(
+        "Daemon started, reminders every {}",
+        format_interval(daemon.config.interval_seconds)
+    ));
+    if !daemon.config.profiles.is_empty() {
+        log("Warning: Break profiles only run with the system scheduler ('szmer install')");
+    }
+    supervisor::notify_ready();
+    supervisor::set_timer_slack();
+
+    loop {
+        let timeout = daemon
+            .next_wakeup()
+            .saturating_duration_since(Instant::now());
+
+        let result = events_rx.recv_timeout(timeout);
+        if !matches!(result, Ok((Request::Wake, _))) {
+            daemon.detect_sleep();
+        }
+
+        match result {
+            Ok((request, reply)) => {
+                daemon.wakeups.requests += 1;
+                let response = daemon.handle(request);
+                // The client may have disconnected, nothing to do then
+                let _ = reply.send(response);
+
+                if daemon.shutting_down {
+                    let _ = fs::remove_file(control::socket_path()?);
+                    log("Daemon stopped");
+                    let _ = std::io::stdout().flush();
+                    return Ok(());
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                daemon.wakeups.timer += 1;
+                daemon.tick();
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err("Control socket closed unexpectedly".into());
+            }
+        }
+    }
+}
+
+struct Daemon {
+    config: Config,
+    next_fire: Instant,
+    /// Where the user is in the break cycle
+    lifecycle: BreakState,
+    /// When the current break ends on the monotonic clock, see `schedule.clock`
+    break_deadline: Option<Instant>,
+    /// When to look whether the user stepped away for the break
+    activity_check: Option<Instant>,
+    /// When to follow up on an ignored break, see `follow_up.delay`
+    follow_up_at: Option<Instant>,
+    /// Since when nobody has been at the machine, see `away.after`
+    away_since: Option<DateTime<Local>>,
+    dbus: Option<dbus::Service>,
+    bus: Bus,
+    /// How often the service manager expects a watchdog ping
+    watchdog: Option<Duration>,
+    next_watchdog_ping: Instant,
+    started_at: DateTime<Local>,
+    /// Wall-clock and monotonic readings taken together, to spot sleep
+    clock_check: (DateTime<Local>, Instant),
+    shutting_down: bool,
+    wakeups: WakeupStats,
+    clock: Box<dyn Clock>,
+    last_timer_wakeup: Instant,
+}
+
+impl Daemon {
+    fn new(config: Config, dbus: Option<dbus::Service>, bus: Bus, clock: Box<dyn Clock>) -> Self {
+        let now = Instant::now();
+        let lifecycle = BreakState::initial(config.paused, clock.now());
+        if let Err(e) = lifecycle.save() {
+            log(&format!("Warning: Failed to save break state: {e}"));
+        }
+
+        let resumed = state_path()
+            .ok()
+            .and_then(|path| restore_next_fire(&path, clock.as_ref(), interval(&config)));
+        Self {
+            next_fire: now + resumed.unwrap_or(interval(&config)),
+            config,
+            lifecycle,
+            break_deadline: None,
+            activity_check: None,
+            follow_up_at: None,
+            away_since: None,
+            dbus,
+            bus,
+            watchdog: supervisor::watchdog_interval(),
+            next_watchdog_ping: now,
+            started_at: clock.now(),
+            clock_check: (clock.now(), now),
+            shutting_down: false,
+            wakeups: WakeupStats::default(),
+            last_timer_wakeup: now,
+            clock,
+        }
+    }
+
+    /// Earliest moment the loop needs to wake up
+    fn next_deadline(&self) -> Instant {
+        [
+            Some(self.next_fire),
+            self.break_ends_at(),
+            self.pause_ends_at(),
+            self.follow_up_at,
+            self.watchdog.map(|_| self.next_watchdog_ping),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(self.next_fire)
+    }
+
+    /// When the loop should wake up next, no sooner than a minute after the last timer wakeup
+    ///
+    /// The activity check is the exception, pushing it back would let the
+    /// user's idle time outgrow the window it's compared against.
+    fn next_wakeup(&self) -> Instant {
+        let batched = self
+            .next_deadline()
+            .max(self.last_timer_wakeup + TIMER_LEEWAY);
+        self.activity_check.map_or(batched, |at| batched.min(at))
+    }
+
+    /// Handle whichever deadlines have passed or are due within the leeway
+    fn tick(&mut self) {
+        self.last_timer_wakeup = Instant::now();
+        let now = self.last_timer_wakeup + TIMER_LEEWAY;
+
+        // Pinged from the main loop so a hung loop gets the daemon restarted
+        if let Some(watchdog) = self.watchdog {
+            if self.next_watchdog_ping <= now {
+                supervisor::ping_watchdog();
+                self.next_watchdog_ping = self.last_timer_wakeup + watchdog;
+            }
+        }
+
+        if self.break_ends_at().is_some_and(|end| end <= now) {
+            self.end_break();
+        }
+
+        if self
+            .activity_check
+            .is_some_and(|at| at <= self.last_timer_wakeup)
+        {
+            self.activity_check = None;
+            self.check_break_taken();
+        }
+
+        if self.follow_up_at.is_some_and(|at| at <= now) {
+            self.follow_up_at = None;
+            self.follow_up();
+        }
+
+        if self.pause_ends_at().is_some_and(|end| end <= now) {
+            // Loading the config lifts the pause
+            self.reload_config();
+            if !self.config.paused {
+                log("Pause is over, reminders resumed");
+                self.bus.publish(Event::Resumed);
+            }
+        }
+
+        if self.next_fire <= now {
+            self.fire();
+        }
+    }
+
+    /// Deliver a reminder and arm the timer for the next one
+    fn fire(&mut self) -> Outcome {
+        self.reload_config();
+
+        let outcome = match reminder::remind(&self.config, None) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                log(&format!("Failed to send reminder: {e}"));
+                Outcome::Skipped(e.to_string())
+            }
+        };
+
+        match &outcome {
+            Outcome::Sent => {
+                log("Reminder sent");
+                self.bus.publish(Event::ReminderSent);
+                self.transition(Transition::Remind);
+                self.start_break();
+            }
+            Outcome::Paused => log("Reminders are paused, skipping"),
+            Outcome::BackToWork => log("Pomodoro break over, back to work"),
+            Outcome::Waiting(_) => {}
+            Outcome::Away(since) => {
+                if self.away_since.is_none() {
+                    log(&format!(
+                        "No input since {}, holding reminders until you're back",
+                        since.format("%H:%M")
+                    ));
+                    self.bus.publish(Event::Away { since: *since });
+                }
+            }
+            Outcome::Back(_) => {
+                log("Back after being away, starting the interval over");
+                self.bus.publish(Event::Back);
+            }
+            Outcome::Snoozed(until) => {
+                log(&format!(
+                    "Reminders are snoozed until {}",
+                    until.format("%H:%M")
+                ));
+            }
+            Outcome::Skipped(reason) => {
+                log(&format!("Skipping reminder: {reason}"));
+                self.bus.publish(Event::Skipped {
+                    reason: reason.clone(),
+                });
+                self.transition(Transition::Defer);
+            }
+        }
+
+        self.away_since = match outcome {
+            Outcome::Away(since) => Some(since),
+            _ => None,
+        };
+        self.next_fire = match &outcome {
+            Outcome::Snoozed(until) => {
+                let remaining = (*until - self.clock.now()).to_std().unwrap_or_default();
+                Instant::now() + remaining
+            }
+            Outcome::Away(_) => Instant::now() + AWAY_CHECK,
+            _ => Instant::now() + interval(&self.config),
+        };
+        outcome
+    }
+
+    fn start_break(&mut self) {
+        let seconds = match pomodoro::load().filter(|_| self.config.pomodoro.enabled) {
+            Some(cycle) => cycle.phase.seconds(),
+            None => self.config.break_duration_seconds,
+        };
+        self.break_deadline = (!self.config.schedule.clock.is_wall())
+            .then(|| Instant::now() + Duration::from_secs(seconds));
+        self.activity_check = self
+            .config
+            .follow_up
+            .delay_seconds
+            .map(|_| Instant::now() + IGNORED_BREAK_WINDOW);
+        self.follow_up_at = None;
+        let seconds = i64::try_from(seconds).unwrap_or(i64::MAX);
+        self.transition(Transition::StartBreak {
+            duration: chrono::Duration::seconds(seconds),
+        });
+        self.bus.publish(Event::BreakStarted);
+
+        if let Some(Err(e)) = self.dbus.as_ref().map(dbus::Service::break_started) {
+            log(&format!("Warning: Failed to emit BreakStarted: {e}"));
+        }
+    }
+
+    fn end_break(&mut self) {
+        self.transition(Transition::EndBreak);
+        log("Break ended");
+        self.bus.publish(Event::BreakEnded);
+
+        if let Some(Err(e)) = self.dbus.as_ref().map(dbus::Service::break_ended) {
+            log(&format!("Warning: Failed to emit BreakEnded: {e}"));
+        }
+    }
+
+    /// Schedule a follow-up when the user is still at the keyboard a little into the break
+    fn check_break_taken(&mut self) {
+        let Some(delay) = self.config.follow_up.delay_seconds else {
+            return;
+        };
+        if !matches!(self.lifecycle, BreakState::OnBreak { .. }) {
+            return;
+        }
+        // Without a way to tell, give the user the benefit of the doubt
+        let Some(idle) = idle::idle_seconds(&ProcessRunner) else {
+            return;
+        };
+
+        if idle < IGNORED_BREAK_WINDOW.as_secs() {
+            log("Break ignored, input went on after the reminder");
+            self.bus.publish(Event::BreakIgnored);
+            self.follow_up_at = Some(Instant::now() + Duration::from_secs(delay));
+        }
+    }
+
+    /// Gently point out that the ignored break wasn't one
+    fn follow_up(&mut self) {
+        self.reload_config();
+        if self.config.paused {
+            return;
+        }
+
+        let reminder = Reminder {
+            summary: tr("follow-up-summary"),
+            body: tr("follow-up-body"),
+            sound: None,
+            link: None,
+            kind: Kind::Escalation,
+        };
+        match NotifierChain::from_config(&self.config).send(&reminder) {
+            Ok(_) => log("Followed up on the ignored break"),
+            Err(e) => log(&format!("Failed to follow up on the ignored break: {e}")),
+        }
+    }
+
+    /// Announce a break cut short by shutdown or pausing
+    fn interrupt_break(&mut self) {
+        log("Break interrupted");
+        self.bus.publish(Event::BreakInterrupted);
+
+        if let Some(Err(e)) = self.dbus.as_ref().map(dbus::Service::break_ended) {
+            log(&format!("Warning: Failed to emit BreakEnded: {e}"));
+        }
+    }
+
+    /// When the current break ends, if on one
+    ///
+    /// Breaks started on the monotonic clock keep their deadline; others,
+    /// including one picked up from before a restart, follow the wall clock.
+    fn break_ends_at(&self) -> Option<Instant> {
+        let ends_at = self.lifecycle.break_ends_at()?;
+        if let Some(deadline) = self.break_deadline {
+            return Some(deadline);
+        }
+        let remaining = (ends_at - self.clock.now()).to_std().unwrap_or_default();
+        Some(Instant::now() + remaining)
+    }
+
+    /// When a timed pause ends, if paused until a set time
+    fn pause_ends_at(&self) -> Option<Instant> {
+        let until = self.config.paused_until.filter(|_| self.config.paused)?;
+        let remaining = (until - self.clock.now()).to_std().unwrap_or_default();
+        Some(Instant::now() + remaining)
+    }
+
+    /// Move the break cycle along and persist the new state
+    fn transition(&mut self, transition: Transition) {
+        match self.lifecycle.apply(transition, self.clock.now()) {
+            Ok(state) => {
+                self.lifecycle = state;
+                if let Err(e) = state.save() {
+                    log(&format!("Warning: Failed to save break state: {e}"));
+                }
+            }
+            Err(e) => log(&format!("Warning: {e}")),
+        }
+    }
+
+    /// Follow the pause setting, which the CLI may change behind the daemon's back
+    fn sync_pause(&mut self) {
+        let paused = matches!(self.lifecycle, BreakState::Paused { .. });
+
+        if self.config.paused && !paused {
+            if self.lifecycle.break_ends_at().is_some() {
+                self.interrupt_break();
+            }
+            self.transition(Transition::Pause);
+        } else if !self.config.paused && paused {
+            self.transition(Transition::Resume);
+        }
+    }
+
+    /// Treat a wall-clock jump ahead of the monotonic clock as a wake-up
+    ///
+    /// The monotonic clock stops while suspended, so this catches sleep on
+    /// platforms without sleep notifications. On the monotonic clock a jump
+    /// is as likely a clock change, so only sleep notifications count.
+    fn detect_sleep(&mut self) {
+        let (last_wall, last_monotonic) = self.clock_check;
+        let now = (self.clock.now(), Instant::now());
+        self.clock_check = now;
+        if !self.config.schedule.clock.is_wall() {
+            return;
+        }
+
+        let Ok(wall_elapsed) = (now.0 - last_wall).to_std() else {
+            // The clock was set back, nothing to learn from that
+            return;
+        };
+        let monotonic_elapsed = now.1.duration_since(last_monotonic);
+
+        if wall_elapsed.saturating_sub(monotonic_elapsed) > SLEEP_DETECTION_THRESHOLD {
+            self.wake();
+        }
+    }
+
+    /// Re-baseline the timer after sleep, the user has been away anyway
+    fn wake(&mut self) -> Response {
+        match self.lifecycle {
+            BreakState::OnBreak { .. } => self.end_break(),
+            BreakState::Reminded { .. } | BreakState::Overdue { .. } => {
+                self.transition(Transition::Interrupt)
+            }
+            BreakState::Working { .. } | BreakState::Paused { .. } => {}
+        }
+        self.activity_check = None;
+        self.follow_up_at = None;
+        self.next_fire = Instant::now() + interval(&self.config);
+        self.clock_check = (self.clock.now(), Instant::now());
+
+        let message = format!(
+            "Woke up from sleep, next reminder in {}",
+            format_interval(self.config.interval_seconds)
+        );
+        log(&message);
+        Response::Ok { message }
+    }
+
+    fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Stop { until } => self.set_paused(true, until),
+            Request::Resume => self.set_paused(false, None),
+            Request::TogglePause => {
+                self.reload_config();
+                self.set_paused(!self.config.paused, None)
+            }
+            Request::Reload => self.reload(),
+            Request::Sleep => {
+                log("System is going to sleep");
+                Response::Ok {
+                    message: "Timer will restart on wake.".to_string(),
+                }
+            }
+            Request::Wake => self.wake(),
+            Request::Shutdown => self.shutdown(),
+            Request::Snooze { seconds } => self.snooze(seconds),
+            Request::TriggerBreak => self.trigger_break(),
+            Request::Status => Response::Status(self.status()),
+            // Served by the control socket directly from the event bus
+            Request::Events { .. } => Response::Error {
+                message: "Event streams are only available over the control socket".to_string(),
+            },
+        }
+    }
+
+    /// End any break, save the countdown and tell the main loop to exit
+    ///
+    /// Reminders are recorded synchronously when sent, so the countdown is
+    /// the only state left to write.
+    fn shutdown(&mut self) -> Response {
+        log("Shutting down");
+        supervisor::notify_stopping();
+
+        if self.lifecycle.break_ends_at().is_some() {
+            self.interrupt_break();
+            self.transition(Transition::Interrupt);
+        }
+
+        if let Err(e) = save_next_fire(self.status().next_fire) {
+            log(&format!("Warning: Failed to save daemon state: {e}"));
+        }
+
+        self.shutting_down = true;
+        Response::Ok {
+            message: "✓ Daemon stopped.".to_string(),
+        }
+    }
+
+    /// Re-read the config file and apply whatever changed
+    ///
+    /// Triggered by `SIGHUP` and by the config file watcher. An interval
+    /// change re-arms the timer relative to the last reminder.
+    fn reload(&mut self) -> Response {
+        let config = match Config::load() {
+            Ok(config) => config,
+            Err(e) => {
+                // Editors may briefly leave a half-written file behind
+                log(&format!("Warning: Failed to reload configuration: {e}"));
+                return Response::Error {
+                    message: format!("Failed to reload configuration: {e}"),
+                };
+            }
+        };
+
+        let changes = config.changes_from(&self.config);
+        for change in &changes {
+            log(&format!("Config changed, {change}"));
+        }
+
+        if config.timer_seconds() != self.config.timer_seconds() {
+            let last_fire = self.next_fire - interval(&self.config);
+            self.next_fire = (last_fire + interval(&config)).max(Instant::now());
+        }
+        self.config = config;
+        self.sync_pause();
+
+        let message = if changes.is_empty() {
+            "✓ Configuration reloaded, nothing changed.".to_string()
+        } else {
+            format!(
+                "✓ Configuration reloaded, {} change(s) applied.",
+                changes.len()
+            )
+        };
+        Response::Ok { message }
+    }
+
+    fn snooze(&mut self, seconds: u64) -> Response {
+        // The socket takes any number, `szmer snooze` caps it the same way
+        if seconds > MAX_DURATION_SECONDS {
+            return Response::Error {
+                message: format!(
+                    "Can't snooze for longer than {}",
+                    format_interval(MAX_DURATION_SECONDS)
+                ),
+            };
+        }
+
+        let Some(next_fire) = Instant::now().checked_add(Duration::from_secs(seconds)) else {
+            return Response::Error {
+                message: format!("Can't snooze for {seconds} seconds"),
+            };
+        };
+        self.next_fire = next_fire;
+        log(&format!(
+            "Next reminder snoozed for {}",
+            format_interval(seconds)
+        ));
+
+        Response::Ok {
+            message: format!("✓ Next break reminder in {}", format_interval(seconds)),
+        }
+    }
+
+    fn trigger_break(&mut self) -> Response {
+        match self.fire() {
+            Outcome::Sent => Response::Ok {
+                message: "✓ Break reminder sent.".to_string(),
+            },
+            Outcome::Paused => Response::Error {
+                message: "Break reminders are stopped. Run 'szmer resume' first.".to_string(),
+            },
+            Outcome::Snoozed(until) => Response::Error {
+                message: format!(
+                    "Break reminders are snoozed until {}",
+                    until.format("%H:%M")
+                ),
+            },
+            Outcome::Skipped(reason) => Response::Error {
+                message: format!("Skipping notification: {reason}"),
+            },
+            Outcome::BackToWork => Response::Ok {
+                message: "✓ Pomodoro break over, back to work.".to_string(),
+            },
+            Outcome::Away(since) => Response::Error {
+                message: format!(
+                    "No input since {}, reminders wait until you're back.",
+                    since.format("%H:%M")
+                ),
+            },
+            Outcome::Back(_) => Response::Ok {
+                message: "✓ Welcome back, the next break reminder is an interval away.".to_string(),
+            },
+            Outcome::Waiting(until) => Response::Error {
+                message: format!(
+                    "The pomodoro phase runs until {}, run 'szmer pomodoro --stop' for regular breaks.",
+                    until.format("%H:%M")
+                ),
+            },
+        }
+    }
+
+    fn set_paused(&mut self, paused: bool, until: Option<DateTime<Local>>) -> Response {
+        self.reload_config();
+
+        if self.config.paused == paused && self.config.paused_until == until {
+            let message = if paused {
+                "Break reminders are already stopped."
+            } else {
+                "Break reminders are already running."
+            };
+            return Response::Ok {
+                message: message.to_string(),
+            };
+        }
+
+        // Persist so the pause state is shared with the CLI and the scheduler
+        if paused {
+            self.config.pause(until, self.clock.now());
+        } else {
+            self.config.resume();
+        }
+        if let Err(e) = self.config.save() {
+            return Response::Error {
+                message: format!("Failed to save configuration: {e}"),
+            };
+        }
+
+        self.sync_pause();
+
+        let message = match until {
+            Some(until) if paused => {
+                log(&format!(
+                    "Reminders stopped until {}",
+                    until.format("%a %H:%M")
+                ));
+                self.bus.publish(Event::Paused);
+                format!(
+                    "✓ Break reminders stopped until {}, they resume on their own.",
+                    format_time_of_day(self.clock.as_ref(), until)
+                )
+            }
+            _ if paused => {
+                log("Reminders stopped");
+                self.bus.publish(Event::Paused);
+                "✓ Break reminders stopped.\nRun 'szmer resume' to start receiving reminders again."
+                    .to_string()
+            }
+            _ => {
+                log("Reminders resumed");
+                self.bus.publish(Event::Resumed);
+                "✓ Break reminders resumed.".to_string()
+            }
+        };
+
+        Response::Ok { message }
+    }
+
+    fn status(&self) -> DaemonStatus {
+        let until_next = self.next_fire.saturating_duration_since(Instant::now());
+        let next_fire = chrono::Duration::from_std(until_next)
+            .ok()
+            .map(|until_next| self.clock.now() + until_next);
+
+        DaemonStatus {
+            paused: self.config.paused,
+            paused_until: self.config.paused_until,
+            interval_seconds: self.config.interval_seconds,
+            next_fire,
+            started_at: Some(self.started_at),
+            gates: reminder::evaluate_gates(&self.config),
+            wakeups: self.wakeups,
+            state: Some(self.lifecycle),
+        }
+    }
+
+    fn reload_config(&mut self) {
+        match Config::load() {
+            Ok(config) => {
+                self.config = config;
+                self.sync_pause();
+            }
+            Err(e) => log(&format!("Warning: Failed to reload configuration: {e}")),
+        }
+    }
+}
+
+fn state_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(STATE_FILENAME))
+}
+
+fn save_next_fire(next_fire: Option<DateTime<Local>>) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(next_fire) = next_fire else {
+        return Ok(());
+    };
+
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(&SavedState { next_fire })?)?;
+    Ok(())
+}
+
+/// Time left of the countdown saved at `path` at the last shutdown
+///
+/// The state is consumed, so it only applies to the next start. A reminder
+/// that came due while the daemon was down is not sent late, the interval
+/// starts over instead.
+fn restore_next_fire(path: &Path, clock: &dyn Clock, interval: Duration) -> Option<Duration> {
+    let content = fs::read_to_string(path).ok()?;
+    let _ = fs::remove_file(path);
+
+    let state: SavedState = serde_json::from_str(&content).ok()?;
+    let remaining = (state.next_fire - clock.now()).to_std().ok()?.min(interval);
+    log(&format!(
+        "Resuming countdown, next reminder in {}",
+        format_interval(remaining.as_secs())
+    ));
+    Some(remaining)
+}
+
+/// Readiness and watchdog notifications for systemd's `Type=notify` services
+///
+/// All calls are no-ops when not running under systemd.
+#[cfg(target_os = "linux")]
+mod supervisor {
+    use sd_notify::NotifyState;
+    use std::time::Duration;
+
+    pub fn notify_ready() {
+        let _ = sd_notify::notify(false, &[NotifyState::Ready]);
+    }
+
+    /// Ping interval, half of the configured `WatchdogSec`
+    pub fn watchdog_interval() -> Option<Duration> {
+        let mut usec = 0;
+        sd_notify::watchdog_enabled(false, &mut usec).then(|| Duration::from_micros(usec / 2))
+    }
+
+    pub fn ping_watchdog() {
+        let _ = sd_notify::notify(false, &[NotifyState::Watchdog]);
+    }
+
+    pub fn notify_stopping() {
+        let _ = sd_notify::notify(false, &[NotifyState::Stopping]);
+    }
+
+    /// Let the kernel delay our timers by up to a second to batch wakeups
+    pub fn set_timer_slack() {
+        // SAFETY: PR_SET_TIMERSLACK takes a plain integer and only affects this thread
+        unsafe {
+            libc::prctl(libc::PR_SET_TIMERSLACK, 1_000_000_000 as libc::c_ulong);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod supervisor {
+    use std::time::Duration;
+
+    pub fn notify_ready() {}
+
+    pub fn watchdog_interval() -> Option<Duration> {
+        None
+    }
+
+    pub fn ping_watchdog() {}
+
+    pub fn notify_stopping() {}
+
+    pub fn set_timer_slack() {}
+}
+
+fn interval(config: &Config) -> Duration {
+    Duration::from_secs(config.timer_seconds().max(60))
+}
+
+fn log(message: &str) {
+    let line = format!("[{}] {message}", Local::now().format("%Y-%m-%d %H:%M:%S"));
+    crash::remember(&line);
+    println!("{line}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::rc::Rc;
+
+    /// A daemon on `clock` with the default config, put together without the
+    /// state files `Daemon::new` reads and writes
+    fn daemon(clock: Rc<MockClock>) -> Daemon {
+        let now = Instant::now();
+        let config = Config::default();
+        Daemon {
+            next_fire: now + interval(&config),
+            lifecycle: BreakState::initial(false, clock.now()),
+            config,
+            break_deadline: None,
+            activity_check: None,
+            follow_up_at: None,
+            away_since: None,
+            dbus: None,
+            bus: Bus::default(),
+            watchdog: None,
+            next_watchdog_ping: now,
+            started_at: clock.now(),
+            clock_check: (clock.now(), now),
+            shutting_down: false,
+            wakeups: WakeupStats::default(),
+            last_timer_wakeup: now,
+            clock: Box::new(clock),
+        }
+    }
+
+    fn saved_state(next_fire: DateTime<Local>) -> tempfile::TempPath {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(
+            file.path(),
+            serde_json::to_string(&SavedState { next_fire }).unwrap(),
+        )
+        .unwrap();
+        file.into_temp_path()
+    }
+
+    #[test]
+    fn test_restore_next_fire_resumes_the_saved_countdown() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let hour = Duration::from_secs(3600);
+
+        let path = saved_state(clock.now() + chrono::Duration::minutes(20));
+        assert_eq!(
+            restore_next_fire(&path, &clock, hour),
+            Some(Duration::from_secs(20 * 60))
+        );
+        // The state only applies to one start
+        assert!(!path.exists());
+        assert_eq!(restore_next_fire(&path, &clock, hour), None);
+    }
+
+    #[test]
+    fn test_restore_next_fire_clamps_to_the_interval() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+
+        // Saved with a longer interval than the one configured now
+        let path = saved_state(clock.now() + chrono::Duration::hours(2));
+        assert_eq!(
+            restore_next_fire(&path, &clock, Duration::from_secs(1800)),
+            Some(Duration::from_secs(1800))
+        );
+    }
+
+    #[test]
+    fn test_restore_next_fire_starts_over_after_a_missed_reminder() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+
+        let path = saved_state(clock.now() - chrono::Duration::minutes(5));
+        assert_eq!(
+            restore_next_fire(&path, &clock, Duration::from_secs(3600)),
+            None
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_detect_sleep_wakes_when_the_wall_clock_jumps_ahead() {
+        let clock = Rc::new(MockClock::at("2025-01-06 09:00:00"));
+        let mut daemon = daemon(Rc::clone(&clock));
+        daemon.next_fire = Instant::now() + Duration::from_secs(60);
+        daemon.follow_up_at = Some(Instant::now());
+
+        // Under the threshold, e.g. an NTP correction
+        clock.advance(chrono::Duration::seconds(60));
+        daemon.detect_sleep();
+        assert!(daemon.follow_up_at.is_some());
+
+        // Ten minutes on the wall clock while the monotonic one stood still
+        clock.advance(chrono::Duration::minutes(10));
+        daemon.detect_sleep();
+        assert!(daemon.follow_up_at.is_none());
+        let restarted = daemon.next_fire - Instant::now();
+        assert!(restarted > interval(&daemon.config) - Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_detect_sleep_ignores_jumps_on_the_monotonic_clock() {
+        let clock = Rc::new(MockClock::at("2025-01-06 09:00:00"));
+        let mut daemon = daemon(Rc::clone(&clock));
+        daemon.config.schedule.clock = "monotonic".parse().unwrap();
+        daemon.follow_up_at = Some(Instant::now());
+
+        clock.advance(chrono::Duration::minutes(10));
+        daemon.detect_sleep();
+        assert!(daemon.follow_up_at.is_some());
+    }
+
+    #[test]
+    fn test_next_wakeup_batches_deadlines_within_the_leeway() {
+        let mut daemon = daemon(Rc::new(MockClock::at("2025-01-06 09:00:00")));
+        let last = daemon.last_timer_wakeup;
+
+        // A deadline right after the last wakeup waits for the leeway
+        daemon.next_fire = last + Duration::from_secs(10);
+        assert_eq!(daemon.next_wakeup(), last + TIMER_LEEWAY);
+
+        // Later ones wake the daemon when they're due
+        daemon.next_fire = last + Duration::from_secs(600);
+        daemon.follow_up_at = Some(last + Duration::from_secs(300));
+        assert_eq!(daemon.next_wakeup(), last + Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_activity_check_runs_at_the_end_of_its_window() {
+        let mut daemon = daemon(Rc::new(MockClock::at("2025-01-06 09:00:00")));
+        let last = daemon.last_timer_wakeup;
+
+        // Not batched into the next leeway-spaced wakeup
+        daemon.activity_check = Some(last + IGNORED_BREAK_WINDOW);
+        assert_eq!(daemon.next_wakeup(), last + IGNORED_BREAK_WINDOW);
+
+        // Nor handled early by a wakeup for something else
+        daemon.activity_check = Some(Instant::now() + IGNORED_BREAK_WINDOW);
+        daemon.tick();
+        assert!(daemon.activity_check.is_some());
+
+        daemon.activity_check = Some(Instant::now());
+        daemon.tick();
+        assert!(daemon.activity_check.is_none());
+    }
+}
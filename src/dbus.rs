@@ -0,0 +1,134 @@
+use crate::control::Envelope;
+use std::sync::mpsc::Sender;
+
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+use crate::control::{Request, Response};
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+use std::sync::mpsc;
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+use zbus::{blocking::connection, fdo, object_server::SignalEmitter};
+
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+const BUS_NAME: &str = "com.michalczmiel.szmer";
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+const OBJECT_PATH: &str = "/com/michalczmiel/szmer";
+
+/// D-Bus service exposing daemon controls and break lifecycle signals
+///
+/// Registers `com.michalczmiel.szmer` on the session bus with the methods
+/// `Pause`, `Resume`, `Snooze` and `TriggerBreak`, and the signals
+/// `BreakStarted` and `BreakEnded`.
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+pub struct Service {
+    connection: zbus::blocking::Connection,
+}
+
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+struct Interface {
+    events: Sender<Envelope>,
+}
+
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+impl Interface {
+    /// Hand a request to the daemon loop and wait for its reply
+    fn forward(&self, request: Request) -> fdo::Result<String> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        self.events
+            .send((request, reply_tx))
+            .map_err(|e| fdo::Error::Failed(e.to_string()))?;
+
+        match reply_rx.recv() {
+            Ok(Response::Ok { message }) => Ok(message),
+            Ok(Response::Error { message }) => Err(fdo::Error::Failed(message)),
+            Ok(Response::Status(_)) => Err(fdo::Error::Failed("Unexpected status reply".into())),
+            Err(e) => Err(fdo::Error::Failed(e.to_string())),
+        }
+    }
+}
+
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+#[zbus::interface(name = "com.michalczmiel.szmer")]
+impl Interface {
+    /// Pause break reminders
+    fn pause(&self) -> fdo::Result<String> {
+        self.forward(Request::Stop { until: None })
+    }
+
+    /// Resume break reminders
+    fn resume(&self) -> fdo::Result<String> {
+        self.forward(Request::Resume)
+    }
+
+    /// Delay the next reminder by the given number of seconds
+    fn snooze(&self, seconds: u64) -> fdo::Result<String> {
+        self.forward(Request::Snooze { seconds })
+    }
+
+    /// Send a break reminder right now
+    fn trigger_break(&self) -> fdo::Result<String> {
+        self.forward(Request::TriggerBreak)
+    }
+
+    /// Emitted when a break reminder was delivered
+    #[zbus(signal)]
+    async fn break_started(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    /// Emitted when the break following a reminder is over
+    #[zbus(signal)]
+    async fn break_ended(emitter: &SignalEmitter<'_>) -> zbus::Result<()>;
+}
+
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+impl Service {
+    /// Claim the bus name and start serving requests on the session bus
+    pub fn start(events: Sender<Envelope>) -> Result<Self, Box<dyn std::error::Error>> {
+        let connection = connection::Builder::session()?
+            .name(BUS_NAME)?
+            .serve_at(OBJECT_PATH, Interface { events })?
+            .build()?;
+
+        Ok(Self { connection })
+    }
+
+    /// Emit the `BreakStarted` signal
+    pub fn break_started(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let iface = self
+            .connection
+            .object_server()
+            .interface::<_, Interface>(OBJECT_PATH)?;
+        zbus::block_on(Interface::break_started(iface.signal_emitter()))?;
+        Ok(())
+    }
+
+    /// Emit the `BreakEnded` signal
+    pub fn break_ended(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let iface = self
+            .connection
+            .object_server()
+            .interface::<_, Interface>(OBJECT_PATH)?;
+        zbus::block_on(Interface::break_ended(iface.signal_emitter()))?;
+        Ok(())
+    }
+}
+
+#[cfg(not(all(feature = "dbus", unix, not(target_os = "macos"))))]
+pub struct Service;
+
+#[cfg(not(all(feature = "dbus", unix, not(target_os = "macos"))))]
+impl Service {
+    pub fn start(_events: Sender<Envelope>) -> Result<Self, Box<dyn std::error::Error>> {
+        if cfg!(all(unix, not(target_os = "macos"))) {
+            Err("szmer was built without the 'dbus' feature".into())
+        } else {
+            Err(crate::error::SzmerError::Unsupported { feature: "D-Bus" }.into())
+        }
+    }
+
+    pub fn break_started(&self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    pub fn break_ended(&self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
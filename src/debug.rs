@@ -0,0 +1,246 @@
+use crate::command::{run_checked, ProcessRunner, SystemCommandRunner};
+use crate::config::Config;
+use crate::{cleanup, crash, schedule, timestamp, toml_file};
+use chrono::Local;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory the archive unpacks into
+const BUNDLE_DIR: &str = "szmer-debug";
+
+/// Lines kept from the end of each log
+const LOG_LINES: usize = 200;
+
+/// Config keys holding secrets, as paths into the config
+const SECRET_KEYS: &[&[&str]] = &[
+    &["delivery", "webhook_url"],
+    &["ntfy", "token"],
+    // On ntfy.sh anyone who knows the topic can read and post to it
+    &["ntfy", "topic"],
+    &["share", "token"],
+    &["api", "token"],
+];
+
+/// Gather what a bug report needs into a .tar.gz in one go
+///
+/// The bundle holds the config with secrets taken out, `szmer doctor`
+/// output, the end of the logs, crash reports, the installed scheduler
+/// files and what the system is. The home directory becomes `~` and URLs
+/// are replaced throughout, as in crash reports.
+pub fn bundle(file: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let file = match file {
+        Some(file) => file.to_path_buf(),
+        None => PathBuf::from(format!(
+            "szmer-debug-{}.tar.gz",
+            Local::now().format("%Y%m%d-%H%M%S")
+        )),
+    };
+    let file = if file.is_absolute() {
+        file
+    } else {
+        env::current_dir()?.join(file)
+    };
+
+    let staging = env::temp_dir().join(format!("szmer-debug-{}", std::process::id()));
+    let result = write_bundle(&staging.join(BUNDLE_DIR)).and_then(|()| {
+        run_checked(
+            &ProcessRunner,
+            "tar",
+            &[
+                "-czf",
+                file.to_str().ok_or("Invalid bundle path")?,
+                "-C",
+                staging.to_str().ok_or("Invalid temporary directory")?,
+                BUNDLE_DIR,
+            ],
+            "Failed to create the debug bundle",
+        )
+        .map(drop)
+    });
+    let _ = fs::remove_dir_all(&staging);
+    result?;
+
+    println!("✓ Wrote {}", file.display());
+    println!("  Look it over, then attach it to the bug report.");
+    Ok(())
+}
+
+fn write_bundle(dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let runner = ProcessRunner;
+    let home = env::var("HOME").ok();
+    // The ntfy log and doctor's summary of it name the topic
+    let topic = Config::load_checked()
+        .ok()
+        .and_then(|(config, _)| config.ntfy.topic)
+        .filter(|topic| !topic.is_empty());
+    let write = |name: &str, content: &str| -> Result<(), Box<dyn std::error::Error>> {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut content = crash::redact(content, home.as_deref());
+        if let Some(topic) = &topic {
+            content = content.replace(topic.as_str(), "<redacted>");
+        }
+        fs::write(path, content)?;
+        Ok(())
+    };
+
+    write("system.txt", &system_info(&runner))?;
+    write("config.toml", &sanitized_config())?;
+    write("doctor.txt", &doctor_output(&runner))?;
+
+    for path in cleanup::LOG_FILES.iter().map(Path::new) {
+        if let Ok(content) = fs::read_to_string(path) {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            write(&format!("logs/{name}"), &tail(&content, LOG_LINES))?;
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let lines = LOG_LINES.to_string();
+        let journal = command_output(
+            &runner,
+            "journalctl",
+            &[
+                "--user",
+                "-u",
+                "szmer.service",
+                "-u",
+                "szmer-daemon.service",
+                "-n",
+                &lines,
+                "--no-pager",
+            ],
+        );
+        write("logs/journal.txt", &journal)?;
+    }
+    if let Ok(entries) = fs::read_dir(timestamp::get_cache_dir()?.join("crash")) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Ok(content) = fs::read_to_string(entry.path()) {
+                write(&format!("crash/{name}"), &content)?;
+            }
+        }
+    }
+
+    for path in schedule::installed_files() {
+        if let (Ok(content), Some(name)) = (fs::read_to_string(&path), path.file_name()) {
+            write(&format!("units/{}", name.to_string_lossy()), &content)?;
+        }
+    }
+    Ok(())
+}
+
+/// szmer's version and what it runs on
+fn system_info(runner: &dyn SystemCommandRunner) -> String {
+    let mut info = format!(
+        "szmer {}\nPlatform: {} ({})\n",
+        env!("CARGO_PKG_VERSION"),
+        env::consts::OS,
+        env::consts::ARCH
+    );
+    for key in [
+        "XDG_CURRENT_DESKTOP",
+        "XDG_SESSION_TYPE",
+        "DESKTOP_SESSION",
+        "LANG",
+    ] {
+        if let Ok(value) = env::var(key) {
+            info.push_str(&format!("{key}={value}\n"));
+        }
+    }
+
+    info.push_str(&format!(
+        "\n$ uname -a\n{}",
+        command_output(runner, "uname", &["-a"])
+    ));
+    #[cfg(target_os = "macos")]
+    info.push_str(&format!(
+        "\n$ sw_vers\n{}",
+        command_output(runner, "sw_vers", &[])
+    ));
+    #[cfg(not(target_os = "macos"))]
+    if let Ok(release) = fs::read_to_string("/etc/os-release") {
+        info.push_str(&format!("\n/etc/os-release:\n{release}"));
+    }
+    info
+}
+
+/// The config as szmer reads it, with secrets replaced
+fn sanitized_config() -> String {
+    let config = match Config::load_checked() {
+        Ok((config, _)) => config,
+        Err(e) => return format!("# The config couldn't be read: {e}\n"),
+    };
+    let mut value = match serde_json::to_value(&config) {
+        Ok(value) => value,
+        Err(e) => return format!("# The config couldn't be written out: {e}\n"),
+    };
+    redact_secrets(&mut value);
+    toml_file::to_string(&value, "").unwrap_or_else(|e| format!("# {e}\n"))
+}
+
+/// Replace the values of `SECRET_KEYS` that are set
+fn redact_secrets(value: &mut Value) {
+    for path in SECRET_KEYS {
+        let secret = path
+            .iter()
+            .try_fold(&mut *value, |value, key| value.get_mut(*key));
+        if let Some(secret) = secret.filter(|secret| !secret.is_null()) {
+            *secret = Value::String("<redacted>".to_string());
+        }
+    }
+}
+
+/// `szmer doctor` as it prints, run as its own process
+fn doctor_output(runner: &dyn SystemCommandRunner) -> String {
+    match env::current_exe() {
+        Ok(exe) => command_output(runner, &exe.to_string_lossy(), &["doctor"]),
+        Err(e) => format!("Couldn't find the szmer binary: {e}\n"),
+    }
+}
+
+/// Everything a command printed, or why it couldn't run
+fn command_output(runner: &dyn SystemCommandRunner, program: &str, args: &[&str]) -> String {
+    match runner.run(program, args) {
+        Ok(output) => format!("{}{}", output.stdout, output.stderr),
+        Err(e) => format!("Couldn't run {program}: {e}\n"),
+    }
+}
+
+fn tail(content: &str, lines: usize) -> String {
+    let all: Vec<&str> = content.lines().collect();
+    let mut kept = all[all.len().saturating_sub(lines)..].join("\n");
+    kept.push('\n');
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_secrets_keeps_everything_else() {
+        let mut value = json!({
+            "interval_seconds": 1800,
+            "delivery": { "chain": ["webhook"], "webhook_url": "https://hooks.example/abc" },
+            "share": {},
+            "ntfy": { "topic": "breaks-4f7a", "token": null, "priority": "high" },
+        });
+        redact_secrets(&mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "interval_seconds": 1800,
+                "delivery": { "chain": ["webhook"], "webhook_url": "<redacted>" },
+                "share": {},
+                "ntfy": { "topic": "<redacted>", "token": null, "priority": "high" },
+            })
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use crate::clock::{Clock, SystemClock};
+use crate::command::ProcessRunner;
+use crate::config::Config;
+use crate::control::{self, Request, Response};
+use crate::time::format_interval;
+use crate::{
+    break_apps, bundle, drift, focus, focus_mode, idle, ntfy, overlay, presenting, schedule,
+    session, sound,
+};
+use std::env;
+
+/// Print diagnostic information about the environment szmer runs in
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("\nSzmer Doctor");
+    println!("━━━━━━━━━━━━");
+
+    print_platform();
+    print_features();
+    print_session();
+    print_backends();
+    print_break_apps();
+    print_frontmost_app();
+    print_presenting();
+    print_focus_mode();
+    print_container();
+    print_crostini();
+    print_scheduler();
+    print_daemon();
+    print_bundle();
+    print_sound();
+    print_ntfy();
+    print_config_path();
+
+    println!();
+    Ok(())
+}
+
+fn print_platform() {
+    println!(
+        "\nPlatform:     {} ({})",
+        env::consts::OS,
+        env::consts::ARCH
+    );
+}
+
+fn print_features() {
+    let features: Vec<&str> = [
+        ("dbus", cfg!(feature = "dbus")),
+        ("watch", cfg!(feature = "watch")),
+        ("web", cfg!(feature = "web")),
+        ("wayland", cfg!(feature = "wayland")),
+        ("wasm", cfg!(feature = "wasm")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect();
+
+    if features.is_empty() {
+        println!("Features:     none (minimal build)");
+    } else {
+        println!("Features:     {}", features.join(", "));
+    }
+}
+
+fn print_session() {
+    let session_type = session::detect();
+    println!("Session:      {session_type}");
+
+    if let Some(desktop) = session::desktop_name() {
+        println!("Desktop:      {desktop}");
+    }
+
+    let remote = session::ssh_sessions(&ProcessRunner);
+    if !remote.is_empty() {
+        let hosts: Vec<&str> = remote.iter().map(|session| session.host.as_str()).collect();
+        println!("SSH:          logged in from {}", hosts.join(", "));
+    }
+
+    if !session_type.is_graphical() {
+        println!(
+            "              ⚠ No graphical session detected, desktop notifications may not be shown"
+        );
+    }
+}
+
+/// What tells idle time and what draws the break overlay in this session
+fn print_backends() {
+    match idle::backend(&ProcessRunner) {
+        Some(backend) => println!("Idle:         {backend}"),
+        None => println!("Idle:         unknown (away.after and follow-ups have no effect)"),
+    }
+    match overlay::backend(&ProcessRunner) {
+        Some(backend) => println!("Overlay:      {backend}"),
+        None => println!("Overlay:      none (notification.style overlay needs zenity)"),
+    }
+}
+
+fn print_break_apps() {
+    for detected in break_apps::detect(&ProcessRunner) {
+        println!("Break app:    ⚠ {detected} is on too, reminders will come twice");
+    }
+}
+
+fn print_frontmost_app() {
+    match focus::frontmost_app(&ProcessRunner) {
+        Some(app) => println!("Frontmost:    {app}"),
+        None => println!("Frontmost:    unknown (suppress.apps has no effect)"),
+    }
+}
+
+fn print_focus_mode() {
+    if !cfg!(target_os = "macos") {
+        return;
+    }
+
+    match focus_mode::active_focus() {
+        Some(focus) => println!("Focus:        {focus}"),
+        None => println!("Focus:        none (or szmer lacks Full Disk Access)"),
+    }
+}
+
+fn print_presenting() {
+    if let Some(network) = presenting::current_network(&ProcessRunner) {
+        println!("Network:      {network}");
+    }
+    if presenting::is_screen_shared(&ProcessRunner) {
+        println!("Sharing:      Screen is being shared");
+    }
+}
+
+fn print_container() {
+    if let Some(reason) = session::detect_container() {
+        println!("Container:    ⚠ {reason} (scheduler unavailable, use headless mode)");
+    }
+}
+
+fn print_crostini() {
+    if !session::is_crostini() {
+        return;
+    }
+
+    println!("ChromeOS:     Crostini Linux container");
+
+    if session::is_crostini_notification_bridge_active(&ProcessRunner) {
+        println!("              ✓ cros-notificationd is forwarding notifications to ChromeOS");
+    } else {
+        println!(
+            "              ✗ cros-notificationd is not running, notifications won't reach ChromeOS"
+        );
+        println!("              Install cros-guest-tools and run: systemctl --user start cros-notificationd");
+    }
+
+    println!("              Notification sounds are not forwarded, ChromeOS plays its own chime");
+}
+
+fn print_daemon() {
+    let status = match control::send(&Request::Status) {
+        Ok(Some(Response::Status(status))) => status,
+        _ => return,
+    };
+
+    println!("Daemon:       ✓ Running");
+
+    let Some(started_at) = status.started_at else {
+        return;
+    };
+    let hours = (SystemClock.now() - started_at).num_seconds().max(1) as f64 / 3600.0;
+    let wakeups = status.wakeups;
+
+    println!(
+        "              Timer wakeups: {} ({:.1}/hour), requests: {}",
+        wakeups.timer,
+        wakeups.timer as f64 / hours,
+        wakeups.requests
+    );
+    // Rates are meaningless in the first hour
+    if hours >= 1.0 && wakeups.timer as f64 / hours > 60.0 {
+        println!("              ⚠ Waking more than once per minute");
+    }
+}
+
+fn print_scheduler() {
+    if !schedule::is_installed() {
+        println!("Scheduler:    ✗ Not installed");
+        return;
+    }
+
+    let message = match schedule::get_scheduler_status() {
+        Ok(s) if s.is_running => "✓ Running",
+        Ok(_) => "⚠ Installed but not running",
+        Err(_) => "✗ Error checking status",
+    };
+    println!("Scheduler:    {message}");
+
+    let Some(report) = drift::report().filter(|report| report.runs + report.gaps > 0) else {
+        return;
+    };
+    let runs = |count: usize| match count {
+        1 => "1 run".to_string(),
+        count => format!("{count} runs"),
+    };
+    if report.average_late_seconds > drift::ON_TIME_SECONDS {
+        println!(
+            "              ⚠ {} is firing {} late on average ({})",
+            schedule::NAME,
+            format_interval(report.average_late_seconds as u64),
+            runs(report.runs)
+        );
+    } else if report.runs > 0 {
+        println!("              On time ({})", runs(report.runs));
+    }
+    if report.gaps > 0 {
+        println!(
+            "              {} over an interval late, e.g. after sleep",
+            runs(report.gaps)
+        );
+    }
+}
+
+fn print_bundle() {
+    if !cfg!(target_os = "macos") {
+        return;
+    }
+
+    if bundle::is_installed() {
+        println!("App bundle:   ✓ Installed");
+    } else {
+        println!("App bundle:   ○ Not installed (run 'szmer bundle install' for a stable notification identity)");
+    }
+}
+
+/// The configured sound, checked against the desktop's sound theme
+fn print_sound() {
+    let Some(name) = Config::load()
+        .ok()
+        .and_then(|config| config.notification_sound)
+    else {
+        return;
+    };
+
+    #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+    {
+        let theme = sound::theme_name(&ProcessRunner);
+        match sound::resolve(&name) {
+            Some(path) => println!("Sound:        ✓ {name} ({theme} theme, {})", path.display()),
+            None => println!(
+                "Sound:        ⚠ {name} isn't a sound event in the {theme} theme, run 'szmer install' to pick another"
+            ),
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd")))]
+    if sound::is_available(&name) {
+        println!("Sound:        ✓ {name}");
+    } else {
+        println!("Sound:        ⚠ {name} not found, run 'szmer install' to pick another");
+    }
+}
+
+/// The last push to ntfy, when a topic is configured
+fn print_ntfy() {
+    let Some(topic) = Config::load().ok().and_then(|config| config.ntfy.topic) else {
+        return;
+    };
+
+    match ntfy::last_delivery() {
+        Some(line) if line.ends_with(": delivered") => println!("ntfy:         ✓ {line}"),
+        Some(line) => println!("ntfy:         ⚠ {line}"),
+        None => println!("ntfy:         ○ Nothing pushed to {topic} yet"),
+    }
+}
+
+fn print_config_path() {
+    match (Config::find_config_file(), Config::get_config_path()) {
+        (Ok(Some(path)), Ok(config_path)) if path != config_path => println!(
+            "Config:       {} (moves to {} on the next save)",
+            path.display(),
+            config_path.display()
+        ),
+        (Ok(Some(path)), _) => println!("Config:       {}", path.display()),
+        (Ok(None), Ok(path)) => println!("Config:       {} (not created yet)", path.display()),
+        (Err(e), _) | (_, Err(e)) => println!("Config:       ✗ {e}"),
+    }
+}
@@ -0,0 +1,185 @@
+use crate::clock::Clock;
+use crate::timestamp;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const INTENT_FILENAME: &str = "schedule_intent.json";
+const RUNS_FILENAME: &str = "scheduler_runs";
+
+/// Runs kept in the log, plenty for an average without growing forever
+const KEPT_RUNS: usize = 100;
+
+/// Lateness up to this is the scheduler's usual jitter
+pub const ON_TIME_SECONDS: i64 = 60;
+
+/// What the scheduler was set up to do, written when it's installed or reloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Intent {
+    pub interval_seconds: u64,
+    /// When the scheduler was loaded, the first run is due an interval later
+    pub anchor: DateTime<Local>,
+}
+
+/// How closely the scheduler kept to the schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    /// Runs that came within an interval of when they were due
+    pub runs: usize,
+    /// Average lateness of those runs
+    pub average_late_seconds: i64,
+    /// Runs more than an interval late, e.g. after sleep or a shutdown
+    pub gaps: usize,
+}
+
+/// Remember the schedule, starting over the log of runs
+pub fn record_intent(
+    clock: &dyn Clock,
+    interval_seconds: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let intent = Intent {
+        interval_seconds,
+        anchor: clock.now(),
+    };
+    let path = intent_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(&intent)?)?;
+
+    match fs::remove_file(runs_path()?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn load_intent() -> Option<Intent> {
+    let content = fs::read_to_string(intent_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Log a scheduler run and return how many seconds late it was
+///
+/// `None` when no schedule was recorded, or when the run came well before
+/// the next one was due, so it wasn't the scheduler's, e.g. the one-off
+/// reminder after a snooze.
+pub fn record_run(clock: &dyn Clock) -> Result<Option<i64>, Box<dyn std::error::Error>> {
+    let Some(intent) = load_intent() else {
+        return Ok(None);
+    };
+    let mut runs = load_runs();
+    let now = clock.now();
+    let Some(late) = lateness(&intent, runs.last().map(|(at, _)| *at), now) else {
+        return Ok(None);
+    };
+
+    runs.push((now, late));
+    let skip = runs.len().saturating_sub(KEPT_RUNS);
+    let content: String = runs[skip..]
+        .iter()
+        .map(|(at, late)| format!("{} {late}\n", at.timestamp()))
+        .collect();
+    fs::write(runs_path()?, content)?;
+    Ok(Some(late))
+}
+
+/// Seconds `now` is past the run due after `last`, or after the anchor
+fn lateness(intent: &Intent, last: Option<DateTime<Local>>, now: DateTime<Local>) -> Option<i64> {
+    let due = last.unwrap_or(intent.anchor) + Duration::seconds(intent.interval_seconds as i64);
+    let late = (now - due).num_seconds();
+    (late >= -ON_TIME_SECONDS).then_some(late)
+}
+
+/// How the scheduler did since it was last installed or reloaded
+pub fn report() -> Option<Report> {
+    let intent = load_intent()?;
+    let runs: Vec<i64> = load_runs().into_iter().map(|(_, late)| late).collect();
+    Some(summarize(&runs, intent.interval_seconds))
+}
+
+fn summarize(runs: &[i64], interval_seconds: u64) -> Report {
+    let (on_schedule, gaps): (Vec<i64>, Vec<i64>) = runs
+        .iter()
+        .partition(|late| **late < interval_seconds as i64);
+    let average_late_seconds = if on_schedule.is_empty() {
+        0
+    } else {
+        on_schedule.iter().map(|late| late.max(&0)).sum::<i64>() / on_schedule.len() as i64
+    };
+
+    Report {
+        runs: on_schedule.len(),
+        average_late_seconds,
+        gaps: gaps.len(),
+    }
+}
+
+/// Logged runs as (time, seconds late), oldest first
+fn load_runs() -> Vec<(DateTime<Local>, i64)> {
+    let Some(content) = runs_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+    else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let (at, late) = line.split_once(' ')?;
+            let at = DateTime::from_timestamp(at.parse().ok()?, 0)?.with_timezone(&Local);
+            Some((at, late.parse().ok()?))
+        })
+        .collect()
+}
+
+fn intent_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(INTENT_FILENAME))
+}
+
+fn runs_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(RUNS_FILENAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_lateness_against_the_last_run() {
+        let at = |time: &str| MockClock::at(time).now();
+        let intent = Intent {
+            interval_seconds: 1800,
+            anchor: at("2025-01-06 09:00:00"),
+        };
+
+        assert_eq!(
+            lateness(&intent, None, at("2025-01-06 09:37:00")),
+            Some(420)
+        );
+        assert_eq!(
+            lateness(
+                &intent,
+                Some(at("2025-01-06 09:37:00")),
+                at("2025-01-06 10:06:30")
+            ),
+            Some(-30)
+        );
+        // The one-off reminder after a snooze
+        assert_eq!(
+            lateness(
+                &intent,
+                Some(at("2025-01-06 09:37:00")),
+                at("2025-01-06 09:50:00")
+            ),
+            None
+        );
+
+        let report = summarize(&[420, -30, 300, 7200], 1800);
+        assert_eq!(report.runs, 3);
+        assert_eq!(report.average_late_seconds, 240);
+        assert_eq!(report.gaps, 1);
+    }
+}
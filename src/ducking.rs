@@ -0,0 +1,129 @@
+use crate::command::{run_checked, spawn_after, SystemCommandRunner};
+use crate::config::DuckingConfig;
+
+/// How long other audio stays quieter for the notification sound alone
+pub const SOUND_SECONDS: u64 = 4;
+
+/// Lower the system volume, then restore it after `seconds`
+///
+/// The volume is lowered by `config.percent` of its current level, and a
+/// detached job sets it back, so the caller doesn't wait for the restore.
+/// Changing the volume by hand in the meantime is overwritten then.
+pub fn duck(
+    runner: &dyn SystemCommandRunner,
+    config: &DuckingConfig,
+    seconds: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let volume = current_volume(runner)?;
+    let ducked = ducked_volume(volume, config.percent);
+    if ducked == volume {
+        return Ok(());
+    }
+
+    let (program, args) = set_volume_command(ducked);
+    run_checked(
+        runner,
+        program,
+        &as_strs(&args),
+        "Failed to lower the volume",
+    )?;
+
+    let (program, args) = set_volume_command(volume);
+    spawn_after(seconds, program, &as_strs(&args))
+}
+
+/// Volume after lowering `volume` by `percent` of itself
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(ducked_volume(80, 50), 40);
+/// ```
+fn ducked_volume(volume: u32, percent: u8) -> u32 {
+    volume * (100 - u32::from(percent.min(100))) / 100
+}
+
+fn as_strs(args: &[String]) -> Vec<&str> {
+    args.iter().map(String::as_str).collect()
+}
+
+/// Output volume in percent, through CoreAudio's AppleScript bridge
+#[cfg(target_os = "macos")]
+fn current_volume(runner: &dyn SystemCommandRunner) -> Result<u32, Box<dyn std::error::Error>> {
+    let output = run_checked(
+        runner,
+        "osascript",
+        &["-e", "output volume of (get volume settings)"],
+        "Failed to read the volume",
+    )?;
+    output
+        .stdout
+        .trim()
+        .parse()
+        .map_err(|_| format!("Unexpected volume: '{}'", output.stdout.trim()).into())
+}
+
+#[cfg(target_os = "macos")]
+fn set_volume_command(volume: u32) -> (&'static str, Vec<String>) {
+    (
+        "osascript",
+        vec![
+            "-e".to_string(),
+            format!("set volume output volume {volume}"),
+        ],
+    )
+}
+
+/// Volume of the default sink in percent, through PulseAudio or PipeWire
+#[cfg(not(target_os = "macos"))]
+fn current_volume(runner: &dyn SystemCommandRunner) -> Result<u32, Box<dyn std::error::Error>> {
+    let output = run_checked(
+        runner,
+        "pactl",
+        &["get-sink-volume", "@DEFAULT_SINK@"],
+        "Failed to read the volume",
+    )?;
+    parse_pactl_volume(&output.stdout)
+        .ok_or_else(|| format!("Unexpected pactl output: '{}'", output.stdout.trim()).into())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_volume_command(volume: u32) -> (&'static str, Vec<String>) {
+    (
+        "pactl",
+        vec![
+            "set-sink-volume".to_string(),
+            "@DEFAULT_SINK@".to_string(),
+            format!("{volume}%"),
+        ],
+    )
+}
+
+/// First channel's percentage in `pactl get-sink-volume` output
+#[cfg(not(target_os = "macos"))]
+fn parse_pactl_volume(output: &str) -> Option<u32> {
+    output
+        .split_whitespace()
+        .find_map(|word| word.strip_suffix('%')?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ducked_volume() {
+        assert_eq!(ducked_volume(80, 50), 40);
+        assert_eq!(ducked_volume(70, 0), 70);
+        assert_eq!(ducked_volume(70, 100), 0);
+        assert_eq!(ducked_volume(70, 200), 0);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_parse_pactl_volume() {
+        let output = "Volume: front-left: 45875 /  70% / -9.29 dB,   front-right: 45875 /  70% / -9.29 dB\n        balance 0.00\n";
+        assert_eq!(parse_pactl_volume(output), Some(70));
+        assert_eq!(parse_pactl_volume("No such entity\n"), None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::i18n::{tr, tr_with};
+use std::fmt;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Exit code for failures without a more specific category
+const EXIT_FAILURE: u8 = 1;
+/// Exit codes follow sysexits.h so scripts can tell failures apart
+const EXIT_USAGE: u8 = 64;
+const EXIT_UNAVAILABLE: u8 = 69;
+const EXIT_TEMPFAIL: u8 = 75;
+const EXIT_CONFIG: u8 = 78;
+
+/// Errors szmer reports to the user, each with a suggestion for fixing it
+///
+/// Functions keep returning `Box<dyn std::error::Error>`; `main` downcasts
+/// to this type to pick the remediation hint and exit code.
+#[derive(Debug)]
+pub enum SzmerError {
+    /// The system scheduler is not installed
+    NotInstalled,
+    /// The system scheduler is already installed
+    AlreadyInstalled { path: Option<PathBuf> },
+    /// No daemon is listening on the control socket
+    DaemonNotRunning,
+    /// Another daemon holds the pidfile or socket
+    DaemonAlreadyRunning { pid: Option<u32> },
+    /// The scheduler and the daemon would both send every reminder
+    SchedulerConflictsWithDaemon { pid: Option<u32> },
+    /// The feature doesn't exist on this operating system
+    Unsupported { feature: &'static str },
+    /// `szmer config set` with a key that doesn't exist
+    UnknownConfigKey { key: String },
+    /// `szmer config set` with a value the key doesn't accept
+    InvalidConfigValue { key: String, message: String },
+    /// The config file holds values szmer can't use, one line each with the fix
+    InvalidConfig {
+        path: PathBuf,
+        problems: Vec<String>,
+    },
+    /// A reminder was due but an integration decided against sending it
+    NotificationSkipped { reason: String },
+    /// An external tool such as systemctl or launchctl failed
+    CommandFailed { context: String, stderr: String },
+}
+
+impl SzmerError {
+    /// What the user can do about the error, if anything
+    pub fn hint(&self) -> Option<String> {
+        let id = match self {
+            Self::NotInstalled => "hint-install",
+            Self::AlreadyInstalled { .. } => "hint-reinstall",
+            Self::DaemonNotRunning => "hint-start-daemon",
+            Self::DaemonAlreadyRunning { .. } => "hint-daemon-running",
+            Self::SchedulerConflictsWithDaemon { pid: Some(_) } => "hint-stop-daemon",
+            Self::SchedulerConflictsWithDaemon { pid: None } => "hint-uninstall-scheduler",
+            Self::Unsupported { .. } => return None,
+            Self::UnknownConfigKey { .. } => "hint-config-keys",
+            Self::InvalidConfigValue { .. } => "hint-config-show",
+            Self::InvalidConfig { .. } => "hint-config-validate",
+            Self::NotificationSkipped { .. } => return None,
+            Self::CommandFailed { .. } => "hint-doctor",
+        };
+        Some(tr(id))
+    }
+
+    /// Process exit code for the error
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::NotInstalled | Self::DaemonNotRunning | Self::Unsupported { .. } => {
+                EXIT_UNAVAILABLE
+            }
+            Self::UnknownConfigKey { .. } | Self::InvalidConfigValue { .. } => EXIT_USAGE,
+            Self::InvalidConfig { .. } => EXIT_CONFIG,
+            Self::NotificationSkipped { .. } => EXIT_TEMPFAIL,
+            Self::AlreadyInstalled { .. }
+            | Self::DaemonAlreadyRunning { .. }
+            | Self::SchedulerConflictsWithDaemon { .. }
+            | Self::CommandFailed { .. } => EXIT_FAILURE,
+        }
+    }
+}
+
+impl fmt::Display for SzmerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NotInstalled => tr("error-not-installed"),
+            Self::AlreadyInstalled { path: Some(path) } => tr_with(
+                "error-already-installed-at",
+                &[("path", path.display().to_string().into())],
+            ),
+            Self::AlreadyInstalled { path: None } => tr("error-already-installed"),
+            Self::DaemonNotRunning => tr("error-daemon-not-running"),
+            Self::DaemonAlreadyRunning { pid: Some(pid) } => {
+                tr_with("error-daemon-already-running-pid", &[("pid", pid.into())])
+            }
+            Self::DaemonAlreadyRunning { pid: None } => tr("error-daemon-already-running"),
+            Self::SchedulerConflictsWithDaemon { pid: Some(pid) } => {
+                tr_with("error-scheduler-conflict-daemon", &[("pid", pid.into())])
+            }
+            Self::SchedulerConflictsWithDaemon { pid: None } => {
+                tr("error-scheduler-conflict-scheduler")
+            }
+            Self::Unsupported { feature } => {
+                tr_with("error-unsupported", &[("feature", (*feature).into())])
+            }
+            Self::UnknownConfigKey { key } => {
+                tr_with("error-unknown-config-key", &[("key", key.as_str().into())])
+            }
+            Self::InvalidConfigValue { key, message } => format!("{key}: {message}"),
+            Self::InvalidConfig { path, problems } => {
+                let mut message = tr_with(
+                    "error-invalid-config",
+                    &[
+                        ("path", path.display().to_string().into()),
+                        ("count", problems.len().into()),
+                    ],
+                );
+                for problem in problems {
+                    message.push_str(&format!("\n  • {problem}"));
+                }
+                message
+            }
+            Self::NotificationSkipped { reason } => tr_with(
+                "error-notification-skipped",
+                &[("reason", reason.as_str().into())],
+            ),
+            Self::CommandFailed { context, stderr } => format!("{context}: {}", stderr.trim()),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for SzmerError {}
+
+/// Print an error for the user and pick the exit code
+///
+/// Errors are shown as `✗ message` with an indented hint when one is known,
+/// in color when stderr is a terminal and `NO_COLOR` is unset.
+pub fn report(error: &(dyn std::error::Error + 'static)) -> ExitCode {
+    let typed = error.downcast_ref::<SzmerError>();
+    let color = std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+
+    eprintln!("{}", render(error, typed.and_then(SzmerError::hint), color));
+
+    ExitCode::from(typed.map_or(EXIT_FAILURE, SzmerError::exit_code))
+}
+
+fn render(error: &dyn fmt::Display, hint: Option<String>, color: bool) -> String {
+    let (red, yellow, reset) = if color {
+        ("\x1b[31m", "\x1b[33m", "\x1b[0m")
+    } else {
+        ("", "", "")
+    };
+
+    let mut output = format!("{red}✗ {error}{reset}");
+    if let Some(hint) = hint {
+        output.push_str(&format!("\n  {yellow}{}:{reset} {hint}", tr("hint-label")));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_with_hint_without_color() {
+        let error = SzmerError::NotInstalled;
+        assert_eq!(
+            render(&error, error.hint(), false),
+            "✗ Break reminder is not installed\n  hint: Run 'szmer install' first."
+        );
+    }
+
+    #[test]
+    fn test_render_colors_message_and_hint() {
+        let rendered = render(&"Boom", Some("Try again".to_string()), true);
+        assert!(rendered.starts_with("\x1b[31m✗ Boom\x1b[0m"));
+        assert!(rendered.contains("\x1b[33mhint:\x1b[0m Try again"));
+    }
+
+    #[test]
+    fn test_exit_codes_follow_sysexits() {
+        assert_eq!(SzmerError::DaemonNotRunning.exit_code(), 69);
+        assert_eq!(
+            SzmerError::UnknownConfigKey {
+                key: "nope".to_string()
+            }
+            .exit_code(),
+            64
+        );
+        assert_eq!(
+            SzmerError::NotificationSkipped {
+                reason: "not tracking".to_string()
+            }
+            .exit_code(),
+            75
+        );
+    }
+}
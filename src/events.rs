@@ -0,0 +1,136 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// How many past events are replayed to new subscribers
+const HISTORY_LEN: usize = 20;
+
+/// Something that happened in the daemon, streamed to subscribers as JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// A break reminder was delivered
+    ReminderSent,
+    /// A reminder was due but a gate held it back
+    Skipped { reason: String },
+    /// Reminders were paused
+    Paused,
+    /// Reminders were resumed
+    Resumed,
+    /// The break following a reminder started
+    BreakStarted,
+    /// The break following a reminder is over
+    BreakEnded,
+    /// The daemon stopped in the middle of a break
+    BreakInterrupted,
+    /// Input went on right after a break started, so it wasn't taken
+    BreakIgnored,
+    /// Nobody has been at the machine for `away.after`, reminders wait
+    Away { since: DateTime<Local> },
+    /// The user is back after being away, the interval starts over
+    Back,
+}
+
+/// An event with the time it happened, one JSON object per line on the wire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    pub at: DateTime<Local>,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// Fan-out of daemon events to any number of subscribers
+///
+/// Keeps a short history so `szmer events` has something to show even
+/// without `--follow`. Cheap to clone, all clones share the same state.
+#[derive(Clone, Default)]
+pub struct Bus {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    history: VecDeque<Record>,
+    subscribers: Vec<Sender<Record>>,
+}
+
+impl Bus {
+    /// Record an event and send it to every live subscriber
+    pub fn publish(&self, event: Event) {
+        let record = Record {
+            at: Local::now(),
+            event,
+        };
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        if inner.history.len() == HISTORY_LEN {
+            inner.history.pop_front();
+        }
+        inner.history.push_back(record.clone());
+
+        // Subscribers whose receiver is gone have disconnected
+        inner
+            .subscribers
+            .retain(|subscriber| subscriber.send(record.clone()).is_ok());
+    }
+
+    /// Recent events, oldest first
+    pub fn history(&self) -> Vec<Record> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.history.iter().cloned().collect()
+    }
+
+    /// Recent events plus a receiver for everything published afterwards
+    ///
+    /// Both are taken under the same lock so no event is missed or repeated.
+    pub fn subscribe(&self) -> (Vec<Record>, Receiver<Record>) {
+        let (tx, rx) = mpsc::channel();
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.subscribers.push(tx);
+        (inner.history.iter().cloned().collect(), rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscriber_receives_history_then_new_events() {
+        let bus = Bus::default();
+        bus.publish(Event::Paused);
+
+        let (history, rx) = bus.subscribe();
+        bus.publish(Event::Resumed);
+
+        assert!(matches!(
+            history[..],
+            [Record {
+                event: Event::Paused,
+                ..
+            }]
+        ));
+        assert!(matches!(rx.try_recv().unwrap().event, Event::Resumed));
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let bus = Bus::default();
+        for _ in 0..HISTORY_LEN + 5 {
+            bus.publish(Event::ReminderSent);
+        }
+        assert_eq!(bus.history().len(), HISTORY_LEN);
+    }
+
+    #[test]
+    fn test_event_json_shape() {
+        let json = serde_json::to_value(Event::Skipped {
+            reason: "no active timewarrior session".to_string(),
+        })
+        .unwrap();
+        assert_eq!(json["event"], "skipped");
+        assert_eq!(json["reason"], "no active timewarrior session");
+    }
+}
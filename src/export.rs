@@ -0,0 +1,154 @@
+use crate::clock::Clock;
+use crate::config::Config;
+use crate::i18n::tr;
+use chrono::{Datelike, Duration, NaiveTime, Utc, Weekday};
+use std::fs;
+use std::path::Path;
+
+/// Days breaks repeat on, as an iCalendar BYDAY list
+const WORK_DAYS: &str = "MO,TU,WE,TH,FR";
+
+/// Write the planned breaks of a working day as an iCalendar file
+///
+/// Breaks fall every interval after `from` and before `to`, repeating on
+/// weekdays. Printed to stdout unless `output` is given.
+pub fn run_ics(
+    clock: &dyn Clock,
+    from: NaiveTime,
+    to: NaiveTime,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if from >= to {
+        return Err("--from must be earlier than --to".into());
+    }
+
+    let calendar = render_ics(&Config::load()?, clock, from, to);
+    match output {
+        Some(path) => {
+            fs::write(path, calendar)?;
+            println!("✓ Wrote {}", path.display());
+        }
+        None => print!("{calendar}"),
+    }
+
+    Ok(())
+}
+
+/// Parse a time of day like "09:00" for clap
+pub fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| format!("Invalid time: '{value}'. Use HH:MM, e.g. 09:00"))
+}
+
+/// Render one weekly recurring event per break
+///
+/// Times are floating (no time zone), so calendars show the breaks at the
+/// same wall-clock time wherever the user is.
+fn render_ics(config: &Config, clock: &dyn Clock, from: NaiveTime, to: NaiveTime) -> String {
+    // Start on a work day so the first event is also the first occurrence
+    let mut first_day = clock.now().date_naive();
+    while matches!(first_day.weekday(), Weekday::Sat | Weekday::Sun) {
+        first_day = first_day.succ_opt().expect("date in range");
+    }
+    let stamp = clock
+        .now()
+        .with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string();
+    let interval = Duration::seconds(config.interval_seconds as i64);
+    let break_minutes = (config.break_duration_seconds / 60).max(1);
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//szmer//szmer {}//EN", env!("CARGO_PKG_VERSION")),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Breaks".to_string(),
+    ];
+
+    let mut start = from + interval;
+    // Adding wraps past midnight, which would make `start` small again
+    while start < to && start > from {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:szmer-break-{}@szmer", start.format("%H%M")),
+            format!("DTSTAMP:{stamp}"),
+            format!(
+                "DTSTART:{}",
+                first_day.and_time(start).format("%Y%m%dT%H%M%S")
+            ),
+            format!("DURATION:PT{break_minutes}M"),
+            format!("RRULE:FREQ=WEEKLY;BYDAY={WORK_DAYS}"),
+            format!("SUMMARY:{}", escape_text(&tr("notification-summary"))),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+        start += interval;
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    // iCalendar lines end in CRLF
+    lines.iter().map(|line| format!("{line}\r\n")).collect()
+}
+
+/// Escape a value for an iCalendar TEXT property
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_render_ics_lists_breaks_within_working_hours() {
+        let clock = MockClock::at("2025-01-06 08:00:00");
+        let config = Config {
+            interval_seconds: 90 * 60,
+            break_duration_seconds: 600,
+            ..Config::default()
+        };
+        let calendar = render_ics(
+            &config,
+            &clock,
+            parse_time("09:00").unwrap(),
+            parse_time("13:00").unwrap(),
+        );
+
+        let starts: Vec<&str> = calendar
+            .lines()
+            .filter_map(|line| line.strip_prefix("DTSTART:"))
+            .collect();
+        assert_eq!(starts, ["20250106T103000", "20250106T120000"]);
+        assert!(calendar.contains("DURATION:PT10M\r\n"));
+        assert!(calendar.contains("RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_render_ics_starts_on_a_work_day() {
+        let saturday = MockClock::at("2025-01-11 08:00:00");
+        let calendar = render_ics(
+            &Config::default(),
+            &saturday,
+            parse_time("09:00").unwrap(),
+            parse_time("11:00").unwrap(),
+        );
+        assert!(calendar.contains("DTSTART:20250113T100000\r\n"));
+    }
+
+    #[test]
+    fn test_parse_time_rejects_garbage() {
+        assert!(parse_time("9am").is_err());
+        assert_eq!(
+            parse_time("17:30"),
+            Ok(NaiveTime::from_hms_opt(17, 30, 0).unwrap())
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::command::SystemCommandRunner;
+use serde_json::Value;
+use std::env;
+use wildmatch::WildMatch;
+
+/// Name of the application in front, as far as the platform can tell
+///
+/// Uses System Events on macOS, the sway tree on sway and `xdotool` on
+/// X11. GNOME and KDE on Wayland don't expose the focused window to other
+/// programs, so there this returns `None`.
+pub fn frontmost_app(runner: &dyn SystemCommandRunner) -> Option<String> {
+    if cfg!(target_os = "macos") {
+        return run_trimmed(
+            runner,
+            "osascript",
+            &[
+                "-e",
+                "tell application \"System Events\" to get name of first application process whose frontmost is true",
+            ],
+        );
+    }
+
+    if env::var_os("SWAYSOCK").is_some() {
+        let tree = run_trimmed(runner, "swaymsg", &["-t", "get_tree", "--raw"])?;
+        return focused_sway_app(&serde_json::from_str(&tree).ok()?);
+    }
+
+    if env::var_os("DISPLAY").is_some() {
+        return run_trimmed(
+            runner,
+            "xdotool",
+            &["getactivewindow", "getwindowclassname"],
+        );
+    }
+
+    None
+}
+
+fn run_trimmed(runner: &dyn SystemCommandRunner, program: &str, args: &[&str]) -> Option<String> {
+    let output = runner.run(program, args).ok()?;
+    let value = output.stdout.trim();
+    (output.success && !value.is_empty()).then(|| value.to_string())
+}
+
+/// App id (Wayland) or window class (XWayland) of the focused sway window
+fn focused_sway_app(node: &Value) -> Option<String> {
+    if node["focused"].as_bool() == Some(true) {
+        return node["app_id"]
+            .as_str()
+            .or_else(|| node["window_properties"]["class"].as_str())
+            .map(String::from);
+    }
+
+    ["nodes", "floating_nodes"]
+        .iter()
+        .filter_map(|key| node[key].as_array())
+        .flatten()
+        .find_map(focused_sway_app)
+}
+
+/// First pattern in `patterns` that matches `app`, ignoring case
+///
+/// Patterns are globs: `*` matches any run of characters and `?` a single
+/// one.
+///
+/// # Examples
+///
+/// ```
+/// let patterns = vec!["obs*".to_string(), "keynote".to_string()];
+/// assert_eq!(matching_pattern("OBS Studio", &patterns), Some("obs*"));
+/// ```
+pub fn matching_pattern<'a>(app: &str, patterns: &'a [String]) -> Option<&'a str> {
+    let app = app.to_lowercase();
+    patterns
+        .iter()
+        .find(|pattern| WildMatch::new(&pattern.to_lowercase()).matches(&app))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_pattern_globs_ignore_case() {
+        let patterns = vec!["obs*".to_string(), "virt-manager".to_string()];
+        assert_eq!(matching_pattern("OBS Studio", &patterns), Some("obs*"));
+        assert_eq!(
+            matching_pattern("Virt-Manager", &patterns),
+            Some("virt-manager")
+        );
+        assert_eq!(matching_pattern("firefox", &patterns), None);
+    }
+
+    #[test]
+    fn test_focused_sway_app() {
+        let tree = serde_json::json!({
+            "focused": false,
+            "nodes": [{
+                "focused": false,
+                "nodes": [
+                    { "focused": false, "app_id": "foot", "nodes": [] },
+                    { "focused": true, "app_id": null, "window_properties": { "class": "obs" } },
+                ],
+            }],
+        });
+        assert_eq!(focused_sway_app(&tree).as_deref(), Some("obs"));
+    }
+}
@@ -0,0 +1,80 @@
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where macOS keeps Focus state, relative to the home directory
+const DND_DB_DIR: &str = "Library/DoNotDisturb/DB";
+
+/// Name of the macOS Focus that is on right now, like "Work" or "Personal"
+///
+/// Read from the Do Not Disturb database, which needs Full Disk Access on
+/// recent macOS versions. Returns `None` when no Focus is on, the files
+/// can't be read, or on other platforms.
+pub fn active_focus() -> Option<String> {
+    let db = PathBuf::from(env::var_os("HOME")?).join(DND_DB_DIR);
+    active_focus_in(&db)
+}
+
+fn active_focus_in(db: &Path) -> Option<String> {
+    let assertions = read_json(&db.join("Assertions.json"))?;
+    let configurations = read_json(&db.join("ModeConfigurations.json"))?;
+
+    let mode_id = active_mode_id(&assertions)?;
+    configurations["data"][0]["modeConfigurations"][&mode_id]["mode"]["name"]
+        .as_str()
+        .map(String::from)
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Identifier of the Focus mode asserted most recently
+fn active_mode_id(assertions: &Value) -> Option<String> {
+    assertions["data"][0]["storeAssertionRecords"]
+        .as_array()?
+        .iter()
+        .max_by(|a, b| {
+            let start = |record: &Value| record["assertionStartDateTimestamp"].as_f64();
+            start(a)
+                .partial_cmp(&start(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?["assertionDetails"]["assertionDetailsModeIdentifier"]
+        .as_str()
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_focus_reads_dnd_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let assertions = serde_json::json!({ "data": [{ "storeAssertionRecords": [
+            { "assertionStartDateTimestamp": 100.0,
+              "assertionDetails": { "assertionDetailsModeIdentifier": "com.apple.focus.personal-time" } },
+            { "assertionStartDateTimestamp": 200.0,
+              "assertionDetails": { "assertionDetailsModeIdentifier": "com.apple.focus.work" } },
+        ] }] });
+        let configurations = serde_json::json!({ "data": [{ "modeConfigurations": {
+            "com.apple.focus.work": { "mode": { "name": "Work" } },
+            "com.apple.focus.personal-time": { "mode": { "name": "Personal" } },
+        } }] });
+        fs::write(dir.path().join("Assertions.json"), assertions.to_string()).unwrap();
+        fs::write(
+            dir.path().join("ModeConfigurations.json"),
+            configurations.to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(active_focus_in(dir.path()).as_deref(), Some("Work"));
+    }
+
+    #[test]
+    fn test_no_focus_without_assertions() {
+        let empty = serde_json::json!({ "data": [{ "storeAssertionRecords": [] }] });
+        assert_eq!(active_mode_id(&empty), None);
+    }
+}
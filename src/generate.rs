@@ -0,0 +1,518 @@
+use crate::config::{BreakProfile, Config, TimerClock};
+use crate::schedule;
+use clap::ValueEnum;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Declarative output formats for `szmer generate`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// home-manager module (systemd user units and xdg config file)
+    HomeManager,
+    /// NixOS module (systemd user units and a tmpfiles rule for the config)
+    Nix,
+}
+
+/// Operating systems `szmer generate-unit` can render service files for
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum TargetOs {
+    /// launchd agent plist
+    Macos,
+    /// systemd user service and timer
+    Linux,
+}
+
+/// Render the scheduler files `install` would write for a target OS
+///
+/// Files are printed to stdout, or written to `output_dir` when given.
+///
+/// # Arguments
+/// * `os` - Target operating system
+/// * `binary_path` - Binary the scheduler should run (defaults to the current executable)
+/// * `output_dir` - Directory to write the files to instead of printing them
+/// * `daemon` - Render a supervised `szmer daemon` service instead of the timer
+pub fn run_unit(
+    os: TargetOs,
+    binary_path: Option<String>,
+    output_dir: Option<&Path>,
+    daemon: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let binary_path = resolve_binary_path(binary_path)?;
+    let files: Vec<(String, String)> = if daemon {
+        owned(render_daemon_units(os, &binary_path))
+    } else {
+        let config = Config::load()?;
+        let mut files = owned(render_units(
+            os,
+            &binary_path,
+            config.interval_seconds,
+            config.schedule.clock,
+        ));
+        files.extend(render_profile_units(os, &binary_path, &config.profiles));
+        files
+    };
+
+    match output_dir {
+        Some(dir) => {
+            fs::create_dir_all(dir)?;
+            for (name, content) in &files {
+                let path = dir.join(name);
+                fs::write(&path, content)?;
+                println!("✓ Wrote {}", path.display());
+            }
+        }
+        None if files.len() == 1 => print!("{}", files[0].1),
+        None => {
+            let sections: Vec<String> = files
+                .iter()
+                .map(|(name, content)| format!("==> {name} <==\n{content}"))
+                .collect();
+            print!("{}", sections.join("\n"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Render the scheduler files for a target OS as (file name, content) pairs
+pub fn render_units(
+    os: TargetOs,
+    binary_path: &str,
+    interval_seconds: u64,
+    clock: TimerClock,
+) -> Vec<(&'static str, String)> {
+    match os {
+        TargetOs::Macos => vec![(
+            "com.michalczmiel.szmer.plist",
+            schedule::render_launchd_plist(binary_path, interval_seconds),
+        )],
+        TargetOs::Linux => vec![
+            (
+                "szmer.service",
+                schedule::render_systemd_service(binary_path, clock),
+            ),
+            (
+                "szmer.timer",
+                schedule::render_systemd_timer(interval_seconds, clock),
+            ),
+        ],
+    }
+}
+
+/// Render the scheduler files of each break profile as (file name, content) pairs
+pub fn render_profile_units(
+    os: TargetOs,
+    binary_path: &str,
+    profiles: &[BreakProfile],
+) -> Vec<(String, String)> {
+    profiles
+        .iter()
+        .flat_map(|profile| match os {
+            TargetOs::Macos => vec![(
+                format!("{}.plist", schedule::profile_label(&profile.name)),
+                schedule::render_profile_launchd_plist(binary_path, profile),
+            )],
+            TargetOs::Linux => {
+                let unit = schedule::profile_unit(&profile.name);
+                vec![
+                    (
+                        format!("{unit}.service"),
+                        schedule::render_profile_systemd_service(binary_path, profile),
+                    ),
+                    (
+                        format!("{unit}.timer"),
+                        schedule::render_profile_systemd_timer(profile),
+                    ),
+                ]
+            }
+        })
+        .collect()
+}
+
+fn owned(files: Vec<(&'static str, String)>) -> Vec<(String, String)> {
+    files
+        .into_iter()
+        .map(|(name, content)| (name.to_string(), content))
+        .collect()
+}
+
+/// Render the files supervising `szmer daemon` for a target OS
+pub fn render_daemon_units(os: TargetOs, binary_path: &str) -> Vec<(&'static str, String)> {
+    match os {
+        TargetOs::Macos => vec![(
+            "com.michalczmiel.szmer.daemon.plist",
+            schedule::render_daemon_launchd_plist(binary_path),
+        )],
+        TargetOs::Linux => vec![(
+            "szmer-daemon.service",
+            schedule::render_daemon_systemd_service(binary_path),
+        )],
+    }
+}
+
+/// Print declarative snippets equivalent to what `install` writes imperatively
+///
+/// # Arguments
+/// * `format` - Which Nix module flavour to emit
+/// * `binary_path` - Binary to run from the timer (defaults to the current executable)
+pub fn run(format: Format, binary_path: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let binary_path = resolve_binary_path(binary_path)?;
+
+    let config_value = serde_json::to_value(&config)?;
+    let snippet = match format {
+        Format::HomeManager => {
+            home_manager_module(&binary_path, config.interval_seconds, &config_value)
+        }
+        Format::Nix => nixos_module(&binary_path, config.interval_seconds, &config_value),
+    };
+
+    print!("{snippet}");
+    Ok(())
+}
+
+fn resolve_binary_path(binary_path: Option<String>) -> Result<String, Box<dyn std::error::Error>> {
+    match binary_path {
+        Some(path) => Ok(path),
+        None => env::current_exe()?
+            .canonicalize()?
+            .to_str()
+            .ok_or_else(|| "Failed to convert binary path to string".into())
+            .map(String::from),
+    }
+}
+
+fn home_manager_module(binary_path: &str, interval_seconds: u64, config: &Value) -> String {
+    let exec_start = nix_string(&format!("{} notify", schedule::systemd_arg(binary_path)));
+    let config = to_nix(config, 1);
+
+    format!(
+        r#"# Generated by `szmer generate --format home-manager`
+{{ pkgs, ... }}:
+{{
+  systemd.user.services.szmer = {{
+    Unit.Description = "Szmer break reminder";
+    Service = {{
+      Type = "oneshot";
+      ExecStart = {exec_start};
+    }};
+  }};
+
+  systemd.user.timers.szmer = {{
+    Unit.Description = "Szmer break reminder timer";
+    Timer = {{
+      OnBootSec = {interval_seconds};
+      OnUnitActiveSec = {interval_seconds};
+      Persistent = true;
+    }};
+    Install.WantedBy = [ "timers.target" ];
+  }};
+
+  xdg.configFile."szmer/config.toml".source = (pkgs.formats.toml {{ }}).generate "szmer-config.toml" {config};
+}}
+"#
+    )
+}
+
+fn nixos_module(binary_path: &str, interval_seconds: u64, config: &Value) -> String {
+    let exec_start = nix_string(&format!("{} notify", schedule::systemd_arg(binary_path)));
+    let config = to_nix(config, 1);
+
+    format!(
+        r#"# Generated by `szmer generate --format nix`
+{{ pkgs, ... }}:
+let
+  szmerConfig = (pkgs.formats.toml {{ }}).generate "szmer-config.toml" {config};
+in
+{{
+  systemd.user.services.szmer = {{
+    description = "Szmer break reminder";
+    serviceConfig = {{
+      Type = "oneshot";
+      ExecStart = {exec_start};
+    }};
+  }};
+
+  systemd.user.timers.szmer = {{
+    description = "Szmer break reminder timer";
+    wantedBy = [ "timers.target" ];
+    timerConfig = {{
+      OnBootSec = "{interval_seconds}";
+      OnUnitActiveSec = "{interval_seconds}";
+      Persistent = true;
+    }};
+  }};
+
+  systemd.user.tmpfiles.rules = [
+    "L+ %h/.config/szmer/config.toml - - - - ${{szmerConfig}}"
+  ];
+}}
+"#
+    )
+}
+
+/// Render a JSON value as a Nix expression
+///
+/// # Examples
+///
+/// ```
+/// let value = serde_json::json!({ "paused": false, "interval_seconds": 3600 });
+/// assert_eq!(to_nix(&value, 0), "{\n  interval_seconds = 3600;\n  paused = false;\n}");
+/// ```
+pub fn to_nix(value: &Value, indent: usize) -> String {
+    let padding = "  ".repeat(indent);
+
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => nix_string(s),
+        Value::Array(items) if items.is_empty() => "[ ]".to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| format!("{padding}  {}", to_nix(item, indent + 1)))
+                .collect();
+            format!("[\n{}\n{padding}]", items.join("\n"))
+        }
+        Value::Object(map) if map.is_empty() => "{ }".to_string(),
+        Value::Object(map) => {
+            let attrs: Vec<String> = map
+                .iter()
+                .map(|(key, value)| {
+                    format!(
+                        "{padding}  {} = {};",
+                        nix_attr_name(key),
+                        to_nix(value, indent + 1)
+                    )
+                })
+                .collect();
+            format!("{{\n{}\n{padding}}}", attrs.join("\n"))
+        }
+    }
+}
+
+fn nix_string(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn nix_attr_name(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '\''));
+
+    if is_identifier {
+        key.to_string()
+    } else {
+        nix_string(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_units_per_os() {
+        let macos = render_units(
+            TargetOs::Macos,
+            "/usr/local/bin/szmer",
+            1500,
+            TimerClock::Wall,
+        );
+        assert_eq!(macos.len(), 1);
+        assert!(macos[0].1.contains("<integer>1500</integer>"));
+
+        let linux = render_units(TargetOs::Linux, "/usr/bin/szmer", 1500, TimerClock::Wall);
+        let names: Vec<&str> = linux.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["szmer.service", "szmer.timer"]);
+        assert!(linux[0].1.contains("ExecStart=/usr/bin/szmer notify"));
+        assert!(!linux[0].1.contains("ExecStopPost"));
+        assert!(linux[1].1.contains("OnUnitActiveSec=1500"));
+
+        // Each run re-arms a one-shot timer on the monotonic clock
+        let monotonic = render_units(
+            TargetOs::Linux,
+            "/usr/bin/szmer",
+            1500,
+            TimerClock::Monotonic,
+        );
+        assert!(monotonic[0]
+            .1
+            .contains("ExecStopPost=systemctl --user restart szmer.timer"));
+        assert!(monotonic[1].1.contains("OnActiveSec=1500"));
+        assert!(!monotonic[1].1.contains("Persistent"));
+    }
+
+    #[test]
+    fn test_render_profile_units() {
+        let profiles = [BreakProfile {
+            name: "eyes".to_string(),
+            interval_seconds: 1200,
+            category: None,
+            sound: None,
+        }];
+
+        let linux = render_profile_units(TargetOs::Linux, "/usr/bin/szmer", &profiles);
+        let names: Vec<&str> = linux.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["szmer-profile-eyes.service", "szmer-profile-eyes.timer"]
+        );
+        assert!(linux[0]
+            .1
+            .contains("ExecStart=/usr/bin/szmer notify --profile eyes"));
+        assert!(linux[1].1.contains("Requires=szmer-profile-eyes.service"));
+        assert!(linux[1].1.contains("OnUnitActiveSec=1200"));
+
+        let macos = render_profile_units(TargetOs::Macos, "/usr/local/bin/szmer", &profiles);
+        assert_eq!(macos[0].0, "com.michalczmiel.szmer.profile.eyes.plist");
+        assert!(macos[0].1.contains("<string>--profile</string>"));
+        assert!(macos[0].1.contains("<integer>1200</integer>"));
+    }
+
+    #[test]
+    fn test_render_daemon_units_per_os() {
+        let macos = render_daemon_units(TargetOs::Macos, "/usr/local/bin/szmer");
+        assert!(macos[0].1.contains("<key>KeepAlive</key>"));
+        assert!(macos[0].1.contains("<string>daemon</string>"));
+        assert!(macos[0].1.contains("<string>Aqua</string>"));
+
+        let linux = render_daemon_units(TargetOs::Linux, "/usr/bin/szmer");
+        assert_eq!(linux[0].0, "szmer-daemon.service");
+        assert!(linux[0].1.contains("ExecStart=/usr/bin/szmer daemon"));
+        assert!(linux[0].1.contains("Restart=on-failure"));
+        assert!(linux[0].1.contains("WatchdogSec="));
+        assert!(linux[0].1.contains("WantedBy=graphical-session.target"));
+    }
+
+    #[test]
+    fn test_render_units_quote_odd_paths() {
+        let linux = render_units(TargetOs::Linux, "/opt/a&b \"c\"/100%", 60, TimerClock::Wall);
+        assert!(linux[0]
+            .1
+            .contains(r#"ExecStart="/opt/a&b \"c\"/100%%" notify"#));
+
+        let macos = render_units(TargetOs::Macos, "/opt/a&b <c>", 60, TimerClock::Wall);
+        assert!(macos[0]
+            .1
+            .contains("<string>/opt/a&amp;b &lt;c&gt;</string>"));
+    }
+
+    /// Compare rendered files against `tests/golden/<case>/<file>`
+    ///
+    /// Run with `SZMER_UPDATE_GOLDEN=1` to rewrite the golden files after an
+    /// intentional change, then review the diff.
+    fn assert_golden(case: &str, files: &[(&'static str, String)]) {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(case);
+
+        for (name, content) in files {
+            let path = dir.join(name);
+            if env::var_os("SZMER_UPDATE_GOLDEN").is_some() {
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(&path, content).unwrap();
+                continue;
+            }
+
+            let expected = fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("Missing golden file {}: {e}", path.display()));
+            assert_eq!(
+                content,
+                &expected,
+                "{} changed, rerun with SZMER_UPDATE_GOLDEN=1 if intended",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_units_match_golden_files() {
+        for (os, dir) in [(TargetOs::Macos, "macos"), (TargetOs::Linux, "linux")] {
+            for interval in [60, 1500, 3600, 5400] {
+                let files = render_units(os, "/usr/local/bin/szmer", interval, TimerClock::Wall);
+                assert_golden(&format!("{dir}-{interval}"), &files);
+            }
+
+            // A path with spaces stays one argument: quoted for systemd, escaped in the plist
+            let files = render_units(os, "/Users/Jane Doe/bin/szmer", 1500, TimerClock::Wall);
+            assert_golden(&format!("{dir}-spaces"), &files);
+
+            let files = render_daemon_units(os, "/usr/local/bin/szmer");
+            assert_golden(&format!("{dir}-daemon"), &files);
+        }
+    }
+
+    #[test]
+    fn test_monotonic_units_match_golden_files() {
+        // launchd has no monotonic clock, only systemd units change
+        let files = render_units(
+            TargetOs::Linux,
+            "/usr/local/bin/szmer",
+            1500,
+            TimerClock::Monotonic,
+        );
+        assert_golden("linux-monotonic", &files);
+    }
+
+    #[test]
+    fn test_nix_modules_match_golden_files() {
+        let config = serde_json::to_value(Config::default()).unwrap();
+        let files = [
+            (
+                "home-manager.nix",
+                home_manager_module("/usr/local/bin/szmer", 1500, &config),
+            ),
+            (
+                "nixos.nix",
+                nixos_module("/usr/local/bin/szmer", 1500, &config),
+            ),
+        ];
+        assert_golden("nix", &files);
+    }
+
+    #[test]
+    fn test_to_nix_scalars() {
+        assert_eq!(to_nix(&json!(null), 0), "null");
+        assert_eq!(to_nix(&json!(true), 0), "true");
+        assert_eq!(to_nix(&json!(1500), 0), "1500");
+        assert_eq!(to_nix(&json!("Glass"), 0), "\"Glass\"");
+    }
+
+    #[test]
+    fn test_to_nix_escapes_strings() {
+        assert_eq!(
+            to_nix(&json!("say \"hi\" ${HOME}\\n"), 0),
+            r#""say \"hi\" \${HOME}\\n""#
+        );
+    }
+
+    #[test]
+    fn test_to_nix_nested_object() {
+        let value = json!({ "timewarrior": { "enabled": true }, "tips": ["a", "b"] });
+        assert_eq!(
+            to_nix(&value, 0),
+            "{\n  timewarrior = {\n    enabled = true;\n  };\n  tips = [\n    \"a\"\n    \"b\"\n  ];\n}"
+        );
+    }
+
+    #[test]
+    fn test_to_nix_quotes_non_identifier_keys() {
+        let value = json!({ "work hours": 8, "9to5": true });
+        assert_eq!(
+            to_nix(&value, 0),
+            "{\n  \"9to5\" = true;\n  \"work hours\" = 8;\n}"
+        );
+    }
+}
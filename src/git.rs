@@ -0,0 +1,98 @@
+use crate::command::SystemCommandRunner;
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+/// Number of commits made in `repo` since `since`, amends included
+///
+/// Counted from HEAD's reflog rather than history, so amending or
+/// rebasing your own work counts while commits pulled from others don't.
+pub fn commits_since(
+    runner: &dyn SystemCommandRunner,
+    repo: &Path,
+    since: DateTime<Local>,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let repo = repo.to_string_lossy();
+    let output = runner
+        .run(
+            "git",
+            &[
+                "-C",
+                &repo,
+                "reflog",
+                "show",
+                "--date=unix",
+                "--format=%gd %gs",
+                "HEAD",
+            ],
+        )
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.success {
+        return Err(format!("git failed in {repo}: {}", output.stderr.trim()).into());
+    }
+
+    Ok(count_commits(&output.stdout, since.timestamp()))
+}
+
+/// Commits since `since` across all `repos`
+///
+/// Repositories that can't be read are skipped with a warning, so one
+/// moved checkout doesn't hide the rest.
+pub fn total_commits_since(
+    runner: &dyn SystemCommandRunner,
+    repos: &[PathBuf],
+    since: DateTime<Local>,
+) -> usize {
+    repos
+        .iter()
+        .filter_map(|repo| match commits_since(runner, repo, since) {
+            Ok(count) => Some(count),
+            Err(e) => {
+                eprintln!("Warning: {e}");
+                None
+            }
+        })
+        .sum()
+}
+
+/// Count commit entries newer than `since` in `%gd %gs` reflog output
+///
+/// # Examples
+///
+/// ```
+/// let reflog = "HEAD@{200} commit (amend): Fix\nHEAD@{100} commit: Fix\n";
+/// assert_eq!(count_commits(reflog, 150), 1);
+/// ```
+fn count_commits(reflog: &str, since: i64) -> usize {
+    reflog
+        .lines()
+        .filter_map(|line| {
+            let (selector, subject) = line.split_once(' ')?;
+            let timestamp = selector
+                .strip_prefix("HEAD@{")?
+                .strip_suffix('}')?
+                .parse::<i64>()
+                .ok()?;
+            Some((timestamp, subject))
+        })
+        // "commit:", "commit (amend):", "commit (merge):" and so on
+        .filter(|(timestamp, subject)| *timestamp >= since && subject.starts_with("commit"))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_commits_skips_checkouts_and_old_entries() {
+        let reflog = "\
+HEAD@{1700000300} checkout: moving from main to feature
+HEAD@{1700000200} commit (amend): Fix parser
+HEAD@{1700000100} commit: Fix parser
+HEAD@{1600000000} commit (initial): Initial commit
+";
+        assert_eq!(count_commits(reflog, 1700000000), 2);
+        assert_eq!(count_commits(reflog, 0), 3);
+        assert_eq!(count_commits("", 0), 0);
+    }
+}
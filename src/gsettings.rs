@@ -0,0 +1,96 @@
+use crate::command::SystemCommandRunner;
+
+/// Read a string array key, `None` when the schema or key doesn't exist
+pub fn get_string_list(
+    runner: &dyn SystemCommandRunner,
+    schema: &str,
+    key: &str,
+) -> Option<Vec<String>> {
+    let output = runner.run("gsettings", &["get", schema, key]).ok()?;
+    output.success.then(|| parse_string_list(&output.stdout))
+}
+
+/// Read a string key without its quotes, `None` when it doesn't exist
+pub fn get_string(runner: &dyn SystemCommandRunner, schema: &str, key: &str) -> Option<String> {
+    let output = runner.run("gsettings", &["get", schema, key]).ok()?;
+    output
+        .success
+        .then(|| output.stdout.trim().trim_matches('\'').to_string())
+}
+
+/// Write `value`, given in GVariant text form such as `'name'` or `['a', 'b']`
+pub fn set(
+    runner: &dyn SystemCommandRunner,
+    schema: &str,
+    key: &str,
+    value: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = runner
+        .run("gsettings", &["set", schema, key, value])
+        .map_err(|e| format!("Failed to run gsettings: {e}"))?;
+    if !output.success {
+        return Err(format!("gsettings failed to set {key}: {}", output.stderr.trim()).into());
+    }
+    Ok(())
+}
+
+/// Quote a string for GVariant text form
+pub fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "\\'"))
+}
+
+/// Format a string array in GVariant text form
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(string_list(&["/a/".to_string()]), "['/a/']");
+/// assert_eq!(string_list(&[]), "@as []");
+/// ```
+pub fn string_list(items: &[String]) -> String {
+    if items.is_empty() {
+        // A bare [] has no type for gsettings to infer
+        return "@as []".to_string();
+    }
+    let items: Vec<String> = items.iter().map(|item| quote(item)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// Parse a GVariant string array as printed by `gsettings get`
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(parse_string_list("['/a/', '/b/']"), vec!["/a/", "/b/"]);
+/// assert!(parse_string_list("@as []").is_empty());
+/// ```
+fn parse_string_list(value: &str) -> Vec<String> {
+    value
+        .trim()
+        .trim_start_matches("@as")
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|item| item.trim().trim_matches('\'').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_list_round_trips() {
+        assert!(parse_string_list("@as []\n").is_empty());
+        assert_eq!(
+            parse_string_list("['/custom0/', '/custom1/']\n"),
+            vec!["/custom0/", "/custom1/"]
+        );
+
+        let items = vec!["movement".to_string(), "eyesight".to_string()];
+        assert_eq!(parse_string_list(&string_list(&items)), items);
+        assert_eq!(string_list(&[]), "@as []");
+    }
+}
@@ -0,0 +1,488 @@
+use crate::command::SystemCommandRunner;
+use crate::timestamp;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Regions with a built-in calendar, others need `szmer holidays update`
+pub const REGIONS: &[&str] = &["de", "fr", "gb", "pl", "us"];
+
+/// Public holidays by year and country code, e.g. `/2026/PL`
+const API_URL: &str = "https://date.nager.at/api/v3/PublicHolidays";
+
+/// Cache entry holding the days added with `szmer holidays import`
+const IMPORTED: &str = "imported";
+
+/// Environment variables naming the user's locale, most specific first
+const LOCALE_ENV_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// A day without reminders
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// A holiday as date.nager.at returns it
+#[derive(Debug, Deserialize)]
+struct RemoteHoliday {
+    date: NaiveDate,
+    name: String,
+    /// False for holidays kept only in some states or counties
+    global: bool,
+}
+
+/// Whether `value` can be stored in `schedule.holidays`
+pub fn parse_setting(value: &str) -> Result<Option<String>, String> {
+    match value.trim().to_lowercase().as_str() {
+        "" | "off" => Ok(None),
+        "auto" => Ok(Some("auto".to_string())),
+        code if code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()) => {
+            Ok(Some(code.to_string()))
+        }
+        _ => Err("expected a two-letter country code like pl, auto or off".to_string()),
+    }
+}
+
+/// The region `schedule.holidays` stands for, `auto` is the locale's territory
+pub fn region(setting: &str) -> Option<String> {
+    if setting == "auto" {
+        locale_region(|name| std::env::var(name).ok())
+    } else {
+        Some(setting.to_string())
+    }
+}
+
+/// Territory of the first locale variable set, e.g. pl for `pl_PL.UTF-8`
+fn locale_region(lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let locale = LOCALE_ENV_VARS
+        .iter()
+        .filter_map(|name| lookup(name))
+        .find(|value| !value.is_empty())?;
+    let territory = locale.split(['.', '@']).next()?.split_once('_')?.1;
+    (territory.len() == 2 && territory.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| territory.to_lowercase())
+}
+
+/// Whether there is a calendar for `region`, built in or cached
+pub fn is_known(region: &str) -> bool {
+    REGIONS.contains(&region) || !load(region).is_empty()
+}
+
+/// The holiday on `date` in `region`, imported days included
+pub fn on(region: &str, date: NaiveDate) -> Option<Holiday> {
+    calendar(region, date.year())
+        .into_iter()
+        .chain(load(IMPORTED))
+        .find(|holiday| holiday.date == date)
+}
+
+/// Holidays of `region` in `year`, from the cache when it covers the year
+pub fn calendar(region: &str, year: i32) -> Vec<Holiday> {
+    let cached: Vec<Holiday> = load(region)
+        .into_iter()
+        .filter(|holiday| holiday.date.year() == year)
+        .collect();
+    if cached.is_empty() {
+        builtin(region, year).unwrap_or_default()
+    } else {
+        cached
+    }
+}
+
+/// Days added with `szmer holidays import`
+pub fn imported() -> Vec<Holiday> {
+    load(IMPORTED)
+}
+
+/// Fetch this year's and next year's holidays of `region` into the cache
+///
+/// Only nationwide holidays are kept. Returns how many were saved.
+pub fn update(
+    runner: &dyn SystemCommandRunner,
+    region: &str,
+    year: i32,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let years = [year, year + 1];
+    let mut holidays: Vec<Holiday> = load(region)
+        .into_iter()
+        .filter(|holiday| !years.contains(&holiday.date.year()))
+        .collect();
+    let mut fetched = 0;
+    for year in years {
+        let url = format!("{API_URL}/{year}/{}", region.to_uppercase());
+        let output = runner
+            .run(
+                "curl",
+                &[
+                    "--silent",
+                    "--show-error",
+                    "--fail",
+                    "--location",
+                    "--max-time",
+                    "10",
+                    &url,
+                ],
+            )
+            .map_err(|e| format!("Failed to run curl: {e}"))?;
+        if !output.success {
+            return Err(format!("Failed to fetch {url}: {}", output.stderr.trim()).into());
+        }
+        let remote: Vec<RemoteHoliday> = serde_json::from_str(&output.stdout)
+            .map_err(|_| format!("No holidays for {region} at {url}"))?;
+        for holiday in remote.into_iter().filter(|holiday| holiday.global) {
+            fetched += 1;
+            holidays.push(Holiday {
+                date: holiday.date,
+                name: holiday.name,
+            });
+        }
+    }
+    if fetched == 0 {
+        return Err(format!("No holidays for {region} at {API_URL}").into());
+    }
+    save(region, holidays)?;
+    Ok(fetched)
+}
+
+/// Add the all-day events of an iCalendar file, returns how many days were added
+pub fn import(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let days = parse_ics(&content);
+    if days.is_empty() {
+        return Err(format!("{} has no all-day events", path.display()).into());
+    }
+    let count = days.len();
+    let mut holidays = load(IMPORTED);
+    holidays.extend(days);
+    save(IMPORTED, holidays)?;
+    Ok(count)
+}
+
+/// Forget the days added with `szmer holidays import`
+pub fn clear_imported() -> Result<(), Box<dyn std::error::Error>> {
+    match fs::remove_file(cache_path(IMPORTED)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Days covered by the all-day `VEVENT`s of an iCalendar file
+///
+/// An event spans from `DTSTART` up to, but not including, `DTEND`.
+/// Events with a time of day aren't holidays and are left out.
+fn parse_ics(content: &str) -> Vec<Holiday> {
+    // Lines starting with a space or tab continue the previous one
+    let unfolded = content
+        .replace("\r\n", "\n")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut holidays = Vec::new();
+    let (mut start, mut end, mut summary) = (None, None, None);
+    for line in unfolded.lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Parameters like `;VALUE=DATE` follow the name
+        let name = name.split(';').next().unwrap_or(name);
+        match name.to_uppercase().as_str() {
+            "BEGIN" if value == "VEVENT" => (start, end, summary) = (None, None, None),
+            "DTSTART" => start = ics_date(value),
+            "DTEND" => end = ics_date(value),
+            "SUMMARY" => summary = Some(ics_text(value)),
+            "END" if value == "VEVENT" => {
+                let Some(start) = start else { continue };
+                let end = end
+                    .filter(|end| *end > start)
+                    .unwrap_or(start + Duration::days(1));
+                let name = summary.clone().unwrap_or_else(|| "holiday".to_string());
+                holidays.extend(start.iter_days().take_while(|day| *day < end).map(|date| {
+                    Holiday {
+                        date,
+                        name: name.clone(),
+                    }
+                }));
+            }
+            _ => {}
+        }
+    }
+    holidays
+}
+
+/// Date of an all-day `DTSTART` or `DTEND`, `None` for one with a time
+fn ics_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y%m%d").ok()
+}
+
+fn ics_text(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+        .trim()
+        .to_string()
+}
+
+fn cache_path(name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?
+        .join("holidays")
+        .join(format!("{name}.json")))
+}
+
+fn load(name: &str) -> Vec<Holiday> {
+    cache_path(name)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(name: &str, mut holidays: Vec<Holiday>) -> Result<(), Box<dyn std::error::Error>> {
+    holidays.sort();
+    holidays.dedup();
+    let path = cache_path(name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&holidays)?)?;
+    Ok(())
+}
+
+/// Nationwide public holidays of a region with a built-in calendar
+///
+/// Days moved to a weekday when the holiday falls on a weekend aren't included.
+fn builtin(region: &str, year: i32) -> Option<Vec<Holiday>> {
+    let date = |month, day| NaiveDate::from_ymd_opt(year, month, day);
+    let easter = easter(year)?;
+    let after_easter = |days| Some(easter + Duration::days(days));
+    let weekday = |month, weekday, n| nth_weekday(year, month, weekday, n);
+
+    let days = match region {
+        "de" => vec![
+            (date(1, 1), "New Year's Day"),
+            (after_easter(-2), "Good Friday"),
+            (after_easter(1), "Easter Monday"),
+            (date(5, 1), "Labour Day"),
+            (after_easter(39), "Ascension Day"),
+            (after_easter(50), "Whit Monday"),
+            (date(10, 3), "German Unity Day"),
+            (date(12, 25), "Christmas Day"),
+            (date(12, 26), "St. Stephen's Day"),
+        ],
+        "fr" => vec![
+            (date(1, 1), "New Year's Day"),
+            (after_easter(1), "Easter Monday"),
+            (date(5, 1), "Labour Day"),
+            (date(5, 8), "Victory in Europe Day"),
+            (after_easter(39), "Ascension Day"),
+            (after_easter(50), "Whit Monday"),
+            (date(7, 14), "Bastille Day"),
+            (date(8, 15), "Assumption Day"),
+            (date(11, 1), "All Saints' Day"),
+            (date(11, 11), "Armistice Day"),
+            (date(12, 25), "Christmas Day"),
+        ],
+        "gb" => vec![
+            (date(1, 1), "New Year's Day"),
+            (after_easter(-2), "Good Friday"),
+            (weekday(5, Weekday::Mon, 1), "Early May Bank Holiday"),
+            (weekday(5, Weekday::Mon, -1), "Spring Bank Holiday"),
+            (date(12, 25), "Christmas Day"),
+            (date(12, 26), "Boxing Day"),
+        ],
+        "pl" => {
+            let mut days = vec![
+                (date(1, 1), "New Year's Day"),
+                (date(1, 6), "Epiphany"),
+                (Some(easter), "Easter Sunday"),
+                (after_easter(1), "Easter Monday"),
+                (date(5, 1), "May Day"),
+                (date(5, 3), "Constitution Day"),
+                (after_easter(49), "Pentecost Sunday"),
+                (after_easter(60), "Corpus Christi"),
+                (date(8, 15), "Assumption Day"),
+                (date(11, 1), "All Saints' Day"),
+                (date(11, 11), "Independence Day"),
+                (date(12, 25), "Christmas Day"),
+                (date(12, 26), "St. Stephen's Day"),
+            ];
+            if year >= 2025 {
+                days.push((date(12, 24), "Christmas Eve"));
+            }
+            days
+        }
+        "us" => {
+            let mut days = vec![
+                (date(1, 1), "New Year's Day"),
+                (weekday(1, Weekday::Mon, 3), "Martin Luther King, Jr. Day"),
+                (weekday(2, Weekday::Mon, 3), "Presidents Day"),
+                (weekday(5, Weekday::Mon, -1), "Memorial Day"),
+                (date(7, 4), "Independence Day"),
+                (weekday(9, Weekday::Mon, 1), "Labor Day"),
+                (weekday(10, Weekday::Mon, 2), "Columbus Day"),
+                (date(11, 11), "Veterans Day"),
+                (weekday(11, Weekday::Thu, 4), "Thanksgiving Day"),
+                (date(12, 25), "Christmas Day"),
+            ];
+            if year >= 2021 {
+                days.push((date(6, 19), "Juneteenth"));
+            }
+            days
+        }
+        _ => return None,
+    };
+
+    let mut holidays: Vec<Holiday> = days
+        .into_iter()
+        .filter_map(|(date, name)| {
+            Some(Holiday {
+                date: date?,
+                name: name.to_string(),
+            })
+        })
+        .collect();
+    holidays.sort_by_key(|holiday| holiday.date);
+    Some(holidays)
+}
+
+/// Western Easter Sunday, by the anonymous Gregorian algorithm
+fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// The `n`th `weekday` of a month, counting from its end when `n` is -1
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i8) -> Option<NaiveDate> {
+    if n > 0 {
+        return NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8);
+    }
+    let last = NaiveDate::from_ymd_opt(year, month + 1, 1)
+        .or_else(|| NaiveDate::from_ymd_opt(year + 1, 1, 1))?
+        .pred_opt()?;
+    let back = (last.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+    Some(last - Duration::days(back.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_easter() {
+        assert_eq!(easter(2024), Some(day(2024, 3, 31)));
+        assert_eq!(easter(2025), Some(day(2025, 4, 20)));
+        assert_eq!(easter(2026), Some(day(2026, 4, 5)));
+        assert_eq!(easter(2038), Some(day(2038, 4, 25)));
+    }
+
+    #[test]
+    fn test_builtin_calendars() {
+        let names = |region, date| {
+            builtin(region, 2026)
+                .unwrap()
+                .into_iter()
+                .find(|holiday| holiday.date == date)
+                .map(|holiday| holiday.name)
+        };
+
+        assert_eq!(
+            names("pl", day(2026, 6, 4)).as_deref(),
+            Some("Corpus Christi")
+        );
+        assert_eq!(
+            names("de", day(2026, 5, 25)).as_deref(),
+            Some("Whit Monday")
+        );
+        assert_eq!(
+            names("gb", day(2026, 5, 25)).as_deref(),
+            Some("Spring Bank Holiday")
+        );
+        assert_eq!(
+            names("us", day(2026, 11, 26)).as_deref(),
+            Some("Thanksgiving Day")
+        );
+        assert_eq!(
+            names("fr", day(2026, 7, 14)).as_deref(),
+            Some("Bastille Day")
+        );
+        assert_eq!(names("pl", day(2026, 6, 5)), None);
+        assert_eq!(builtin("xx", 2026), None);
+    }
+
+    #[test]
+    fn test_locale_region() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            locale_region(env(&[("LANG", "pl_PL.UTF-8")])),
+            Some("pl".into())
+        );
+        assert_eq!(
+            locale_region(env(&[("LC_ALL", ""), ("LANG", "de_DE@euro")])),
+            Some("de".into())
+        );
+        assert_eq!(locale_region(env(&[("LANG", "C.UTF-8")])), None);
+        assert_eq!(locale_region(env(&[])), None);
+    }
+
+    #[test]
+    fn test_parse_ics() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART;VALUE=DATE:20261224\r\n\
+                   DTEND;VALUE=DATE:20261227\r\n\
+                   SUMMARY:Office\\, closed\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART:20261201T100000Z\r\n\
+                   SUMMARY:Standup\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART;VALUE=DATE:20261231\r\n\
+                   SUMMARY:New Year's\r\n  Eve\r\n\
+                   END:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+
+        let holidays = parse_ics(ics);
+        let dates: Vec<NaiveDate> = holidays.iter().map(|holiday| holiday.date).collect();
+        assert_eq!(
+            dates,
+            [
+                day(2026, 12, 24),
+                day(2026, 12, 25),
+                day(2026, 12, 26),
+                day(2026, 12, 31)
+            ]
+        );
+        assert_eq!(holidays[0].name, "Office, closed");
+        assert_eq!(holidays[3].name, "New Year's Eve");
+    }
+}
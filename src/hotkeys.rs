@@ -0,0 +1,251 @@
+use crate::command::{ProcessRunner, SystemCommandRunner};
+use crate::control::Request;
+use crate::gsettings;
+use crate::session;
+use clap::ValueEnum;
+use std::env;
+
+/// How long the snooze shortcut delays the next reminder
+pub const SNOOZE_SECONDS: u64 = 600;
+
+const GNOME_SCHEMA: &str = "org.gnome.settings-daemon.plugins.media-keys";
+const GNOME_KEYBINDING_SCHEMA: &str =
+    "org.gnome.settings-daemon.plugins.media-keys.custom-keybinding";
+const GNOME_KEYBINDING_PATH: &str =
+    "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings";
+
+/// Something a global shortcut can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Action {
+    /// Pause reminders, or resume them when paused
+    Pause,
+    /// Delay the next reminder by 10 minutes
+    Snooze,
+    /// Send a break reminder right now
+    Break,
+}
+
+const ACTIONS: [Action; 3] = [Action::Pause, Action::Snooze, Action::Break];
+
+impl Action {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Snooze => "snooze",
+            Self::Break => "break",
+        }
+    }
+
+    /// Key pressed together with Super+Alt (Cmd+Alt on macOS)
+    fn key(self) -> char {
+        match self {
+            Self::Pause => 'p',
+            Self::Snooze => 's',
+            Self::Break => 'b',
+        }
+    }
+
+    /// Control request the daemon handles for this action
+    pub fn request(self) -> Request {
+        match self {
+            Self::Pause => Request::TogglePause,
+            Self::Snooze => Request::Snooze {
+                seconds: SNOOZE_SECONDS,
+            },
+            Self::Break => Request::TriggerBreak,
+        }
+    }
+}
+
+/// Where global shortcuts can be registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Desktop {
+    /// GNOME custom keybindings, written with gsettings
+    Gnome,
+    /// sway config snippet
+    Sway,
+    /// i3 config snippet
+    I3,
+    /// skhd config snippet for macOS
+    Skhd,
+}
+
+/// Guess the desktop from the session environment
+fn detect() -> Option<Desktop> {
+    if cfg!(target_os = "macos") {
+        return Some(Desktop::Skhd);
+    }
+    if env::var_os("SWAYSOCK").is_some() {
+        return Some(Desktop::Sway);
+    }
+    if env::var_os("I3SOCK").is_some() {
+        return Some(Desktop::I3);
+    }
+    session::desktop_name()
+        .filter(|name| name.to_uppercase().contains("GNOME"))
+        .map(|_| Desktop::Gnome)
+}
+
+/// Register shortcuts for pause, snooze and break now
+///
+/// GNOME keybindings are written directly; for sway, i3 and skhd a config
+/// snippet is printed to paste into the user's config.
+pub fn install(desktop: Option<Desktop>) -> Result<(), Box<dyn std::error::Error>> {
+    let desktop = desktop.or_else(detect).ok_or(
+        "Could not detect the desktop. Pass --desktop gnome, sway, i3 or skhd to pick one",
+    )?;
+    let binary = binary_path()?;
+
+    match desktop {
+        Desktop::Gnome => {
+            install_gnome(&ProcessRunner, &binary)?;
+            println!("✓ Registered GNOME shortcuts:");
+            for action in ACTIONS {
+                println!(
+                    "  Super+Alt+{}  {}",
+                    action.key().to_ascii_uppercase(),
+                    action.name()
+                );
+            }
+        }
+        Desktop::Sway | Desktop::I3 => {
+            let name = if desktop == Desktop::Sway {
+                "sway"
+            } else {
+                "i3"
+            };
+            println!("# Add to ~/.config/{name}/config, then reload {name}");
+            print!("{}", render_snippet(desktop, &binary));
+        }
+        Desktop::Skhd => {
+            println!("# Add to ~/.skhdrc, then run 'skhd --reload'");
+            print!("{}", render_snippet(desktop, &binary));
+            println!("\n# Without skhd, create a Shortcuts action with \"Run Shell Script\"");
+            println!("# running '{binary} hotkeys run <pause|snooze|break>' and give it a");
+            println!("# keyboard shortcut in its details.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Config lines binding each action, for window managers and skhd
+fn render_snippet(desktop: Desktop, binary: &str) -> String {
+    ACTIONS
+        .iter()
+        .map(|action| {
+            let command = command_line(binary, *action);
+            let key = action.key();
+            match desktop {
+                Desktop::Sway => format!("bindsym Mod4+Mod1+{key} exec {command}\n"),
+                Desktop::I3 => format!("bindsym Mod4+Mod1+{key} exec --no-startup-id {command}\n"),
+                Desktop::Skhd => format!("cmd + alt - {key} : {command}\n"),
+                Desktop::Gnome => format!("<Super><Alt>{key}  {command}\n"),
+            }
+        })
+        .collect()
+}
+
+/// Add szmer's keybindings to GNOME's custom keybinding list
+///
+/// Re-running replaces szmer's entries and keeps everyone else's.
+fn install_gnome(
+    runner: &dyn SystemCommandRunner,
+    binary: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut paths = gsettings::get_string_list(runner, GNOME_SCHEMA, "custom-keybindings")
+        .ok_or("Failed to read GNOME custom keybindings with gsettings")?;
+
+    for action in ACTIONS {
+        let path = format!("{GNOME_KEYBINDING_PATH}/szmer-{}/", action.name());
+        let schema = format!("{GNOME_KEYBINDING_SCHEMA}:{path}");
+        let binding = format!("<Super><Alt>{}", action.key());
+        let name = format!("szmer {}", action.name());
+        let command = command_line(binary, action);
+
+        for (key, value) in [
+            ("name", &name),
+            ("command", &command),
+            ("binding", &binding),
+        ] {
+            gsettings::set(runner, &schema, key, &gsettings::quote(value))?;
+        }
+
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    gsettings::set(
+        runner,
+        GNOME_SCHEMA,
+        "custom-keybindings",
+        &gsettings::string_list(&paths),
+    )
+}
+
+fn command_line(binary: &str, action: Action) -> String {
+    let binary = if binary.contains(' ') {
+        format!("\"{binary}\"")
+    } else {
+        binary.to_string()
+    };
+    format!("{binary} hotkeys run {}", action.name())
+}
+
+fn binary_path() -> Result<String, Box<dyn std::error::Error>> {
+    env::current_exe()?
+        .canonicalize()?
+        .to_str()
+        .ok_or_else(|| "Failed to convert binary path to string".into())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+
+    #[test]
+    fn test_render_sway_snippet() {
+        assert_eq!(
+            render_snippet(Desktop::Sway, "/usr/bin/szmer"),
+            "bindsym Mod4+Mod1+p exec /usr/bin/szmer hotkeys run pause\n\
+             bindsym Mod4+Mod1+s exec /usr/bin/szmer hotkeys run snooze\n\
+             bindsym Mod4+Mod1+b exec /usr/bin/szmer hotkeys run break\n"
+        );
+    }
+
+    #[test]
+    fn test_install_gnome_keeps_other_keybindings() {
+        let custom0 = "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings/custom0/";
+        let get = format!("gsettings get {GNOME_SCHEMA} custom-keybindings");
+        let mut runner = RecordingRunner::default().reply(&get, true, &format!("['{custom0}']\n"));
+
+        let mut expected_list = vec![format!("'{custom0}'")];
+        for action in ACTIONS {
+            let path = format!("{GNOME_KEYBINDING_PATH}/szmer-{}/", action.name());
+            let schema = format!("{GNOME_KEYBINDING_SCHEMA}:{path}");
+            let values = [
+                ("name", format!("'szmer {}'", action.name())),
+                (
+                    "command",
+                    format!("'{}'", command_line("/bin/szmer", action)),
+                ),
+                ("binding", format!("'<Super><Alt>{}'", action.key())),
+            ];
+            for (key, value) in values {
+                runner = runner.reply(&format!("gsettings set {schema} {key} {value}"), true, "");
+            }
+            expected_list.push(format!("'{path}'"));
+        }
+        let set_list = format!(
+            "gsettings set {GNOME_SCHEMA} custom-keybindings [{}]",
+            expected_list.join(", ")
+        );
+        runner = runner.reply(&set_list, true, "");
+
+        install_gnome(&runner, "/bin/szmer").unwrap();
+        assert_eq!(runner.calls().last(), Some(&set_list));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::config::Config;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use include_dir::{include_dir, Dir};
+use std::env;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+/// Translations, one `<locale>/szmer.ftl` per directory
+static LOCALES: Dir = include_dir!("$CARGO_MANIFEST_DIR/locales");
+
+/// Locale every message exists in, always last in the fallback chain
+const DEFAULT_LOCALE: &str = "en";
+
+const MESSAGES_FILE: &str = "szmer.ftl";
+
+/// Picks szmer's language without changing the rest of the environment
+const OVERRIDE_ENV_VAR: &str = "SZMER_LOCALE";
+
+/// Environment variables naming the user's locale, most specific first
+const LOCALE_ENV_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Translate message `id` for the user's locale
+pub fn tr(id: &str) -> String {
+    tr_with(id, &[])
+}
+
+/// Translate message `id`, filling in its `{ $name }` placeables
+///
+/// # Examples
+///
+/// ```
+/// let message = tr_with("stop-stopped", &[]);
+/// let hint = tr_with("error-daemon-already-running-pid", &[("pid", 42.into())]);
+/// ```
+pub fn tr_with(id: &str, args: &[(&str, FluentValue)]) -> String {
+    localizer().format(id, args)
+}
+
+/// Language messages are shown in, e.g. `pl`
+pub fn language() -> String {
+    localizer().language()
+}
+
+/// Languages szmer has translations for, e.g. `en, pl`
+pub fn available() -> String {
+    let mut names: Vec<String> = available_locales()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    names.sort();
+    names.join(", ")
+}
+
+/// Whether there are translations for `locale`, e.g. `pl` or `pl_PL.UTF-8`
+pub fn is_available(locale: &str) -> bool {
+    parse_posix_locale(locale).is_some_and(|locale| {
+        available_locales()
+            .iter()
+            .any(|available| available.language == locale.language)
+    })
+}
+
+fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(|| Localizer::new(&requested_locales()))
+}
+
+/// Messages for a chain of locales, falling back along the chain
+pub struct Localizer {
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    /// Build the fallback chain that best matches `requested`, ending in English
+    pub fn new(requested: &[LanguageIdentifier]) -> Self {
+        let available = available_locales();
+        let default: LanguageIdentifier = DEFAULT_LOCALE.parse().expect("valid default locale");
+        let chain = negotiate_languages(
+            requested,
+            &available,
+            Some(&default),
+            NegotiationStrategy::Filtering,
+        );
+
+        let bundles = chain.into_iter().filter_map(load_bundle).collect();
+
+        Self { bundles }
+    }
+
+    /// Format a message from the first locale that has it
+    ///
+    /// Falls back to the message id itself so a missing translation is
+    /// visible but never fatal.
+    pub fn format(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+
+        for bundle in &self.bundles {
+            let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                .into_owned();
+        }
+
+        id.to_string()
+    }
+
+    /// Language of the first locale in the chain
+    pub fn language(&self) -> String {
+        self.bundles
+            .first()
+            .and_then(|bundle| bundle.locales.first())
+            .map_or(DEFAULT_LOCALE.to_string(), |locale| {
+                locale.language.to_string()
+            })
+    }
+}
+
+fn available_locales() -> Vec<LanguageIdentifier> {
+    LOCALES
+        .dirs()
+        .filter_map(|dir| dir.path().to_str()?.parse().ok())
+        .collect()
+}
+
+fn load_bundle(locale: &LanguageIdentifier) -> Option<FluentBundle<FluentResource>> {
+    let path = format!("{locale}/{MESSAGES_FILE}");
+    let source = LOCALES.get_file(&path)?.contents_utf8()?;
+
+    // A broken translation keeps the messages that did parse
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, _)| resource);
+
+    let mut bundle = FluentBundle::new_concurrent(vec![locale.clone()]);
+    // Unicode isolation marks show up as garbage in most terminals
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).ok()?;
+    Some(bundle)
+}
+
+/// Locales the user asked for: `SZMER_LOCALE`, then `locale` in the
+/// config, then the environment
+fn requested_locales() -> Vec<LanguageIdentifier> {
+    // Tests assert on English output whatever the machine's locale
+    if cfg!(test) {
+        return Vec::new();
+    }
+
+    env::var(OVERRIDE_ENV_VAR)
+        .ok()
+        .into_iter()
+        .chain(Config::configured_locale())
+        .chain(LOCALE_ENV_VARS.iter().filter_map(|key| env::var(key).ok()))
+        .filter_map(|value| parse_posix_locale(&value))
+        .collect()
+}
+
+/// Turn a POSIX locale such as `pl_PL.UTF-8` into a language identifier
+///
+/// `C` and `POSIX` mean "no preference" and yield `None`.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(parse_posix_locale("pl_PL.UTF-8"), "pl-PL".parse().ok());
+/// assert_eq!(parse_posix_locale("C.UTF-8"), None);
+/// ```
+fn parse_posix_locale(value: &str) -> Option<LanguageIdentifier> {
+    let name = value.split(['.', '@']).next()?.trim();
+    if name.is_empty() || name == "C" || name == "POSIX" {
+        return None;
+    }
+    name.replace('_', "-").parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_ids(locale: &str) -> Vec<String> {
+        let source = LOCALES
+            .get_file(format!("{locale}/{MESSAGES_FILE}"))
+            .and_then(|file| file.contents_utf8())
+            .unwrap();
+        let resource = FluentResource::try_new(source.to_string())
+            .unwrap_or_else(|(_, errors)| panic!("{locale} has syntax errors: {errors:?}"));
+
+        resource
+            .entries()
+            .filter_map(|entry| match entry {
+                fluent_syntax::ast::Entry::Message(message) => Some(message.id.name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_translations_only_use_english_ids() {
+        let english = message_ids(DEFAULT_LOCALE);
+        for locale in available_locales() {
+            for id in message_ids(&locale.to_string()) {
+                assert!(english.contains(&id), "{locale} has unknown message {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_posix_locale() {
+        assert_eq!(parse_posix_locale("pl_PL.UTF-8"), "pl-PL".parse().ok());
+        assert_eq!(parse_posix_locale("de_DE@euro"), "de-DE".parse().ok());
+        assert_eq!(parse_posix_locale("C.UTF-8"), None);
+        assert_eq!(parse_posix_locale("POSIX"), None);
+        assert_eq!(parse_posix_locale(""), None);
+    }
+
+    #[test]
+    fn test_is_available_matches_the_language() {
+        assert!(is_available("pl"));
+        assert!(is_available("pl_PL.UTF-8"));
+        assert!(!is_available("de"));
+        assert!(!is_available("C"));
+        assert_eq!(available(), "en, pl");
+    }
+
+    #[test]
+    fn test_falls_back_to_english() {
+        let polish = Localizer::new(&["pl-PL".parse().unwrap()]);
+        assert_eq!(
+            polish.format("notification-summary", &[]),
+            "Czas na przerwę!"
+        );
+        assert_eq!(polish.format("no-such-message", &[]), "no-such-message");
+
+        let german = Localizer::new(&["de-DE".parse().unwrap()]);
+        assert_eq!(
+            german.format("notification-summary", &[]),
+            "Time for a Break!"
+        );
+    }
+
+    #[test]
+    fn test_plural_rules_follow_locale() {
+        let polish = Localizer::new(&["pl".parse().unwrap()]);
+        let minutes = |count: u64| polish.format("duration-minutes", &[("count", count.into())]);
+        assert_eq!(minutes(1), "1 minuta");
+        assert_eq!(minutes(3), "3 minuty");
+        assert_eq!(minutes(5), "5 minut");
+    }
+
+    #[test]
+    fn test_polish_durations_inflect_after_prepositions() {
+        let polish = Localizer::new(&["pl".parse().unwrap()]);
+        let hours = |count: u64, case: &str| {
+            polish.format(
+                "duration-hours",
+                &[("count", count.into()), ("case", case.into())],
+            )
+        };
+        assert_eq!(hours(1, "nominative"), "1 godzina");
+        assert_eq!(hours(1, "accusative"), "1 godzinę");
+        assert_eq!(hours(2, "accusative"), "2 godziny");
+        // Messages without a case pick the nominative
+        assert_eq!(
+            polish.format("duration-hours", &[("count", 1.into())]),
+            "1 godzina"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::command::SystemCommandRunner;
+use crate::gsettings;
+use crate::timestamp;
+use std::fs;
+use std::path::PathBuf;
+
+const ICON_ON_LIGHT: &str = include_str!("../assets/icons/szmer-on-light.svg");
+const ICON_ON_DARK: &str = include_str!("../assets/icons/szmer-on-dark.svg");
+
+/// Whether the desktop uses a light or a dark theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    /// Read the desktop appearance, assuming light when it can't be told
+    ///
+    /// GNOME 42+ has `color-scheme`, which KDE and others mirror through
+    /// the settings portal. Older desktops only have a GTK theme, which is
+    /// dark when its name says so.
+    pub fn detect(runner: &dyn SystemCommandRunner) -> Self {
+        const SCHEMA: &str = "org.gnome.desktop.interface";
+        let color_scheme = gsettings::get_string(runner, SCHEMA, "color-scheme");
+        let gtk_theme = gsettings::get_string(runner, SCHEMA, "gtk-theme");
+        Self::from_gnome(color_scheme.as_deref(), gtk_theme.as_deref())
+    }
+
+    fn from_gnome(color_scheme: Option<&str>, gtk_theme: Option<&str>) -> Self {
+        match color_scheme {
+            Some("prefer-dark") => Self::Dark,
+            Some("prefer-light") => Self::Light,
+            // "default" leaves it to the theme
+            _ if gtk_theme.is_some_and(|theme| theme.to_lowercase().ends_with("-dark")) => {
+                Self::Dark
+            }
+            _ => Self::Light,
+        }
+    }
+}
+
+/// Path of the notification icon that stands out against `appearance`
+///
+/// The icons are built into szmer and written to `~/.cache/szmer/icons`
+/// on first use, since notification servers load icons from disk.
+pub fn notification_icon(appearance: Appearance) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let (name, svg) = match appearance {
+        Appearance::Light => ("szmer-on-light.svg", ICON_ON_LIGHT),
+        Appearance::Dark => ("szmer-on-dark.svg", ICON_ON_DARK),
+    };
+
+    let path = timestamp::get_cache_dir()?.join("icons").join(name);
+    if fs::read_to_string(&path).ok().as_deref() != Some(svg) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, svg)?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+
+    #[test]
+    fn test_appearance_from_gnome_settings() {
+        assert_eq!(
+            Appearance::from_gnome(Some("prefer-dark"), Some("Adwaita")),
+            Appearance::Dark
+        );
+        assert_eq!(
+            Appearance::from_gnome(Some("default"), Some("Yaru-dark")),
+            Appearance::Dark
+        );
+        assert_eq!(
+            Appearance::from_gnome(Some("prefer-light"), Some("Yaru-dark")),
+            Appearance::Light
+        );
+        assert_eq!(Appearance::from_gnome(None, None), Appearance::Light);
+    }
+
+    #[test]
+    fn test_detect_reads_gsettings() {
+        let runner = RecordingRunner::default().reply(
+            "gsettings get org.gnome.desktop.interface color-scheme",
+            true,
+            "'prefer-dark'\n",
+        );
+        assert_eq!(Appearance::detect(&runner), Appearance::Dark);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::command::SystemCommandRunner;
+#[cfg(all(feature = "wayland", not(target_os = "macos")))]
+use crate::wayland::{self, IdleMonitor};
+#[cfg(all(feature = "wayland", not(target_os = "macos")))]
+use std::sync::OnceLock;
+
+/// Idle time from the compositor, for processes that called `watch`
+#[cfg(all(feature = "wayland", not(target_os = "macos")))]
+static WAYLAND: OnceLock<Option<IdleMonitor>> = OnceLock::new();
+
+/// Follow idle time on Wayland from now on, for long-running processes
+///
+/// ext-idle-notify-v1 only tells a connected client when input stops and
+/// starts again, so `idle_seconds` can use it once the daemon is watching.
+pub fn watch() {
+    #[cfg(all(feature = "wayland", not(target_os = "macos")))]
+    WAYLAND.get_or_init(IdleMonitor::start);
+}
+
+/// Where idle time comes from right now, `None` when it can't be told
+pub fn backend(runner: &dyn SystemCommandRunner) -> Option<&'static str> {
+    #[cfg(target_os = "macos")]
+    {
+        idle_seconds(runner).map(|_| "IOKit")
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        if mutter_idle_seconds_from(runner).is_some() {
+            return Some("GNOME idle monitor");
+        }
+        #[cfg(feature = "wayland")]
+        if wayland::globals()
+            .is_some_and(|globals| globals.iter().any(|g| g == wayland::IDLE_NOTIFY))
+        {
+            return Some("ext-idle-notify-v1, followed by the daemon");
+        }
+        runner
+            .run("xprintidle", &[])
+            .is_ok_and(|output| output.success)
+            .then_some("xprintidle")
+    }
+}
+
+/// Seconds since the last keyboard or mouse input, `None` when unknown
+///
+/// On Linux this asks GNOME's idle monitor first, then the compositor when
+/// `watch` follows it on other Wayland desktops, and falls back to
+/// `xprintidle` on other X11 desktops.
+pub fn idle_seconds(runner: &dyn SystemCommandRunner) -> Option<u64> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = runner
+            .run("ioreg", &["-c", "IOHIDSystem", "-d", "4"])
+            .ok()
+            .filter(|output| output.success)?;
+        hid_idle_seconds(&output.stdout)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        #[cfg(feature = "wayland")]
+        let compositor = || WAYLAND.get()?.as_ref()?.idle_seconds();
+        #[cfg(not(feature = "wayland"))]
+        let compositor = || None;
+
+        mutter_idle_seconds_from(runner)
+            .or_else(compositor)
+            .or_else(|| {
+                let output = runner
+                    .run("xprintidle", &[])
+                    .ok()
+                    .filter(|output| output.success)?;
+                output.stdout.trim().parse::<u64>().ok().map(|ms| ms / 1000)
+            })
+    }
+}
+
+/// Idle time from GNOME's idle monitor, over D-Bus
+#[cfg(not(target_os = "macos"))]
+fn mutter_idle_seconds_from(runner: &dyn SystemCommandRunner) -> Option<u64> {
+    runner
+        .run(
+            "gdbus",
+            &[
+                "call",
+                "--session",
+                "--dest",
+                "org.gnome.Mutter.IdleMonitor",
+                "--object-path",
+                "/org/gnome/Mutter/IdleMonitor/Core",
+                "--method",
+                "org.gnome.Mutter.IdleMonitor.GetIdletime",
+            ],
+        )
+        .ok()
+        .filter(|output| output.success)
+        .and_then(|output| mutter_idle_seconds(&output.stdout))
+}
+
+/// Idle time from the `HIDIdleTime` line of `ioreg`, given in nanoseconds
+#[cfg(target_os = "macos")]
+fn hid_idle_seconds(output: &str) -> Option<u64> {
+    output.lines().find_map(|line| {
+        let (_, value) = line.split_once("\"HIDIdleTime\" = ")?;
+        value
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|ns| ns / 1_000_000_000)
+    })
+}
+
+/// Idle time from a `GetIdletime` reply like `(uint64 61234,)`, in milliseconds
+#[cfg(not(target_os = "macos"))]
+fn mutter_idle_seconds(output: &str) -> Option<u64> {
+    let ms = output
+        .trim()
+        .strip_prefix("(uint64 ")?
+        .strip_suffix(",)")?
+        .parse::<u64>()
+        .ok()?;
+    Some(ms / 1000)
+}
+
+#[cfg(all(test, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+
+    #[test]
+    fn test_idle_seconds_falls_back_to_xprintidle() {
+        assert_eq!(mutter_idle_seconds("(uint64 61234,)\n"), Some(61));
+        assert_eq!(mutter_idle_seconds("Error: no such name\n"), None);
+
+        let runner = RecordingRunner::default().reply("xprintidle", true, "4500\n");
+        assert_eq!(idle_seconds(&runner), Some(4));
+    }
+}
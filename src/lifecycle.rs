@@ -0,0 +1,275 @@
+use crate::timestamp;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+const STATE_FILENAME: &str = "lifecycle.json";
+
+/// Where the user is in the break cycle
+///
+/// The cycle is Working → Reminded → OnBreak → Working. When a gate holds
+/// a due reminder back the break is Overdue until a reminder gets through.
+/// Paused sits outside the cycle and is entered from any state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum BreakState {
+    /// Working towards the next reminder
+    Working { since: DateTime<Local> },
+    /// A reminder was delivered and the break hasn't started yet
+    Reminded { at: DateTime<Local> },
+    /// Taking a break until `ends_at`
+    OnBreak {
+        started_at: DateTime<Local>,
+        ends_at: DateTime<Local>,
+    },
+    /// A reminder was due `since` but gates held it back
+    Overdue { since: DateTime<Local> },
+    /// Reminders are paused
+    Paused { since: DateTime<Local> },
+}
+
+/// Something that moves the break cycle along
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// A reminder was delivered
+    Remind,
+    /// A break lasting `duration` started
+    StartBreak { duration: chrono::Duration },
+    /// A due reminder was held back by a gate
+    Defer,
+    /// The break ran its course
+    EndBreak,
+    /// The cycle was cut short, e.g. by shutdown or waking from sleep
+    Interrupt,
+    /// Reminders were paused
+    Pause,
+    /// Reminders were resumed
+    Resume,
+}
+
+/// A transition that makes no sense in the current state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub from: BreakState,
+    pub transition: Transition,
+}
+
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot apply {:?} while {}",
+            self.transition,
+            self.from.name()
+        )
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+impl BreakState {
+    /// The state a fresh start begins in
+    pub fn initial(paused: bool, now: DateTime<Local>) -> Self {
+        if paused {
+            Self::Paused { since: now }
+        } else {
+            Self::Working { since: now }
+        }
+    }
+
+    /// Move to the next state, or explain why `transition` doesn't apply
+    pub fn apply(
+        self,
+        transition: Transition,
+        now: DateTime<Local>,
+    ) -> Result<Self, InvalidTransition> {
+        use BreakState::*;
+
+        let next = match (self, transition) {
+            (Paused { .. }, Transition::Resume) => Some(Working { since: now }),
+            (Paused { .. }, _) | (_, Transition::Resume) => None,
+            (_, Transition::Pause) => Some(Paused { since: now }),
+
+            (Working { .. } | Reminded { .. } | Overdue { .. }, Transition::Remind) => {
+                Some(Reminded { at: now })
+            }
+            (Reminded { .. }, Transition::StartBreak { duration }) => Some(OnBreak {
+                started_at: now,
+                ends_at: now + duration,
+            }),
+            (Working { .. }, Transition::Defer) => Some(Overdue { since: now }),
+            (Overdue { since }, Transition::Defer) => Some(Overdue { since }),
+            (OnBreak { .. }, Transition::EndBreak) => Some(Working { since: now }),
+            (Reminded { .. } | OnBreak { .. } | Overdue { .. }, Transition::Interrupt) => {
+                Some(Working { since: now })
+            }
+            _ => None,
+        };
+
+        next.ok_or(InvalidTransition {
+            from: self,
+            transition,
+        })
+    }
+
+    /// When the current break ends, if on one
+    pub fn break_ends_at(&self) -> Option<DateTime<Local>> {
+        match self {
+            Self::OnBreak { ends_at, .. } => Some(*ends_at),
+            _ => None,
+        }
+    }
+
+    /// Short name of the state, as used in JSON
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Working { .. } => "working",
+            Self::Reminded { .. } => "reminded",
+            Self::OnBreak { .. } => "on_break",
+            Self::Overdue { .. } => "overdue",
+            Self::Paused { .. } => "paused",
+        }
+    }
+
+    /// Save the state for status bars and other tools to read
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = state_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for BreakState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Working { .. } => write!(f, "Working"),
+            Self::Reminded { .. } => write!(f, "Reminded, break not started"),
+            Self::OnBreak { ends_at, .. } => {
+                write!(f, "On a break until {}", ends_at.format("%H:%M"))
+            }
+            Self::Overdue { since } => {
+                write!(f, "Break overdue since {}", since.format("%H:%M"))
+            }
+            Self::Paused { .. } => write!(f, "Paused"),
+        }
+    }
+}
+
+/// Path of the persisted state, `~/.cache/szmer/lifecycle.json`
+pub fn state_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(STATE_FILENAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+
+    fn all_states(now: DateTime<Local>) -> Vec<BreakState> {
+        vec![
+            BreakState::Working { since: now },
+            BreakState::Reminded { at: now },
+            BreakState::OnBreak {
+                started_at: now,
+                ends_at: now,
+            },
+            BreakState::Overdue { since: now },
+            BreakState::Paused { since: now },
+        ]
+    }
+
+    fn all_transitions() -> Vec<Transition> {
+        vec![
+            Transition::Remind,
+            Transition::StartBreak {
+                duration: chrono::Duration::minutes(5),
+            },
+            Transition::Defer,
+            Transition::EndBreak,
+            Transition::Interrupt,
+            Transition::Pause,
+            Transition::Resume,
+        ]
+    }
+
+    #[test]
+    fn test_full_cycle() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let state = BreakState::initial(false, clock.now());
+
+        clock.advance(chrono::Duration::minutes(60));
+        let due = clock.now();
+        let state = state.apply(Transition::Defer, clock.now()).unwrap();
+        assert_eq!(state, BreakState::Overdue { since: due });
+
+        // Held back again, still overdue since the first due reminder
+        clock.advance(chrono::Duration::minutes(60));
+        let state = state.apply(Transition::Defer, clock.now()).unwrap();
+        assert_eq!(state, BreakState::Overdue { since: due });
+
+        let state = state.apply(Transition::Remind, clock.now()).unwrap();
+        assert_eq!(state, BreakState::Reminded { at: clock.now() });
+
+        let duration = chrono::Duration::minutes(5);
+        let state = state
+            .apply(Transition::StartBreak { duration }, clock.now())
+            .unwrap();
+        assert_eq!(state.break_ends_at(), Some(clock.now() + duration));
+
+        clock.advance(duration);
+        let state = state.apply(Transition::EndBreak, clock.now()).unwrap();
+        assert_eq!(state, BreakState::Working { since: clock.now() });
+    }
+
+    #[test]
+    fn test_transition_table_is_exhaustive() {
+        let now = MockClock::at("2025-01-06 09:00:00").now();
+
+        // Which (state, transition) pairs are allowed, rows in `all_states` order
+        // and columns in `all_transitions` order
+        let allowed = [
+            // Remind StartBreak Defer EndBreak Interrupt Pause Resume
+            [true, false, true, false, false, true, false], // Working
+            [true, true, false, false, true, true, false],  // Reminded
+            [false, false, false, true, true, true, false], // OnBreak
+            [true, false, true, false, true, true, false],  // Overdue
+            [false, false, false, false, false, false, true], // Paused
+        ];
+
+        for (state, row) in all_states(now).into_iter().zip(allowed) {
+            for (transition, expected) in all_transitions().into_iter().zip(row) {
+                assert_eq!(
+                    state.apply(transition, now).is_ok(),
+                    expected,
+                    "{transition:?} from {}",
+                    state.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let now = MockClock::at("2025-01-06 09:00:00").now();
+        let state = BreakState::initial(true, now);
+        assert_eq!(state.name(), "paused");
+
+        let error = state.apply(Transition::Remind, now).unwrap_err();
+        assert_eq!(error.to_string(), "Cannot apply Remind while paused");
+
+        let state = state.apply(Transition::Resume, now).unwrap();
+        assert_eq!(state, BreakState::Working { since: now });
+    }
+
+    #[test]
+    fn test_state_serializes_with_tag() {
+        let now = MockClock::at("2025-01-06 09:00:00").now();
+        let json = serde_json::to_string(&BreakState::Overdue { since: now }).unwrap();
+        assert!(json.starts_with(r#"{"state":"overdue","since":"2025-01-06T09:00:00"#));
+    }
+}
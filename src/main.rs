@@ -1,43 +1,450 @@
+mod after;
+mod attention;
+mod away;
+mod backup;
+mod break_apps;
+mod breathing;
+mod bundle;
+mod cleanup;
+mod clock;
+mod command;
+mod compliance;
 mod config;
+mod control;
+mod crash;
+mod daemon;
+mod dbus;
+mod debug;
+mod doctor;
+mod drift;
+mod ducking;
+mod error;
+mod events;
+mod export;
+mod focus;
+mod focus_mode;
+mod generate;
+mod git;
+mod gsettings;
+mod holidays;
+mod hotkeys;
+mod i18n;
+#[cfg(not(target_os = "macos"))]
+mod icons;
+mod idle;
+mod lifecycle;
 mod notification;
+mod ntfy;
+mod overlay;
+mod pidfile;
+mod plugin;
+mod pomodoro;
+mod power;
+mod presenting;
+mod random;
+mod reminder;
 mod schedule;
+mod session;
+mod signals;
 mod sound;
+mod stats;
+mod stats_view;
 mod time;
 mod timestamp;
 mod timewarrior;
-
+mod tips;
+mod toml_file;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod watcher;
+#[cfg(all(feature = "wayland", not(target_os = "macos")))]
+mod wayland;
+#[cfg(feature = "web")]
+mod web;
+
+use chrono::{DateTime, Datelike, Local, NaiveTime};
 use clap::{Parser, Subcommand};
-use config::Config;
-use dialoguer::{Input, Select};
-use time::{format_interval, format_time_until};
+use clock::SystemClock;
+use command::ProcessRunner;
+use config::{Config, HeadlessMode, NotifierKind};
+use control::{DaemonStatus, Request, Response};
+use dialoguer::{Confirm, Input, Select};
+use error::SzmerError;
+use i18n::{tr, tr_with};
+use reminder::Outcome;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use time::{
+    format_elapsed_since, format_interval, format_seconds, format_time_until, start_of_today,
+};
 
 #[derive(Parser)]
 #[command(name = "szmer")]
-#[command(about = "A simple break reminder for macOS and Linux", long_about = None)]
+#[command(about = "A simple break reminder for macOS, Linux and BSD", long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Print JSON instead of text, for status, config show and stats
+    #[arg(long, global = true)]
+    json: bool,
+    /// Pick tips and titles from this seed, for repeatable demos
+    /// (defaults to $SZMER_SEED)
+    #[arg(long, global = true, value_name = "NUMBER")]
+    seed: Option<u64>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Install the break reminder as a launchd agent
-    Install,
+    Install {
+        /// Break interval in minutes or a named preset like preset:deep-work,
+        /// skips the interactive prompts
+        #[arg(long, value_name = "MINUTES|preset:NAME", value_parser = parse_install_interval)]
+        interval: Option<IntervalChoice>,
+        /// Install even when a container or CI environment is detected
+        #[arg(long)]
+        force: bool,
+    },
     /// Uninstall the break reminder
     Uninstall,
     /// Send a break notification (used internally by launchd)
-    Notify,
+    Notify {
+        /// Send the reminder of this break profile
+        #[arg(long)]
+        profile: Option<String>,
+    },
     /// Stop break reminders temporarily
-    Stop,
+    Stop {
+        /// Resume on their own after this long, e.g. 45m or 2h
+        #[arg(long = "for", value_name = "DURATION", value_parser = time::parse_duration, conflicts_with = "until")]
+        duration: Option<u64>,
+        /// Resume on their own at this time, e.g. 15:00
+        #[arg(long, value_name = "HH:MM", value_parser = export::parse_time)]
+        until: Option<NaiveTime>,
+    },
     /// Resume break reminders
     Resume,
     /// Show current status and next notification time
-    Status,
+    Status {
+        /// One line for shell prompts, tmux and scripts
+        #[arg(long)]
+        short: bool,
+    },
+    /// Run break reminders in a long-lived process instead of the system scheduler
+    Daemon,
+    /// Print daemon events as line-delimited JSON
+    Events {
+        /// Keep streaming new events as they happen
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Serve a local web page and REST API for controlling the daemon
+    #[cfg(feature = "web")]
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+        /// Listen on all interfaces so the share page can be opened remotely
+        #[arg(long)]
+        public: bool,
+    },
+    /// Share a read-only summary of today's breaks through 'szmer serve --public'
+    #[cfg(feature = "web")]
+    Share {
+        /// Turn sharing off and invalidate the link
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Create the token other tools send to 'szmer serve' to trigger and pause reminders
+    #[cfg(feature = "web")]
+    ApiToken {
+        /// Turn the trigger endpoints off and invalidate the token
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Count a break down in the break overlay, started by reminders
+    #[command(hide = true)]
+    Overlay {
+        /// Length of the break in seconds
+        #[arg(long)]
+        seconds: u64,
+        /// Window title
+        title: String,
+        /// Tip shown in the window
+        tip: String,
+    },
+    /// Answer the last break reminder, as its notification buttons do
+    Respond {
+        /// snooze delays the next reminder by 5 minutes
+        answer: timestamp::Answer,
+    },
+    /// Record a break you took, the same as the reminder's Break taken button
+    TookBreak,
+    /// Take a break now in the terminal, doing what break.activity is set to
+    Break {
+        /// How long the break lasts, e.g. 2m (default: the configured break duration)
+        #[arg(long = "for", value_name = "DURATION", value_parser = time::parse_duration)]
+        duration: Option<u64>,
+    },
+    /// Show the break history: reminders per day, gaps and compliance
+    Stats {
+        /// Cover the week since Monday instead of today
+        #[arg(long, conflicts_with = "month")]
+        week: bool,
+        /// Cover the month so far instead of today
+        #[arg(long)]
+        month: bool,
+        /// Browse weekly charts, breaks by hour and the streak calendar
+        #[arg(long, short, conflicts_with_all = ["week", "month"])]
+        interactive: bool,
+    },
+    /// Delay the next break reminder without changing the interval
+    Snooze {
+        /// How long to wait, e.g. 90s, 10m or 1h (a bare number is minutes)
+        #[arg(long = "for", value_name = "DURATION", default_value = "10m", value_parser = time::parse_duration)]
+        duration: u64,
+    },
+    /// Alternate 25-minute work sessions with 5-minute breaks, and a 15-minute break every 4th time
+    Pomodoro {
+        /// Go back to the regular break interval
+        #[arg(long)]
+        stop: bool,
+    },
+    /// Print a wellness tip, e.g. for a shell greeting or MOTD
+    Tip {
+        /// Only pick tips of this category
+        #[arg(long)]
+        category: Option<tips::TipCategory>,
+    },
+    /// Run a command, then suggest a break if you've worked long enough
+    After {
+        /// Minutes since the last reminder that call for a break (default: half the interval)
+        #[arg(long)]
+        min_work: Option<u64>,
+        /// Command to run, after --
+        #[arg(required = true, last = true)]
+        command: Vec<String>,
+    },
+    /// Diagnose the environment szmer is running in
+    Doctor,
+    /// Print declarative Nix snippets instead of installing the scheduler
+    Generate {
+        /// Output format
+        #[arg(long, value_enum)]
+        format: generate::Format,
+        /// Binary path the timer should run (defaults to this executable)
+        #[arg(long)]
+        binary: Option<String>,
+    },
+    /// Render the scheduler files for any OS without installing them
+    GenerateUnit {
+        /// Target operating system
+        #[arg(long, value_enum)]
+        os: generate::TargetOs,
+        /// Binary path the scheduler should run (defaults to this executable)
+        #[arg(long)]
+        binary: Option<String>,
+        /// Write the files to this directory instead of printing them
+        #[arg(long)]
+        output_dir: Option<PathBuf>,
+        /// Render a crash-resilient service for 'szmer daemon' instead of the timer
+        #[arg(long)]
+        daemon: bool,
+    },
+    /// Export planned breaks for other tools
+    Export {
+        #[command(subcommand)]
+        format: ExportFormat,
+    },
+    /// Save or restore config and reminder history, e.g. to move machines
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// Collect what a bug report needs
+    Debug {
+        #[command(subcommand)]
+        action: DebugAction,
+    },
+    /// Manage the macOS app bundle used for notifications
+    Bundle {
+        #[command(subcommand)]
+        action: BundleAction,
+    },
     /// Manage configuration settings
     Config {
         #[command(subcommand)]
         action: ConfigAction,
     },
+    /// Manage break profiles, extra reminders on their own interval
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+    /// Manage the public holidays reminders skip, see schedule.holidays
+    Holidays {
+        #[command(subcommand)]
+        action: HolidaysAction,
+    },
+    /// Manage plugins (szmer-<name> executables)
+    Plugin {
+        #[command(subcommand)]
+        action: PluginAction,
+    },
+    /// Manage global keyboard shortcuts for pause, snooze and break now
+    Hotkeys {
+        #[command(subcommand)]
+        action: HotkeysAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum HotkeysAction {
+    /// Register the shortcuts with the desktop, or print a config snippet
+    Install {
+        /// Desktop to register with (detected when omitted)
+        #[arg(long, value_enum)]
+        desktop: Option<hotkeys::Desktop>,
+    },
+    /// Run the action bound to a shortcut
+    Run {
+        #[arg(value_enum)]
+        action: hotkeys::Action,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExportFormat {
+    /// iCalendar file with a recurring event for each break of a working day
+    Ics {
+        /// Start of the working day
+        #[arg(long, default_value = "09:00", value_parser = export::parse_time)]
+        from: chrono::NaiveTime,
+        /// End of the working day
+        #[arg(long, default_value = "17:00", value_parser = export::parse_time)]
+        to: chrono::NaiveTime,
+        /// Write the calendar to this file instead of printing it
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileAction {
+    /// List break profiles
+    List,
+    /// Add a break profile, or replace the one with the same name
+    Add {
+        /// Name of the profile, e.g. eyes
+        name: String,
+        /// How often it reminds you, e.g. 20m or 1h
+        #[arg(long, value_name = "DURATION", value_parser = time::parse_duration)]
+        every: u64,
+        /// Only show tips of this category
+        #[arg(long)]
+        category: Option<tips::TipCategory>,
+        /// Notification sound, the main sound when not given
+        #[arg(long)]
+        sound: Option<String>,
+    },
+    /// Remove a break profile
+    Remove {
+        /// Name of the profile
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum HolidaysAction {
+    /// List the holidays of a year
+    List {
+        /// Year to list, this year when omitted
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    /// Refresh the region's calendar from date.nager.at into the cache
+    Update,
+    /// Skip the all-day events of an iCalendar (.ics) file too
+    Import {
+        /// Calendar file, e.g. exported from your company calendar
+        path: PathBuf,
+    },
+    /// Forget the imported days
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum PluginAction {
+    /// List installed plugins and what they do
+    List,
+    /// Copy a plugin executable into ~/.config/szmer/plugins
+    Install {
+        /// Plugin executable to install
+        path: PathBuf,
+    },
+}
+
+/// Interval given to `szmer install --interval`
+#[derive(Debug, Clone)]
+enum IntervalChoice {
+    Minutes(u64),
+    /// Resolved against the config's presets, which clap can't see
+    Preset(String),
+}
+
+fn parse_install_interval(value: &str) -> Result<IntervalChoice, String> {
+    if let Some(name) = value.strip_prefix("preset:") {
+        return Ok(IntervalChoice::Preset(name.to_string()));
+    }
+    match value.parse::<u64>() {
+        Ok(minutes) if (1..=1440).contains(&minutes) => Ok(IntervalChoice::Minutes(minutes)),
+        _ => Err(format!(
+            "'{value}' is not a number of minutes from 1 to 1440 or preset:<name>"
+        )),
+    }
+}
+
+#[derive(Subcommand)]
+enum BackupAction {
+    /// Write config, plugins, history and cached tips to a .tar.gz file
+    Create {
+        /// Archive to write
+        file: PathBuf,
+    },
+    /// Restore a backup made with 'szmer backup create'
+    Restore {
+        /// Archive to read
+        file: PathBuf,
+        /// Replace the existing config and history
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DebugAction {
+    /// Write config, doctor output, logs and scheduler files to a .tar.gz, secrets removed
+    Bundle {
+        /// Archive to write, szmer-debug-<time>.tar.gz by default
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BundleAction {
+    /// Wrap szmer in a signed app bundle with its own notification identity
+    Install {
+        /// Application icon (.icns) to include in the bundle
+        #[arg(long)]
+        icon: Option<PathBuf>,
+        /// Code signing identity (defaults to ad-hoc signing)
+        #[arg(long)]
+        identity: Option<String>,
+    },
+    /// Remove the app bundle
+    Uninstall,
 }
 
 #[derive(Subcommand)]
@@ -51,294 +458,1785 @@ enum ConfigAction {
         /// Configuration value (e.g., "true", "false")
         value: String,
     },
+    /// Check the config file for values szmer can't use
+    Validate,
+    /// List the keys `config set` takes
+    Keys,
+    /// Put a single key back to its default value
+    Unset {
+        /// Configuration key (e.g., "schedule.days")
+        key: String,
+    },
+    /// Put every setting back to its default, keeping a backup of the file
+    Reset {
+        /// Don't ask for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() -> ExitCode {
+    crash::install_hook();
     let cli = Cli::parse();
+    random::init(cli.seed);
+    crash::announce_new_reports();
+    attention::announce(&SystemClock);
+
+    match run(cli.command, cli.json) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => error::report(e.as_ref()),
+    }
+}
 
-    match cli.command {
-        Commands::Install => install(),
+fn run(command: Commands, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Commands::Install { interval, force } => install(interval, force),
         Commands::Uninstall => uninstall(),
-        Commands::Notify => notify(),
-        Commands::Stop => stop(),
+        Commands::Notify { profile } => {
+            let result = notify(profile.as_deref());
+            // Nobody sees scheduled runs fail
+            if !std::io::stdin().is_terminal() {
+                attention::record(&SystemClock, &result);
+            }
+            result
+        }
+        Commands::Stop { duration, until } => stop(duration, until),
         Commands::Resume => resume(),
-        Commands::Status => status(),
-        Commands::Config { action } => config(action),
+        Commands::Status { short } => status(short, json),
+        Commands::Daemon => daemon::run(),
+        Commands::Events { follow } => events(follow),
+        #[cfg(feature = "web")]
+        Commands::Serve { port, public } => web::serve(port, public),
+        #[cfg(feature = "web")]
+        Commands::Share { revoke } => share(revoke),
+        #[cfg(feature = "web")]
+        Commands::ApiToken { revoke } => api_token(revoke),
+        Commands::Overlay {
+            seconds,
+            title,
+            tip,
+        } => overlay::run(
+            &ProcessRunner,
+            &Config::load()?.overlay,
+            &title,
+            &tip,
+            seconds,
+        ),
+        Commands::Respond { answer } => respond(answer),
+        Commands::TookBreak => respond(timestamp::Answer::Taken),
+        Commands::Break { duration } => take_break(duration),
+        Commands::Stats {
+            interactive: true, ..
+        } => stats_interactive(json),
+        Commands::Stats { week, month, .. } => {
+            let range = if month {
+                stats::Range::Month
+            } else if week {
+                stats::Range::Week
+            } else {
+                stats::Range::Today
+            };
+            stats(range, json)
+        }
+        Commands::Snooze { duration } => snooze(duration),
+        Commands::Pomodoro { stop } => pomodoro(stop),
+        Commands::Tip { category } => {
+            println!(
+                "{}",
+                tips::tip(&Config::load()?, &mut random::rng(), category).text
+            );
+            Ok(())
+        }
+        Commands::After { min_work, command } => {
+            let code = after::run(&SystemClock, &command, min_work)?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+            Ok(())
+        }
+        Commands::Doctor => doctor::run(),
+        Commands::Generate { format, binary } => generate::run(format, binary),
+        Commands::GenerateUnit {
+            os,
+            binary,
+            output_dir,
+            daemon,
+        } => generate::run_unit(os, binary, output_dir.as_deref(), daemon),
+        Commands::Export {
+            format: ExportFormat::Ics { from, to, output },
+        } => export::run_ics(&SystemClock, from, to, output.as_deref()),
+        Commands::Backup { action } => match action {
+            BackupAction::Create { file } => backup::create(&file),
+            BackupAction::Restore { file, force } => backup::restore(&file, force),
+        },
+        Commands::Debug {
+            action: DebugAction::Bundle { output },
+        } => debug::bundle(output.as_deref()),
+        Commands::Bundle { action } => bundle(action),
+        Commands::Config { action } => config(action, json),
+        Commands::Profile { action } => profile(action),
+        Commands::Holidays { action } => holidays(action),
+        Commands::Plugin { action } => plugin(action),
+        Commands::Hotkeys { action } => match action {
+            HotkeysAction::Install { desktop } => hotkeys::install(desktop),
+            HotkeysAction::Run { action } => hotkey(action),
+        },
     }
 }
 
-fn notify() -> Result<(), Box<dyn std::error::Error>> {
+fn notify(profile: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::load()?;
+    let profile = profile.map(|name| config.profile(name)).transpose()?;
 
-    if config.paused {
-        return Ok(());
+    // Without a terminal it's the scheduler running us, profiles aside
+    if profile.is_none() && !std::io::stdin().is_terminal() {
+        log_scheduler_drift(&config);
     }
 
-    // Check timewarrior integration - skip notification if not tracking
-    if !timewarrior::should_send_notification(&config.timewarrior) {
-        return Err("Skipping notification: no active timewarrior session".into());
+    match reminder::remind(&config, profile)? {
+        Outcome::Sent => {
+            notification::wait_for_answers();
+            Ok(())
+        }
+        Outcome::BackToWork
+        | Outcome::Paused
+        | Outcome::Snoozed(_)
+        | Outcome::Waiting(_)
+        | Outcome::Away(_)
+        | Outcome::Back(_) => Ok(()),
+        Outcome::Skipped(reason) => Err(SzmerError::NotificationSkipped { reason }.into()),
     }
-
-    notification::send_break_reminder(config.notification_sound, None)
 }
 
-fn install() -> Result<(), Box<dyn std::error::Error>> {
-    if schedule::is_installed() {
-        return Err(
-            "Break reminder is already installed. Run 'uninstall' first if you want to reinstall."
-                .into(),
-        );
+/// Note how late the scheduler ran, for `szmer doctor`
+fn log_scheduler_drift(config: &Config) {
+    match drift::record_run(&SystemClock) {
+        Ok(Some(late)) if late > drift::ON_TIME_SECONDS && late < config.timer_seconds() as i64 => {
+            eprintln!(
+                "{} ran {} late",
+                schedule::NAME,
+                format_interval(late as u64)
+            );
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: Failed to log the scheduler run: {e}"),
     }
+}
 
-    let interval_seconds = select_interval()?;
-    println!(
-        "\n✓ Break interval set to {} minutes",
-        interval_seconds / 60
-    );
-
-    let selected_sound = select_notification_sound()?;
-
-    let timewarrior_config = configure_timewarrior()?;
-
-    let config = Config {
-        notification_sound: selected_sound.clone(),
-        paused: false,
-        interval_seconds,
-        timewarrior: timewarrior_config,
-    };
-    config.save()?;
+/// Record how the last reminder was answered, snoozing when asked to
+fn respond(answer: timestamp::Answer) -> Result<(), Box<dyn std::error::Error>> {
+    match answer {
+        timestamp::Answer::Snooze => snooze(notification::SNOOZE_SECONDS)?,
+        timestamp::Answer::Skip => println!("✓ Break skipped"),
+        timestamp::Answer::Taken => println!("✓ Break taken, well done"),
+    }
 
-    print_sound_confirmation(&selected_sound);
+    timestamp::record_answer(&SystemClock, answer)
+}
 
-    schedule::install(interval_seconds)?;
+/// Walk through a break in the terminal, then record it as taken
+///
+/// Stopping early with Ctrl-C leaves the break unrecorded.
+fn take_break(duration: Option<u64>) -> Result<(), Box<dyn std::error::Error>> {
+    if !std::io::stdout().is_terminal() {
+        return Err("szmer break draws in the terminal, run it in one".into());
+    }
+    let config = Config::load()?;
+    let seconds = duration.unwrap_or(config.break_duration_seconds);
+
+    match config.breaks.activity {
+        config::BreakActivity::None => count_down(seconds)?,
+        config::BreakActivity::Breathing => breathing::run(
+            config.breaks.breathing,
+            seconds,
+            overlay::reduces_motion(&ProcessRunner, config.overlay.motion),
+        )?,
+    }
+    respond(timestamp::Answer::Taken)
+}
 
-    println!("\nTip: You can test the notification by running: szmer notify");
+fn count_down(seconds: u64) -> std::io::Result<()> {
+    use std::io::Write;
 
+    for left in (1..=seconds).rev() {
+        print!(
+            "\r\x1b[2K{}",
+            tr_with(
+                "break-left",
+                &[("time", format!("{}:{:02}", left / 60, left % 60).into())],
+            )
+        );
+        std::io::stdout().flush()?;
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+    println!();
     Ok(())
 }
 
-fn select_interval() -> Result<u64, Box<dyn std::error::Error>> {
-    println!("\nSelect a break interval:");
-
-    let interval_presets = [
-        ("Eye Saver (20 minutes) - 20-20-20 rule", 20),
-        ("Pomodoro Focus (25 minutes)", 25),
-        ("Answer to Everything (42 minutes)", 42),
-        ("Standard Hour (60 minutes)", 60),
-        ("Deep Work (90 minutes)", 90),
-        ("The Numbers (108 minutes)", 108),
-        ("Custom interval", 0),
-    ];
-
-    let interval_options: Vec<_> = interval_presets.iter().map(|(desc, _)| *desc).collect();
-
-    let interval_selection = Select::new()
-        .items(&interval_options)
-        .default(3)
-        .interact()?;
-
-    let minutes = if interval_presets[interval_selection].1 == 0 {
-        get_custom_interval()?
-    } else {
-        interval_presets[interval_selection].1
+fn stats_interactive(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if json || !std::io::stdout().is_terminal() {
+        return Err(
+            "szmer stats --interactive draws in the terminal, run it in one without --json".into(),
+        );
+    }
+    let reminders = timestamp::notification_history()?;
+    let answers = timestamp::answer_history()?;
+    let history = stats_view::History {
+        reminders: &reminders,
+        answers: &answers,
     };
-
-    Ok(minutes * 60)
-}
-
-fn get_custom_interval() -> Result<u64, Box<dyn std::error::Error>> {
-    let input: String = Input::new()
-        .with_prompt("Enter custom interval in minutes")
-        .validate_with(|input: &String| -> Result<(), &str> {
-            match input.parse::<u64>() {
-                Ok(n) if n > 0 && n <= 1440 => Ok(()),
-                Ok(_) => Err("Please enter a value between 1 and 1440 minutes (24 hours)"),
-                Err(_) => Err("Please enter a valid number"),
-            }
-        })
-        .interact()?;
-
-    Ok(input.parse().expect("validated input"))
+    stats_view::run(&history, Local::now().date_naive())?;
+    Ok(())
 }
 
-#[cfg(any(target_os = "macos", target_os = "linux"))]
-fn select_notification_sound() -> Result<Option<String>, Box<dyn std::error::Error>> {
-    println!("\nSelect a notification sound:");
-    let sounds = sound::get_available_sounds()?;
+fn stats(range: stats::Range, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let since = range.start(&SystemClock);
+    let mut stats = stats::Stats::compute(
+        &timestamp::notification_history()?,
+        &timestamp::answer_history()?,
+        since,
+        Local::now().date_naive(),
+    );
+    let repos = Config::load()?.integrations.git.repos;
+    stats.commits =
+        (!repos.is_empty()).then(|| git::total_commits_since(&ProcessRunner, &repos, since));
+
+    if json {
+        let per_day: Vec<serde_json::Value> = stats
+            .per_day
+            .iter()
+            .map(|(date, count)| serde_json::json!({ "date": date, "reminders": count }))
+            .collect();
+        let value = serde_json::json!({
+            "range": range.name(),
+            "since": since,
+            "reminders": stats.total(),
+            "per_day": per_day,
+            "average_gap_seconds": stats.average_gap_seconds,
+            "longest_gap": stats.longest_gap.map(|(date, seconds)| {
+                serde_json::json!({ "date": date, "seconds": seconds })
+            }),
+            "compliance": stats.compliance.to_json(),
+            "commits": stats.commits,
+        });
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
 
-    if sounds.is_empty() {
-        return Err("No sounds found in system sounds directory".into());
+    let title = format!("Break Stats ({})", range.label());
+    println!("\n{title}");
+    println!("{}", "━".repeat(title.chars().count()));
+
+    if range != stats::Range::Today {
+        println!();
+        for (day, count) in &stats.per_day {
+            let line = format!(
+                "{:<12}{count:>3}  {}",
+                day.format("%a %d %b").to_string(),
+                "▇".repeat(*count)
+            );
+            println!("{}", line.trim_end());
+        }
     }
 
-    let selection = Select::new().items(&sounds).default(0).interact()?;
-    Ok(Some(sounds[selection].clone()))
-}
+    println!("\nReminders:       {}", stats.total());
+    if let Some(commits) = stats.commits {
+        println!("Commits:         {commits}");
+    }
+    if let Some(seconds) = stats.average_gap_seconds {
+        println!("Average gap:     {}", format_interval(seconds));
+    }
+    if let Some((day, seconds)) = stats.longest_gap {
+        println!(
+            "Longest stretch: {} ({})",
+            format_interval(seconds),
+            day.format("%a %d %b")
+        );
+    }
 
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
-fn select_notification_sound() -> Result<Option<String>, Box<dyn std::error::Error>> {
-    Ok(None)
+    let compliance = stats.compliance;
+    if compliance.has_answers() {
+        let rate = compliance
+            .rate()
+            .map(|rate| format!(" ({rate}%)"))
+            .unwrap_or_default();
+        println!(
+            "Compliance:      {} of {} taken{rate}, {} skipped, {} snoozed",
+            compliance.taken, compliance.reminders, compliance.skipped, compliance.snoozed
+        );
+    } else {
+        println!(
+            "\nNo answers yet. Use the reminder's buttons or 'szmer took-break' after a break."
+        );
+    }
+    println!();
+    Ok(())
 }
 
-fn configure_timewarrior() -> Result<config::TimewarriorConfig, Box<dyn std::error::Error>> {
-    timewarrior::prompt_for_configuration()
-}
+/// Delay the next reminder by `seconds`
+///
+/// The daemon just moves its timer. With the system scheduler the
+/// reminders due before then are skipped and a one-off reminder is
+/// scheduled for the end of the snooze.
+fn snooze(seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(response) = control::send(&Request::Snooze { seconds })? {
+        return print_control_response(response);
+    }
 
-fn print_sound_confirmation(sound: &Option<String>) {
-    match sound {
-        Some(s) => println!("\n✓ Configuration saved with sound: {s}"),
-        None => println!("\n✓ Configuration saved"),
+    if !schedule::is_installed() {
+        return Err(SzmerError::NotInstalled.into());
+    }
+    if Config::load()?.paused {
+        println!("Break reminders are stopped, there's nothing to snooze.");
+        println!("{}", tr("resume-hint"));
+        return Ok(());
     }
-}
 
-fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
-    schedule::uninstall()
+    let until = Local::now() + chrono::Duration::seconds(seconds as i64);
+    timestamp::record_snooze(until)?;
+    schedule::notify_after(seconds)?;
+
+    let delay = if seconds < 60 {
+        format_seconds(seconds)
+    } else {
+        format_interval(seconds)
+    };
+    println!(
+        "✓ Next break reminder in {delay} ({})",
+        until.format("%H:%M")
+    );
+    Ok(())
 }
 
-fn stop() -> Result<(), Box<dyn std::error::Error>> {
-    if !schedule::is_installed() {
-        return Err("Break reminder is not installed. Run 'install' first.".into());
+/// Start pomodoro cycles from a fresh work session, or go back to the interval
+fn pomodoro(stop: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !schedule::is_installed() && query_daemon_status()?.is_none() {
+        return Err(SzmerError::NotInstalled.into());
     }
 
     let mut config = Config::load()?;
-
-    if config.paused {
-        println!("Break reminders are already stopped.");
+    if stop && !config.pomodoro.enabled {
+        println!("{}", tr("pomodoro-not-running"));
         return Ok(());
     }
 
-    config.paused = true;
+    config.pomodoro.enabled = !stop;
+    if stop {
+        pomodoro::reset()?;
+    } else {
+        pomodoro::save(&pomodoro::Cycle::start(Local::now()))?;
+    }
     config.save()?;
 
-    println!("✓ Break reminders stopped.");
-    println!("Run 'szmer resume' to start receiving reminders again.");
-
+    if schedule::is_installed() {
+        schedule::reschedule(config.timer_seconds())?;
+    }
+    // A running daemon picks the new timer up from the config file
+    control::send(&Request::Reload)?;
+
+    if stop {
+        println!(
+            "{}",
+            tr_with(
+                "pomodoro-stopped",
+                &[("every", time::format_every(config.interval_seconds).into())],
+            )
+        );
+    } else {
+        println!(
+            "{}",
+            tr_with(
+                "pomodoro-started",
+                &[
+                    ("work", format_interval(pomodoro::WORK_SECONDS).into()),
+                    (
+                        "short",
+                        format_interval(pomodoro::SHORT_BREAK_SECONDS).into()
+                    ),
+                    ("long", format_interval(pomodoro::LONG_BREAK_SECONDS).into()),
+                    ("every", pomodoro::SESSIONS_PER_LONG_BREAK.into()),
+                ],
+            )
+        );
+    }
     Ok(())
 }
 
-fn resume() -> Result<(), Box<dyn std::error::Error>> {
-    if !schedule::is_installed() {
-        return Err("Break reminder is not installed. Run 'install' first.".into());
-    }
-
+/// Create (or show) the share link, or revoke it
+#[cfg(feature = "web")]
+fn share(revoke: bool) -> Result<(), Box<dyn std::error::Error>> {
     let mut config = Config::load()?;
 
-    if !config.paused {
-        println!("Break reminders are already running.");
+    if revoke {
+        if config.share.token.take().is_none() {
+            println!("Sharing is already off.");
+            return Ok(());
+        }
+        config.save()?;
+        println!("✓ Share link revoked");
         return Ok(());
     }
 
-    config.paused = false;
-    config.save()?;
+    let token = match &config.share.token {
+        Some(token) => token.clone(),
+        None => {
+            let token = web::new_token();
+            config.share.token = Some(token.clone());
+            config.save()?;
+            println!("✓ Share link created");
+            token
+        }
+    };
 
-    println!("✓ Break reminders resumed.");
+    let host = command::run_checked(&ProcessRunner, "hostname", &[], "Failed to get hostname")
+        .map(|output| output.stdout.trim().to_string())
+        .unwrap_or_else(|_| "localhost".to_string());
+    println!("  http://{host}:7878/share/{token}");
+    println!("\nRun 'szmer serve --public' so others can open it.");
+    println!("Anyone with the link sees how many reminders you got today; 'szmer share --revoke' turns it off.");
 
     Ok(())
 }
 
-fn status() -> Result<(), Box<dyn std::error::Error>> {
-    println!("\nSzmer Status");
-    println!("━━━━━━━━━━━━");
+/// Create (or show) the token for the trigger endpoints, or revoke it
+#[cfg(feature = "web")]
+fn api_token(revoke: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
 
-    if !schedule::is_installed() {
-        println!("\nScheduler:    ✗ Not installed");
-        println!("\nRun 'szmer install' to set up break reminders.");
+    if revoke {
+        if config.api.token.take().is_none() {
+            println!("The trigger endpoints are already off.");
+            return Ok(());
+        }
+        config.save()?;
+        println!("✓ API token revoked");
         return Ok(());
     }
 
-    let config = Config::load()?;
-    let scheduler_status = schedule::get_scheduler_status();
+    let token = match &config.api.token {
+        Some(token) => token.clone(),
+        None => {
+            let token = web::new_token();
+            config.api.token = Some(token.clone());
+            config.save()?;
+            println!("✓ API token created");
+            token
+        }
+    };
 
-    print_scheduler_status(&scheduler_status);
-    print_interval(&config);
-    print_sound_setting(&config);
-    print_pause_status(&config);
-    print_next_break(&scheduler_status, &config);
+    println!("  {token}");
+    println!("\nSend it as 'Authorization: Bearer <token>' to 'szmer serve', e.g.");
+    println!("  curl -X POST -H 'Authorization: Bearer {token}' http://localhost:7878/notify");
+    println!("\nPOST /pause takes an optional {{\"seconds\": 1800}}, POST /resume undoes it.");
+    println!("Add --public to 'szmer serve' for tools on other machines.");
 
-    println!();
     Ok(())
 }
 
-fn print_scheduler_status(status: &Result<schedule::SchedulerStatus, Box<dyn std::error::Error>>) {
-    let message = match status {
-        Ok(s) if s.is_running => "✓ Running",
-        Ok(_) => "⚠ Installed but not running",
-        Err(_) => "✗ Error checking status",
-    };
-    println!("\nScheduler:    {message}");
-}
+/// Handle a global shortcut, through the daemon when one is running
+///
+/// Without a daemon pause toggles the config, break sends a reminder right
+/// away and snooze works as `szmer snooze`.
+fn hotkey(action: hotkeys::Action) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(response) = control::send(&action.request())? {
+        return print_control_response(response);
+    }
 
-fn print_interval(config: &Config) {
-    println!("Interval:     {}", format_interval(config.interval_seconds));
+    match action {
+        hotkeys::Action::Pause if Config::load()?.paused => resume(),
+        hotkeys::Action::Pause => stop(None, None),
+        hotkeys::Action::Break => notify(None),
+        hotkeys::Action::Snooze => snooze(hotkeys::SNOOZE_SECONDS),
+    }
 }
 
-fn print_sound_setting(config: &Config) {
-    let sound = config
-        .notification_sound
-        .as_deref()
-        .unwrap_or("(system default)");
-    println!("Sound:        {sound}");
-}
+fn events(follow: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let connected = control::stream_events(follow, |line| println!("{line}"))?;
 
-fn print_pause_status(config: &Config) {
-    if config.paused {
-        println!("Status:       ⏸ Paused");
-        println!("\nRun 'szmer resume' to start receiving reminders again.");
-    } else {
-        println!("Status:       ▶ Active");
+    if !connected {
+        return Err(SzmerError::DaemonNotRunning.into());
     }
+
+    Ok(())
 }
 
-fn print_next_break(
-    scheduler_status: &Result<schedule::SchedulerStatus, Box<dyn std::error::Error>>,
-    config: &Config,
-) {
-    if config.paused {
-        return;
+/// Set up the config and scheduler
+///
+/// With `interval` no prompts are shown: the default sound is used and
+/// Timewarrior integration stays off, so installs can be scripted.
+fn install(
+    interval: Option<IntervalChoice>,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if schedule::is_installed() {
+        return Err(SzmerError::AlreadyInstalled { path: None }.into());
     }
 
-    let Ok(status) = scheduler_status else {
-        return;
-    };
+    if let Some(pid) = pidfile::running_pid() {
+        return Err(SzmerError::SchedulerConflictsWithDaemon { pid: Some(pid) }.into());
+    }
 
-    match status.next_run {
-        Some(next_run) => {
-            let time_until = format_time_until(next_run);
-            println!(
-                "Next break:   {time_until} ({})",
-                next_run.format("%I:%M %p")
-            );
-        }
-        None => {
-            println!(
-                "Next break:   Every {} (no notification sent yet)",
-                format_interval(config.interval_seconds)
-            );
-        }
+    if let Some(reason) = session::detect_container().filter(|_| !force) {
+        return offer_terminal_delivery(&reason);
     }
-}
 
-fn config(action: ConfigAction) -> Result<(), Box<dyn std::error::Error>> {
+    let interactive = interval.is_none();
+    let other_apps = if force {
+        Vec::new()
+    } else {
+        match resolve_break_apps(interactive)? {
+            Some(found) => found,
+            None => return Ok(()),
+        }
+    };
+
+    // Presets and break profiles are kept from an earlier install
+    let previous = Config::load().unwrap_or_default();
+    let fresh = Config {
+        interval_presets: previous.interval_presets,
+        profiles: previous.profiles,
+        ..Config::default()
+    };
+    let interval_seconds = match interval {
+        Some(IntervalChoice::Minutes(minutes)) => minutes * 60,
+        Some(IntervalChoice::Preset(name)) => fresh.interval_preset(&name)?,
+        None => match import_interval(&other_apps)? {
+            Some(seconds) => seconds,
+            None => select_interval(&fresh)?,
+        },
+    };
+    println!(
+        "\n{}",
+        tr_with(
+            "install-interval-set",
+            &[("interval", format_interval(interval_seconds).into())]
+        )
+    );
+
+    let headless = session::is_headless(HeadlessMode::Auto);
+    let selected_sound = if !interactive {
+        None
+    } else if headless {
+        println!("\nNo graphical session detected, reminders will be delivered to the terminal.");
+        None
+    } else if session::is_crostini() {
+        println!("\nChromeOS detected, notifications will use the system chime.");
+        None
+    } else {
+        select_notification_sound()?
+    };
+
+    let timewarrior_config = if interactive {
+        configure_timewarrior()?
+    } else {
+        config::TimewarriorConfig::default()
+    };
+
+    let config = Config {
+        notification_sound: selected_sound.clone(),
+        paused: false,
+        interval_seconds,
+        timewarrior: timewarrior_config,
+        ..fresh
+    };
+    config.save()?;
+
+    print_sound_confirmation(&selected_sound);
+
+    schedule::install(interval_seconds, config.schedule.clock, &config.profiles)?;
+
+    println!("\nTip: You can test the notification by running: szmer notify");
+
+    Ok(())
+}
+
+/// Skip scheduler installation where launchd/systemd can't work and offer terminal delivery
+fn offer_terminal_delivery(reason: &str) -> Result<(), Box<dyn std::error::Error>> {
+    println!("⚠ {reason} detected, skipping scheduler installation.");
+    println!("The system scheduler and desktop notifications are not available here.");
+    println!("Run 'szmer install --force' to install the scheduler anyway, or 'szmer daemon' to");
+    println!("keep reminders going with szmer's own timer.");
+
+    if !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    let use_terminal = Confirm::new()
+        .with_prompt("\nDeliver reminders to the terminal instead?")
+        .default(true)
+        .interact()?;
+
+    if use_terminal {
+        let mut config = Config::load()?;
+        config.headless.mode = HeadlessMode::Always;
+        config.save()?;
+
+        println!("✓ Headless mode enabled, 'szmer notify' will print reminders to the terminal");
+        println!(
+            "Run it periodically, e.g.: while sleep {}; do szmer notify; done",
+            config.interval_seconds
+        );
+    }
+
+    Ok(())
+}
+
+/// Avoid doubled reminders when another break app already reminds the user
+///
+/// Returns the apps found, or `None` when the user would rather keep theirs
+/// and not install szmer's reminders.
+fn resolve_break_apps(
+    interactive: bool,
+) -> Result<Option<Vec<break_apps::Detected>>, Box<dyn std::error::Error>> {
+    let found = break_apps::detect(&ProcessRunner);
+    if found.is_empty() {
+        return Ok(Some(found));
+    }
+
+    for detected in &found {
+        println!("⚠ {detected} already reminds you to take breaks.");
+    }
+
+    if !interactive || !std::io::stdin().is_terminal() {
+        println!("You'll get reminders from both. Turn one off to avoid doubled reminders.");
+        return Ok(Some(found));
+    }
+
+    let names: Vec<String> = found
+        .iter()
+        .map(|detected| detected.app.to_string())
+        .collect();
+    let choice = Select::new()
+        .with_prompt("\nTwo break reminders would notify you twice")
+        .items(&[
+            format!("Turn off {} and use szmer", names.join(", ")),
+            "Keep both".to_string(),
+            format!("Cancel, keep using {}", names.join(", ")),
+        ])
+        .default(0)
+        .interact()?;
+
+    match choice {
+        0 => {
+            break_apps::turn_off(&ProcessRunner, &found)?;
+            Ok(Some(found))
+        }
+        1 => Ok(Some(found)),
+        _ => {
+            println!("Installation cancelled.");
+            Ok(None)
+        }
+    }
+}
+
+/// Offer the interval another break app was set to, so it carries over
+fn import_interval(
+    found: &[break_apps::Detected],
+) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    let Some((app, seconds)) = found
+        .iter()
+        .find_map(|detected| Some((detected.app, detected.interval_seconds?)))
+    else {
+        return Ok(None);
+    };
+
+    let import = Confirm::new()
+        .with_prompt(format!(
+            "\nUse {app}'s interval of {}?",
+            time::format_interval(seconds)
+        ))
+        .default(true)
+        .interact()?;
+
+    Ok(import.then_some(seconds.max(60)))
+}
+
+/// Ask for the interval in seconds, offering the built-in and the user's presets
+fn select_interval(config: &Config) -> Result<u64, Box<dyn std::error::Error>> {
+    println!("\nSelect a break interval:");
+
+    let interval_presets = config.interval_presets();
+
+    let mut interval_options: Vec<String> = interval_presets
+        .iter()
+        .map(|(name, seconds)| format!("{name} ({})", format_interval(*seconds)))
+        .collect();
+    interval_options.push("Custom interval".to_string());
+
+    let default = interval_presets
+        .iter()
+        .position(|(_, seconds)| *seconds == 3600)
+        .unwrap_or(0);
+    let interval_selection = Select::new()
+        .items(&interval_options)
+        .default(default)
+        .interact()?;
+
+    match interval_presets.get(interval_selection) {
+        Some((_, seconds)) => Ok(*seconds),
+        None => Ok(get_custom_interval()? * 60),
+    }
+}
+
+fn get_custom_interval() -> Result<u64, Box<dyn std::error::Error>> {
+    let input: String = Input::new()
+        .with_prompt("Enter custom interval in minutes")
+        .validate_with(|input: &String| -> Result<(), &str> {
+            match input.parse::<u64>() {
+                Ok(n) if n > 0 && n <= 1440 => Ok(()),
+                Ok(_) => Err("Please enter a value between 1 and 1440 minutes (24 hours)"),
+                Err(_) => Err("Please enter a valid number"),
+            }
+        })
+        .interact()?;
+
+    Ok(input.parse().expect("validated input"))
+}
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+))]
+fn select_notification_sound() -> Result<Option<String>, Box<dyn std::error::Error>> {
+    println!("\nSelect a notification sound:");
+    let sounds = sound::get_available_sounds()?;
+
+    if sounds.is_empty() {
+        return Err("No sounds found in system sounds directory".into());
+    }
+
+    let selection = Select::new().items(&sounds).default(0).interact()?;
+    Ok(Some(sounds[selection].clone()))
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+fn select_notification_sound() -> Result<Option<String>, Box<dyn std::error::Error>> {
+    Ok(None)
+}
+
+fn configure_timewarrior() -> Result<config::TimewarriorConfig, Box<dyn std::error::Error>> {
+    timewarrior::prompt_for_configuration(&ProcessRunner)
+}
+
+fn print_sound_confirmation(sound: &Option<String>) {
+    match sound {
+        Some(s) => println!("\n✓ Configuration saved with sound: {s}"),
+        None => println!("\n✓ Configuration saved"),
+    }
+}
+
+/// Uninstall the scheduler, then report what's left and offer to remove it
+fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
+    let installed = schedule::installed_files();
+    schedule::uninstall()?;
+
+    let removed: Vec<&PathBuf> = installed.iter().filter(|path| !path.exists()).collect();
+    if !removed.is_empty() {
+        println!("\nRemoved:");
+        for path in removed {
+            println!("  ✓ {}", path.display());
+        }
+    }
+
+    let leftovers = cleanup::leftovers();
+    if leftovers.is_empty() {
+        println!("\nNothing else of szmer's is left on disk.");
+        return Ok(());
+    }
+
+    println!("\nStill on disk:");
+    for leftover in &leftovers {
+        println!("  {:<10} {}", leftover.kind, leftover.path.display());
+    }
+
+    if !std::io::stdin().is_terminal() {
+        println!("\nDelete them by hand to remove szmer completely.");
+        return Ok(());
+    }
+
+    let clean_up = Confirm::new()
+        .with_prompt("\nRemove these too?")
+        .default(false)
+        .interact()?;
+    if !clean_up {
+        return Ok(());
+    }
+
+    let failed = cleanup::remove(&leftovers);
+    for (leftover, e) in &failed {
+        println!("  ✗ {}: {e}", leftover.path.display());
+    }
+    if failed.is_empty() {
+        println!("✓ Removed everything szmer left behind");
+    }
+    Ok(())
+}
+
+fn stop(duration: Option<u64>, until: Option<NaiveTime>) -> Result<(), Box<dyn std::error::Error>> {
+    let until = match (duration, until) {
+        (Some(seconds), _) => Some(
+            time::checked_add_seconds(Local::now(), seconds)
+                .ok_or_else(|| format!("Can't stop reminders for {seconds} seconds"))?,
+        ),
+        (None, Some(time)) => Some(time::next_time_of_day(&SystemClock, time)),
+        (None, None) => None,
+    };
+
+    if let Some(response) = control::send(&Request::Stop { until })? {
+        return print_control_response(response);
+    }
+
+    if !schedule::is_installed() {
+        return Err(SzmerError::NotInstalled.into());
+    }
+
+    let mut config = Config::load()?;
+
+    if config.paused && config.paused_until == until {
+        println!("{}", tr("stop-already"));
+        return Ok(());
+    }
+
+    config.pause(until, Local::now());
+    config.save()?;
+
+    match until {
+        Some(until) => println!(
+            "{}",
+            tr_with(
+                "stop-stopped-until",
+                &[("time", time::format_time_of_day(&SystemClock, until).into())],
+            )
+        ),
+        None => {
+            println!("{}", tr("stop-stopped"));
+            println!("{}", tr("resume-hint"));
+        }
+    }
+
+    Ok(())
+}
+
+fn resume() -> Result<(), Box<dyn std::error::Error>> {
+    // Read before resuming clears it
+    let paused_since = Config::load()?
+        .paused_since
+        .filter(|since| Local::now() - *since >= chrono::Duration::seconds(LONG_PAUSE_SECONDS));
+
+    if let Some(response) = control::send(&Request::Resume)? {
+        print_control_response(response)?;
+    } else {
+        if !schedule::is_installed() {
+            return Err(SzmerError::NotInstalled.into());
+        }
+
+        let mut config = Config::load()?;
+
+        if !config.paused {
+            println!("{}", tr("resume-already"));
+            return Ok(());
+        }
+
+        config.resume();
+        config.save()?;
+
+        println!("{}", tr("resume-resumed"));
+    }
+
+    match paused_since {
+        Some(since) => welcome_back(since),
+        None => Ok(()),
+    }
+}
+
+/// Pauses at least this long get a summary on `szmer resume`
+const LONG_PAUSE_SECONDS: i64 = 24 * 3600;
+
+/// Sum up a long pause and offer to pick the interval again, as on install
+///
+/// Without a terminal, e.g. resumed from a keyboard shortcut, the summary
+/// comes as a notification instead.
+fn welcome_back(since: DateTime<Local>) -> Result<(), Box<dyn std::error::Error>> {
+    let seconds = (Local::now() - since).num_seconds().max(0) as u64;
+    let mut lines = vec![tr_with(
+        "resume-time-off",
+        &[
+            ("duration", time::format_time_off(seconds).into()),
+            ("since", since.format("%a %d %b").to_string().into()),
+        ],
+    )];
+    let last_break = timestamp::answer_history()?
+        .into_iter()
+        .filter(|(_, answer)| *answer == timestamp::Answer::Taken)
+        .map(|(at, _)| at)
+        .max();
+    if let Some(at) = last_break {
+        lines.push(tr_with(
+            "resume-last-break",
+            &[("time", at.format("%a %d %b %H:%M").to_string().into())],
+        ));
+    }
+
+    if !std::io::stdout().is_terminal() {
+        let reminder = notification::Reminder {
+            summary: tr("resume-resumed"),
+            body: lines.join("\n"),
+            sound: None,
+            link: None,
+            kind: notification::Kind::Summary,
+        };
+        let config = Config::load()?;
+        notification::NotifierChain::from_config(&config).send(&reminder)?;
+        return Ok(());
+    }
+
+    println!();
+    for line in &lines {
+        println!("{line}");
+    }
+    if !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    let keep = Confirm::new()
+        .with_prompt(tr_with(
+            "resume-keep-interval",
+            &[("every", time::format_every(config.interval_seconds).into())],
+        ))
+        .default(true)
+        .interact()?;
+    if keep {
+        return Ok(());
+    }
+    let seconds = select_interval(&config)?;
+    set_config("interval", &format!("{seconds}s"))
+}
+
+/// Print the reply of a daemon that handled a control request
+fn print_control_response(response: Response) -> Result<(), Box<dyn std::error::Error>> {
+    match response {
+        Response::Ok { message } => {
+            println!("{message}");
+            Ok(())
+        }
+        Response::Error { message } => Err(message.into()),
+        Response::Status(_) => Err("Unexpected response from daemon".into()),
+    }
+}
+
+/// What `szmer status` reports, gathered once for the full and short views
+struct StatusReport {
+    /// Config with the daemon's pause state and interval, when it runs
+    config: Config,
+    daemon: Option<DaemonStatus>,
+    /// Scheduler check, made when the daemon isn't running
+    scheduler: Option<Result<schedule::SchedulerStatus, Box<dyn std::error::Error>>>,
+    next_run: Option<DateTime<Local>>,
+    snoozed_until: Option<DateTime<Local>>,
+    breaks_today: usize,
+}
+
+/// Gather the status, `None` when neither the scheduler nor the daemon is set up
+fn gather_status() -> Result<Option<StatusReport>, Box<dyn std::error::Error>> {
+    let daemon = query_daemon_status()?;
+    if !schedule::is_installed() && daemon.is_none() {
+        return Ok(None);
+    }
+
+    let mut config = Config::load()?;
+    if let Some(daemon) = &daemon {
+        // The daemon may be on another machine when $SZMER_SOCKET is set
+        config.paused = daemon.paused;
+        config.paused_until = daemon.paused_until;
+        config.interval_seconds = daemon.interval_seconds;
+    }
+
+    let snoozed_until = timestamp::snoozed_until(&SystemClock);
+    let (scheduler, next_run) = match &daemon {
+        Some(daemon) => (None, daemon.next_fire),
+        None => {
+            let scheduler = schedule::get_scheduler_status();
+            // A snooze skips the scheduled runs until its one-off reminder
+            let next_run = snoozed_until.or_else(|| {
+                scheduler
+                    .as_ref()
+                    .ok()
+                    .and_then(|scheduler| scheduler.next_run)
+            });
+            (Some(scheduler), next_run)
+        }
+    };
+
+    let since = start_of_today(&SystemClock);
+    let breaks_today = timestamp::notification_history()
+        .unwrap_or_default()
+        .iter()
+        .filter(|at| **at >= since)
+        .count();
+
+    Ok(Some(StatusReport {
+        config,
+        daemon,
+        scheduler,
+        next_run,
+        snoozed_until,
+        breaks_today,
+    }))
+}
+
+fn status(short: bool, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let report = gather_status()?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&status_json(report.as_ref()))?
+        );
+        return Ok(());
+    }
+    if short {
+        println!("{}", short_status(report.as_ref()));
+        return Ok(());
+    }
+
+    let title = tr("status-title");
+    println!("\n{title}");
+    println!("{}", "━".repeat(title.chars().count()));
+
+    let Some(report) = report else {
+        println!();
+        print_field("status-scheduler", &tr("status-not-installed"));
+        println!("\n{}", tr("status-install-hint"));
+        return Ok(());
+    };
+    let config = &report.config;
+
+    if let Some(daemon_status) = &report.daemon {
+        println!();
+        print_field("status-daemon", &tr("status-running"));
+        if let Some(started_at) = daemon_status.started_at {
+            print_field(
+                "status-uptime",
+                &format_elapsed_since(&SystemClock, started_at),
+            );
+        }
+        if let Some(state) = &daemon_status.state {
+            print_field("status-state", &state.to_string());
+        }
+        if schedule::is_installed() && !control::is_overridden() {
+            print_field("status-scheduler", &tr("status-scheduler-duplicate"));
+            println!("\n{}\n", tr("status-keep-daemon-hint"));
+        }
+    }
+    if let Some(scheduler_status) = &report.scheduler {
+        print_scheduler_status(scheduler_status);
+    }
+
+    if config.pomodoro.enabled {
+        print_pomodoro();
+    } else {
+        print_interval(config);
+    }
+    print_profiles(config);
+    print_days(config);
+    print_holidays(config);
+    print_work_hours(config);
+    print_clock(config, report.daemon.is_some());
+    print_sound_setting(config);
+    print_pause_status(config, report.snoozed_until);
+    print_next_break(report.next_run, config);
+    print_today(config, report.breaks_today);
+    print_compliance();
+
+    if let Some(daemon_status) = &report.daemon {
+        print_gates(&daemon_status.gates);
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Status for `--json`, `{"installed": false}` when nothing is set up
+fn status_json(report: Option<&StatusReport>) -> serde_json::Value {
+    let Some(report) = report else {
+        return serde_json::json!({ "installed": false });
+    };
+
+    let state = if report.config.paused {
+        "paused"
+    } else if report.snoozed_until.is_some() {
+        "snoozed"
+    } else {
+        "active"
+    };
+    let scheduler = report.scheduler.as_ref().map(|status| match status {
+        Ok(status) if status.is_running => "running",
+        Ok(_) => "not-running",
+        Err(_) => "error",
+    });
+    let (today, week) = compliance::today_and_week(&SystemClock).unwrap_or_default();
+
+    serde_json::json!({
+        "installed": true,
+        "state": state,
+        "paused_until": report.config.paused_until,
+        "snoozed_until": report.snoozed_until,
+        "pomodoro": pomodoro::load().filter(|_| report.config.pomodoro.enabled),
+        "interval_seconds": report.config.interval_seconds,
+        "clock": report.config.schedule.clock.to_string(),
+        "next_run": report.next_run.filter(|_| !report.config.paused),
+        "breaks_today": report.breaks_today,
+        "scheduler": scheduler,
+        "daemon": report.daemon,
+        "compliance": { "today": today.to_json(), "week": week.to_json() },
+    })
+}
+
+/// Status on one line, e.g. "active · 25m interval · next in 12m · 5 breaks today"
+///
+/// Kept in English and stable, since prompts and scripts parse it.
+fn short_status(report: Option<&StatusReport>) -> String {
+    let Some(report) = report else {
+        return "not installed".to_string();
+    };
+
+    let state = if let Some(until) = report.config.paused_until {
+        format!("paused until {}", until.format("%H:%M"))
+    } else if report.config.paused {
+        "paused".to_string()
+    } else if let Some(until) = report.snoozed_until {
+        format!("snoozed until {}", until.format("%H:%M"))
+    } else {
+        "active".to_string()
+    };
+    let mut parts = vec![
+        state,
+        format!(
+            "{} interval",
+            time::format_compact(report.config.interval_seconds)
+        ),
+    ];
+    if let Some(cycle) = pomodoro::load().filter(|_| report.config.pomodoro.enabled) {
+        let phase = match cycle.phase {
+            pomodoro::Phase::Work => "work",
+            pomodoro::Phase::ShortBreak | pomodoro::Phase::LongBreak => "break",
+        };
+        parts[1] = format!("pomodoro {phase} until {}", cycle.ends_at().format("%H:%M"));
+    } else if let Some(next_run) = report.next_run.filter(|_| !report.config.paused) {
+        let seconds = (next_run - Local::now()).num_seconds().max(0) as u64;
+        parts.push(format!("next in {}", time::format_compact(seconds)));
+    }
+    parts.push(match report.breaks_today {
+        1 => "1 break today".to_string(),
+        count => format!("{count} breaks today"),
+    });
+    parts.join(" · ")
+}
+
+fn print_gates(gates: &[reminder::Gate]) {
+    if gates.is_empty() {
+        return;
+    }
+
+    println!("\n{}:", tr("status-gates"));
+    for gate in gates {
+        let icon = if gate.open { "✓" } else { "✗" };
+        println!("  {icon} {:<12}{}", gate.name, gate.detail);
+    }
+}
+
+fn query_daemon_status() -> Result<Option<DaemonStatus>, Box<dyn std::error::Error>> {
+    match control::send(&Request::Status)? {
+        Some(Response::Status(status)) => Ok(Some(status)),
+        _ => Ok(None),
+    }
+}
+
+fn print_scheduler_status(status: &Result<schedule::SchedulerStatus, Box<dyn std::error::Error>>) {
+    let message = match status {
+        Ok(s) if s.is_running => "status-running",
+        Ok(_) => "status-not-running",
+        Err(_) => "status-check-failed",
+    };
+    println!();
+    print_field("status-scheduler", &tr(message));
+}
+
+/// Print a `Label:       value` line of `szmer status`
+fn print_field(label_id: &str, value: &str) {
+    println!("{:<14}{value}", format!("{}:", tr(label_id)));
+}
+
+fn print_interval(config: &Config) {
+    print_field("status-interval", &format_interval(config.interval_seconds));
+}
+
+fn print_pomodoro() {
+    let Some(cycle) = pomodoro::load() else {
+        return;
+    };
+
+    let time = cycle.ends_at().format("%H:%M").to_string();
+    let message = match cycle.phase {
+        pomodoro::Phase::Work => tr_with(
+            "status-pomodoro-work",
+            &[
+                ("session", (cycle.sessions + 1).into()),
+                ("sessions", pomodoro::SESSIONS_PER_LONG_BREAK.into()),
+                ("time", time.into()),
+            ],
+        ),
+        pomodoro::Phase::ShortBreak => tr_with("status-pomodoro-break", &[("time", time.into())]),
+        pomodoro::Phase::LongBreak => {
+            tr_with("status-pomodoro-long-break", &[("time", time.into())])
+        }
+    };
+    print_field("status-pomodoro", &message);
+}
+
+fn print_profiles(config: &Config) {
+    if config.profiles.is_empty() {
+        return;
+    }
+
+    let profiles: Vec<String> = config
+        .profiles
+        .iter()
+        .map(|profile| {
+            format!(
+                "{} ({})",
+                profile.name,
+                format_interval(profile.interval_seconds)
+            )
+        })
+        .collect();
+    print_field("status-profiles", &profiles.join(", "));
+}
+
+fn print_days(config: &Config) {
+    if config.schedule.days.is_empty() {
+        return;
+    }
+
+    let days = time::format_days(&config.schedule.days);
+    if config.schedule.is_reminder_day(Local::now().weekday()) {
+        print_field("status-days", &days);
+    } else {
+        print_field(
+            "status-days",
+            &tr_with("status-days-off-today", &[("days", days.into())]),
+        );
+    }
+}
+
+fn print_holidays(config: &Config) {
+    let Some(region) = config
+        .schedule
+        .holidays
+        .as_deref()
+        .and_then(holidays::region)
+    else {
+        return;
+    };
+
+    match holidays::on(&region, Local::now().date_naive()) {
+        Some(holiday) => print_field(
+            "status-holidays",
+            &tr_with("status-holidays-today", &[("name", holiday.name.into())]),
+        ),
+        None if !holidays::is_known(&region) => print_field(
+            "status-holidays",
+            &tr_with("status-holidays-unknown", &[("region", region.into())]),
+        ),
+        None => print_field("status-holidays", &region),
+    }
+}
+
+fn print_work_hours(config: &Config) {
+    let Some(hours) = config.schedule.work_hours else {
+        return;
+    };
+
+    let hours = hours.to_string();
+    // Off days count too, reminders resume when the next workday starts
+    let message = match config.schedule.resumes_at(Local::now()) {
+        Some(at) => tr_with(
+            "status-work-hours-outside",
+            &[
+                ("hours", hours.into()),
+                ("time_until", format_time_until(&SystemClock, at).into()),
+                ("time", at.format("%a %H:%M").to_string().into()),
+            ],
+        ),
+        None => tr_with("status-work-hours-inside", &[("hours", hours.into())]),
+    };
+    print_field("status-work-hours", &message);
+}
+
+fn print_clock(config: &Config, daemon_running: bool) {
+    let message = match config.schedule.clock {
+        config::TimerClock::Wall => tr("status-clock-wall"),
+        config::TimerClock::Monotonic if daemon_running || schedule::SUPPORTS_MONOTONIC => {
+            tr("status-clock-monotonic")
+        }
+        config::TimerClock::Monotonic => tr_with(
+            "status-clock-monotonic-unsupported",
+            &[("scheduler", schedule::NAME.into())],
+        ),
+    };
+    print_field("status-clock", &message);
+}
+
+fn print_sound_setting(config: &Config) {
+    let sound = config
+        .notification_sound
+        .clone()
+        .unwrap_or_else(|| tr("status-default-sound"));
+    print_field("status-sound", &sound);
+}
+
+fn print_pause_status(config: &Config, snoozed_until: Option<DateTime<Local>>) {
+    if let Some(until) = config.paused_until.filter(|_| config.paused) {
+        let message = tr_with(
+            "status-paused-until",
+            &[
+                ("time", time::format_time_of_day(&SystemClock, until).into()),
+                ("time_until", format_time_until(&SystemClock, until).into()),
+            ],
+        );
+        print_field("status-status", &message);
+    } else if config.paused {
+        print_field("status-status", &tr("status-paused"));
+        println!("\n{}", tr("resume-hint"));
+    } else if let Some(until) = snoozed_until {
+        let message = tr_with(
+            "status-snoozed",
+            &[("time", until.format("%H:%M").to_string().into())],
+        );
+        print_field("status-status", &message);
+    } else {
+        print_field("status-status", &tr("status-active"));
+    }
+}
+
+fn print_next_break(next_run: Option<DateTime<Local>>, config: &Config) {
+    // The pomodoro line says when the phase ends
+    if config.paused || config.pomodoro.enabled {
+        return;
+    }
+
+    match next_run {
+        Some(next_run) => {
+            let time_until = format_time_until(&SystemClock, next_run);
+            let message = tr_with(
+                "status-next-break-at",
+                &[
+                    ("time_until", time_until.into()),
+                    ("time", next_run.format("%I:%M %p").to_string().into()),
+                ],
+            );
+            print_field("status-next-break", &message);
+        }
+        None => {
+            let message = tr_with(
+                "status-next-break-unknown",
+                &[("interval", format_interval(config.interval_seconds).into())],
+            );
+            print_field("status-next-break", &message);
+        }
+    }
+}
+
+/// Print today's reminder count, with commits when git repos are configured
+fn print_today(config: &Config, breaks: usize) {
+    let mut parts = vec![tr_with("status-today-breaks", &[("count", breaks.into())])];
+    if !config.integrations.git.repos.is_empty() {
+        let since = start_of_today(&SystemClock);
+        let commits =
+            git::total_commits_since(&ProcessRunner, &config.integrations.git.repos, since);
+        parts.push(tr_with(
+            "status-today-commits",
+            &[("count", commits.into())],
+        ));
+    }
+    print_field("status-today", &parts.join(", "));
+}
+
+/// Print the share of reminders that ended in a break, once anything was answered
+fn print_compliance() {
+    let Ok((today, week)) = compliance::today_and_week(&SystemClock) else {
+        return;
+    };
+    if !week.has_answers() {
+        return;
+    }
+
+    let mut parts = Vec::new();
+    for (id, compliance) in [
+        ("status-compliance-today", today),
+        ("status-compliance-week", week),
+    ] {
+        if let Some(rate) = compliance.rate() {
+            parts.push(tr_with(
+                id,
+                &[
+                    ("taken", compliance.taken.into()),
+                    ("reminders", compliance.reminders.into()),
+                    ("rate", rate.into()),
+                ],
+            ));
+        }
+    }
+    if !parts.is_empty() {
+        print_field("status-compliance", &parts.join(", "));
+    }
+}
+
+fn bundle(action: BundleAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        BundleAction::Install { icon, identity } => {
+            bundle::install(icon.as_deref(), identity.as_deref())
+        }
+        BundleAction::Uninstall => bundle::uninstall(),
+    }
+}
+
+fn holidays(action: HolidaysAction) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let region = || -> Result<String, Box<dyn std::error::Error>> {
+        let setting =
+            config.schedule.holidays.as_deref().ok_or(
+                "Holidays are off, turn them on with: szmer config set schedule.holidays pl",
+            )?;
+        Ok(holidays::region(setting).ok_or(
+            "The locale names no region, set one with: szmer config set schedule.holidays pl",
+        )?)
+    };
+    let this_year = Local::now().year();
+
+    match action {
+        HolidaysAction::List { year } => {
+            let region = region()?;
+            let year = year.unwrap_or(this_year);
+            let mut days = holidays::calendar(&region, year);
+            days.extend(
+                holidays::imported()
+                    .into_iter()
+                    .filter(|holiday| holiday.date.year() == year),
+            );
+            days.sort();
+            if days.is_empty() {
+                println!(
+                    "No holidays for {region} in {year}. Fetch them with: szmer holidays update"
+                );
+                return Ok(());
+            }
+            println!("\nHolidays ({region}, {year})");
+            println!("━━━━━━━━━━━━━━━━━━━━━━━━━━");
+            for holiday in days {
+                println!("{}  {}", holiday.date.format("%a %Y-%m-%d"), holiday.name);
+            }
+            println!();
+        }
+        HolidaysAction::Update => {
+            let region = region()?;
+            let count = holidays::update(&ProcessRunner, &region, this_year)?;
+            println!(
+                "✓ Saved {count} {region} holidays for {this_year} and {}",
+                this_year + 1
+            );
+        }
+        HolidaysAction::Import { path } => {
+            let count = holidays::import(&path)?;
+            println!("✓ Reminders skip {count} more days from {}", path.display());
+            if config.schedule.holidays.is_none() {
+                println!("Turn holidays on with: szmer config set schedule.holidays auto");
+            }
+        }
+        HolidaysAction::Clear => {
+            holidays::clear_imported()?;
+            println!("✓ Forgot the imported days");
+        }
+    }
+    Ok(())
+}
+
+fn plugin(action: PluginAction) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        PluginAction::List => list_plugins(),
+        PluginAction::Install { path } => {
+            let plugin = plugin::install(&path)?;
+            println!(
+                "✓ Installed plugin {} to {}",
+                plugin.name,
+                plugin.path.display()
+            );
+            println!(
+                "Enable it with: szmer config set plugins.enabled {}",
+                plugin.name
+            );
+            Ok(())
+        }
+    }
+}
+
+fn profile(action: ProfileAction) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match action {
+        ProfileAction::List => {
+            println!("\nBreak Profiles");
+            println!("━━━━━━━━━━━━━━");
+            println!(
+                "\n{:<21}every {}",
+                "main",
+                format_interval(config.interval_seconds)
+            );
+            for profile in &config.profiles {
+                println!("{:<21}{profile}", profile.name);
+            }
+            println!();
+            return Ok(());
+        }
+        ProfileAction::Add {
+            name,
+            every,
+            category,
+            sound,
+        } => {
+            check_name(&name)?;
+            if every < 60 {
+                return Err("Break profiles remind at most once a minute".into());
+            }
+            if let Some(sound) = sound.as_deref().filter(|s| !sound::is_available(s)) {
+                println!("⚠ Sound {sound} wasn't found, the system default may play instead");
+            }
+
+            let profile = config::BreakProfile {
+                name,
+                interval_seconds: every,
+                category,
+                sound,
+            };
+            println!("✓ Break profile {}: {profile}", profile.name);
+            match config.profiles.iter_mut().find(|p| p.name == profile.name) {
+                Some(existing) => *existing = profile,
+                None => config.profiles.push(profile),
+            }
+        }
+        ProfileAction::Remove { name } => {
+            config.profile(&name)?;
+            config.profiles.retain(|profile| profile.name != name);
+            println!("✓ Removed break profile {name}");
+        }
+    }
+
+    config.save()?;
+
+    if schedule::is_installed() {
+        schedule::reload_profiles(&config.profiles)?;
+        println!("✓ Scheduler updated");
+    } else if pidfile::running_pid().is_some() {
+        println!("⚠ Break profiles only run with the system scheduler, not the daemon");
+    }
+
+    Ok(())
+}
+
+/// Check a preset or profile name, which ends up in file names and arguments
+fn check_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid name '{name}'. Use letters, digits, - and _"
+        ))
+    }
+}
+
+fn list_plugins() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let plugins = plugin::discover();
+
+    println!("\nPlugins");
+    println!("━━━━━━━");
+
+    if plugins.is_empty() {
+        println!(
+            "\nNo plugins found. Put szmer-<name> executables on PATH or in {}",
+            plugin::plugins_dir()?.display()
+        );
+        println!();
+        return Ok(());
+    }
+
+    for plugin in plugins.values() {
+        let enabled = config.plugins.contains(&plugin.name);
+        println!(
+            "\n{} {}{}",
+            if enabled { "✓" } else { "○" },
+            plugin.name,
+            if enabled { "" } else { " (disabled)" }
+        );
+        println!("  Path:                {}", plugin.path.display());
+
+        match plugin.describe(&ProcessRunner) {
+            Ok(manifest) => {
+                if let Some(description) = manifest.description {
+                    println!("  Description:         {description}");
+                }
+                let roles = manifest
+                    .roles
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("  Roles:               {roles}");
+            }
+            Err(e) => println!("  Status:              ⚠ {e}"),
+        }
+    }
+
+    println!("\nEnable plugins with: szmer config set plugins.enabled <name>,<name>");
+    println!();
+    Ok(())
+}
+
+fn config(action: ConfigAction, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     match action {
-        ConfigAction::Show => show_config(),
+        ConfigAction::Show => show_config(json),
         ConfigAction::Set { key, value } => set_config(&key, &value),
+        ConfigAction::Validate => validate_config(json),
+        ConfigAction::Keys => list_config_keys(json),
+        ConfigAction::Unset { key } => unset_config(&key),
+        ConfigAction::Reset { yes } => reset_config(yes),
+    }
+}
+
+/// List every problem in the config file with its fix, failing when there are any
+///
+/// A missing sound only falls back to the system default, so loading the
+/// config doesn't fail on it, but it's worth fixing all the same.
+fn validate_config(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let path = Config::find_config_file()?;
+    let (config, mut problems) = Config::load_checked()?;
+    if let Some(sound) = config
+        .notification_sound
+        .as_deref()
+        .filter(|sound| !sound::is_available(sound))
+    {
+        problems.push(config::Problem::new(
+            "sound",
+            format!("{sound} wasn't found, the system default plays instead"),
+            "szmer config set sound default",
+        ));
+    }
+
+    if json {
+        let value = serde_json::json!({
+            "path": path,
+            "valid": problems.is_empty(),
+            "problems": problems,
+        });
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    let Some(path) = path else {
+        println!("✓ No config file yet, szmer uses the defaults");
+        return Ok(());
+    };
+    if problems.is_empty() {
+        println!("✓ {} is valid", path.display());
+        return Ok(());
+    }
+    Err(SzmerError::InvalidConfig {
+        path,
+        problems: problems.iter().map(ToString::to_string).collect(),
     }
+    .into())
 }
 
-fn show_config() -> Result<(), Box<dyn std::error::Error>> {
+fn show_config(json: bool) -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::load()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&config)?);
+        return Ok(());
+    }
 
     println!("\nCurrent Configuration");
     println!("━━━━━━━━━━━━━━━━━━━━━");
     println!("\nSound:                 {}",
         config.notification_sound.as_deref().unwrap_or("(system default)"));
     println!("Paused:                {}", config.paused);
+    println!(
+        "Language:              {}{}",
+        i18n::language(),
+        if config.locale.is_some() {
+            ""
+        } else {
+            " (system)"
+        }
+    );
+    println!(
+        "Days:                  {}",
+        config::describe_days(&config.schedule.days)
+    );
+    println!("Tone:                  {}", config.notification.tone);
+    println!("Style:                 {}", config.notification.style);
+    println!("Speak:                 {}", config.notification.speak);
+    if config.notification.sticky {
+        println!("Timeout:               never (sticky)");
+    } else {
+        println!(
+            "Timeout:               {}",
+            format_seconds(config.notification.timeout_seconds)
+        );
+    }
+    println!("Urgency:               {}", config.notification.urgency);
+    println!(
+        "Work hours:            {}",
+        config::describe_work_hours(config.schedule.work_hours)
+    );
+    println!("Clock:                 {}", config.schedule.clock);
+    println!(
+        "Holidays:              {}",
+        config.schedule.holidays.as_deref().unwrap_or("off")
+    );
     println!("Interval:              {}", format_interval(config.interval_seconds));
+    println!(
+        "Break duration:        {}",
+        format_interval(config.break_duration_seconds)
+    );
+    println!("Pomodoro:              {}", config.pomodoro.enabled);
+    println!("Break activity:        {}", config.breaks.activity);
+    if config.breaks.activity == config::BreakActivity::Breathing {
+        println!("  Breathing:           {}", config.breaks.breathing);
+    }
+    for (name, seconds) in &config.interval_presets {
+        println!(
+            "  {:<21}{}",
+            format!("Preset ({name}):"),
+            format_interval(*seconds)
+        );
+    }
 
     println!("\nTimewarrior Integration:");
     println!("  Enabled:             {}", config.timewarrior.enabled);
 
     if config.timewarrior.enabled {
-        let status = timewarrior::get_status();
+        let status = timewarrior::get_status(&ProcessRunner);
 
         if status.is_installed {
             if let Some(path) = status.binary_path {
@@ -356,40 +2254,921 @@ fn show_config() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    println!("\nDelivery:");
+    println!("  Chain:               {}", format_chain(&config));
+    if let Some(url) = &config.delivery.webhook_url {
+        println!("  Webhook:             {url}");
+    }
+    println!(
+        "  Gate deadline:       {}",
+        format_seconds(config.delivery.gate_deadline_seconds)
+    );
+
+    if let Some(topic) = &config.ntfy.topic {
+        println!("\nntfy:");
+        println!(
+            "  Topic:               {}/{topic}",
+            config
+                .ntfy
+                .server
+                .as_deref()
+                .unwrap_or(ntfy::DEFAULT_SERVER)
+        );
+        if config.ntfy.token.is_some() {
+            println!("  Token:               set");
+        }
+        println!("  Priority:            {}", config.ntfy.priority);
+        println!("  Mirror:              {}", config.ntfy.mirror);
+    }
+
+    if !config.plugins.is_empty() {
+        println!("\nPlugins:");
+        println!("  Enabled:             {}", config.plugins.join(", "));
+    }
+
+    if !config.suppress.is_empty() {
+        println!("\nSuppression:");
+        if !config.suppress.apps.is_empty() {
+            println!("  Apps:                {}", config.suppress.apps.join(", "));
+        }
+        if !config.suppress.networks.is_empty() {
+            println!(
+                "  Networks:            {}",
+                config.suppress.networks.join(", ")
+            );
+        }
+        if !config.suppress.focus_modes.is_empty() {
+            println!(
+                "  Focus modes:         {}",
+                config.suppress.focus_modes.join(", ")
+            );
+        }
+        println!("  Screen sharing:      {}", config.suppress.screen_sharing);
+    }
+
+    if !config.integrations.is_empty() {
+        println!("\nGit Activity:");
+        for repo in &config.integrations.git.repos {
+            println!("  Repository:          {}", repo.display());
+        }
+    }
+
+    if !config.overlay.is_empty() {
+        let look = overlay::Look::new(&config.overlay);
+        println!("\nBreak Overlay:");
+        println!("  Theme:               {}", config.overlay.theme);
+        println!(
+            "  Text:                {} at {}pt",
+            look.text_color, look.font_size
+        );
+        println!("  Countdown:           {}", look.countdown);
+        println!("  Motion:              {}", config.overlay.motion);
+        match config.overlay.grace_seconds {
+            0 => println!("  Grace:               off"),
+            seconds => println!("  Grace:               {}", format_seconds(seconds)),
+        }
+        if let Some(count) = config.overlay.max_postpones {
+            println!("  Postpones a day:     {count}");
+        }
+    }
+
+    if !config.ducking.is_empty() {
+        println!("\nAudio Ducking:");
+        println!("  Lower by:            {}%", config.ducking.percent);
+        println!("  During break:        {}", config.ducking.during_break);
+    }
+
+    if !config.follow_up.is_empty() {
+        println!("\nIgnored Breaks:");
+        println!(
+            "  Follow up after:     {}",
+            config::describe_optional_duration(config.follow_up.delay_seconds)
+        );
+    }
+
+    if !config.away.is_empty() {
+        println!("\nAway:");
+        println!(
+            "  After no input for:  {}",
+            config::describe_optional_duration(config.away.after_seconds)
+        );
+    }
+
+    println!("\nTips:");
+    println!("  Source:              {}", config.tips);
+    let custom = tips::custom_tips(&config).len();
+    if custom > 0 {
+        let use_of = if !config.tips.is_builtin() {
+            "unused with this source"
+        } else if config.replace_builtin_tips {
+            "replacing the built-in ones"
+        } else {
+            "mixed with the built-in ones"
+        };
+        println!("  Custom tips:         {custom} ({use_of})");
+    }
+    if !config.tip_weights.is_empty() {
+        println!(
+            "  Weights:             {}",
+            config::describe_tip_weights(&config.tip_weights)
+        );
+    }
+    for (category, link) in &config.links {
+        println!("  {:<21}{link}", format!("Link ({category}):"));
+    }
+
+    println!("\nHeadless Delivery:");
+    println!("  Mode:                {}", config.headless.mode);
+    println!("  Method:              {}", config.headless.method);
+    if let Some(pipe_path) = &config.headless.pipe_path {
+        println!("  Pipe:                {}", pipe_path.display());
+    }
+    println!("  Mirror to SSH:       {}", config.headless.mirror_ssh);
+    println!(
+        "  Active:              {}",
+        session::is_headless(config.headless.mode)
+    );
+
     println!();
     Ok(())
 }
 
 fn set_config(key: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let mut config = Config::load()?;
+    // Setting a value is how a broken one gets fixed, so problems don't stop it
+    let (mut config, _) = Config::load_checked()?;
 
     match key {
+        "interval" => {
+            let seconds = match value.strip_prefix("preset:") {
+                Some(name) => config.interval_preset(name),
+                None => time::parse_duration(value),
+            }
+            .map_err(|e| invalid_value(key, e))?;
+            if !config::INTERVAL_SECONDS.contains(&seconds) {
+                return Err(invalid_value(key, "Must be from 1m to 24h".to_string()).into());
+            }
+            config.interval_seconds = seconds;
+            println!("✓ Break interval set to {}", format_interval(seconds));
+        }
+        "break_duration" => {
+            let seconds = time::parse_duration(value).map_err(|e| invalid_value(key, e))?;
+            if seconds > config.interval_seconds {
+                return Err(invalid_value(
+                    key,
+                    format!(
+                        "Must be shorter than the interval of {}",
+                        format_interval(config.interval_seconds)
+                    ),
+                )
+                .into());
+            }
+            config.break_duration_seconds = seconds;
+            println!("✓ Breaks last {}", format_interval(seconds));
+        }
+        "break.activity" => {
+            config.breaks.activity = value.parse().map_err(|e| invalid_value(key, e))?;
+            match config.breaks.activity {
+                config::BreakActivity::None => println!("✓ szmer break counts the break down"),
+                config::BreakActivity::Breathing => println!(
+                    "✓ szmer break guides your breathing, {}",
+                    config.breaks.breathing
+                ),
+            }
+        }
+        "break.breathing" => {
+            config.breaks.breathing = value.parse().map_err(|e| invalid_value(key, e))?;
+            let pattern = config.breaks.breathing;
+            println!(
+                "✓ Breathe in for {}s, hold for {}s and breathe out for {}s",
+                pattern.inhale, pattern.hold, pattern.exhale
+            );
+        }
+        "sound" => {
+            config.notification_sound = match value.trim() {
+                "" | "default" => None,
+                sound => Some(sound.to_string()),
+            };
+            match &config.notification_sound {
+                Some(sound) => {
+                    if !sound::is_available(sound) {
+                        println!(
+                            "⚠ Sound {sound} wasn't found, the system default may play instead"
+                        );
+                    }
+                    println!("✓ Reminders play {sound}");
+                }
+                None => println!("✓ Reminders play the system default sound"),
+            }
+        }
+        "paused" => {
+            if parse_bool(value).map_err(|e| invalid_value(key, e))? {
+                config.pause(None, Local::now());
+                println!("{}", tr("stop-stopped"));
+            } else {
+                config.resume();
+                println!("{}", tr("resume-resumed"));
+            }
+        }
+        "pomodoro.enabled" => {
+            config.pomodoro.enabled = parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            if config.pomodoro.enabled {
+                pomodoro::save(&pomodoro::Cycle::start(Local::now()))?;
+                println!("✓ Pomodoro cycles started, from a work session");
+            } else {
+                pomodoro::reset()?;
+                println!(
+                    "✓ Pomodoro cycles stopped, reminders go out {}",
+                    time::format_every(config.interval_seconds)
+                );
+            }
+        }
+        "schedule.days" => {
+            let days = time::parse_days(value).map_err(|e| invalid_value(key, e))?;
+            // All seven days is the same as no restriction
+            config.schedule.days = if days.len() == 7 { Vec::new() } else { days };
+            println!(
+                "✓ Reminders go out {}",
+                match config.schedule.days.as_slice() {
+                    [] => "every day".to_string(),
+                    days => format!("on {}", time::format_days(days)),
+                }
+            );
+        }
+        "schedule.work_hours" => {
+            config.schedule.work_hours = match value.trim() {
+                "" | "off" => None,
+                hours => Some(hours.parse().map_err(|e| invalid_value(key, e))?),
+            };
+            match config.schedule.work_hours {
+                Some(hours) => println!(
+                    "✓ Reminders go out between {} and {}",
+                    hours.start.format("%H:%M"),
+                    hours.end.format("%H:%M")
+                ),
+                None => println!("✓ Reminders go out at any time of day"),
+            }
+        }
+        "schedule.holidays" => {
+            config.schedule.holidays =
+                holidays::parse_setting(value).map_err(|e| invalid_value(key, e))?;
+            match config.schedule.holidays.as_deref().map(holidays::region) {
+                None => println!("✓ Reminders go out on public holidays"),
+                Some(None) => {
+                    println!("⚠ The locale names no region, set one like: pl");
+                }
+                Some(Some(region)) => {
+                    println!("✓ Reminders skip {region} public holidays");
+                    if !holidays::is_known(&region) {
+                        println!("Fetch the calendar with: szmer holidays update");
+                    }
+                }
+            }
+        }
+        "schedule.clock" => {
+            config.schedule.clock = value.parse().map_err(|e| invalid_value(key, e))?;
+            match config.schedule.clock {
+                config::TimerClock::Wall => println!("✓ Intervals follow the wall clock"),
+                config::TimerClock::Monotonic => {
+                    println!("✓ Intervals follow elapsed time, clock changes are ignored");
+                    if !schedule::SUPPORTS_MONOTONIC {
+                        println!(
+                            "⚠ {} only has wall-clock timers, only the daemon will use it",
+                            schedule::NAME
+                        );
+                    }
+                }
+            }
+        }
+        "locale" => {
+            config.locale = match value.trim() {
+                "" | "system" => None,
+                locale if i18n::is_available(locale) => Some(locale.to_string()),
+                locale => {
+                    return Err(invalid_value(
+                        key,
+                        format!(
+                            "There are no translations for {locale}, only for {}",
+                            i18n::available()
+                        ),
+                    )
+                    .into())
+                }
+            };
+            match &config.locale {
+                Some(locale) => println!("✓ szmer speaks {locale} from the next run on"),
+                None => println!("✓ szmer follows the system language from the next run on"),
+            }
+        }
+        "notification.tone" => {
+            config.notification.tone = value.parse().map_err(|e| invalid_value(key, e))?;
+            println!("✓ Reminder tone set to {}", config.notification.tone);
+        }
+        "notification.style" => {
+            config.notification.style = value.parse().map_err(|e| invalid_value(key, e))?;
+            match config.notification.style {
+                config::Style::Notification => println!("✓ Reminders show as notifications"),
+                config::Style::Overlay => println!(
+                    "✓ Reminders open a break overlay for {}",
+                    format_interval(config.break_duration_seconds)
+                ),
+            }
+        }
+        "notification.speak" => {
+            config.notification.speak = parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            if !config.notification.speak {
+                println!("✓ Reminders will no longer be read aloud");
+            } else if config.delivery.chain == [NotifierKind::Speech] {
+                println!("✓ Reminders are already only read aloud, speech is the delivery chain");
+            } else {
+                println!("✓ Reminders will also be read aloud");
+                println!("  To only hear them, run 'szmer config set delivery.chain speech'");
+            }
+        }
+        "notification.timeout" => {
+            config.notification.timeout_seconds =
+                time::parse_duration(value).map_err(|e| invalid_value(key, e))?;
+            println!(
+                "✓ Notifications stay on screen for {}",
+                format_seconds(config.notification.timeout_seconds)
+            );
+            if config.notification.sticky {
+                println!("  Sticky notifications stay until dismissed, run 'szmer config set notification.sticky false' for the timeout to apply");
+            }
+            if cfg!(target_os = "macos") {
+                println!("  macOS decides how long banners show, choose Alerts in System Settings → Notifications to keep them");
+            }
+        }
+        "notification.urgency" => {
+            config.notification.urgency = value.parse().map_err(|e| invalid_value(key, e))?;
+            println!(
+                "✓ Notifications sent with {} urgency",
+                config.notification.urgency
+            );
+            if cfg!(target_os = "macos") {
+                println!("  macOS has no notification urgency, this applies on Linux and the BSDs");
+            }
+        }
+        "notification.sticky" => {
+            config.notification.sticky = parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            if config.notification.sticky {
+                println!("✓ Notifications stay until dismissed");
+            } else {
+                println!(
+                    "✓ Notifications go away after {}",
+                    format_seconds(config.notification.timeout_seconds)
+                );
+            }
+            if cfg!(target_os = "macos") {
+                println!("  On macOS, choose Alerts for szmer in System Settings → Notifications to keep them");
+            }
+        }
         "timewarrior.enabled" => {
-            let enabled = parse_bool(value)?;
+            let enabled = parse_bool(value).map_err(|e| invalid_value(key, e))?;
 
-            if enabled && !timewarrior::is_installed() {
+            if enabled && !timewarrior::is_installed(&ProcessRunner) {
                 return Err("Cannot enable timewarrior integration: timewarrior not found in PATH".into());
             }
 
             config.timewarrior.enabled = enabled;
             println!("✓ Timewarrior integration {}", if enabled { "enabled (will skip notifications when not tracking)" } else { "disabled" });
         }
+        "headless.mode" => {
+            config.headless.mode = value.parse().map_err(|e| invalid_value(key, e))?;
+            println!("✓ Headless mode set to {}", config.headless.mode);
+        }
+        "headless.method" => {
+            config.headless.method = value.parse().map_err(|e| invalid_value(key, e))?;
+            println!(
+                "✓ Headless delivery method set to {}",
+                config.headless.method
+            );
+        }
+        "headless.mirror_ssh" => {
+            config.headless.mirror_ssh = parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            println!(
+                "✓ SSH mirroring {}",
+                if config.headless.mirror_ssh {
+                    "enabled (reminders also appear in your SSH sessions)"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+        "headless.pipe" => {
+            config.headless.pipe_path = Some(value.into());
+            println!("✓ Headless pipe set to {value}");
+        }
+        "delivery.chain" => {
+            config.delivery.chain = value
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|e| invalid_value(key, e))?;
+            println!("✓ Delivery chain set to {}", format_chain(&config));
+        }
+        "delivery.webhook" => {
+            config.delivery.webhook_url = Some(value.to_string());
+            println!("✓ Webhook URL set to {value}");
+        }
+        "delivery.deadline" => {
+            config.delivery.gate_deadline_seconds = match value.parse::<u64>() {
+                Ok(seconds) if seconds > 0 => seconds,
+                _ => {
+                    return Err(invalid_value(
+                        key,
+                        "Expected a number of seconds greater than 0".to_string(),
+                    )
+                    .into())
+                }
+            };
+            println!(
+                "✓ Gates get {} to answer before the reminder is sent anyway",
+                format_seconds(config.delivery.gate_deadline_seconds)
+            );
+        }
+        "ntfy.topic" => {
+            if value.is_empty() || value.contains(['/', ' ']) {
+                return Err(invalid_value(
+                    key,
+                    "Expected a topic name without slashes or spaces".to_string(),
+                )
+                .into());
+            }
+            config.ntfy.topic = Some(value.to_string());
+            println!("✓ ntfy topic set to {value}");
+            if config.ntfy.server.is_none() {
+                println!("  Topics on ntfy.sh are public, anyone who guesses the name can read it");
+            }
+            if !config.delivery.chain.contains(&NotifierKind::Ntfy) && !config.ntfy.mirror {
+                println!("  Run 'szmer config set ntfy.mirror true' to push every reminder, or add ntfy to delivery.chain");
+            }
+        }
+        "ntfy.server" => {
+            if !value.starts_with("http://") && !value.starts_with("https://") {
+                return Err(
+                    invalid_value(key, "Expected an http:// or https:// URL".to_string()).into(),
+                );
+            }
+            config.ntfy.server = Some(value.trim_end_matches('/').to_string());
+            println!("✓ ntfy server set to {value}");
+        }
+        "ntfy.token" => {
+            config.ntfy.token = Some(value.to_string());
+            println!("✓ ntfy token set");
+        }
+        "ntfy.priority" => {
+            config.ntfy.priority = value.parse().map_err(|e| invalid_value(key, e))?;
+            println!(
+                "✓ Reminders pushed with {} priority, escalations {} and summaries {}",
+                config.ntfy.priority,
+                config.ntfy.priority.raised(),
+                config.ntfy.priority.lowered()
+            );
+        }
+        "ntfy.mirror" => {
+            config.ntfy.mirror = parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            if config.ntfy.mirror {
+                println!("✓ Reminders will also be pushed to ntfy");
+                if config.ntfy.topic.is_none() {
+                    println!("  Run 'szmer config set ntfy.topic <topic>' to choose where");
+                }
+            } else {
+                println!("✓ Reminders will only be pushed with ntfy in delivery.chain");
+            }
+        }
+        "suppress.apps" => {
+            config.suppress.apps = parse_list(value);
+
+            if config.suppress.apps.is_empty() {
+                println!("✓ App suppression disabled");
+            } else {
+                println!(
+                    "✓ Reminders wait while these apps are in front: {}",
+                    config.suppress.apps.join(", ")
+                );
+            }
+        }
+        "suppress.networks" => {
+            config.suppress.networks = parse_list(value);
+
+            if config.suppress.networks.is_empty() {
+                println!("✓ Network suppression disabled");
+            } else {
+                println!(
+                    "✓ Reminders wait while connected to: {}",
+                    config.suppress.networks.join(", ")
+                );
+            }
+        }
+        "suppress.focus_modes" => {
+            config.suppress.focus_modes = parse_list(value);
+
+            if config.suppress.focus_modes.is_empty() {
+                println!("✓ Focus suppression disabled");
+            } else {
+                println!(
+                    "✓ Reminders are off during these Focus modes: {}",
+                    config.suppress.focus_modes.join(", ")
+                );
+            }
+        }
+        "suppress.screen_sharing" => {
+            config.suppress.screen_sharing =
+                parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            println!(
+                "✓ Screen sharing suppression {}",
+                if config.suppress.screen_sharing {
+                    "enabled (reminders wait while the screen is shared)"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+        "integrations.git.repos" => {
+            config.integrations.git.repos = parse_list(value).iter().map(PathBuf::from).collect();
+
+            for repo in &config.integrations.git.repos {
+                if !repo.exists() {
+                    println!("⚠ {} does not exist", repo.display());
+                }
+            }
+
+            if config.integrations.git.repos.is_empty() {
+                println!("✓ Git activity tracking disabled");
+            } else {
+                println!(
+                    "✓ Counting commits in {} repositories",
+                    config.integrations.git.repos.len()
+                );
+            }
+        }
+        _ if key.starts_with("interval_presets.") => {
+            let name = &key["interval_presets.".len()..];
+            check_name(name).map_err(|e| invalid_value(key, e))?;
+
+            if value.is_empty() {
+                config.interval_presets.remove(name);
+                println!("✓ Removed the {name} preset");
+            } else {
+                let seconds = time::parse_duration(value).map_err(|e| invalid_value(key, e))?;
+                config.interval_presets.insert(name.to_string(), seconds);
+                println!(
+                    "✓ Preset {name} set to {}, use it with --interval preset:{name}",
+                    format_interval(seconds)
+                );
+            }
+        }
+        _ if key.starts_with("links.") && config::is_config_key(key) => {
+            let category = &key["links.".len()..];
+            if value.is_empty() {
+                config.links.remove(category);
+                println!("✓ Removed the {category} link");
+            } else {
+                config.links.insert(category.to_string(), value.to_string());
+                if category == tips::DEFAULT_LINK {
+                    println!("✓ Reminders open {value}");
+                } else {
+                    println!("✓ Reminders with {category} tips open {value}");
+                }
+            }
+        }
+        "overlay.theme" => {
+            config.overlay.theme = value.parse().map_err(|e| invalid_value(key, e))?;
+            println!("✓ Overlay theme set to {}", config.overlay.theme);
+        }
+        "overlay.text_color" => {
+            if !config::is_hex_color(value) {
+                return Err(invalid_value(key, "Use a color like #ffcc00".to_string()).into());
+            }
+            config.overlay.text_color = Some(value.to_string());
+            println!("✓ Overlay text is drawn in {value}");
+        }
+        "overlay.font_size" => {
+            let size = value
+                .trim_end_matches("pt")
+                .parse()
+                .ok()
+                .filter(|size| config::OVERLAY_FONT_SIZES.contains(size))
+                .ok_or_else(|| {
+                    invalid_value(
+                        key,
+                        format!(
+                            "Use a size from {} to {} points",
+                            config::OVERLAY_FONT_SIZES.start(),
+                            config::OVERLAY_FONT_SIZES.end()
+                        ),
+                    )
+                })?;
+            config.overlay.font_size = Some(size);
+            println!("✓ Overlay text size set to {size}pt");
+        }
+        "overlay.countdown" => {
+            let countdown = value.parse().map_err(|e| invalid_value(key, e))?;
+            config.overlay.countdown = Some(countdown);
+            match countdown {
+                config::Countdown::Clock => println!("✓ The overlay shows the minutes left"),
+                config::Countdown::Bar => println!("✓ The overlay only shows a progress bar"),
+            }
+        }
+        "overlay.motion" => {
+            config.overlay.motion = value.parse().map_err(|e| invalid_value(key, e))?;
+            match config.overlay.motion {
+                config::Motion::Auto => {
+                    println!("✓ Countdowns and breathing move unless the desktop reduces motion")
+                }
+                config::Motion::Full => println!("✓ Countdowns tick every second"),
+                config::Motion::Reduced => {
+                    println!("✓ Countdowns go by whole minutes and breathing has no animation")
+                }
+            }
+        }
+        "overlay.grace" => {
+            config.overlay.grace_seconds = match value.trim() {
+                "off" => 0,
+                grace => time::parse_duration(grace).map_err(|e| invalid_value(key, e))?,
+            };
+            match config.overlay.grace_seconds {
+                0 => println!("✓ The overlay comes up without a countdown"),
+                seconds => println!(
+                    "✓ A {} countdown, with time to postpone, comes before the overlay",
+                    format_seconds(seconds)
+                ),
+            }
+        }
+        "overlay.max_postpones" => {
+            config.overlay.max_postpones = match value.trim() {
+                "" | "off" => None,
+                count => Some(count.parse().map_err(|_| {
+                    invalid_value(key, "Use a number of postpones, or off".to_string())
+                })?),
+            };
+            match config.overlay.max_postpones {
+                Some(count) => println!("✓ Breaks can be postponed {count} times a day"),
+                None => println!("✓ Breaks can be postponed any number of times"),
+            }
+        }
+        "ducking.percent" => {
+            config.ducking.percent = value
+                .trim_end_matches('%')
+                .parse()
+                .ok()
+                .filter(|percent| *percent <= 100)
+                .ok_or_else(|| invalid_value(key, "Use a percentage from 0 to 100".to_string()))?;
+
+            if config.ducking.percent == 0 {
+                println!("✓ Audio ducking disabled");
+            } else {
+                println!(
+                    "✓ Other audio is lowered by {}% while a reminder sounds",
+                    config.ducking.percent
+                );
+            }
+        }
+        "ducking.during_break" => {
+            config.ducking.during_break = parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            println!(
+                "✓ Audio stays lowered {}",
+                if config.ducking.during_break {
+                    "for the whole break"
+                } else {
+                    "only while the reminder sounds"
+                }
+            );
+        }
+        "follow_up.delay" => {
+            config.follow_up.delay_seconds = match value.trim() {
+                "" | "off" => None,
+                delay => Some(time::parse_duration(delay).map_err(|e| invalid_value(key, e))?),
+            };
+            match config.follow_up.delay_seconds {
+                Some(seconds) => println!(
+                    "✓ An ignored break gets a follow-up {} later",
+                    format_interval(seconds)
+                ),
+                None => println!("✓ Ignored breaks get no follow-up"),
+            }
+        }
+        "away.after" => {
+            config.away.after_seconds = match value.trim() {
+                "" | "off" => None,
+                after => {
+                    let seconds = time::parse_duration(after).map_err(|e| invalid_value(key, e))?;
+                    if seconds < away::MIN_AFTER_SECONDS {
+                        return Err(invalid_value(
+                            key,
+                            format!(
+                                "Must be at least {}, or breaks would count as being away",
+                                format_interval(away::MIN_AFTER_SECONDS)
+                            ),
+                        )
+                        .into());
+                    }
+                    Some(seconds)
+                }
+            };
+            match config.away.after_seconds {
+                Some(seconds) => println!(
+                    "✓ Reminders wait after {} without input, until you're back",
+                    format_interval(seconds)
+                ),
+                None => println!("✓ Reminders go out whether you're at the machine or not"),
+            }
+        }
+        "tips.source" => {
+            config.tips = value.parse().map_err(|e| invalid_value(key, e))?;
+            println!("✓ Tips source set to {}", config.tips);
+        }
+        "tips.replace_builtin" => {
+            config.replace_builtin_tips = parse_bool(value).map_err(|e| invalid_value(key, e))?;
+            let custom = tips::custom_tips(&config).len();
+            if !config.replace_builtin_tips {
+                println!("✓ Custom tips are mixed with the built-in ones");
+            } else if custom == 0 {
+                println!(
+                    "⚠ There are no custom tips yet, the built-in ones are shown until there are"
+                );
+            } else {
+                println!("✓ Only your {custom} custom tips are shown");
+            }
+        }
+        _ if key.starts_with("tips.weights.") && config::is_config_key(key) => {
+            let name = &key["tips.weights.".len()..];
+            let category =
+                tips::TipCategory::from_name(name).expect("is_config_key checked the name");
+            let weight = value.trim().parse().map_err(|_| {
+                invalid_value(
+                    key,
+                    format!("'{value}' is not a whole number like 2, or 0 for none"),
+                )
+            })?;
+            config.tip_weights.insert(category, weight);
+
+            let total: u32 = tips::TipCategory::ALL
+                .iter()
+                .map(|category| tips::weight(&config.tip_weights, *category))
+                .sum();
+            if total == 0 {
+                return Err(invalid_value(
+                    key,
+                    "At least one tip category has to stay on".to_string(),
+                )
+                .into());
+            }
+            if weight == 0 {
+                println!("✓ No more {name} tips");
+            } else {
+                println!(
+                    "✓ About {}% of tips are {name} tips",
+                    (f64::from(weight) * 100.0 / f64::from(total)).round()
+                );
+            }
+            if !config.tips.is_builtin() {
+                println!(
+                    "⚠ Weights apply to the built-in tips, while tips come from {}",
+                    config.tips
+                );
+            }
+        }
+        "plugins.enabled" => {
+            config.plugins = parse_list(value);
+
+            let installed = plugin::discover();
+            for name in &config.plugins {
+                if !installed.contains_key(name) {
+                    println!("⚠ Plugin {name} is not installed yet");
+                }
+            }
+
+            if config.plugins.is_empty() {
+                println!("✓ Plugins disabled");
+            } else {
+                println!("✓ Enabled plugins: {}", config.plugins.join(", "));
+            }
+        }
         _ => {
-            return Err(format!(
-                "Unknown configuration key: '{key}'. Available keys:\n  - timewarrior.enabled"
-            ).into());
+            return Err(SzmerError::UnknownConfigKey {
+                key: key.to_string(),
+            }
+            .into());
         }
     }
 
     config.save()?;
+    reschedule_after(key, &config)
+}
+
+/// Reload the scheduler when `key` changes how often it runs
+///
+/// A running daemon picks the change up from the config file.
+fn reschedule_after(key: &str, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    if matches!(key, "interval" | "schedule.clock" | "pomodoro.enabled") && schedule::is_installed()
+    {
+        schedule::reschedule(config.timer_seconds())?;
+        println!("✓ Scheduler reloaded");
+    }
+    Ok(())
+}
+
+/// Print every key `config set` takes, with what its value looks like
+fn list_config_keys(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(config::CONFIG_KEYS)?);
+        return Ok(());
+    }
+
+    let width = config::CONFIG_KEYS
+        .iter()
+        .map(|key| key.name.len())
+        .max()
+        .unwrap_or_default();
+    for key in config::CONFIG_KEYS {
+        println!("{:<width$}  {} ({})", key.name, key.about, key.value);
+    }
+    Ok(())
+}
+
+fn unset_config(key: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut config, _) = Config::load_checked()?;
+    if !config.unset(key) {
+        return Err(SzmerError::UnknownConfigKey {
+            key: key.to_string(),
+        }
+        .into());
+    }
+    config.save()?;
+    println!("✓ {key} is back to its default");
+    reschedule_after(key, &config)
+}
+
+/// Start over with the default settings
+///
+/// Pausing, profiles and presets go too. The old file is kept next to the
+/// new one, so nothing is lost for good.
+fn reset_config(yes: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if Config::find_config_file()?.is_none() {
+        println!("✓ Nothing to reset, szmer already uses the defaults");
+        return Ok(());
+    }
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            return Err("Resetting the config needs confirmation, pass --yes to skip it".into());
+        }
+        let confirmed = Confirm::new()
+            .with_prompt("Reset every setting, profile and preset to the defaults?")
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            return Ok(());
+        }
+    }
+
+    let backup_path = Config::reset()?;
+    println!(
+        "✓ Settings are back to their defaults, the old file is kept as {}",
+        backup_path.display()
+    );
+
+    if schedule::is_installed() {
+        schedule::reschedule(Config::default().timer_seconds())?;
+        println!("✓ Scheduler reloaded");
+    }
     Ok(())
 }
 
-fn parse_bool(value: &str) -> Result<bool, Box<dyn std::error::Error>> {
+fn format_chain(config: &Config) -> String {
+    config
+        .delivery
+        .chain
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+fn invalid_value(key: &str, message: String) -> SzmerError {
+    SzmerError::InvalidConfigValue {
+        key: key.to_string(),
+        message,
+    }
+}
+
+/// Split a comma-separated config value, dropping empty entries
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
     match value.to_lowercase().as_str() {
         "true" | "1" | "yes" | "y" => Ok(true),
         "false" | "0" | "no" | "n" => Ok(false),
-        _ => Err(format!("Invalid boolean value: '{value}'. Use 'true' or 'false'").into()),
+        _ => Err(format!(
+            "Invalid boolean value: '{value}'. Use 'true' or 'false'"
+        )),
     }
 }
-
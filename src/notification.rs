@@ -1,59 +1,929 @@
-use notify_rust::Notification;
+#[cfg(target_os = "macos")]
+use crate::bundle;
+use crate::clock::SystemClock;
+use crate::command::{ProcessRunner, SystemCommandRunner};
+use crate::config::{
+    Config, HeadlessConfig, NotificationConfig, NotifierKind, NtfyConfig, Style, TerminalMethod,
+    Tone,
+};
+use crate::i18n::{tr, tr_with};
+#[cfg(not(target_os = "macos"))]
+use crate::icons;
+use crate::ntfy;
+use crate::overlay;
+use crate::plugin;
+use crate::session;
+use crate::timestamp::Answer;
+use notify_rust::{Notification, Timeout};
 use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::fs::OpenOptions;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-const WELLNESS_TIPS: &[&str] = &[
-    "Stand up and walk around your office for 2-3 minutes.",
-    "Drink a glass of water to stay hydrated.",
-    "Do 10 shoulder rolls to release tension.",
-    "Look at something far away for 20 seconds to rest your eyes.",
-    "Take 5 deep breaths to reduce stress and increase oxygen flow.",
-    "Stretch your arms above your head and hold for 10 seconds.",
-    "Do 10 neck stretches - gently tilt your head side to side.",
-    "Stand up and do 10 squats to get your blood flowing.",
-    "Roll your wrists and ankles to improve circulation.",
-    "Walk to get a healthy snack or refill your water bottle.",
-    "Stretch your back by doing a seated twist in your chair.",
-    "Stand up and shake out your arms and legs.",
-    "Close your eyes and relax your facial muscles for 30 seconds.",
-    "Open a window or step outside for fresh air.",
-    "Massage your temples to relieve tension headaches.",
-    "Straighten your posture and adjust your chair height.",
-    "Do 10 arm circles forward and backward.",
-];
-
-/// Send a break reminder notification with a random wellness tip
-///
-/// # Arguments
-/// * `notification_sound` - Optional sound to play with the notification
-/// * `custom_message` - Optional custom message to display instead of a random tip
-pub fn send_break_reminder(
-    notification_sound: Option<String>,
-    custom_message: Option<&str>,
+/// A break reminder ready to be delivered
+#[derive(Debug, Clone)]
+pub struct Reminder {
+    pub summary: String,
+    pub body: String,
+    /// Sound to play, for notifiers that support one
+    pub sound: Option<String>,
+    /// URL opened when the reminder is clicked
+    pub link: Option<String>,
+    pub kind: Kind,
+}
+
+/// What a notification is about, so desktops and hooks can tell them apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Time for a break, or back to work after a pomodoro break
+    Reminder,
+    /// Something needs a second look: an ignored break, failing reminders
+    Escalation,
+    /// A look back, like the one on resuming after a long pause
+    Summary,
+}
+
+impl Kind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reminder => "reminder",
+            Self::Escalation => "escalation",
+            Self::Summary => "summary",
+        }
+    }
+}
+
+/// Message ids of the titles for a tone, one is picked at random
+fn summary_pool(tone: Tone) -> &'static [&'static str] {
+    match tone {
+        Tone::Neutral => &["notification-summary"],
+        Tone::Gentle => &[
+            "tone-gentle-summary-1",
+            "tone-gentle-summary-2",
+            "tone-gentle-summary-3",
+        ],
+        Tone::DrillSergeant => &[
+            "tone-drill-sergeant-summary-1",
+            "tone-drill-sergeant-summary-2",
+            "tone-drill-sergeant-summary-3",
+        ],
+        Tone::Humorous => &[
+            "tone-humorous-summary-1",
+            "tone-humorous-summary-2",
+            "tone-humorous-summary-3",
+        ],
+    }
+}
+
+/// Message ids wrapping a tip in a tone's voice, empty for the tip as is
+fn tip_pool(tone: Tone) -> &'static [&'static str] {
+    match tone {
+        Tone::Neutral => &[],
+        Tone::Gentle => &[
+            "tone-gentle-tip-1",
+            "tone-gentle-tip-2",
+            "tone-gentle-tip-3",
+        ],
+        Tone::DrillSergeant => &[
+            "tone-drill-sergeant-tip-1",
+            "tone-drill-sergeant-tip-2",
+            "tone-drill-sergeant-tip-3",
+        ],
+        Tone::Humorous => &[
+            "tone-humorous-tip-1",
+            "tone-humorous-tip-2",
+            "tone-humorous-tip-3",
+        ],
+    }
+}
+
+impl Reminder {
+    /// Build a reminder showing `tip`, phrased in `tone`, `rng` picking the wording
+    pub fn with_tip(
+        rng: &mut dyn RngCore,
+        tip: String,
+        link: Option<String>,
+        sound: Option<String>,
+        tone: Tone,
+    ) -> Self {
+        let summary = summary_pool(tone)
+            .choose(rng)
+            .expect("every tone has titles");
+        let body = match tip_pool(tone).choose(rng) {
+            Some(id) => tr_with(id, &[("tip", tip.into())]),
+            None => tip,
+        };
+
+        Self {
+            summary: tr(summary),
+            body,
+            sound,
+            link,
+            kind: Kind::Reminder,
+        }
+    }
+
+    /// Body with the link on its own line, for channels that can't open it
+    #[cfg(target_os = "macos")]
+    fn body_with_link(&self) -> String {
+        match &self.link {
+            Some(link) => format!("{}\n{link}", self.body),
+            None => self.body.clone(),
+        }
+    }
+}
+
+/// A channel that can deliver break reminders
+pub trait Notifier {
+    /// Short name used in logs and error messages
+    fn name(&self) -> &'static str;
+
+    /// Deliver the reminder, or explain why it couldn't be delivered
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// How long `szmer notify` stays around for a click on the reminder's buttons
+///
+/// Clicks are delivered to the process that sent the notification, so
+/// `wait_for_answers` keeps it alive while the notification is on screen.
+const ANSWER_WAIT: Duration = Duration::from_secs(60);
+
+/// How long the notification's Snooze button delays the next reminder
+pub const SNOOZE_SECONDS: u64 = 5 * 60;
+
+const ANSWERS: [Answer; 3] = [Answer::Snooze, Answer::Skip, Answer::Taken];
+
+/// Listeners for clicks on notifications this process sent
+static PENDING_ANSWERS: Mutex<Vec<mpsc::Receiver<()>>> = Mutex::new(Vec::new());
+
+/// Native desktop notification through notify-rust
+///
+/// Reminders get Snooze, Skip and Break taken buttons. A link becomes an
+/// "Open" action on Linux. macOS notifications only open the sending app
+/// when clicked, so there the link is shown in the body instead.
+///
+/// On Linux the kind goes along as an `x-szmer.<kind>` category hint, which
+/// notification daemons like dunst and mako can match rules on. On macOS,
+/// when running from the app bundle, the bundle's helper posts the
+/// notification instead, with a `szmer.<kind>` thread and category, so each
+/// kind groups on its own in Notification Center.
+pub struct DesktopNotifier {
+    pub notification: NotificationConfig,
+}
+
+impl Notifier for DesktopNotifier {
+    fn name(&self) -> &'static str {
+        "desktop"
+    }
+
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        let timeout = if self.notification.sticky {
+            Timeout::Never
+        } else {
+            Timeout::Milliseconds(
+                (self.notification.timeout_seconds * 1000).min(u32::MAX.into()) as u32,
+            )
+        };
+        #[cfg(target_os = "macos")]
+        if let Some(helper) = bundle::notify_helper() {
+            return send_with_helper(&helper, reminder);
+        }
+        #[cfg(target_os = "macos")]
+        bundle::claim_notifications();
+
+        let mut notification = Notification::new();
+        notification
+            .summary(&reminder.summary)
+            .body(&reminder.body)
+            .timeout(timeout);
+
+        // macOS has no urgency, how long a notification stays is up to its settings
+        #[cfg(not(target_os = "macos"))]
+        notification.urgency(match self.notification.urgency {
+            crate::config::Urgency::Low => notify_rust::Urgency::Low,
+            crate::config::Urgency::Normal => notify_rust::Urgency::Normal,
+            crate::config::Urgency::Critical => notify_rust::Urgency::Critical,
+        });
+
+        if let Some(sound) = &reminder.sound {
+            notification.sound_name(sound);
+        }
+
+        #[cfg(target_os = "macos")]
+        notification.body(&reminder.body_with_link());
+
+        // macOS always shows the app's icon, set by `szmer bundle install --icon`
+        #[cfg(not(target_os = "macos"))]
+        match icons::notification_icon(icons::Appearance::detect(&ProcessRunner)) {
+            Ok(icon) => {
+                notification.icon(&icon.to_string_lossy());
+            }
+            Err(e) => eprintln!("Warning: Failed to prepare the notification icon: {e}"),
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        notification.hint(notify_rust::Hint::Category(format!(
+            "x-szmer.{}",
+            reminder.kind.as_str()
+        )));
+
+        #[cfg(not(target_os = "macos"))]
+        if reminder.link.is_some() {
+            let label = tr("notification-open-link");
+            notification
+                .action("default", &label)
+                .action("open", &label);
+        }
+
+        // Answers only make sense for a break
+        if reminder.kind == Kind::Reminder {
+            for answer in ANSWERS {
+                notification.action(answer.name(), &answer_label(answer));
+            }
+        }
+
+        listen_for_answer(notification.show()?, reminder.link.clone());
+
+        Ok(())
+    }
+}
+
+/// Act on the button the user clicks, in the background
+///
+/// Answers run `szmer respond` rather than being handled in here, so a
+/// snooze clicked on a reminder from the daemon reaches the daemon through
+/// its control socket like any other request.
+fn listen_for_answer(handle: notify_rust::NotificationHandle, link: Option<String>) {
+    in_background(move || {
+        handle.wait_for_action(|action| act_on(action, link.as_deref()));
+    });
+}
+
+/// Run `listen` on its own thread, for `wait_for_answers` to wait on
+fn in_background(listen: impl FnOnce() + Send + 'static) {
+    let (done, finished) = mpsc::channel();
+    thread::spawn(move || {
+        listen();
+        let _ = done.send(());
+    });
+
+    if let Ok(mut pending) = PENDING_ANSWERS.lock() {
+        pending.push(finished);
+    }
+}
+
+/// Open the link or answer the reminder, for the notification action clicked
+fn act_on(action: &str, link: Option<&str>) {
+    if action == "default" || action == "open" {
+        if let Some(link) = link {
+            open_link(link);
+        }
+    } else if let Some(answer) = ANSWERS.iter().find(|answer| answer.name() == action) {
+        respond(*answer);
+    }
+}
+
+/// Post the reminder with the app bundle's notification helper
+///
+/// The helper says "sent" once the notification is up, then which action
+/// was clicked, if any, before `ANSWER_WAIT` is over.
+#[cfg(target_os = "macos")]
+fn send_with_helper(
+    helper: &std::path::Path,
+    reminder: &Reminder,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let body = if let Some(message) = custom_message {
-        message
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(helper)
+        .args(helper_args(reminder))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {e}", helper.display()))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or("The notification helper has no output")?;
+    let mut lines = BufReader::new(stdout).lines();
+
+    if lines.next().transpose()?.as_deref() != Some("sent") {
+        let output = child.wait_with_output()?;
+        return Err(format!(
+            "The notification helper failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    let link = reminder.link.clone();
+    in_background(move || {
+        if let Some(Ok(action)) = lines.next() {
+            act_on(&action, link.as_deref());
+        }
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Arguments of the bundle's notification helper, see
+/// `assets/macos/szmer-notify.swift`
+#[cfg(target_os = "macos")]
+fn helper_args(reminder: &Reminder) -> Vec<String> {
+    let mut args = vec![
+        reminder.kind.as_str().to_string(),
+        ANSWER_WAIT.as_secs().to_string(),
+        reminder.summary.clone(),
+        reminder.body_with_link(),
+        reminder.sound.clone().unwrap_or_default(),
+    ];
+    // The helper only puts buttons on reminders
+    args.extend(
+        ANSWERS
+            .iter()
+            .map(|answer| format!("{}={}", answer.name(), answer_label(*answer))),
+    );
+    args
+}
+
+/// Wait up to `ANSWER_WAIT` for clicks on the notifications sent so far
+///
+/// For commands that exit right after sending a reminder. The daemon
+/// doesn't call this, its listeners keep running alongside it.
+pub fn wait_for_answers() {
+    let deadline = Instant::now() + ANSWER_WAIT;
+    let pending = match PENDING_ANSWERS.lock() {
+        Ok(mut pending) => std::mem::take(&mut *pending),
+        Err(_) => return,
+    };
+    for finished in pending {
+        let _ = finished.recv_timeout(deadline.saturating_duration_since(Instant::now()));
+    }
+}
+
+fn answer_label(answer: Answer) -> String {
+    tr(match answer {
+        Answer::Snooze => "notification-snooze",
+        Answer::Skip => "notification-skip",
+        Answer::Taken => "notification-taken",
+    })
+}
+
+fn respond(answer: Answer) {
+    let binary = match std::env::current_exe() {
+        Ok(binary) => binary,
+        Err(e) => {
+            eprintln!("Warning: Failed to find the szmer binary: {e}");
+            return;
+        }
+    };
+    match ProcessRunner.run(&binary.to_string_lossy(), &["respond", answer.name()]) {
+        Ok(output) if output.success => {}
+        Ok(output) => eprintln!(
+            "Warning: Failed to {}: {}",
+            answer.name(),
+            output.stderr.trim()
+        ),
+        Err(e) => eprintln!("Warning: Failed to {}: {e}", answer.name()),
+    }
+}
+
+fn open_link(link: &str) {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
     } else {
-        WELLNESS_TIPS
-            .choose(&mut rand::thread_rng())
-            .expect("WELLNESS_TIPS is not empty")
+        "xdg-open"
     };
+    match ProcessRunner.run(opener, &[link]) {
+        Ok(output) if output.success => {}
+        Ok(output) => eprintln!("Warning: Failed to open {link}: {}", output.stderr.trim()),
+        Err(e) => eprintln!("Warning: Failed to open {link}: {e}"),
+    }
+}
+
+/// Break overlay for `notification.style = overlay`
+///
+/// Comes before the desktop notifier in the chain, so reminders still get
+/// through as notifications where the overlay can't be shown.
+pub struct OverlayNotifier {
+    /// How long the overlay counts down, the break duration
+    pub seconds: u64,
+}
 
-    let mut notification = Notification::new();
-    notification
-        .summary("Time for a Break!")
-        .body(body)
-        .timeout(5000); // 5 seconds
+impl Notifier for OverlayNotifier {
+    fn name(&self) -> &'static str {
+        "overlay"
+    }
 
-    if let Some(sound) = notification_sound {
-        notification.sound_name(&sound);
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        overlay::show(&ProcessRunner, reminder, self.seconds)
     }
+}
 
-    notification.show()?;
+/// Terminal delivery for headless sessions (bell, wall or named pipe)
+pub struct TerminalNotifier {
+    pub headless: HeadlessConfig,
+}
 
-    // Record the timestamp of this notification
-    if let Err(e) = crate::timestamp::record_notification() {
-        eprintln!("Warning: Failed to record notification timestamp: {e}");
+impl Notifier for TerminalNotifier {
+    fn name(&self) -> &'static str {
+        "terminal"
+    }
+
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        // Terminals turn the URL into something clickable
+        let message = match &reminder.link {
+            Some(link) => format!("{} {} {link}", reminder.summary, reminder.body),
+            None => format!("{} {}", reminder.summary, reminder.body),
+        };
+        let message = strip_control(&message);
+
+        match self.headless.method {
+            TerminalMethod::Bell => println!("\x07{message}"),
+            TerminalMethod::Wall => send_wall_message(&message)?,
+            TerminalMethod::Pipe => {
+                let pipe_path = self
+                    .headless
+                    .pipe_path
+                    .as_ref()
+                    .ok_or("No pipe configured. Run 'szmer config set headless.pipe <path>'")?;
+
+                let mut options = OpenOptions::new();
+                options.append(true);
+                // Non-blocking so a named pipe without a reader fails instead of hanging
+                #[cfg(unix)]
+                options.custom_flags(libc::O_NONBLOCK);
+
+                let mut pipe = options
+                    .open(pipe_path)
+                    .map_err(|e| format!("Failed to open {}: {e}", pipe_path.display()))?;
+
+                writeln!(pipe, "{message}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// POST the reminder as JSON to a webhook, e.g. a chat or home automation hook
+pub struct WebhookNotifier {
+    pub url: Option<String>,
+}
+
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        let url = self
+            .url
+            .as_deref()
+            .ok_or("No webhook URL configured. Run 'szmer config set delivery.webhook <url>'")?;
+
+        let payload = serde_json::json!({
+            "summary": reminder.summary,
+            "body": reminder.body,
+            "link": reminder.link,
+            "kind": reminder.kind.as_str(),
+        });
+
+        let output = ProcessRunner
+            .run_with_input(
+                "curl",
+                &[
+                    "--silent",
+                    "--show-error",
+                    "--fail",
+                    "--max-time",
+                    "10",
+                    "--header",
+                    "Content-Type: application/json",
+                    "--data-binary",
+                    "@-",
+                    url,
+                ],
+                &payload.to_string(),
+            )
+            .map_err(|e| format!("Failed to run curl: {e}"))?;
+
+        if !output.success {
+            return Err(format!("Webhook request failed: {}", output.stderr.trim()).into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Read the reminder aloud with the platform's speech synthesizer
+pub struct SpeechNotifier;
+
+impl Notifier for SpeechNotifier {
+    fn name(&self) -> &'static str {
+        "speech"
+    }
+
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        let text = format!("{} {}", reminder.summary, reminder.body);
+
+        #[cfg(target_os = "macos")]
+        let synthesizers: &[&str] = &["say"];
+        #[cfg(not(target_os = "macos"))]
+        let synthesizers: &[&str] = &["spd-say", "espeak-ng", "espeak"];
+
+        for synthesizer in synthesizers {
+            match ProcessRunner.run(synthesizer, &[&text]) {
+                Ok(output) if output.success => return Ok(()),
+                Ok(output) => {
+                    return Err(format!("{synthesizer} failed: {}", output.stderr.trim()).into())
+                }
+                // Not installed, try the next one
+                Err(_) => continue,
+            }
+        }
+
+        Err(format!(
+            "No speech synthesizer found (tried {})",
+            synthesizers.join(", ")
+        )
+        .into())
+    }
+}
+
+/// Push the reminder to an ntfy topic, e.g. one a phone is subscribed to
+pub struct NtfyNotifier {
+    pub config: NtfyConfig,
+}
+
+impl Notifier for NtfyNotifier {
+    fn name(&self) -> &'static str {
+        "ntfy"
+    }
+
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        ntfy::publish(&ProcessRunner, &self.config, reminder)
+    }
+}
+
+/// Hand the reminder to a notifier plugin
+pub struct PluginNotifier {
+    pub name: String,
+}
+
+impl Notifier for PluginNotifier {
+    fn name(&self) -> &'static str {
+        "plugin"
+    }
+
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        let plugin = plugin::discover()
+            .remove(&self.name)
+            .ok_or_else(|| format!("Plugin {} is not installed", self.name))?;
+        plugin.notify(&ProcessRunner, reminder)
+    }
+}
+
+/// Notifiers tried in order until one delivers the reminder
+pub struct NotifierChain {
+    notifiers: Vec<Box<dyn Notifier>>,
+    /// Where delivered reminders are pushed as well, with `ntfy.mirror`
+    mirror: Option<NtfyConfig>,
+}
+
+impl NotifierChain {
+    pub fn new(notifiers: Vec<Box<dyn Notifier>>) -> Self {
+        Self {
+            notifiers,
+            mirror: None,
+        }
+    }
+
+    /// Build the chain from `delivery.chain`, or terminal-only when headless
+    pub fn from_config(config: &Config) -> Self {
+        let mut chain = Self::chain_from_config(config);
+        if config.ntfy.mirror {
+            chain.mirror = Some(config.ntfy.clone());
+        }
+        chain
+    }
+
+    fn chain_from_config(config: &Config) -> Self {
+        if session::is_headless(config.headless.mode) {
+            return Self::new(vec![Box::new(TerminalNotifier {
+                headless: config.headless.clone(),
+            })]);
+        }
+
+        let notifiers = config
+            .delivery
+            .chain
+            .iter()
+            .flat_map(|kind| -> Vec<Box<dyn Notifier>> {
+                match kind {
+                    NotifierKind::Desktop if config.notification.style == Style::Overlay => vec![
+                        Box::new(OverlayNotifier {
+                            seconds: config.break_duration_seconds,
+                        }),
+                        Box::new(DesktopNotifier {
+                            notification: config.notification.clone(),
+                        }),
+                    ],
+                    NotifierKind::Desktop => vec![Box::new(DesktopNotifier {
+                        notification: config.notification.clone(),
+                    })],
+                    NotifierKind::Terminal => vec![Box::new(TerminalNotifier {
+                        headless: config.headless.clone(),
+                    })],
+                    NotifierKind::Webhook => vec![Box::new(WebhookNotifier {
+                        url: config.delivery.webhook_url.clone(),
+                    })],
+                    NotifierKind::Speech => vec![Box::new(SpeechNotifier)],
+                    NotifierKind::Ntfy => vec![Box::new(NtfyNotifier {
+                        config: config.ntfy.clone(),
+                    })],
+                    NotifierKind::Plugin(name) => {
+                        vec![Box::new(PluginNotifier { name: name.clone() })]
+                    }
+                }
+            })
+            .collect();
+
+        Self::new(notifiers)
+    }
+
+    /// Deliver through the first notifier that succeeds
+    ///
+    /// Returns the name of the notifier that delivered the reminder. Fails
+    /// only when every notifier in the chain failed.
+    pub fn send(&self, reminder: &Reminder) -> Result<&'static str, Box<dyn std::error::Error>> {
+        let mut failures = Vec::new();
+
+        for notifier in &self.notifiers {
+            match notifier.send(reminder) {
+                Ok(()) => {
+                    self.push_to_mirror(reminder);
+                    return Ok(notifier.name());
+                }
+                Err(e) => {
+                    eprintln!("Warning: {} delivery failed: {e}", notifier.name());
+                    failures.push(format!("{}: {e}", notifier.name()));
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            return Err(
+                "No notifiers configured. Run 'szmer config set delivery.chain desktop'".into(),
+            );
+        }
+        Err(format!("Failed to deliver reminder ({})", failures.join("; ")).into())
+    }
+
+    /// Push a delivered reminder to ntfy too, failures are warnings only
+    ///
+    /// A chain with ntfy in it already had its go at pushing.
+    fn push_to_mirror(&self, reminder: &Reminder) {
+        let Some(config) = &self.mirror else {
+            return;
+        };
+        if self
+            .notifiers
+            .iter()
+            .any(|notifier| notifier.name() == "ntfy")
+        {
+            return;
+        }
+        if let Err(e) = ntfy::publish(&ProcessRunner, config, reminder) {
+            eprintln!("Warning: Failed to push reminder to ntfy: {e}");
+        }
+    }
+}
+
+/// Print the reminder in every terminal the user is logged into over SSH
+///
+/// Runs after the reminder was delivered, so working remotely on this
+/// machine doesn't mean missing breaks. Failures are warnings only.
+pub fn mirror_to_ssh_sessions(reminder: &Reminder) {
+    for remote in session::ssh_sessions(&ProcessRunner) {
+        let device = format!("/dev/{}", remote.tty);
+        if let Err(e) = write_to_terminal(&device, reminder) {
+            eprintln!(
+                "Warning: Failed to mirror reminder to {device} ({}): {e}",
+                remote.host
+            );
+        }
+    }
+}
+
+fn write_to_terminal(device: &str, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+    let mut options = OpenOptions::new();
+    options.append(true);
+    // A terminal that stopped reading must not block delivery
+    #[cfg(unix)]
+    options.custom_flags(libc::O_NONBLOCK | libc::O_NOCTTY);
+
+    let mut terminal = options.open(device)?;
+    // Raw terminals need \r to return to the first column
+    let message = strip_control(&format!("{} {}", reminder.summary, reminder.body));
+    write!(terminal, "\r\n\x07{}\r\n", message.replace('\n', "\r\n"))?;
+    Ok(())
+}
+
+/// `text` without control characters other than newlines
+///
+/// Tips can come from a URL, a command or a plugin, and escape sequences in
+/// them would reach the terminal and could retitle it, write to its
+/// clipboard (OSC 52) or redraw what's on screen.
+fn strip_control(text: &str) -> String {
+    text.chars()
+        .filter(|c| *c == '\n' || !c.is_control())
+        .collect()
+}
+
+fn send_wall_message(message: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let output = ProcessRunner.run_with_input("wall", &[], &format!("{message}\n"))?;
+    if !output.success {
+        return Err(format!("wall failed: {}", output.stderr).into());
     }
 
     Ok(())
 }
+
+/// Record the timestamp of a delivered notification
+pub fn record_notification() {
+    if let Err(e) = crate::timestamp::record_notification(&SystemClock) {
+        eprintln!("Warning: Failed to record notification timestamp: {e}");
+    }
+}
+
+/// Notifier that records what it was asked to send, for tests
+#[cfg(test)]
+pub struct MockNotifier {
+    pub fail: bool,
+    pub sent: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+}
+
+#[cfg(test)]
+impl Notifier for MockNotifier {
+    fn name(&self) -> &'static str {
+        if self.fail {
+            "failing mock"
+        } else {
+            "mock"
+        }
+    }
+
+    fn send(&self, reminder: &Reminder) -> Result<(), Box<dyn std::error::Error>> {
+        if self.fail {
+            return Err("mock failure".into());
+        }
+        self.sent.borrow_mut().push(reminder.body.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_strip_control_keeps_newlines_and_text() {
+        assert_eq!(
+            strip_control("Stretch\x1b]52;c;ZWNobyBoaQ==\x07 now\x1b[2J\r\n\u{9b}31mźle\x7f"),
+            "Stretch]52;c;ZWNobyBoaQ== now[2J\n31mźle"
+        );
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_helper_args_carry_the_kind_and_answers() {
+        let reminder = Reminder {
+            summary: "Nudge".to_string(),
+            body: "That wasn't a break".to_string(),
+            sound: Some("Glass".to_string()),
+            link: Some("https://example.org".to_string()),
+            kind: Kind::Escalation,
+        };
+
+        let args = helper_args(&reminder);
+        assert_eq!(
+            args[..5],
+            [
+                "escalation",
+                "60",
+                "Nudge",
+                "That wasn't a break\nhttps://example.org",
+                "Glass"
+            ]
+        );
+        assert!(args[5].starts_with("snooze="));
+        assert_eq!(args.len(), 5 + ANSWERS.len());
+    }
+
+    #[test]
+    fn test_write_to_terminal_strips_escape_sequences() {
+        let terminal = tempfile::NamedTempFile::new().unwrap();
+        let reminder = Reminder {
+            summary: "Break\x1b]0;pwned\x07".to_string(),
+            body: "Stand up\nand stretch".to_string(),
+            sound: None,
+            link: None,
+            kind: Kind::Reminder,
+        };
+
+        write_to_terminal(terminal.path().to_str().unwrap(), &reminder).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(terminal.path()).unwrap(),
+            "\r\n\x07Break]0;pwned Stand up\r\nand stretch\r\n"
+        );
+    }
+
+    #[test]
+    fn test_same_seed_picks_same_wording() {
+        use rand::SeedableRng;
+        let reminder = |seed| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let reminder =
+                Reminder::with_tip(&mut rng, "Drink water".into(), None, None, Tone::Humorous);
+            (reminder.summary, reminder.body)
+        };
+        assert_eq!(reminder(7), reminder(7));
+    }
+
+    #[test]
+    fn test_tone_pools_are_translated() {
+        let english = crate::i18n::Localizer::new(&["en".parse().unwrap()]);
+        for tone in [
+            Tone::Gentle,
+            Tone::Neutral,
+            Tone::DrillSergeant,
+            Tone::Humorous,
+        ] {
+            for id in summary_pool(tone).iter().chain(tip_pool(tone)) {
+                let message = english.format(id, &[("tip", "Stretch.".into())]);
+                assert_ne!(message, *id);
+            }
+        }
+        assert!(tip_pool(Tone::Neutral).is_empty());
+    }
+
+    fn reminder() -> Reminder {
+        Reminder {
+            summary: "Time for a Break!".to_string(),
+            body: "Stretch.".to_string(),
+            sound: None,
+            link: None,
+            kind: Kind::Reminder,
+        }
+    }
+
+    #[test]
+    fn test_chain_falls_back_to_next_notifier() {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let chain = NotifierChain::new(vec![
+            Box::new(MockNotifier {
+                fail: true,
+                sent: sent.clone(),
+            }),
+            Box::new(MockNotifier {
+                fail: false,
+                sent: sent.clone(),
+            }),
+        ]);
+
+        assert_eq!(chain.send(&reminder()).unwrap(), "mock");
+        assert_eq!(*sent.borrow(), ["Stretch."]);
+    }
+
+    #[test]
+    fn test_chain_fails_when_every_notifier_fails() {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let chain = NotifierChain::new(vec![Box::new(MockNotifier {
+            fail: true,
+            sent: sent.clone(),
+        })]);
+
+        let error = chain.send(&reminder()).unwrap_err().to_string();
+        assert!(error.contains("failing mock: mock failure"));
+        assert!(sent.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_empty_chain_fails() {
+        assert!(NotifierChain::new(Vec::new()).send(&reminder()).is_err());
+    }
+
+    #[test]
+    fn test_webhook_without_url_fails() {
+        let error = WebhookNotifier { url: None }.send(&reminder()).unwrap_err();
+        assert!(error.to_string().contains("delivery.webhook"));
+    }
+}
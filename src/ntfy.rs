@@ -0,0 +1,251 @@
+use crate::command::SystemCommandRunner;
+use crate::config::{NtfyConfig, NtfyPriority};
+use crate::notification::{Kind, Reminder};
+use crate::timestamp;
+use chrono::Local;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Server used when `ntfy.server` is unset
+pub const DEFAULT_SERVER: &str = "https://ntfy.sh";
+
+const LOG_FILENAME: &str = "ntfy.log";
+
+/// Deliveries the log keeps, older ones are dropped
+const MAX_LOG_LINES: usize = 200;
+
+/// How urgently a notification of this kind goes out
+///
+/// Break reminders use `ntfy.priority`. Escalations of ignored breaks go a
+/// level up, so they can break through a phone's quiet settings, and
+/// summaries a level down.
+pub fn priority(configured: NtfyPriority, kind: Kind) -> NtfyPriority {
+    match kind {
+        Kind::Reminder => configured,
+        Kind::Escalation => configured.raised(),
+        Kind::Summary => configured.lowered(),
+    }
+}
+
+/// Publish the reminder to the configured topic
+///
+/// Every attempt, delivered or not, ends up in the delivery log.
+pub fn publish(
+    runner: &dyn SystemCommandRunner,
+    config: &NtfyConfig,
+    reminder: &Reminder,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let topic = config
+        .topic
+        .as_deref()
+        .ok_or("No ntfy topic configured. Run 'szmer config set ntfy.topic <topic>'")?;
+    let priority = priority(config.priority, reminder.kind);
+
+    let result = send(runner, config, topic, priority, reminder);
+    let outcome = match &result {
+        Ok(()) => "delivered".to_string(),
+        Err(e) => format!("failed: {e}"),
+    };
+    let line = format!(
+        "[{}] {} to {topic}, priority {priority}: {outcome}",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        reminder.kind.as_str(),
+    );
+    if let Err(e) = append_to_log(&line) {
+        eprintln!("Warning: Failed to log ntfy delivery: {e}");
+    }
+    result
+}
+
+fn send(
+    runner: &dyn SystemCommandRunner,
+    config: &NtfyConfig,
+    topic: &str,
+    priority: NtfyPriority,
+    reminder: &Reminder,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let server = config
+        .server
+        .as_deref()
+        .unwrap_or(DEFAULT_SERVER)
+        .trim_end_matches('/');
+    let payload = PayloadFile::write(&payload(topic, priority, reminder).to_string())?;
+    let path = payload.path.to_string_lossy();
+    let (args, curl_config) = request(server, config.token.as_deref(), &path);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let output = runner
+        .run_with_input("curl", &args, &curl_config)
+        .map_err(|e| format!("Failed to run curl: {e}"))?;
+    if !output.success {
+        return Err(format!("ntfy request failed: {}", output.stderr.trim()).into());
+    }
+    Ok(())
+}
+
+/// The JSON message published for `reminder`
+///
+/// JSON publishing keeps non-ASCII titles intact, headers wouldn't.
+fn payload(topic: &str, priority: NtfyPriority, reminder: &Reminder) -> serde_json::Value {
+    let mut payload = serde_json::json!({
+        "topic": topic,
+        "title": reminder.summary,
+        "message": reminder.body,
+        "priority": priority.number(),
+        "tags": ["szmer", reminder.kind.as_str()],
+    });
+    if let Some(link) = &reminder.link {
+        payload["click"] = link.clone().into();
+    }
+    payload
+}
+
+/// curl's arguments and the config it reads from stdin, posting the file at
+/// `payload_path`
+///
+/// The token and the topic (which is the password on ntfy.sh) stay off the
+/// command line, where any user can read them from `/proc`: the header goes
+/// in the config and the topic in the payload file.
+fn request(server: &str, token: Option<&str>, payload_path: &str) -> (Vec<String>, String) {
+    let args = [
+        "--silent",
+        "--show-error",
+        "--fail",
+        "--max-time",
+        "10",
+        "--header",
+        "Content-Type: application/json",
+        "--config",
+        "-",
+        "--data-binary",
+        &format!("@{payload_path}"),
+        server,
+    ];
+    let curl_config = token
+        .map(|token| {
+            let header = format!("Authorization: Bearer {token}")
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"");
+            format!("header = \"{header}\"\n")
+        })
+        .unwrap_or_default();
+    (args.map(str::to_string).to_vec(), curl_config)
+}
+
+/// The payload of one request, in a file only the current user can read
+/// that is removed once it's sent
+struct PayloadFile {
+    path: PathBuf,
+}
+
+impl PayloadFile {
+    fn write(payload: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let dir = timestamp::get_runtime_dir()?;
+        timestamp::create_private_dir(&dir)?;
+        let path = dir.join(format!("ntfy-{}.json", std::process::id()));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = Self { path };
+        options.open(&file.path)?.write_all(payload.as_bytes())?;
+        Ok(file)
+    }
+}
+
+impl Drop for PayloadFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// The most recent line of the delivery log
+pub fn last_delivery() -> Option<String> {
+    let content = fs::read_to_string(log_path().ok()?).ok()?;
+    content.lines().last().map(str::to_string)
+}
+
+fn append_to_log(line: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path = log_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let mut lines: Vec<&str> = existing.lines().collect();
+    lines.push(line);
+    let kept = &lines[lines.len().saturating_sub(MAX_LOG_LINES)..];
+    fs::write(path, format!("{}\n", kept.join("\n")))?;
+    Ok(())
+}
+
+pub fn log_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(LOG_FILENAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_follows_the_notification_kind() {
+        assert_eq!(
+            priority(NtfyPriority::High, Kind::Reminder),
+            NtfyPriority::High
+        );
+        assert_eq!(
+            priority(NtfyPriority::High, Kind::Escalation),
+            NtfyPriority::Urgent
+        );
+        assert_eq!(
+            priority(NtfyPriority::Urgent, Kind::Escalation),
+            NtfyPriority::Urgent
+        );
+        assert_eq!(
+            priority(NtfyPriority::Min, Kind::Summary),
+            NtfyPriority::Min
+        );
+        assert_eq!(NtfyPriority::Default.number(), 3);
+    }
+
+    #[test]
+    fn test_payload() {
+        let reminder = Reminder {
+            summary: "Przerwa".to_string(),
+            body: "Wstań".to_string(),
+            sound: None,
+            link: Some("https://example.org/stretch".to_string()),
+            kind: Kind::Escalation,
+        };
+
+        let payload = payload("breaks", NtfyPriority::High, &reminder);
+        assert_eq!(payload["topic"], "breaks");
+        assert_eq!(payload["title"], "Przerwa");
+        assert_eq!(payload["priority"], 4);
+        assert_eq!(payload["tags"][1], "escalation");
+        assert_eq!(payload["click"], "https://example.org/stretch");
+    }
+
+    #[test]
+    fn test_request_keeps_secrets_off_the_command_line() {
+        let (args, curl_config) = request(
+            "https://ntfy.example.org",
+            Some("tk_\"secret"),
+            "/run/user/1000/szmer/ntfy-1.json",
+        );
+        assert_eq!(
+            args.join(" "),
+            "--silent --show-error --fail --max-time 10 \
+             --header Content-Type: application/json --config - \
+             --data-binary @/run/user/1000/szmer/ntfy-1.json https://ntfy.example.org"
+        );
+        assert_eq!(
+            curl_config,
+            "header = \"Authorization: Bearer tk_\\\"secret\"\n"
+        );
+
+        let (_, curl_config) = request("https://ntfy.sh", None, "/tmp/ntfy.json");
+        assert!(curl_config.is_empty());
+    }
+}
@@ -0,0 +1,859 @@
+use crate::command::{spawn_after, SystemCommandRunner};
+use crate::config::{Countdown, Motion, OverlayConfig, OverlayTheme};
+use crate::i18n::{tr, tr_with};
+use crate::notification::Reminder;
+use crate::timestamp::{self, Answer, AnswerRecord};
+#[cfg(all(feature = "wayland", not(target_os = "macos")))]
+use crate::wayland::{LayerShell, Surface};
+use chrono::{Local, NaiveDate};
+use std::env;
+use std::process::Command;
+
+#[cfg(not(target_os = "macos"))]
+use std::io::Write;
+#[cfg(not(target_os = "macos"))]
+use std::process::{Child, Stdio};
+#[cfg(not(target_os = "macos"))]
+use std::thread;
+#[cfg(not(target_os = "macos"))]
+use std::time::Duration;
+
+/// Program that draws the overlay, checked before the overlay is shown
+#[cfg(not(target_os = "macos"))]
+const OVERLAY_PROGRAM: (&str, &[&str]) = ("zenity", &["--version"]);
+
+#[cfg(target_os = "macos")]
+const OVERLAY_PROGRAM: (&str, &[&str]) = ("osascript", &["-e", "return"]);
+
+/// Shows the tip in a dialog that closes itself when the break is over
+///
+/// Arguments: title, tip, break seconds. The dialog can't count down or be
+/// themed, so neither the time left nor the look applies.
+#[cfg(target_os = "macos")]
+const DIALOG_SCRIPT: [&str; 3] = [
+    "on run argv",
+    "display dialog (item 2 of argv) with title (item 1 of argv) buttons {\"OK\"} default button \"OK\" giving up after ((item 3 of argv) as integer)",
+    "end run",
+];
+
+/// [`DIALOG_SCRIPT`] with a Postpone button, which exits with an error
+///
+/// Arguments: title, tip, Postpone label, break seconds.
+#[cfg(target_os = "macos")]
+const POSTPONE_DIALOG_SCRIPT: [&str; 3] = [
+    "on run argv",
+    "display dialog (item 2 of argv) with title (item 1 of argv) buttons {(item 3 of argv), \"OK\"} default button \"OK\" cancel button (item 3 of argv) giving up after ((item 4 of argv) as integer)",
+    "end run",
+];
+
+/// How the overlay looks, the theme with the user's values on top
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Look {
+    /// GTK theme the window is drawn with, dark or light
+    pub gtk_theme: &'static str,
+    /// Window color of the GTK theme, for overlays szmer draws itself
+    pub background: &'static str,
+    pub text_color: String,
+    /// Text size in points
+    pub font_size: u32,
+    pub countdown: Countdown,
+    /// Count down in whole minutes, for reduced motion
+    pub still: bool,
+}
+
+impl Look {
+    pub fn new(config: &OverlayConfig) -> Self {
+        let (gtk_theme, background, text_color, font_size, countdown) = match config.theme {
+            OverlayTheme::Dark => ("Adwaita:dark", "#242424", "#ffffff", 28, Countdown::Clock),
+            OverlayTheme::Light => ("Adwaita", "#fafafa", "#1e1e1e", 28, Countdown::Clock),
+            OverlayTheme::Minimal => ("Adwaita:dark", "#242424", "#bbbbbb", 16, Countdown::Bar),
+            OverlayTheme::HighContrast => {
+                ("HighContrast", "#000000", "#ffffff", 36, Countdown::Clock)
+            }
+        };
+        Self {
+            gtk_theme,
+            background,
+            text_color: config
+                .text_color
+                .clone()
+                .unwrap_or_else(|| text_color.to_string()),
+            font_size: config.font_size.unwrap_or(font_size),
+            countdown: config.countdown.unwrap_or(countdown),
+            still: config.motion == Motion::Reduced,
+        }
+    }
+
+    /// The seconds left as the countdown shows them, rounded up to whole
+    /// minutes when it keeps still
+    pub fn shown_left(&self, left: u64) -> u64 {
+        if self.still {
+            left.div_ceil(60) * 60
+        } else {
+            left
+        }
+    }
+
+    /// The window text, `left` seconds before the break ends
+    pub fn text(&self, tip: &str, left: u64) -> String {
+        match self.countdown {
+            Countdown::Clock => format!("{tip} ({}:{:02})", left / 60, left % 60),
+            Countdown::Bar => tip.to_string(),
+        }
+    }
+
+    /// `text` as Pango markup in the overlay's color and size
+    pub fn markup_text(&self, text: &str) -> String {
+        format!(
+            "<span foreground=\"{}\" font=\"{}\">{}</span>",
+            self.text_color,
+            self.font_size,
+            escape_markup(text)
+        )
+    }
+}
+
+/// Escape text for Pango markup, on one line as zenity reads it
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', " ")
+}
+
+/// Show the reminder in a window that stays up for the whole break
+///
+/// The window runs on its own in `szmer overlay`, detached from szmer, so
+/// neither the daemon nor `szmer notify` waits for it.
+pub fn show(
+    runner: &dyn SystemCommandRunner,
+    reminder: &Reminder,
+    seconds: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if backend(runner).is_none() {
+        let (program, _) = OVERLAY_PROGRAM;
+        return Err(format!("{program} is needed for the break overlay").into());
+    }
+
+    let binary = env::current_exe()?;
+    let args = overlay_args(reminder, seconds);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    spawn_after(0, &binary.to_string_lossy(), &args)
+}
+
+/// What would draw the overlay right now, `None` when nothing can
+pub fn backend(runner: &dyn SystemCommandRunner) -> Option<&'static str> {
+    #[cfg(all(feature = "wayland", not(target_os = "macos")))]
+    if layer_shell(runner).is_some() {
+        return Some("layer-shell, covering every display");
+    }
+    #[cfg(not(target_os = "macos"))]
+    if every_display(runner) {
+        return Some("yad, covering every X11 display");
+    }
+
+    let (program, args) = OVERLAY_PROGRAM;
+    runner
+        .run(program, args)
+        .is_ok_and(|output| output.success)
+        .then_some(if cfg!(target_os = "macos") {
+            "dialog, on the main display only"
+        } else {
+            "zenity window"
+        })
+}
+
+/// Whether countdowns and the breathing exercise should keep still
+///
+/// With `Motion::Auto` this follows the desktop: GNOME's animations
+/// setting, or Reduce motion in the macOS accessibility settings.
+pub fn reduces_motion(runner: &dyn SystemCommandRunner, motion: Motion) -> bool {
+    match motion {
+        Motion::Full => false,
+        Motion::Reduced => true,
+        Motion::Auto => {
+            #[cfg(target_os = "macos")]
+            let (program, args, reduced) = (
+                "defaults",
+                &["read", "com.apple.universalaccess", "reduceMotion"],
+                "1",
+            );
+            #[cfg(not(target_os = "macos"))]
+            let (program, args, reduced) = (
+                "gsettings",
+                &["get", "org.gnome.desktop.interface", "enable-animations"],
+                "false",
+            );
+            runner
+                .run(program, args)
+                .is_ok_and(|output| output.success && output.stdout.trim() == reduced)
+        }
+    }
+}
+
+/// `szmer overlay` arguments for a reminder
+fn overlay_args(reminder: &Reminder, seconds: u64) -> Vec<String> {
+    vec![
+        "overlay".to_string(),
+        "--seconds".to_string(),
+        seconds.to_string(),
+        // A tip starting with a dash isn't an option
+        "--".to_string(),
+        reminder.summary.clone(),
+        reminder.body.clone(),
+    ]
+}
+
+/// Whether today's postpones leave room for one more
+///
+/// Snoozes from notification buttons count too, they put the break off
+/// the same way.
+fn can_postpone(max_postpones: Option<u32>, answers: &[AnswerRecord], today: NaiveDate) -> bool {
+    let Some(max) = max_postpones else {
+        return true;
+    };
+    let used = answers
+        .iter()
+        .filter(|(at, answer)| *answer == Answer::Snooze && at.date_naive() == today)
+        .count();
+    used < max as usize
+}
+
+/// Count down the grace period, then the break in a large window
+///
+/// Postpone and Esc both close a window with exit code 1, which snoozes
+/// while there are postpones left. Without them the windows have no
+/// Postpone button, and closing the grace countdown starts the break.
+///
+/// With `yad` on X11 the break covers every display instead, see
+/// [`Placement::EveryDisplay`], and on a Wayland compositor with
+/// layer-shell szmer draws both itself, see [`Placement::LayerShell`].
+#[cfg(not(target_os = "macos"))]
+pub fn run(
+    runner: &dyn SystemCommandRunner,
+    config: &OverlayConfig,
+    title: &str,
+    tip: &str,
+    seconds: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut look = Look::new(config);
+    look.still = reduces_motion(runner, config.motion);
+    let postpone_label = can_postpone(
+        config.max_postpones,
+        &timestamp::answer_history()?,
+        Local::now().date_naive(),
+    )
+    .then(|| tr("overlay-postpone"));
+
+    #[cfg(feature = "wayland")]
+    let layer_shell = layer_shell(runner);
+    #[cfg(feature = "wayland")]
+    let placements = match &layer_shell {
+        Some(shell) => (
+            Placement::LayerShell(shell, Surface::Banner),
+            Placement::LayerShell(shell, Surface::Cover),
+        ),
+        None => windows(runner),
+    };
+    #[cfg(not(feature = "wayland"))]
+    let placements = windows(runner);
+    let (grace_placement, break_placement) = placements;
+
+    if config.grace_seconds > 0 {
+        let grace = |left: u64| {
+            let time = format!("{}:{:02}", left / 60, left % 60);
+            tr_with("overlay-break-in", &[("time", time.into())])
+        };
+        let closed = count_down(
+            &look,
+            title,
+            grace_placement,
+            postpone_label.as_deref(),
+            config.grace_seconds,
+            grace,
+        )?;
+        if closed && postpone_label.is_some() {
+            return postpone();
+        }
+    }
+
+    let closed = count_down(
+        &look,
+        title,
+        break_placement,
+        postpone_label.as_deref(),
+        seconds,
+        |left| look.text(tip, left),
+    )?;
+    if closed && postpone_label.is_some() {
+        postpone()?;
+    }
+    Ok(())
+}
+
+/// Where countdown windows go
+#[cfg(not(target_os = "macos"))]
+enum Placement<'a> {
+    /// One zenity window of this width and height, placed by the desktop
+    Window(u32, u32),
+    /// A yad window covering each display, opened and closed as displays
+    /// come and go during the countdown
+    EveryDisplay(&'a dyn SystemCommandRunner),
+    /// Drawn by szmer on layer surfaces, for Wayland where windows can't
+    /// cover a display or pick one
+    #[cfg(feature = "wayland")]
+    LayerShell(&'a LayerShell, Surface),
+}
+
+/// Countdown windows for the grace period and the break, covering every
+/// display with the latter when yad can
+#[cfg(not(target_os = "macos"))]
+fn windows(runner: &dyn SystemCommandRunner) -> (Placement<'_>, Placement<'_>) {
+    let covering = if every_display(runner) {
+        Placement::EveryDisplay(runner)
+    } else {
+        Placement::Window(900, 500)
+    };
+    (Placement::Window(420, 120), covering)
+}
+
+/// Whether yad can cover every display, which takes X11
+#[cfg(not(target_os = "macos"))]
+fn every_display(runner: &dyn SystemCommandRunner) -> bool {
+    let has_yad = runner
+        .run("yad", &["--version"])
+        .is_ok_and(|output| output.success);
+    has_yad && !monitors(runner).is_empty()
+}
+
+/// The compositor's layer shell, with the desktop's sans-serif font to draw with
+#[cfg(all(feature = "wayland", not(target_os = "macos")))]
+fn layer_shell(runner: &dyn SystemCommandRunner) -> Option<LayerShell> {
+    let output = runner
+        .run("fc-match", &["--format=%{file}", "sans-serif"])
+        .ok()
+        .filter(|output| output.success)?;
+    let font = output.stdout.trim();
+    (!font.is_empty())
+        .then(|| LayerShell::connect(std::path::Path::new(font)))
+        .flatten()
+}
+
+/// A display and where it sits on the desktop
+#[cfg(not(target_os = "macos"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Monitor {
+    name: String,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Displays in use, from `xrandr`, none outside X11
+#[cfg(not(target_os = "macos"))]
+fn monitors(runner: &dyn SystemCommandRunner) -> Vec<Monitor> {
+    match runner.run("xrandr", &["--listactivemonitors"]) {
+        Ok(output) if output.success => parse_monitors(&output.stdout),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `xrandr --listactivemonitors`
+///
+/// # Examples
+///
+/// ```
+/// let monitors = parse_monitors("Monitors: 1\n 0: +*eDP-1 1920/344x1080/193+0+0  eDP-1\n");
+/// assert_eq!(monitors[0].width, 1920);
+/// ```
+#[cfg(not(target_os = "macos"))]
+fn parse_monitors(output: &str) -> Vec<Monitor> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (geometry, name) = (fields.get(2)?, fields.get(3)?);
+            // <width>/<mm>x<height>/<mm><x offset><y offset>, offsets signed
+            let (width, rest) = geometry.split_once('/')?;
+            let (_, rest) = rest.split_once('x')?;
+            let (height, rest) = rest.split_once('/')?;
+            let offsets = &rest[rest.find(['+', '-'])?..];
+            let split = offsets[1..].find(['+', '-'])? + 1;
+            Some(Monitor {
+                name: name.to_string(),
+                x: offsets[..split].parse().ok()?,
+                y: offsets[split..].parse().ok()?,
+                width: width.parse().ok()?,
+                height: height.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Displays to close windows on and displays to open them on
+#[cfg(not(target_os = "macos"))]
+fn changed_displays(covered: &[Monitor], connected: &[Monitor]) -> (Vec<Monitor>, Vec<Monitor>) {
+    let gone = covered
+        .iter()
+        .filter(|monitor| !connected.contains(monitor))
+        .cloned()
+        .collect();
+    let new = connected
+        .iter()
+        .filter(|monitor| !covered.contains(monitor))
+        .cloned()
+        .collect();
+    (gone, new)
+}
+
+/// Count `seconds` down with the text `text(left)`, the same in every window
+///
+/// Returns whether a window was closed before the end.
+#[cfg(not(target_os = "macos"))]
+fn count_down(
+    look: &Look,
+    title: &str,
+    placement: Placement,
+    postpone_label: Option<&str>,
+    seconds: u64,
+    text: impl Fn(u64) -> String,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    #[cfg(feature = "wayland")]
+    if let Placement::LayerShell(shell, surface) = placement {
+        return shell.count_down(look, title, surface, postpone_label, seconds, text);
+    }
+
+    // The progress divides by it
+    let seconds = seconds.max(1);
+    let open = |mut command: Command| {
+        command
+            .env("GTK_THEME", look.gtk_theme)
+            .arg(format!("--title={title}"))
+            .arg(format!(
+                "--text={}",
+                look.markup_text(&text(look.shown_left(seconds)))
+            ))
+            .stdin(Stdio::piped())
+            .spawn()
+    };
+
+    let mut windows: Vec<(Option<Monitor>, Child)> = Vec::new();
+    match placement {
+        Placement::Window(width, height) => {
+            let window = open(zenity_command(width, height, postpone_label))?;
+            windows.push((None, window));
+        }
+        Placement::EveryDisplay(runner) => {
+            for monitor in monitors(runner) {
+                let window = open(yad_command(&monitor, postpone_label))?;
+                windows.push((Some(monitor), window));
+            }
+        }
+        #[cfg(feature = "wayland")]
+        Placement::LayerShell(..) => {}
+    }
+
+    let mut closed = false;
+    'countdown: for elapsed in 0..seconds {
+        if let Placement::EveryDisplay(runner) = placement {
+            let connected = monitors(runner);
+            // xrandr failing for a moment isn't every display going away
+            if !connected.is_empty() {
+                let covered: Vec<Monitor> = windows.iter().filter_map(|(m, _)| m.clone()).collect();
+                let (gone, new) = changed_displays(&covered, &connected);
+                windows.retain_mut(|(monitor, window)| {
+                    let keep = !monitor.as_ref().is_some_and(|m| gone.contains(m));
+                    if !keep {
+                        let _ = window.kill();
+                        let _ = window.wait();
+                    }
+                    keep
+                });
+                for monitor in new {
+                    if let Ok(window) = open(yad_command(&monitor, postpone_label)) {
+                        windows.push((Some(monitor), window));
+                    }
+                }
+            }
+        }
+
+        let left = look.shown_left(seconds - elapsed);
+        let line = progress_line(
+            seconds.saturating_sub(left) * 100 / seconds,
+            &look.markup_text(&text(left)),
+        );
+        for (_, window) in &mut windows {
+            if window.try_wait()?.is_some() {
+                closed = true;
+                break 'countdown;
+            }
+            if let Some(input) = window.stdin.as_mut() {
+                let _ = input.write_all(line.as_bytes());
+            }
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    let mut postponed = false;
+    for (_, mut window) in windows {
+        if closed {
+            // The rest go with the one that was closed
+            let _ = window.kill();
+        } else if let Some(mut input) = window.stdin.take() {
+            let _ = input.write_all(b"100\n");
+        }
+        // zenity exits with 1 on Postpone and Esc, yad with 1 and 252
+        postponed |= matches!(window.wait()?.code(), Some(1 | 252));
+    }
+    Ok(postponed)
+}
+
+/// A zenity progress window, where the desktop puts it
+#[cfg(not(target_os = "macos"))]
+fn zenity_command(width: u32, height: u32, postpone_label: Option<&str>) -> Command {
+    let mut command = Command::new("zenity");
+    command.args([
+        "--progress".to_string(),
+        "--auto-close".to_string(),
+        format!("--width={width}"),
+        format!("--height={height}"),
+    ]);
+    match postpone_label {
+        Some(label) => command.arg(format!("--cancel-label={label}")),
+        None => command.arg("--no-cancel"),
+    };
+    command
+}
+
+/// A yad progress window covering `monitor`, placed through X11
+#[cfg(not(target_os = "macos"))]
+fn yad_command(monitor: &Monitor, postpone_label: Option<&str>) -> Command {
+    let mut command = Command::new("yad");
+    // Wayland doesn't let windows pick where they go
+    command.env("GDK_BACKEND", "x11").args([
+        "--progress".to_string(),
+        "--auto-close".to_string(),
+        "--undecorated".to_string(),
+        "--on-top".to_string(),
+        "--skip-taskbar".to_string(),
+        "--text-align=center".to_string(),
+        format!(
+            "--geometry={}x{}{:+}{:+}",
+            monitor.width, monitor.height, monitor.x, monitor.y
+        ),
+    ]);
+    match postpone_label {
+        Some(label) => command.arg(format!("--button={label}:1")),
+        None => command.arg("--no-buttons"),
+    };
+    command
+}
+
+/// Ask before the break in a dialog, then show the tip until the break is over
+#[cfg(target_os = "macos")]
+pub fn run(
+    _runner: &dyn SystemCommandRunner,
+    config: &OverlayConfig,
+    title: &str,
+    tip: &str,
+    seconds: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let postpone_label = can_postpone(
+        config.max_postpones,
+        &timestamp::answer_history()?,
+        Local::now().date_naive(),
+    )
+    .then(|| tr("overlay-postpone"));
+
+    if config.grace_seconds > 0 {
+        let time = format!(
+            "{}:{:02}",
+            config.grace_seconds / 60,
+            config.grace_seconds % 60
+        );
+        let text = tr_with("overlay-break-in", &[("time", time.into())]);
+        if !show_dialog(
+            title,
+            &text,
+            postpone_label.as_deref(),
+            config.grace_seconds,
+        )? && postpone_label.is_some()
+        {
+            return postpone();
+        }
+    }
+
+    if !show_dialog(title, tip, postpone_label.as_deref(), seconds)? && postpone_label.is_some() {
+        postpone()?;
+    }
+    Ok(())
+}
+
+/// Show a dialog that gives up after `seconds`, `false` when Postpone was picked
+#[cfg(target_os = "macos")]
+fn show_dialog(
+    title: &str,
+    text: &str,
+    postpone_label: Option<&str>,
+    seconds: u64,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let status = Command::new("osascript")
+        .args(dialog_args(title, text, postpone_label, seconds))
+        .stdout(std::process::Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+/// osascript arguments for the dialog, with a Postpone button only when there's a label
+#[cfg(target_os = "macos")]
+fn dialog_args(title: &str, text: &str, postpone_label: Option<&str>, seconds: u64) -> Vec<String> {
+    let script = match postpone_label {
+        Some(_) => &POSTPONE_DIALOG_SCRIPT,
+        None => &DIALOG_SCRIPT,
+    };
+    script
+        .iter()
+        .flat_map(|line| ["-e", line])
+        .chain([title, text])
+        .chain(postpone_label)
+        .map(str::to_string)
+        .chain([seconds.to_string()])
+        .collect()
+}
+
+/// zenity progress input: the percentage, then the text
+#[cfg(not(target_os = "macos"))]
+fn progress_line(percent: u64, markup: &str) -> String {
+    format!("{percent}\n# {markup}\n")
+}
+
+/// Snooze the way the reminder's Postpone button does
+fn postpone() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(env::current_exe()?)
+        .args(["respond", "snooze"])
+        .status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+    #[cfg(not(target_os = "macos"))]
+    use crate::command::RecordingRunner;
+    use crate::notification::Kind;
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_dialog_args_only_offer_postpone_with_a_label() {
+        let args = dialog_args("Break", "Look away", None, 300);
+        assert_eq!(args[3], DIALOG_SCRIPT[1]);
+        assert_eq!(args[args.len() - 3..], ["Break", "Look away", "300"]);
+
+        let args = dialog_args("Break", "Look away", Some("Postpone"), 300);
+        assert_eq!(args[3], POSTPONE_DIALOG_SCRIPT[1]);
+        assert_eq!(
+            args[args.len() - 4..],
+            ["Break", "Look away", "Postpone", "300"]
+        );
+    }
+
+    #[test]
+    fn test_overlay_args_end_options_before_the_text() {
+        let reminder = Reminder {
+            summary: "Time for a Break!".to_string(),
+            body: "-- stretch your legs".to_string(),
+            sound: None,
+            link: None,
+            kind: Kind::Reminder,
+        };
+        assert_eq!(
+            overlay_args(&reminder, 300),
+            [
+                "overlay",
+                "--seconds",
+                "300",
+                "--",
+                "Time for a Break!",
+                "-- stretch your legs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_look_takes_user_values_over_the_theme() {
+        let mut config = OverlayConfig {
+            theme: OverlayTheme::Minimal,
+            ..OverlayConfig::default()
+        };
+        assert_eq!(Look::new(&config).countdown, Countdown::Bar);
+        assert_eq!(Look::new(&config).font_size, 16);
+
+        config.font_size = Some(40);
+        config.text_color = Some("#ffcc00".to_string());
+        let look = Look::new(&config);
+        assert_eq!(look.font_size, 40);
+        assert_eq!(look.text_color, "#ffcc00");
+        assert_eq!(look.gtk_theme, "Adwaita:dark");
+
+        let high_contrast = Look::new(&OverlayConfig {
+            theme: OverlayTheme::HighContrast,
+            ..OverlayConfig::default()
+        });
+        assert_eq!(high_contrast.gtk_theme, "HighContrast");
+        assert_eq!(
+            (high_contrast.background, high_contrast.text_color.as_str()),
+            ("#000000", "#ffffff")
+        );
+        assert!(high_contrast.font_size > 28);
+    }
+
+    #[test]
+    fn test_still_look_counts_whole_minutes() {
+        let mut look = Look::new(&OverlayConfig::default());
+        assert_eq!(look.shown_left(125), 125);
+
+        look.still = true;
+        assert_eq!(look.shown_left(125), 180);
+        assert_eq!(look.shown_left(120), 120);
+        assert_eq!(look.text("Stretch", look.shown_left(61)), "Stretch (2:00)");
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_reduces_motion_follows_gnome_animations() {
+        let animations = "gsettings get org.gnome.desktop.interface enable-animations";
+        let off = RecordingRunner::default().reply(animations, true, "false\n");
+        let on = RecordingRunner::default().reply(animations, true, "true\n");
+
+        assert!(reduces_motion(&off, Motion::Auto));
+        assert!(!reduces_motion(&on, Motion::Auto));
+        assert!(!reduces_motion(&RecordingRunner::default(), Motion::Auto));
+        assert!(!reduces_motion(&off, Motion::Full));
+        assert!(reduces_motion(&on, Motion::Reduced));
+    }
+
+    #[test]
+    fn test_markup_escapes_the_tip() {
+        let look = Look::new(&OverlayConfig::default());
+        assert_eq!(
+            look.markup_text(&look.text("Tea & <biscuits>", 125)),
+            "<span foreground=\"#ffffff\" font=\"28\">Tea &amp; &lt;biscuits&gt; (2:05)</span>"
+        );
+
+        let bar = Look {
+            countdown: Countdown::Bar,
+            ..look
+        };
+        assert!(bar
+            .markup_text(&bar.text("Stretch", 125))
+            .ends_with(">Stretch</span>"));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_progress_line() {
+        let look = Look::new(&OverlayConfig::default());
+        assert_eq!(
+            progress_line(25, &look.markup_text(&look.text("Stretch", 45))),
+            "25\n# <span foreground=\"#ffffff\" font=\"28\">Stretch (0:45)</span>\n"
+        );
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_parse_monitors() {
+        let output = "Monitors: 2
+ 0: +*eDP-1 1920/344x1080/193+0+0  eDP-1
+ 1: +HDMI-1 2560/597x1440/336-2560-200  HDMI-1
+";
+        assert_eq!(
+            parse_monitors(output),
+            [
+                Monitor {
+                    name: "eDP-1".to_string(),
+                    x: 0,
+                    y: 0,
+                    width: 1920,
+                    height: 1080
+                },
+                Monitor {
+                    name: "HDMI-1".to_string(),
+                    x: -2560,
+                    y: -200,
+                    width: 2560,
+                    height: 1440
+                },
+            ]
+        );
+        assert!(parse_monitors("Monitors: 0\n").is_empty());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_monitors_are_empty_without_xrandr() {
+        assert!(monitors(&RecordingRunner::default()).is_empty());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_changed_displays_follow_hot_plugs() {
+        let monitor = |name: &str, x: i32| Monitor {
+            name: name.to_string(),
+            x,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        };
+        let covered = [monitor("eDP-1", 0), monitor("HDMI-1", 1920)];
+        // HDMI-1 unplugged, DP-1 plugged in
+        let connected = [monitor("eDP-1", 0), monitor("DP-1", 1920)];
+        assert_eq!(
+            changed_displays(&covered, &connected),
+            (vec![monitor("HDMI-1", 1920)], vec![monitor("DP-1", 1920)])
+        );
+        assert_eq!(
+            changed_displays(&covered, &covered),
+            (Vec::new(), Vec::new())
+        );
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_yad_window_covers_its_display() {
+        let monitor = Monitor {
+            name: "HDMI-1".to_string(),
+            x: -2560,
+            y: 0,
+            width: 2560,
+            height: 1440,
+        };
+        let command = yad_command(&monitor, None);
+        let args: Vec<_> = command.get_args().collect();
+        assert!(args.contains(&"--geometry=2560x1440-2560+0".as_ref()));
+        assert!(args.contains(&"--no-buttons".as_ref()));
+
+        let command = yad_command(&monitor, Some("Postpone"));
+        assert!(command.get_args().any(|arg| arg == "--button=Postpone:1"));
+    }
+
+    #[test]
+    fn test_can_postpone_counts_todays_snoozes() {
+        let at = |time: &str| MockClock::at(time).now();
+        let answers = [
+            (at("2025-01-05 16:00:00"), Answer::Snooze),
+            (at("2025-01-06 10:00:00"), Answer::Snooze),
+            (at("2025-01-06 11:00:00"), Answer::Taken),
+            (at("2025-01-06 12:00:00"), Answer::Snooze),
+        ];
+        let today = at("2025-01-06 13:00:00").date_naive();
+
+        assert!(can_postpone(None, &answers, today));
+        assert!(can_postpone(Some(3), &answers, today));
+        assert!(!can_postpone(Some(2), &answers, today));
+        assert!(!can_postpone(Some(0), &[], today));
+    }
+}
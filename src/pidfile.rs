@@ -0,0 +1,84 @@
+use crate::error::SzmerError;
+use crate::timestamp;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+
+const PIDFILE_FILENAME: &str = "szmer.pid";
+
+/// Exclusive lock proving this process is the only running daemon
+///
+/// The lock is held on the open pidfile and released by the OS when the
+/// process exits, so a crashed daemon never leaves a stale lock behind.
+pub struct PidLock {
+    path: PathBuf,
+    _file: File,
+}
+
+/// Path to the daemon's pidfile
+pub fn pidfile_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_runtime_dir()?.join(PIDFILE_FILENAME))
+}
+
+/// Take the daemon lock and record our PID
+///
+/// Fails if another daemon already holds the lock.
+pub fn acquire() -> Result<PidLock, Box<dyn std::error::Error>> {
+    let path = pidfile_path()?;
+
+    if let Some(parent) = path.parent() {
+        timestamp::create_private_dir(parent)?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.read(true).write(true).create(true).truncate(false);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&path)?;
+
+    if !try_lock(&file) {
+        return Err(SzmerError::DaemonAlreadyRunning { pid: running_pid() }.into());
+    }
+
+    file.set_len(0)?;
+    writeln!(file, "{}", std::process::id())?;
+
+    Ok(PidLock { path, _file: file })
+}
+
+/// PID of the running daemon, if any
+///
+/// Only reports a PID while its lock is held, so leftovers from a daemon
+/// that was killed are ignored.
+pub fn running_pid() -> Option<u32> {
+    let path = pidfile_path().ok()?;
+    let file = File::open(&path).ok()?;
+
+    if try_lock(&file) {
+        // Nobody holds the lock, the daemon isn't running
+        return None;
+    }
+
+    fs::read_to_string(&path).ok()?.trim().parse().ok()
+}
+
+impl Drop for PidLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(unix)]
+fn try_lock(file: &File) -> bool {
+    // SAFETY: flock only operates on the file descriptor, which stays open
+    // for the duration of the call
+    unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) == 0 }
+}
+
+#[cfg(not(unix))]
+fn try_lock(_file: &File) -> bool {
+    true
+}
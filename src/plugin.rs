@@ -0,0 +1,474 @@
+use crate::command::SystemCommandRunner;
+use crate::config::Config;
+use crate::notification::Reminder;
+#[cfg(feature = "wasm")]
+use crate::wasm;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Executables on PATH named `szmer-<name>` are plugins
+pub const PREFIX: &str = "szmer-";
+
+/// Plugins directory relative to the user's config directory
+const PLUGINS_DIR: &str = "plugins";
+
+/// Extensions of sandboxed WebAssembly plugins, binary and text format
+const WASM_EXTENSIONS: &[&str] = &["wasm", "wat"];
+
+/// What a plugin can do for szmer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Decides whether a due reminder is sent
+    Gate,
+    /// Delivers reminders, usable in `delivery.chain` as `plugin:<name>`
+    Notifier,
+    /// Supplies the tip shown in a reminder
+    Tip,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Gate => "gate",
+            Self::Notifier => "notifier",
+            Self::Tip => "tip",
+        })
+    }
+}
+
+/// What a plugin prints in reply to `describe`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Manifest {
+    pub roles: Vec<Role>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A gate plugin's verdict
+#[derive(Debug, Clone, Deserialize)]
+pub struct Verdict {
+    pub open: bool,
+    #[serde(default)]
+    pub detail: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TipReply {
+    tip: String,
+}
+
+/// An external executable speaking szmer's JSON-over-stdio contract
+///
+/// The plugin is run with the action as its only argument: `describe`,
+/// `gate`, `notify` or `tip`. Requests are written to stdin as a single
+/// JSON object and replies are read from stdout; a non-zero exit is a failure.
+/// A `.wasm` or `.wat` module gets the same JSON in a sandbox instead, see
+/// `wasm::call`, and can't be a notifier.
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Plugin {
+    /// Ask the plugin which roles it implements
+    pub fn describe(
+        &self,
+        runner: &dyn SystemCommandRunner,
+    ) -> Result<Manifest, Box<dyn std::error::Error>> {
+        let mut manifest: Manifest = self.call(runner, "describe", None)?;
+        if self.is_wasm() {
+            manifest.roles.retain(|role| *role != Role::Notifier);
+        }
+        Ok(manifest)
+    }
+
+    /// Whether the plugin is a sandboxed WebAssembly module
+    pub fn is_wasm(&self) -> bool {
+        is_wasm(&self.path)
+    }
+
+    /// Whether the plugin implements `role`, reporting plugins that fail to answer
+    pub fn has_role(&self, role: Role, runner: &dyn SystemCommandRunner) -> bool {
+        match self.describe(runner) {
+            Ok(manifest) => manifest.roles.contains(&role),
+            Err(e) => {
+                eprintln!("Warning: {e}");
+                false
+            }
+        }
+    }
+
+    /// Ask a gate plugin whether the reminder should be sent
+    pub fn gate(
+        &self,
+        runner: &dyn SystemCommandRunner,
+        config: &Config,
+    ) -> Result<Verdict, Box<dyn std::error::Error>> {
+        let request = json!({ "interval_seconds": config.interval_seconds });
+        self.call(runner, "gate", Some(request))
+    }
+
+    /// Hand a reminder to a notifier plugin
+    pub fn notify(
+        &self,
+        runner: &dyn SystemCommandRunner,
+        reminder: &Reminder,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let request = json!({
+            "summary": reminder.summary,
+            "body": reminder.body,
+            "sound": reminder.sound,
+            "link": reminder.link,
+            "kind": reminder.kind.as_str(),
+        });
+        self.run(runner, "notify", Some(request))?;
+        Ok(())
+    }
+
+    /// Ask a tip plugin for the tip to show
+    pub fn tip(
+        &self,
+        runner: &dyn SystemCommandRunner,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let reply: TipReply = self.call(runner, "tip", None)?;
+        Ok(reply.tip)
+    }
+
+    fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        runner: &dyn SystemCommandRunner,
+        action: &str,
+        request: Option<serde_json::Value>,
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let stdout = self.run(runner, action, request)?;
+        serde_json::from_str(&stdout)
+            .map_err(|e| format!("Plugin {} sent an invalid {action} reply: {e}", self.name).into())
+    }
+
+    fn run(
+        &self,
+        runner: &dyn SystemCommandRunner,
+        action: &str,
+        request: Option<serde_json::Value>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if self.is_wasm() {
+            return self.run_wasm(action, request);
+        }
+
+        let program = self.path.to_string_lossy();
+        let output = match request {
+            Some(request) => runner.run_with_input(&program, &[action], &request.to_string()),
+            None => runner.run(&program, &[action]),
+        }
+        .map_err(|e| format!("Failed to run plugin {}: {e}", self.name))?;
+
+        if !output.success {
+            return Err(format!(
+                "Plugin {} failed to {action}: {}",
+                self.name,
+                output.stderr.trim()
+            )
+            .into());
+        }
+
+        Ok(output.stdout)
+    }
+
+    #[cfg(feature = "wasm")]
+    fn run_wasm(
+        &self,
+        action: &str,
+        request: Option<serde_json::Value>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if action == "notify" {
+            return Err(
+                format!("Plugin {} is a WASM module, which can't notify", self.name).into(),
+            );
+        }
+        let request = request.map(|request| request.to_string());
+        wasm::call(&self.path, action, request.as_deref())
+            .map_err(|e| format!("Plugin {} failed to {action}: {e}", self.name).into())
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    fn run_wasm(
+        &self,
+        _action: &str,
+        _request: Option<serde_json::Value>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        Err(format!(
+            "Plugin {} is a WASM module, but szmer was built without the wasm feature",
+            self.name
+        )
+        .into())
+    }
+}
+
+/// Directory for plugins that shouldn't be on PATH: `~/.config/szmer/plugins`
+pub fn plugins_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let config_path = Config::get_config_path()?;
+    let config_dir = config_path
+        .parent()
+        .ok_or("Config path has no parent directory")?;
+    Ok(config_dir.join(PLUGINS_DIR))
+}
+
+/// Find every installed plugin, keyed by name
+///
+/// Plugins in the plugins directory win over `szmer-<name>` executables on
+/// PATH with the same name.
+pub fn discover() -> BTreeMap<String, Plugin> {
+    let mut plugins = BTreeMap::new();
+
+    if let Ok(dir) = plugins_dir() {
+        for path in plugin_files_in(&dir) {
+            if let Some(name) = plugin_name(&path, false) {
+                plugins.entry(name.clone()).or_insert(Plugin { name, path });
+            }
+        }
+    }
+
+    if let Some(search_path) = env::var_os("PATH") {
+        for dir in env::split_paths(&search_path) {
+            for path in executables_in(&dir) {
+                if let Some(name) = plugin_name(&path, true) {
+                    plugins.entry(name.clone()).or_insert(Plugin { name, path });
+                }
+            }
+        }
+    }
+
+    plugins
+}
+
+/// Copy a plugin executable or WASM module into the plugins directory
+///
+/// The plugin still has to be enabled with `szmer config set plugins.enabled`.
+pub fn install(source: &Path) -> Result<Plugin, Box<dyn std::error::Error>> {
+    if is_wasm(source) {
+        validate_wasm(source)?;
+    } else if !is_executable(source) {
+        return Err(format!("{} is not an executable file", source.display()).into());
+    }
+
+    let file_name = source.file_name().ok_or("Plugin path has no file name")?;
+    let path = plugins_dir()?.join(file_name);
+    let name = plugin_name(&path, false).ok_or("Plugin file name is not a valid plugin name")?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(source, &path)?;
+
+    Ok(Plugin { name, path })
+}
+
+/// Enabled plugins that are installed, in the order they were enabled
+///
+/// Plugins that are missing are reported and left out.
+pub fn enabled(config: &Config) -> Vec<Plugin> {
+    if config.plugins.is_empty() {
+        return Vec::new();
+    }
+
+    let installed = discover();
+    config
+        .plugins
+        .iter()
+        .filter_map(|name| match installed.get(name) {
+            Some(plugin) => Some(plugin.clone()),
+            None => {
+                eprintln!("Warning: Plugin {name} is enabled but not installed");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Enabled plugins that implement `role`, in the order they were enabled
+///
+/// Plugins that are missing or fail to describe themselves are reported
+/// and left out, so a broken plugin never stops reminders.
+pub fn enabled_with_role(
+    config: &Config,
+    role: Role,
+    runner: &dyn SystemCommandRunner,
+) -> Vec<Plugin> {
+    enabled(config)
+        .into_iter()
+        .filter(|plugin| plugin.has_role(role, runner))
+        .collect()
+}
+
+/// Plugin name for an executable, `None` if it isn't a plugin
+///
+/// On PATH only `szmer-<name>` counts; in the plugins directory the prefix
+/// is optional.
+fn plugin_name(path: &Path, require_prefix: bool) -> Option<String> {
+    let file_name = path.file_stem()?.to_str()?;
+
+    let name = match file_name.strip_prefix(PREFIX) {
+        Some(name) => name,
+        None if require_prefix => return None,
+        None => file_name,
+    };
+
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Executables and WASM modules in the plugins directory
+fn plugin_files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_executable(path) || (is_wasm(path) && path.is_file()))
+        .collect()
+}
+
+fn is_wasm(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| WASM_EXTENSIONS.contains(&extension))
+}
+
+#[cfg(feature = "wasm")]
+fn validate_wasm(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    wasm::validate(path)
+        .map_err(|e| format!("{} is not a usable WASM plugin: {e}", path.display()).into())
+}
+
+#[cfg(not(feature = "wasm"))]
+fn validate_wasm(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    Err(format!(
+        "{} is a WASM plugin, but szmer was built without the wasm feature",
+        path.display()
+    )
+    .into())
+}
+
+fn executables_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_executable(path))
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+    use crate::notification::Kind;
+
+    fn plugin() -> Plugin {
+        Plugin {
+            name: "focus".to_string(),
+            path: PathBuf::from("/plugins/szmer-focus"),
+        }
+    }
+
+    #[test]
+    fn test_plugin_name_from_path() {
+        assert_eq!(
+            plugin_name(Path::new("/usr/bin/szmer-slack"), true).as_deref(),
+            Some("slack")
+        );
+        assert_eq!(plugin_name(Path::new("/usr/bin/slack"), true), None);
+        assert_eq!(plugin_name(Path::new("/usr/bin/szmer-"), true), None);
+        assert_eq!(
+            plugin_name(Path::new("/plugins/slack"), false).as_deref(),
+            Some("slack")
+        );
+    }
+
+    #[test]
+    fn test_wasm_plugins_by_extension() {
+        assert!(is_wasm(Path::new("/plugins/stretch.wasm")));
+        assert!(is_wasm(Path::new("/plugins/stretch.wat")));
+        assert!(!is_wasm(Path::new("/plugins/szmer-focus")));
+        assert_eq!(
+            plugin_name(Path::new("/plugins/stretch.wasm"), false).as_deref(),
+            Some("stretch")
+        );
+    }
+
+    #[test]
+    fn test_describe_parses_manifest() {
+        let runner = RecordingRunner::default().reply(
+            "/plugins/szmer-focus describe",
+            true,
+            r#"{"roles":["gate","tip"],"description":"Focus mode"}"#,
+        );
+
+        let manifest = plugin().describe(&runner).unwrap();
+        assert_eq!(manifest.roles, [Role::Gate, Role::Tip]);
+        assert_eq!(manifest.description.as_deref(), Some("Focus mode"));
+    }
+
+    #[test]
+    fn test_gate_sends_request_on_stdin() {
+        let runner = RecordingRunner::default().reply(
+            "/plugins/szmer-focus gate",
+            true,
+            r#"{"open":false,"detail":"in a meeting"}"#,
+        );
+
+        let verdict = plugin().gate(&runner, &Config::default()).unwrap();
+        assert!(!verdict.open);
+        assert_eq!(verdict.detail, "in a meeting");
+        assert_eq!(
+            runner.calls(),
+            [r#"/plugins/szmer-focus gate <<< {"interval_seconds":3600}"#]
+        );
+    }
+
+    #[test]
+    fn test_invalid_reply_names_plugin() {
+        let runner = RecordingRunner::default().reply("/plugins/szmer-focus tip", true, "tip");
+
+        let error = plugin().tip(&runner).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Plugin focus sent an invalid tip reply"));
+    }
+
+    #[test]
+    fn test_failing_plugin_is_an_error() {
+        let runner = RecordingRunner::default().reply("/plugins/szmer-focus notify", false, "");
+        let reminder = Reminder {
+            summary: "Break".to_string(),
+            body: "Stretch.".to_string(),
+            sound: None,
+            link: None,
+            kind: Kind::Reminder,
+        };
+
+        assert!(plugin().notify(&runner, &reminder).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::i18n::{tr, tr_with};
+use crate::time::format_interval;
+use crate::timestamp;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const STATE_FILENAME: &str = "pomodoro.json";
+
+pub const WORK_SECONDS: u64 = 25 * 60;
+pub const SHORT_BREAK_SECONDS: u64 = 5 * 60;
+pub const LONG_BREAK_SECONDS: u64 = 15 * 60;
+/// Every this many work sessions, the break is a long one
+pub const SESSIONS_PER_LONG_BREAK: u32 = 4;
+
+/// How often the scheduler runs `szmer notify` in pomodoro mode
+///
+/// Every phase lasts a multiple of it, so phases end on a scheduler run.
+pub const TICK_SECONDS: u64 = 5 * 60;
+
+/// How early a scheduler run may be and still end a phase
+const LEEWAY_SECONDS: i64 = 60;
+
+/// Part of a pomodoro cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    pub fn seconds(self) -> u64 {
+        match self {
+            Self::Work => WORK_SECONDS,
+            Self::ShortBreak => SHORT_BREAK_SECONDS,
+            Self::LongBreak => LONG_BREAK_SECONDS,
+        }
+    }
+}
+
+/// Where the user is in the pomodoro cycle, kept in `~/.cache/szmer/pomodoro.json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cycle {
+    pub phase: Phase,
+    pub started_at: DateTime<Local>,
+    /// Work sessions finished since the last long break
+    pub sessions: u32,
+}
+
+impl Cycle {
+    /// A fresh cycle, starting with work
+    pub fn start(now: DateTime<Local>) -> Self {
+        Self {
+            phase: Phase::Work,
+            started_at: now,
+            sessions: 0,
+        }
+    }
+
+    pub fn ends_at(&self) -> DateTime<Local> {
+        self.started_at + Duration::seconds(self.phase.seconds() as i64)
+    }
+
+    /// The phase that follows this one, `None` while this one lasts
+    ///
+    /// A cycle left alone for longer than a long break, e.g. overnight or
+    /// while paused, starts over with work.
+    pub fn advance(&self, now: DateTime<Local>) -> Option<Self> {
+        let ends_at = self.ends_at();
+        if now + Duration::seconds(LEEWAY_SECONDS) < ends_at {
+            return None;
+        }
+        if now > ends_at + Duration::seconds(LONG_BREAK_SECONDS as i64) {
+            return Some(Self::start(now));
+        }
+
+        let (phase, sessions) = match self.phase {
+            Phase::Work if self.sessions + 1 >= SESSIONS_PER_LONG_BREAK => (Phase::LongBreak, 0),
+            Phase::Work => (Phase::ShortBreak, self.sessions + 1),
+            Phase::ShortBreak | Phase::LongBreak => (Phase::Work, self.sessions),
+        };
+        Some(Self {
+            phase,
+            started_at: now,
+            sessions,
+        })
+    }
+}
+
+/// Title of the reminder starting `cycle`'s phase
+pub fn summary(cycle: &Cycle) -> String {
+    let duration = format_interval(cycle.phase.seconds());
+    match cycle.phase {
+        Phase::Work => tr("pomodoro-work-summary"),
+        Phase::ShortBreak => tr_with("pomodoro-break-summary", &[("duration", duration.into())]),
+        Phase::LongBreak => tr_with(
+            "pomodoro-long-break-summary",
+            &[("duration", duration.into())],
+        ),
+    }
+}
+
+/// Body of the reminder sending the user back to work
+pub fn work_body(cycle: &Cycle) -> String {
+    tr_with(
+        "pomodoro-work-body",
+        &[("time", cycle.ends_at().format("%H:%M").to_string().into())],
+    )
+}
+
+/// The saved cycle, `None` when there's none or it can't be read
+pub fn load() -> Option<Cycle> {
+    let content = fs::read_to_string(state_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save(cycle: &Cycle) -> Result<(), Box<dyn std::error::Error>> {
+    let path = state_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(cycle)?)?;
+    Ok(())
+}
+
+/// Forget the cycle, so the next one starts with work
+pub fn reset() -> Result<(), Box<dyn std::error::Error>> {
+    match fs::remove_file(state_path()?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn state_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(timestamp::get_cache_dir()?.join(STATE_FILENAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+
+    #[test]
+    fn test_cycle_alternates_with_a_long_break_every_fourth() {
+        let mut now = MockClock::at("2025-01-06 09:00:00").now();
+        let mut cycle = Cycle::start(now);
+        let mut phases = Vec::new();
+
+        for _ in 0..8 {
+            // Not over yet halfway through
+            assert_eq!(cycle.advance(now + Duration::seconds(60)), None);
+            // A scheduler run a few seconds early still ends the phase
+            now = cycle.ends_at() - Duration::seconds(5);
+            cycle = cycle.advance(now).unwrap();
+            phases.push(cycle.phase);
+        }
+
+        use Phase::*;
+        assert_eq!(
+            phases,
+            [ShortBreak, Work, ShortBreak, Work, ShortBreak, Work, LongBreak, Work]
+        );
+    }
+
+    #[test]
+    fn test_stale_cycle_starts_over_with_work() {
+        let now = MockClock::at("2025-01-06 09:00:00").now();
+        let cycle = Cycle {
+            phase: Phase::ShortBreak,
+            started_at: now,
+            sessions: 2,
+        };
+
+        let next = cycle.advance(now + Duration::hours(10)).unwrap();
+        assert_eq!(next.phase, Phase::Work);
+        assert_eq!(next.sessions, 0);
+    }
+}
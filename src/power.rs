@@ -0,0 +1,60 @@
+use crate::control::Envelope;
+use std::sync::mpsc::Sender;
+
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+use crate::control::Request;
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+use std::sync::mpsc;
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+use std::thread;
+
+/// Forward system sleep and wake notifications to the daemon loop
+///
+/// On Linux this listens for logind's `PrepareForSleep` signal on the
+/// system bus. Elsewhere the daemon relies on spotting wall-clock jumps,
+/// and external tools can send `{"command":"sleep"}` / `{"command":"wake"}`
+/// over the control socket.
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+pub fn spawn_watcher(events: Sender<Envelope>) -> Result<(), Box<dyn std::error::Error>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = zbus::blocking::Proxy::new(
+        &connection,
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1",
+        "org.freedesktop.login1.Manager",
+    )?;
+    let signals = proxy.receive_signal("PrepareForSleep")?;
+
+    thread::spawn(move || {
+        // Keep the connection alive for as long as we listen
+        let _connection = connection;
+
+        for message in signals {
+            // true right before suspending, false after resuming
+            let Ok(going_to_sleep) = message.body().deserialize::<bool>() else {
+                continue;
+            };
+            let request = if going_to_sleep {
+                Request::Sleep
+            } else {
+                Request::Wake
+            };
+
+            let (reply_tx, _reply_rx) = mpsc::channel();
+            if events.send((request, reply_tx)).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(not(all(feature = "dbus", target_os = "linux")))]
+pub fn spawn_watcher(_events: Sender<Envelope>) -> Result<(), Box<dyn std::error::Error>> {
+    if cfg!(target_os = "linux") {
+        Err("built without the 'dbus' feature, relying on clock jumps".into())
+    } else {
+        Err("sleep notifications are not supported on this platform, relying on clock jumps".into())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::command::SystemCommandRunner;
+use serde_json::Value;
+
+/// Processes that only run while the screen is being shared on macOS
+///
+/// macOS has no public way to ask whether a capture session is active, so
+/// this looks for the helpers of the common tools: Zoom's sharing host and
+/// the Screen Sharing server used for incoming VNC sessions.
+#[cfg(target_os = "macos")]
+const MACOS_SHARING_PROCESSES: &[&str] = &["CptHost", "screensharingd"];
+
+/// Name of the Wi-Fi network this machine is connected to
+pub fn current_network(runner: &dyn SystemCommandRunner) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = runner
+            .run("networksetup", &["-getairportnetwork", "en0"])
+            .ok()?;
+        output
+            .stdout
+            .trim()
+            .strip_prefix("Current Wi-Fi Network: ")
+            .map(String::from)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let output = runner
+            .run("nmcli", &["-t", "-f", "active,ssid", "device", "wifi"])
+            .ok()
+            .filter(|output| output.success)?;
+        active_nmcli_ssid(&output.stdout)
+    }
+}
+
+/// Whether the screen is being shared or recorded right now
+///
+/// On Linux this looks for a running PipeWire video stream that isn't a
+/// camera, which is how the desktop portal hands out screencasts on
+/// Wayland.
+pub fn is_screen_shared(runner: &dyn SystemCommandRunner) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        MACOS_SHARING_PROCESSES.iter().any(|name| {
+            runner
+                .run("pgrep", &["-x", name])
+                .is_ok_and(|output| output.success)
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        runner
+            .run("pw-dump", &[])
+            .ok()
+            .filter(|output| output.success)
+            .and_then(|output| serde_json::from_str(&output.stdout).ok())
+            .is_some_and(|objects: Value| has_running_screencast(&objects))
+    }
+}
+
+/// SSID of the active connection in `nmcli -t -f active,ssid` output
+///
+/// Colons inside an SSID are escaped by nmcli as `\:`.
+#[cfg(not(target_os = "macos"))]
+fn active_nmcli_ssid(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("yes:"))
+        .filter(|ssid| !ssid.is_empty())
+        .map(|ssid| ssid.replace("\\:", ":"))
+}
+
+/// Whether a `pw-dump` object list contains a running non-camera video source
+#[cfg(not(target_os = "macos"))]
+fn has_running_screencast(objects: &Value) -> bool {
+    objects.as_array().into_iter().flatten().any(|object| {
+        let info = &object["info"];
+        let props = &info["props"];
+        props["media.class"] == "Video/Source"
+            && info["state"] == "running"
+            && props.get("device.api").is_none()
+    })
+}
+
+#[cfg(all(test, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_nmcli_ssid() {
+        let output = "no:Guest\nyes:Conference\\: Room A\nno:\n";
+        assert_eq!(
+            active_nmcli_ssid(output).as_deref(),
+            Some("Conference: Room A")
+        );
+        assert_eq!(active_nmcli_ssid("no:Guest\n"), None);
+    }
+
+    #[test]
+    fn test_screencast_ignores_cameras() {
+        let camera = serde_json::json!([{
+            "type": "PipeWire:Interface:Node",
+            "info": { "state": "running", "props": { "media.class": "Video/Source", "device.api": "v4l2" } },
+        }]);
+        assert!(!has_running_screencast(&camera));
+
+        let screencast = serde_json::json!([{
+            "type": "PipeWire:Interface:Node",
+            "info": { "state": "running", "props": { "media.class": "Video/Source", "node.name": "xdpw_stream" } },
+        }]);
+        assert!(has_running_screencast(&screencast));
+    }
+}
@@ -0,0 +1,439 @@
+use crate::away::{self, Presence};
+use crate::clock::Clock;
+use crate::clock::SystemClock;
+use crate::command::ProcessRunner;
+use crate::config::{BreakProfile, Config, TerminalMethod};
+use crate::ducking;
+use crate::focus;
+use crate::focus_mode;
+use crate::holidays;
+use crate::notification::{self, Kind, Notifier, NotifierChain, Reminder, SpeechNotifier};
+use crate::plugin::{self, Role};
+use crate::pomodoro::{self, Cycle, Phase};
+use crate::presenting;
+use crate::random;
+use crate::session;
+use crate::timestamp;
+use crate::timewarrior;
+use crate::tips;
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Result of attempting to deliver a break reminder
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The reminder was delivered
+    Sent,
+    /// Reminders are paused
+    Paused,
+    /// `szmer snooze` holds reminders back until this time
+    Snoozed(DateTime<Local>),
+    /// An integration decided the reminder should not be shown
+    Skipped(String),
+    /// A pomodoro break ended and the user was sent back to work
+    BackToWork,
+    /// The current pomodoro phase lasts until this time, nothing is due
+    Waiting(DateTime<Local>),
+    /// Nobody has been at the machine since this time, see `away.after`
+    Away(DateTime<Local>),
+    /// The user is back after being away since this time, the interval starts over
+    Back(DateTime<Local>),
+}
+
+/// Deliver a break reminder, honoring pause state and integrations
+///
+/// Shared by `szmer notify` (run by the system scheduler) and the daemon.
+/// A break `profile` picks the tip category and sound of its reminder.
+pub fn remind(
+    config: &Config,
+    profile: Option<&BreakProfile>,
+) -> Result<Outcome, Box<dyn std::error::Error>> {
+    if config.paused {
+        return Ok(Outcome::Paused);
+    }
+
+    if let Some(until) = timestamp::snoozed_until(&SystemClock) {
+        return Ok(Outcome::Snoozed(until));
+    }
+
+    // Pomodoro phases keep their own time
+    if let Some(after) = config
+        .away
+        .after_seconds
+        .filter(|_| !config.pomodoro.enabled)
+    {
+        // Profiles follow the main reminder back from being away
+        match away::check(&SystemClock, &ProcessRunner, after, profile.is_none()) {
+            Presence::Here => {}
+            Presence::Away(since) => return Ok(Outcome::Away(since)),
+            Presence::Back(since) => return Ok(Outcome::Back(since)),
+        }
+    }
+
+    // Phases move on even when a gate below holds their reminder back
+    let pomodoro = if config.pomodoro.enabled {
+        let now = SystemClock.now();
+        let saved = pomodoro::load();
+        // `szmer pomodoro` saves the first phase, this covers a lost one
+        let current = saved.unwrap_or_else(|| Cycle::start(now));
+        let Some(next) = current.advance(now) else {
+            if saved.is_none() {
+                pomodoro::save(&current)?;
+            }
+            return Ok(Outcome::Waiting(current.ends_at()));
+        };
+        pomodoro::save(&next)?;
+        Some(next)
+    } else {
+        None
+    };
+
+    if let Some(gate) = days_gate(config, SystemClock.now().weekday()).filter(|gate| !gate.open) {
+        return Ok(Outcome::Skipped(format!("{}: {}", gate.name, gate.detail)));
+    }
+
+    if let Some(gate) =
+        holidays_gate(config, SystemClock.now().date_naive()).filter(|gate| !gate.open)
+    {
+        return Ok(Outcome::Skipped(format!("{}: {}", gate.name, gate.detail)));
+    }
+
+    if let Some(gate) = hours_gate(config, SystemClock.now().time()).filter(|gate| !gate.open) {
+        return Ok(Outcome::Skipped(format!("{}: {}", gate.name, gate.detail)));
+    }
+
+    if let Some(gate) = run_gates(config).into_iter().find(|gate| !gate.open) {
+        return Ok(Outcome::Skipped(format!("{}: {}", gate.name, gate.detail)));
+    }
+
+    let sound = profile
+        .and_then(|profile| profile.sound.clone())
+        .or_else(|| config.notification_sound.clone());
+    let back_to_work = pomodoro.is_some_and(|cycle| cycle.phase == Phase::Work);
+    let reminder = match pomodoro {
+        Some(cycle) if back_to_work => Reminder {
+            summary: pomodoro::summary(&cycle),
+            body: pomodoro::work_body(&cycle),
+            sound,
+            link: None,
+            kind: Kind::Reminder,
+        },
+        _ => {
+            let mut rng = random::rng();
+            let tip = tips::tip(
+                config,
+                &mut rng,
+                profile.and_then(|profile| profile.category),
+            );
+            let mut reminder = Reminder::with_tip(
+                &mut rng,
+                tip.text,
+                tips::link(config, tip.category),
+                sound,
+                config.notification.tone,
+            );
+            if let Some(cycle) = pomodoro {
+                reminder.summary = pomodoro::summary(&cycle);
+            }
+            reminder
+        }
+    };
+    let channel = NotifierChain::from_config(config).send(&reminder)?;
+    // Only desktop notifications play a sound
+    if channel == "desktop" && !config.ducking.is_empty() {
+        let seconds = if config.ducking.during_break {
+            config.break_duration_seconds
+        } else {
+            ducking::SOUND_SECONDS
+        };
+        if let Err(e) = ducking::duck(&ProcessRunner, &config.ducking, seconds) {
+            eprintln!("Warning: {e}");
+        }
+    }
+    // wall already reaches every terminal, SSH sessions included
+    let delivered_by_wall = session::is_headless(config.headless.mode)
+        && config.headless.method == TerminalMethod::Wall;
+    if config.headless.mirror_ssh && !delivered_by_wall {
+        notification::mirror_to_ssh_sessions(&reminder);
+    }
+    // Heard from across the room, unless the chain already read it out
+    if config.notification.speak && channel != "speech" {
+        if let Err(e) = SpeechNotifier.send(&reminder) {
+            eprintln!("Warning: Failed to read the reminder aloud: {e}");
+        }
+    }
+    // Only breaks count towards the reminder history
+    if back_to_work {
+        return Ok(Outcome::BackToWork);
+    }
+    notification::record_notification();
+
+    Ok(Outcome::Sent)
+}
+
+/// Current verdict of one condition that can hold a reminder back
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Gate {
+    /// Short name of the condition
+    pub name: String,
+    /// Whether a reminder would get through this gate right now
+    pub open: bool,
+    /// Human-readable reason for the verdict
+    pub detail: String,
+}
+
+/// Evaluate every gate `remind` checks, in the same order
+pub fn evaluate_gates(config: &Config) -> Vec<Gate> {
+    let pause = Gate {
+        name: "pause".to_string(),
+        open: !config.paused,
+        detail: if config.paused { "paused" } else { "active" }.to_string(),
+    };
+
+    let mut gates = vec![pause];
+    gates.extend(days_gate(config, SystemClock.now().weekday()));
+    gates.extend(holidays_gate(config, SystemClock.now().date_naive()));
+    gates.extend(hours_gate(config, SystemClock.now().time()));
+    gates.extend(run_gates(config));
+    gates
+}
+
+/// Whether `today` is one of `schedule.days`, `None` when every day is
+fn days_gate(config: &Config, today: Weekday) -> Option<Gate> {
+    if config.schedule.days.is_empty() {
+        return None;
+    }
+
+    let open = config.schedule.is_reminder_day(today);
+    let today = today.to_string().to_lowercase();
+    Some(Gate {
+        name: "days".to_string(),
+        open,
+        detail: if open {
+            format!("{today} is a reminder day")
+        } else {
+            format!("no reminders on {today}")
+        },
+    })
+}
+
+/// Whether `today` is a public holiday, `None` when `schedule.holidays` is off
+fn holidays_gate(config: &Config, today: NaiveDate) -> Option<Gate> {
+    let region = holidays::region(config.schedule.holidays.as_deref()?)?;
+    let holiday = holidays::on(&region, today);
+    Some(Gate {
+        name: "holidays".to_string(),
+        open: holiday.is_none(),
+        detail: match holiday {
+            Some(holiday) => format!("no reminders on {}", holiday.name),
+            None => format!("no {region} holiday today"),
+        },
+    })
+}
+
+/// Whether `now` is within `schedule.work_hours`, `None` when any time is
+fn hours_gate(config: &Config, now: NaiveTime) -> Option<Gate> {
+    let hours = config.schedule.work_hours?;
+    let open = hours.contains(now);
+    Some(Gate {
+        name: "work hours".to_string(),
+        open,
+        detail: if open {
+            format!("inside work hours ({hours})")
+        } else {
+            format!("outside work hours ({hours})")
+        },
+    })
+}
+
+/// A gate check, `None` when it turns out not to apply
+type Check = Box<dyn FnOnce() -> Option<Gate> + Send>;
+
+/// Run the timewarrior and plugin gates concurrently, bounded by the deadline
+///
+/// Each gate runs on its own thread. A gate that hasn't answered within
+/// `delivery.gate_deadline_seconds` counts as open, so one slow network
+/// call can't hold a reminder back. Gates that fail count as open too.
+fn run_gates(config: &Config) -> Vec<Gate> {
+    let deadline = Duration::from_secs(config.delivery.gate_deadline_seconds);
+    let checks = gate_checks(config);
+    let names: Vec<String> = checks.iter().map(|(name, _)| name.clone()).collect();
+
+    let (tx, rx) = mpsc::channel();
+    for (index, (_, check)) in checks.into_iter().enumerate() {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let _ = tx.send((index, check()));
+        });
+    }
+    drop(tx);
+
+    let started = Instant::now();
+    let mut answers: Vec<Option<Option<Gate>>> = names.iter().map(|_| None).collect();
+    while answers.iter().any(Option::is_none) {
+        let remaining = deadline.saturating_sub(started.elapsed());
+        match rx.recv_timeout(remaining) {
+            Ok((index, gate)) => answers[index] = Some(gate),
+            Err(_) => break,
+        }
+    }
+
+    answers
+        .into_iter()
+        .zip(names)
+        .filter_map(|(answer, name)| match answer {
+            Some(gate) => gate,
+            None => {
+                let detail = format!(
+                    "ignored: no answer within {}s",
+                    config.delivery.gate_deadline_seconds
+                );
+                eprintln!("Warning: Gate {name} {detail}");
+                Some(Gate {
+                    name,
+                    open: true,
+                    detail,
+                })
+            }
+        })
+        .collect()
+}
+
+fn gate_checks(config: &Config) -> Vec<(String, Check)> {
+    let timewarrior_config = config.timewarrior.clone();
+    let timewarrior: Check = Box::new(move || {
+        if !timewarrior_config.enabled {
+            return Some(Gate {
+                name: "timewarrior".to_string(),
+                open: true,
+                detail: "disabled".to_string(),
+            });
+        }
+
+        let open = timewarrior::should_send_notification(&timewarrior_config, &ProcessRunner);
+        Some(Gate {
+            name: "timewarrior".to_string(),
+            open,
+            detail: if open {
+                "tracking active"
+            } else {
+                "no active session"
+            }
+            .to_string(),
+        })
+    });
+
+    let mut checks = vec![("timewarrior".to_string(), timewarrior)];
+
+    if !config.suppress.apps.is_empty() {
+        let patterns = config.suppress.apps.clone();
+        let apps: Check = Box::new(move || {
+            let (open, detail) = match focus::frontmost_app(&ProcessRunner) {
+                Some(app) => match focus::matching_pattern(&app, &patterns) {
+                    Some(pattern) => (false, format!("{app} is in front (matches {pattern})")),
+                    None => (true, format!("{app} is in front")),
+                },
+                None => (true, "frontmost app unknown".to_string()),
+            };
+            Some(Gate {
+                name: "apps".to_string(),
+                open,
+                detail,
+            })
+        });
+        checks.push(("apps".to_string(), apps));
+    }
+
+    if !config.suppress.focus_modes.is_empty() {
+        let patterns = config.suppress.focus_modes.clone();
+        let focus: Check = Box::new(move || {
+            let (open, detail) = match focus_mode::active_focus() {
+                Some(focus) if focus::matching_pattern(&focus, &patterns).is_some() => {
+                    (false, format!("{focus} Focus is on"))
+                }
+                Some(focus) => (true, format!("{focus} Focus is on")),
+                None => (true, "no Focus on".to_string()),
+            };
+            Some(Gate {
+                name: "focus".to_string(),
+                open,
+                detail,
+            })
+        });
+        checks.push(("focus".to_string(), focus));
+    }
+
+    if config.suppress.is_presenting_enabled() {
+        let suppress = config.suppress.clone();
+        let presenting: Check = Box::new(move || {
+            let shared = suppress.screen_sharing && presenting::is_screen_shared(&ProcessRunner);
+            let presentation_network = if suppress.networks.is_empty() {
+                None
+            } else {
+                presenting::current_network(&ProcessRunner).filter(|network| {
+                    focus::matching_pattern(network, &suppress.networks).is_some()
+                })
+            };
+
+            let (open, detail) = match (shared, presentation_network) {
+                (true, _) => (false, "screen is being shared".to_string()),
+                (false, Some(network)) => (false, format!("on presentation network {network}")),
+                (false, None) => (true, "not presenting".to_string()),
+            };
+            Some(Gate {
+                name: "presenting".to_string(),
+                open,
+                detail,
+            })
+        });
+        checks.push(("presenting".to_string(), presenting));
+    }
+
+    // Plugins describe themselves inside the check, that can be slow too
+    for plugin in plugin::enabled(config) {
+        let config = config.clone();
+        let name = format!("plugin:{}", plugin.name);
+        let gate_name = name.clone();
+        let check: Check = Box::new(move || {
+            if !plugin.has_role(Role::Gate, &ProcessRunner) {
+                return None;
+            }
+
+            let (open, detail) = match plugin.gate(&ProcessRunner, &config) {
+                Ok(verdict) => (verdict.open, verdict.detail),
+                Err(e) => {
+                    eprintln!("Warning: {e}");
+                    (true, format!("ignored: {e}"))
+                }
+            };
+            Some(Gate {
+                name: gate_name,
+                open,
+                detail,
+            })
+        });
+        checks.push((name, check));
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_checks_skip_apps_without_patterns() {
+        let mut config = Config::default();
+        config.suppress.focus_modes = vec!["Work".to_string()];
+
+        let names: Vec<String> = gate_checks(&config)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert!(!names.contains(&"apps".to_string()));
+    }
+}
@@ -1,16 +1,25 @@
-use chrono::{DateTime, Duration, Local};
+use chrono::{DateTime, Local};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 #[cfg(target_os = "linux")]
 use crate::time::parse_next_run_timestamp;
 
-#[cfg(target_os = "macos")]
-use crate::{config::Config, timestamp};
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+use crate::time::cron_schedules;
+
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "openbsd"))]
+use {crate::timestamp, chrono::Duration};
+
+use crate::clock::SystemClock;
+use crate::command::{run_checked, spawn_after, ProcessRunner, SystemCommandRunner};
+use crate::config::{BreakProfile, TimerClock};
+use crate::error::SzmerError;
+use crate::i18n::tr_with;
+use crate::time::format_every;
+use crate::{config::Config, drift};
 
-#[cfg(target_os = "macos")]
 const SERVICE_LABEL: &str = "com.michalczmiel.szmer";
 #[cfg(target_os = "macos")]
 const SERVICE_FILENAME: &str = "com.michalczmiel.szmer.plist";
@@ -18,15 +27,44 @@ const SERVICE_FILENAME: &str = "com.michalczmiel.szmer.plist";
 #[cfg(target_os = "linux")]
 const SERVICE_FILENAME: &str = "szmer.service";
 
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+const SERVICE_FILENAME: &str = "szmer.cron";
+
+/// The system scheduler running `szmer notify`, for messages
+#[cfg(target_os = "macos")]
+pub const NAME: &str = "launchd";
+#[cfg(target_os = "linux")]
+pub const NAME: &str = "systemd";
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+pub const NAME: &str = "cron";
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+pub const NAME: &str = "The scheduler";
+/// Whether the system scheduler can run on elapsed time, see `schedule.clock`
+pub const SUPPORTS_MONOTONIC: bool = cfg!(target_os = "linux");
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+const CRONTAB_MARKER: &str = "# szmer break reminder";
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+const PROFILE_CRONTAB_MARKER: &str = "# szmer break profile";
+
 /// Install the scheduler to run break reminders at the specified interval
-pub fn install(interval_seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// Each break profile gets a scheduler entry of its own next to the main one.
+pub fn install(
+    interval_seconds: u64,
+    clock: TimerClock,
+    profiles: &[BreakProfile],
+) -> Result<(), Box<dyn std::error::Error>> {
     let service_path = get_service_path()?;
 
     if service_path.exists() {
-        return Err(format!(
-            "Service is already installed at {}. Run 'uninstall' first if you want to reinstall.",
-            service_path.display()
-        )
+        return Err(SzmerError::AlreadyInstalled {
+            path: Some(service_path),
+        }
         .into());
     }
 
@@ -36,18 +74,26 @@ pub fn install(interval_seconds: u64) -> Result<(), Box<dyn std::error::Error>>
         fs::create_dir_all(parent)?;
     }
 
-    let service_content = generate_service_file(&binary_path, interval_seconds);
+    let service_content = generate_service_file(&binary_path, interval_seconds, clock);
     fs::write(&service_path, service_content)?;
 
     println!("Created service file at: {}", service_path.display());
 
-    load_service(&service_path, interval_seconds)?;
+    load_service(&ProcessRunner, &service_path, interval_seconds, clock)?;
+    record_intent(interval_seconds);
+    load_profiles(&ProcessRunner, &profile_dir()?, &binary_path, profiles)?;
 
     println!("✓ Break reminder installed successfully!");
     println!(
-        "You will receive break reminders every {} minutes.",
-        interval_seconds / 60
+        "{}",
+        tr_with(
+            "install-reminders-every",
+            &[("every", format_every(interval_seconds).into())]
+        )
     );
+    for profile in profiles {
+        println!("Break profile {}: {profile}", profile.name);
+    }
     println!("\nNote: Do not move or delete the binary at: {binary_path}");
     println!("To uninstall, run: szmer uninstall");
 
@@ -55,6 +101,15 @@ pub fn install(interval_seconds: u64) -> Result<(), Box<dyn std::error::Error>>
 }
 
 fn get_binary_path() -> Result<String, Box<dyn std::error::Error>> {
+    // Run from the app bundle when present so notifications keep its identity
+    #[cfg(target_os = "macos")]
+    if let Some(path) = crate::bundle::executable_path() {
+        return path
+            .to_str()
+            .ok_or_else(|| "Failed to convert binary path to string".into())
+            .map(String::from);
+    }
+
     env::current_exe()?
         .canonicalize()?
         .to_str()
@@ -71,8 +126,12 @@ pub fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if let Err(e) = unload_profiles(&ProcessRunner, &profile_dir()?) {
+        eprintln!("Warning: Failed to remove break profiles: {e}");
+    }
+
     // Unload/disable the service
-    if let Err(e) = unload_service(&service_path) {
+    if let Err(e) = unload_service(&ProcessRunner, &service_path) {
         eprintln!("Warning: Failed to unload service: {e}");
         eprintln!("Continuing with service file removal...");
     }
@@ -85,6 +144,140 @@ pub fn uninstall() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Regenerate the installed service file and reload it so changes apply immediately
+pub fn reload(binary_path: &str, interval_seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let service_path = get_service_path()?;
+
+    if !service_path.exists() {
+        return Err(SzmerError::NotInstalled.into());
+    }
+
+    if let Err(e) = unload_service(&ProcessRunner, &service_path) {
+        eprintln!("Warning: Failed to unload service: {e}");
+    }
+
+    let config = Config::load()?;
+    let service_content =
+        generate_service_file(binary_path, interval_seconds, config.schedule.clock);
+    fs::write(&service_path, service_content)?;
+
+    load_service(
+        &ProcessRunner,
+        &service_path,
+        interval_seconds,
+        config.schedule.clock,
+    )?;
+    record_intent(interval_seconds);
+    reload_profiles_with(binary_path, &config.profiles)
+}
+
+/// Note the new schedule so `szmer doctor` can tell how well it's kept
+fn record_intent(interval_seconds: u64) {
+    if let Err(e) = drift::record_intent(&SystemClock, interval_seconds) {
+        eprintln!("Warning: Failed to record the schedule: {e}");
+    }
+}
+
+/// Replace the installed break profile entries with `profiles`
+pub fn reload_profiles(profiles: &[BreakProfile]) -> Result<(), Box<dyn std::error::Error>> {
+    if !is_installed() {
+        return Err(SzmerError::NotInstalled.into());
+    }
+
+    reload_profiles_with(&get_binary_path()?, profiles)
+}
+
+fn reload_profiles_with(
+    binary_path: &str,
+    profiles: &[BreakProfile],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = profile_dir()?;
+    unload_profiles(&ProcessRunner, &dir)?;
+    load_profiles(&ProcessRunner, &dir, binary_path, profiles)
+}
+
+/// Directory break profile entries are written to, next to the main one
+fn profile_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    get_service_path()?
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Service path has no parent directory".into())
+}
+
+/// systemd unit name of a break profile, without the extension
+pub fn profile_unit(name: &str) -> String {
+    format!("szmer-profile-{name}")
+}
+
+/// launchd label of a break profile
+pub fn profile_label(name: &str) -> String {
+    format!("{SERVICE_LABEL}.profile.{name}")
+}
+
+/// Break profile entries in `dir`, found by name so removed profiles go too
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn installed_profile_files(dir: &Path, prefix: &str, extension: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == extension)
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(prefix))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Reload the installed scheduler with a new interval, keeping the binary it runs
+pub fn reschedule(interval_seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
+    reload(&get_binary_path()?, interval_seconds)
+}
+
+/// Run `szmer notify` once after `seconds`, for a snoozed reminder
+///
+/// launchd, systemd and cron only know the regular interval, so a
+/// detached `sleep` delivers the delayed reminder.
+pub fn notify_after(seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
+    spawn_after(seconds, &get_binary_path()?, &["notify"])
+}
+
+/// Scheduler files szmer wrote that are on disk: the main entry and its
+/// timer, and those of break profiles
+///
+/// Crontab entries on the BSDs aren't files, so only the service file
+/// kept next to the config is listed there.
+pub fn installed_files() -> Vec<PathBuf> {
+    let Ok(service_path) = get_service_path() else {
+        return Vec::new();
+    };
+    let mut files = vec![service_path.clone()];
+
+    #[cfg(target_os = "linux")]
+    {
+        files.push(service_path.with_extension("timer"));
+        if let Ok(dir) = profile_dir() {
+            for extension in ["service", "timer"] {
+                files.extend(installed_profile_files(&dir, &profile_unit(""), extension));
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Ok(dir) = profile_dir() {
+        let prefix = format!("{SERVICE_LABEL}.profile.");
+        files.extend(installed_profile_files(&dir, &prefix, "plist"));
+    }
+
+    files.retain(|path| path.exists());
+    files
+}
+
 /// Check if the scheduler is installed
 pub fn is_installed() -> bool {
     get_service_path().ok().is_some_and(|p| p.exists())
@@ -93,10 +286,10 @@ pub fn is_installed() -> bool {
 /// Get the scheduler status including next run time if available
 pub fn get_scheduler_status() -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
     if !is_installed() {
-        return Err("Scheduler is not installed".into());
+        return Err(SzmerError::NotInstalled.into());
     }
 
-    get_scheduler_status_impl()
+    get_scheduler_status_impl(&ProcessRunner)
 }
 
 #[derive(Debug)]
@@ -105,40 +298,77 @@ pub struct SchedulerStatus {
     pub next_run: Option<DateTime<Local>>,
 }
 
-// Platform-specific implementations
+// Service file renderers, available on every platform for `generate-unit`
 
-#[cfg(target_os = "macos")]
-fn get_service_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let home = env::var("HOME")?;
-    Ok(PathBuf::from(home)
-        .join("Library")
-        .join("LaunchAgents")
-        .join(SERVICE_FILENAME))
+/// Render the launchd agent plist running `szmer notify` every interval
+pub fn render_launchd_plist(binary_path: &str, interval_seconds: u64) -> String {
+    render_notify_plist(SERVICE_LABEL, binary_path, None, interval_seconds)
 }
 
-#[cfg(target_os = "linux")]
-fn get_service_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let home = env::var("HOME")?;
-    Ok(PathBuf::from(home)
-        .join(".config")
-        .join("systemd")
-        .join("user")
-        .join(SERVICE_FILENAME))
+/// Render the launchd agent plist running a break profile's reminders
+pub fn render_profile_launchd_plist(binary_path: &str, profile: &BreakProfile) -> String {
+    render_notify_plist(
+        &profile_label(&profile.name),
+        binary_path,
+        Some(&profile.name),
+        profile.interval_seconds,
+    )
 }
 
-#[cfg(target_os = "macos")]
-fn generate_service_file(binary_path: &str, interval_seconds: u64) -> String {
+/// Escape a value for a plist `<string>` element
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Quote a single argument for a systemd `ExecStart=` line
+///
+/// systemd splits the line on whitespace and expands `%` specifiers and
+/// `$` variables, so anything beyond a plain word is double quoted with
+/// those characters escaped.
+pub fn systemd_arg(arg: &str) -> String {
+    let escaped = arg.replace('%', "%%").replace('$', "$$");
+    let plain = !escaped.is_empty()
+        && !escaped
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';'));
+    if plain {
+        escaped
+    } else {
+        format!("\"{}\"", escaped.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+fn render_notify_plist(
+    label: &str,
+    binary_path: &str,
+    profile: Option<&str>,
+    interval_seconds: u64,
+) -> String {
+    let profile_args = match profile {
+        Some(name) => format!(
+            "\n        <string>--profile</string>\n        <string>{}</string>",
+            xml_escape(name)
+        ),
+        None => String::new(),
+    };
+    let label = xml_escape(label);
+    let binary_path = xml_escape(binary_path);
     format!(
         r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
 <plist version="1.0">
 <dict>
     <key>Label</key>
-    <string>{SERVICE_LABEL}</string>
+    <string>{label}</string>
     <key>ProgramArguments</key>
     <array>
         <string>{binary_path}</string>
-        <string>notify</string>
+        <string>notify</string>{profile_args}
     </array>
     <key>StartInterval</key>
     <integer>{interval_seconds}</integer>
@@ -154,66 +384,237 @@ fn generate_service_file(binary_path: &str, interval_seconds: u64) -> String {
     )
 }
 
-#[cfg(target_os = "linux")]
-fn generate_service_file(binary_path: &str, _interval_seconds: u64) -> String {
+/// Render the systemd user service running `szmer notify`
+///
+/// On the monotonic clock the service restarts the timer once it's done,
+/// however the run went, so each run arms the next one.
+pub fn render_systemd_service(binary_path: &str, clock: TimerClock) -> String {
+    render_notify_service(
+        "Szmer break reminder",
+        &format!("{} notify", systemd_arg(binary_path)),
+        (clock == TimerClock::Monotonic).then_some("szmer.timer"),
+    )
+}
+
+/// Render the systemd user service running a break profile's reminders
+pub fn render_profile_systemd_service(binary_path: &str, profile: &BreakProfile) -> String {
+    render_notify_service(
+        &format!("Szmer {} break reminder", profile.name),
+        &format!(
+            "{} notify --profile {}",
+            systemd_arg(binary_path),
+            systemd_arg(&profile.name)
+        ),
+        None,
+    )
+}
+
+fn render_notify_service(description: &str, command: &str, rearm: Option<&str>) -> String {
+    let rearm = rearm
+        .map(|timer| format!("ExecStopPost=systemctl --user restart {timer}\n"))
+        .unwrap_or_default();
     format!(
         r#"[Unit]
-Description=Szmer break reminder
+Description={description}
 After=default.target
 
 [Service]
 Type=oneshot
-ExecStart={} notify
-
+ExecStart={command}
+{rearm}
 [Install]
 WantedBy=default.target
-"#,
-        binary_path
+"#
+    )
+}
+
+/// Render the systemd user timer triggering the service every interval
+///
+/// On the monotonic clock the timer fires once, an interval after it was
+/// (re)started, and the service starts it again. It names the service with
+/// `Unit=` rather than `Requires=`, which would start the service with every
+/// restart of the timer and so loop. Nothing is caught up after a shutdown,
+/// as `Persistent=` only applies to calendar timers.
+pub fn render_systemd_timer(interval_seconds: u64, clock: TimerClock) -> String {
+    match clock {
+        TimerClock::Wall => {
+            render_notify_timer("Szmer break reminder timer", "szmer", interval_seconds)
+        }
+        TimerClock::Monotonic => format!(
+            r#"[Unit]
+Description=Szmer break reminder timer
+
+[Timer]
+OnActiveSec={interval_seconds}
+Unit=szmer.service
+
+[Install]
+WantedBy=timers.target
+"#
+        ),
+    }
+}
+
+/// Render the systemd user timer triggering a break profile's service
+pub fn render_profile_systemd_timer(profile: &BreakProfile) -> String {
+    render_notify_timer(
+        &format!("Szmer {} break reminder timer", profile.name),
+        &profile_unit(&profile.name),
+        profile.interval_seconds,
     )
 }
 
+fn render_notify_timer(description: &str, unit: &str, interval_seconds: u64) -> String {
+    format!(
+        r#"[Unit]
+Description={description}
+Requires={unit}.service
+
+[Timer]
+OnBootSec={interval_seconds}
+OnUnitActiveSec={interval_seconds}
+Persistent=true
+
+[Install]
+WantedBy=timers.target
+"#
+    )
+}
+
+/// Render a launchd agent keeping `szmer daemon` alive
+///
+/// `KeepAlive` with `SuccessfulExit` false makes launchd restart the daemon
+/// after a crash, while a clean exit stays stopped. The agent only loads in
+/// Aqua (GUI) sessions, not for SSH or other background logins.
+pub fn render_daemon_launchd_plist(binary_path: &str) -> String {
+    let binary_path = xml_escape(binary_path);
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{SERVICE_LABEL}.daemon</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{binary_path}</string>
+        <string>daemon</string>
+    </array>
+    <key>LimitLoadToSessionType</key>
+    <string>Aqua</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>ThrottleInterval</key>
+    <integer>30</integer>
+    <key>StandardOutPath</key>
+    <string>/tmp/szmer-daemon.log</string>
+    <key>StandardErrorPath</key>
+    <string>/tmp/szmer-daemon.err</string>
+</dict>
+</plist>
+"#
+    )
+}
+
+/// Render a systemd user service running `szmer daemon` under a watchdog
+///
+/// The daemon reports readiness and pings the watchdog through sd_notify,
+/// so systemd restarts it if it crashes or its main loop hangs. It is bound
+/// to `graphical-session.target`, so it starts and stops with the desktop
+/// session rather than running on headless logins.
+pub fn render_daemon_systemd_service(binary_path: &str) -> String {
+    let binary_path = systemd_arg(binary_path);
+    format!(
+        r#"[Unit]
+Description=Szmer break reminder daemon
+PartOf=graphical-session.target
+After=graphical-session.target
+Requisite=graphical-session.target
+
+[Service]
+Type=notify
+ExecStart={binary_path} daemon
+Restart=on-failure
+RestartSec=10
+WatchdogSec=180
+
+[Install]
+WantedBy=graphical-session.target
+"#
+    )
+}
+
+// Platform-specific implementations
+
+#[cfg(target_os = "macos")]
+fn get_service_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let home = env::var("HOME")?;
+    Ok(PathBuf::from(home)
+        .join("Library")
+        .join("LaunchAgents")
+        .join(SERVICE_FILENAME))
+}
+
+#[cfg(target_os = "linux")]
+fn get_service_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let home = env::var("HOME")?;
+    Ok(PathBuf::from(home)
+        .join(".config")
+        .join("systemd")
+        .join("user")
+        .join(SERVICE_FILENAME))
+}
+
+#[cfg(target_os = "macos")]
+fn generate_service_file(binary_path: &str, interval_seconds: u64, _clock: TimerClock) -> String {
+    render_launchd_plist(binary_path, interval_seconds)
+}
+
+#[cfg(target_os = "linux")]
+fn generate_service_file(binary_path: &str, _interval_seconds: u64, clock: TimerClock) -> String {
+    render_systemd_service(binary_path, clock)
+}
+
 #[cfg(target_os = "macos")]
 fn load_service(
+    runner: &dyn SystemCommandRunner,
     service_path: &Path,
     _interval_seconds: u64,
+    _clock: TimerClock,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    run_command(
+    run_checked(
+        runner,
         "launchctl",
         &["load", service_path.to_str().unwrap()],
         "Failed to load launchd agent",
-    )
+    )?;
+    Ok(())
 }
 
 #[cfg(target_os = "linux")]
 fn load_service(
+    runner: &dyn SystemCommandRunner,
     service_path: &Path,
     interval_seconds: u64,
+    clock: TimerClock,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    run_command(
+    run_checked(
+        runner,
         "systemctl",
         &["--user", "daemon-reload"],
         "Failed to reload systemd",
     )?;
 
     let timer_path = service_path.with_extension("timer");
-    let timer_content = format!(
-        r#"[Unit]
-Description=Szmer break reminder timer
-Requires=szmer.service
-
-[Timer]
-OnBootSec={}
-OnUnitActiveSec={}
-Persistent=true
-
-[Install]
-WantedBy=timers.target
-"#,
-        interval_seconds, interval_seconds
-    );
-    fs::write(&timer_path, timer_content)?;
+    fs::write(&timer_path, render_systemd_timer(interval_seconds, clock))?;
 
-    run_command(
+    run_checked(
+        runner,
         "systemctl",
         &["--user", "enable", "--now", "szmer.timer"],
         "Failed to enable systemd timer",
@@ -223,71 +624,383 @@ WantedBy=timers.target
 }
 
 #[cfg(target_os = "macos")]
-fn unload_service(service_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    run_command(
+fn unload_service(
+    runner: &dyn SystemCommandRunner,
+    service_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    run_checked(
+        runner,
         "launchctl",
         &["unload", service_path.to_str().unwrap()],
         "launchctl unload failed",
-    )
+    )?;
+    Ok(())
 }
 
 #[cfg(target_os = "linux")]
-fn unload_service(service_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    run_command(
+fn unload_service(
+    runner: &dyn SystemCommandRunner,
+    service_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let disabled = run_checked(
+        runner,
         "systemctl",
         &["--user", "disable", "--now", "szmer.timer"],
         "Failed to disable systemd timer",
-    )?;
+    );
 
+    // Remove the timer even when systemd couldn't disable it, so it doesn't linger
     let timer_path = service_path.with_extension("timer");
     if timer_path.exists() {
         fs::remove_file(timer_path)?;
     }
+    disabled?;
+
+    runner.run("systemctl", &["--user", "daemon-reload"])?;
+
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn load_profiles(
+    runner: &dyn SystemCommandRunner,
+    dir: &Path,
+    binary_path: &str,
+    profiles: &[BreakProfile],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for profile in profiles {
+        let path = dir.join(format!("{}.plist", profile_label(&profile.name)));
+        fs::write(&path, render_profile_launchd_plist(binary_path, profile))?;
+        run_checked(
+            runner,
+            "launchctl",
+            &["load", path.to_str().unwrap()],
+            "Failed to load launchd agent",
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn unload_profiles(
+    runner: &dyn SystemCommandRunner,
+    dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let prefix = format!("{SERVICE_LABEL}.profile.");
+    for path in installed_profile_files(dir, &prefix, "plist") {
+        if let Err(e) = unload_service(runner, &path) {
+            eprintln!("Warning: Failed to unload {}: {e}", path.display());
+        }
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn load_profiles(
+    runner: &dyn SystemCommandRunner,
+    dir: &Path,
+    binary_path: &str,
+    profiles: &[BreakProfile],
+) -> Result<(), Box<dyn std::error::Error>> {
+    if profiles.is_empty() {
+        return Ok(());
+    }
+
+    for profile in profiles {
+        let unit = profile_unit(&profile.name);
+        fs::write(
+            dir.join(format!("{unit}.service")),
+            render_profile_systemd_service(binary_path, profile),
+        )?;
+        fs::write(
+            dir.join(format!("{unit}.timer")),
+            render_profile_systemd_timer(profile),
+        )?;
+    }
+
+    run_checked(
+        runner,
+        "systemctl",
+        &["--user", "daemon-reload"],
+        "Failed to reload systemd",
+    )?;
+
+    for profile in profiles {
+        let timer = format!("{}.timer", profile_unit(&profile.name));
+        run_checked(
+            runner,
+            "systemctl",
+            &["--user", "enable", "--now", &timer],
+            "Failed to enable systemd timer",
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn unload_profiles(
+    runner: &dyn SystemCommandRunner,
+    dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let timers = installed_profile_files(dir, &profile_unit(""), "timer");
+    if timers.is_empty() {
+        return Ok(());
+    }
+
+    for timer in &timers {
+        let name = timer
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if let Err(e) = run_checked(
+            runner,
+            "systemctl",
+            &["--user", "disable", "--now", name],
+            "Failed to disable systemd timer",
+        ) {
+            eprintln!("Warning: {e}");
+        }
+        fs::remove_file(timer)?;
+        let service = timer.with_extension("service");
+        if service.exists() {
+            fs::remove_file(service)?;
+        }
+    }
+
+    runner.run("systemctl", &["--user", "daemon-reload"])?;
+
+    Ok(())
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn get_service_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let home = env::var("HOME")?;
+    Ok(PathBuf::from(home)
+        .join(".config")
+        .join("szmer")
+        .join(SERVICE_FILENAME))
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn generate_service_file(binary_path: &str, interval_seconds: u64, _clock: TimerClock) -> String {
+    let command = cron_command(binary_path, "notify");
+    cron_schedules(interval_seconds)
+        .iter()
+        .map(|schedule| format!("{schedule} {command} {CRONTAB_MARKER}\n"))
+        .collect()
+}
+
+/// `szmer <args>` as a cron job command
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn cron_command(binary_path: &str, args: &str) -> String {
+    // cron starts jobs with an empty environment, so carry over the session
+    // variables notify-send style notifications need to reach the desktop
+    let session_env: Vec<String> = ["DISPLAY", "DBUS_SESSION_BUS_ADDRESS"]
+        .iter()
+        .filter_map(|key| env::var(key).ok().map(|value| format!("{key}={value}")))
+        .collect();
+
+    // cron hands the line to sh, so quote the path in case it has spaces
+    let binary_path = format!("'{}'", binary_path.replace('\'', "'\\''"));
+    if session_env.is_empty() {
+        format!("{binary_path} {args}")
+    } else {
+        format!("env {} {binary_path} {args}", session_env.join(" "))
+    }
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn load_service(
+    runner: &dyn SystemCommandRunner,
+    service_path: &Path,
+    _interval_seconds: u64,
+    _clock: TimerClock,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let entry = fs::read_to_string(service_path)?;
+    let mut crontab = read_crontab_without(runner, CRONTAB_MARKER)?;
+    crontab.push_str(&entry);
+    write_crontab(runner, &crontab)
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn unload_service(
+    runner: &dyn SystemCommandRunner,
+    _service_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let crontab = read_crontab_without(runner, CRONTAB_MARKER)?;
+    write_crontab(runner, &crontab)
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn load_profiles(
+    runner: &dyn SystemCommandRunner,
+    _dir: &Path,
+    binary_path: &str,
+    profiles: &[BreakProfile],
+) -> Result<(), Box<dyn std::error::Error>> {
+    if profiles.is_empty() {
+        return Ok(());
+    }
 
-    Command::new("systemctl")
-        .arg("--user")
-        .arg("daemon-reload")
-        .output()?;
+    let mut crontab = read_crontab_without(runner, PROFILE_CRONTAB_MARKER)?;
+    for profile in profiles {
+        let command = cron_command(binary_path, &format!("notify --profile {}", profile.name));
+        for schedule in cron_schedules(profile.interval_seconds) {
+            crontab.push_str(&format!("{schedule} {command} {PROFILE_CRONTAB_MARKER}\n"));
+        }
+    }
+    write_crontab(runner, &crontab)
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn unload_profiles(
+    runner: &dyn SystemCommandRunner,
+    _dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let crontab = read_crontab_without(runner, PROFILE_CRONTAB_MARKER)?;
+    write_crontab(runner, &crontab)
+}
+
+/// Read the user's crontab, dropping the entries szmer added with `marker`
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn read_crontab_without(
+    runner: &dyn SystemCommandRunner,
+    marker: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let output = runner.run("crontab", &["-l"])?;
+
+    // `crontab -l` fails when the user has no crontab yet
+    if !output.success {
+        return Ok(String::new());
+    }
+
+    Ok(output
+        .stdout
+        .lines()
+        .filter(|line| !line.contains(marker))
+        .map(|line| format!("{line}\n"))
+        .collect())
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn write_crontab(
+    runner: &dyn SystemCommandRunner,
+    content: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = runner.run_with_input("crontab", &["-"], content)?;
+
+    if !output.success {
+        return Err(format!("Failed to update crontab: {}", output.stderr).into());
+    }
 
     Ok(())
 }
 
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
 fn get_service_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    Err("Scheduling is not supported on this platform".into())
+    Err(SzmerError::Unsupported {
+        feature: "Scheduling",
+    }
+    .into())
 }
 
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
-fn generate_service_file(_binary_path: &str, _interval_seconds: u64) -> String {
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+fn generate_service_file(_binary_path: &str, _interval_seconds: u64, _clock: TimerClock) -> String {
     String::new()
 }
 
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
 fn load_service(
+    _runner: &dyn SystemCommandRunner,
     _service_path: &Path,
     _interval_seconds: u64,
+    _clock: TimerClock,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    Err("Scheduling is not supported on this platform".into())
+    Err(SzmerError::Unsupported {
+        feature: "Scheduling",
+    }
+    .into())
 }
 
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
-fn unload_service(_service_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    Err("Scheduling is not supported on this platform".into())
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+fn unload_service(
+    _runner: &dyn SystemCommandRunner,
+    _service_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(SzmerError::Unsupported {
+        feature: "Scheduling",
+    }
+    .into())
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+fn load_profiles(
+    _runner: &dyn SystemCommandRunner,
+    _dir: &Path,
+    _binary_path: &str,
+    profiles: &[BreakProfile],
+) -> Result<(), Box<dyn std::error::Error>> {
+    if profiles.is_empty() {
+        return Ok(());
+    }
+    Err(SzmerError::Unsupported {
+        feature: "Scheduling",
+    }
+    .into())
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+fn unload_profiles(
+    _runner: &dyn SystemCommandRunner,
+    _dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Ok(())
 }
 
 #[cfg(target_os = "macos")]
-fn get_scheduler_status_impl() -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
+fn get_scheduler_status_impl(
+    runner: &dyn SystemCommandRunner,
+) -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
     // Check if the launchd job is loaded/running
-    let output = Command::new("launchctl")
-        .arg("list")
-        .arg(SERVICE_LABEL)
-        .output()?;
+    let output = runner.run("launchctl", &["list", SERVICE_LABEL])?;
 
-    let is_running = output.status.success();
+    let is_running = output.success;
 
     // Calculate next run time based on last notification timestamp
     let next_run = if is_running {
-        calculate_next_run_macos()?
+        calculate_next_run_from_last_notification()?
     } else {
         None
     };
@@ -298,8 +1011,10 @@ fn get_scheduler_status_impl() -> Result<SchedulerStatus, Box<dyn std::error::Er
     })
 }
 
-#[cfg(target_os = "macos")]
-fn calculate_next_run_macos() -> Result<Option<DateTime<Local>>, Box<dyn std::error::Error>> {
+/// Estimate the next run as the last recorded notification plus the configured interval
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "openbsd"))]
+fn calculate_next_run_from_last_notification(
+) -> Result<Option<DateTime<Local>>, Box<dyn std::error::Error>> {
     let last_notification = timestamp::get_last_notification()?;
 
     match last_notification {
@@ -314,16 +1029,14 @@ fn calculate_next_run_macos() -> Result<Option<DateTime<Local>>, Box<dyn std::er
 }
 
 #[cfg(target_os = "linux")]
-fn get_scheduler_status_impl() -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
-    let status_output = Command::new("systemctl")
-        .arg("--user")
-        .arg("is-active")
-        .arg("szmer.timer")
-        .output()?;
-
-    let is_running = status_output.status.success();
+fn get_scheduler_status_impl(
+    runner: &dyn SystemCommandRunner,
+) -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
+    let status_output = runner.run("systemctl", &["--user", "is-active", "szmer.timer"])?;
+
+    let is_running = status_output.success;
     let next_run = if is_running {
-        get_next_run_time()?
+        get_next_run_time(runner)?
     } else {
         None
     };
@@ -335,41 +1048,137 @@ fn get_scheduler_status_impl() -> Result<SchedulerStatus, Box<dyn std::error::Er
 }
 
 #[cfg(target_os = "linux")]
-fn get_next_run_time() -> Result<Option<DateTime<Local>>, Box<dyn std::error::Error>> {
-    let output = Command::new("systemctl")
-        .arg("--user")
-        .arg("show")
-        .arg("szmer.timer")
-        .arg("-p")
-        .arg("NextElapseUSecRealtime")
-        .output()?;
-
-    if !output.status.success() {
+fn get_next_run_time(
+    runner: &dyn SystemCommandRunner,
+) -> Result<Option<DateTime<Local>>, Box<dyn std::error::Error>> {
+    let output = runner.run(
+        "systemctl",
+        &[
+            "--user",
+            "show",
+            "szmer.timer",
+            "-p",
+            "NextElapseUSecRealtime",
+        ],
+    )?;
+
+    if !output.success {
         return Ok(None);
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    parse_next_run_timestamp(&output_str)
+    parse_next_run_timestamp(&output.stdout)
 }
 
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+fn get_scheduler_status_impl(
+    runner: &dyn SystemCommandRunner,
+) -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
+    let output = runner.run("crontab", &["-l"])?;
+
+    let is_running = output.success && output.stdout.contains(CRONTAB_MARKER);
 
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
-fn get_scheduler_status_impl() -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
-    Err("Scheduling is not supported on this platform".into())
+    let next_run = if is_running {
+        calculate_next_run_from_last_notification()?
+    } else {
+        None
+    };
+
+    Ok(SchedulerStatus {
+        is_running,
+        next_run,
+    })
 }
 
-/// Helper function to run a command and return a formatted error if it fails
-fn run_command(
-    program: &str,
-    args: &[&str],
-    error_prefix: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let output = Command::new(program).args(args).output()?;
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "freebsd",
+    target_os = "openbsd"
+)))]
+fn get_scheduler_status_impl(
+    _runner: &dyn SystemCommandRunner,
+) -> Result<SchedulerStatus, Box<dyn std::error::Error>> {
+    Err(SzmerError::Unsupported {
+        feature: "Scheduling",
+    }
+    .into())
+}
 
-    if !output.status.success() {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("{error_prefix}: {error_msg}").into());
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+
+    #[test]
+    fn test_scheduler_status_reads_next_run_from_systemd() {
+        let runner = RecordingRunner::default()
+            .reply("systemctl --user is-active szmer.timer", true, "active\n")
+            .reply(
+                "systemctl --user show szmer.timer -p NextElapseUSecRealtime",
+                true,
+                "NextElapseUSecRealtime=1760970600000000\n",
+            );
+
+        let status = get_scheduler_status_impl(&runner).unwrap();
+        assert!(status.is_running);
+        assert_eq!(status.next_run.unwrap().timestamp(), 1_760_970_600);
     }
 
-    Ok(())
+    #[test]
+    fn test_scheduler_status_inactive_timer() {
+        let runner = RecordingRunner::default().reply(
+            "systemctl --user is-active szmer.timer",
+            false,
+            "inactive\n",
+        );
+
+        let status = get_scheduler_status_impl(&runner).unwrap();
+        assert!(!status.is_running);
+        assert!(status.next_run.is_none());
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[test]
+    fn test_unload_service_disables_timer_then_reloads() {
+        let runner = RecordingRunner::default()
+            .reply("systemctl --user disable --now szmer.timer", true, "")
+            .reply("systemctl --user daemon-reload", true, "");
+
+        unload_service(&runner, Path::new("/nonexistent/szmer.service")).unwrap();
+        assert_eq!(
+            runner.calls(),
+            [
+                "systemctl --user disable --now szmer.timer",
+                "systemctl --user daemon-reload"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unload_service_reports_systemctl_failure() {
+        let runner = RecordingRunner::default().reply(
+            "systemctl --user disable --now szmer.timer",
+            false,
+            "",
+        );
+
+        let error = unload_service(&runner, Path::new("/nonexistent/szmer.service")).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Failed to disable systemd timer"));
+    }
+
+    #[test]
+    fn test_monotonic_timer_rearms_without_starting_the_service() {
+        let service = render_systemd_service("/usr/bin/szmer", TimerClock::Monotonic);
+        assert!(service.contains("ExecStopPost=systemctl --user restart szmer.timer\n"));
+
+        // Requires= would start the service with each restart of the timer
+        let timer = render_systemd_timer(1500, TimerClock::Monotonic);
+        assert!(!timer.contains("Requires="));
+        assert!(timer.contains("OnActiveSec=1500\nUnit=szmer.service\n"));
+
+        let service = render_systemd_service("/usr/bin/szmer", TimerClock::Wall);
+        assert!(!service.contains("ExecStopPost="));
+    }
 }
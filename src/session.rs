@@ -0,0 +1,395 @@
+use crate::command::SystemCommandRunner;
+use crate::config::HeadlessMode;
+use crate::gsettings;
+use std::env;
+use std::fmt;
+use std::path::Path;
+
+/// Environment variables set by common CI providers
+const CI_ENV_VARS: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "JENKINS_URL",
+];
+
+/// Kind of graphical session szmer is running under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionType {
+    /// macOS graphical login session
+    Aqua,
+    /// Wayland compositor session
+    Wayland,
+    /// X11 (Xorg) session
+    X11,
+    /// Text console without a graphical session
+    Tty,
+    /// Session type could not be determined
+    Unknown,
+}
+
+impl SessionType {
+    /// Whether the session has a display server that can show windows
+    pub fn is_graphical(self) -> bool {
+        matches!(self, Self::Aqua | Self::Wayland | Self::X11)
+    }
+}
+
+impl fmt::Display for SessionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Aqua => "Aqua",
+            Self::Wayland => "Wayland",
+            Self::X11 => "X11",
+            Self::Tty => "TTY",
+            Self::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Detect the session type of the current process
+pub fn detect() -> SessionType {
+    if cfg!(target_os = "macos") {
+        return SessionType::Aqua;
+    }
+
+    detect_from_env(|key| env::var(key).ok())
+}
+
+/// Detect the session type from environment variables
+///
+/// `XDG_SESSION_TYPE` is trusted first, then the presence of the Wayland and
+/// X11 display variables.
+///
+/// # Examples
+///
+/// ```
+/// let session = detect_from_env(|key| (key == "WAYLAND_DISPLAY").then(|| "wayland-0".into()));
+/// assert_eq!(session, SessionType::Wayland);
+/// ```
+pub fn detect_from_env(lookup: impl Fn(&str) -> Option<String>) -> SessionType {
+    let is_set = |key: &str| lookup(key).is_some_and(|value| !value.is_empty());
+
+    match lookup("XDG_SESSION_TYPE").as_deref() {
+        Some("wayland") => return SessionType::Wayland,
+        Some("x11") => return SessionType::X11,
+        Some("tty") => return SessionType::Tty,
+        _ => {}
+    }
+
+    if is_set("WAYLAND_DISPLAY") {
+        SessionType::Wayland
+    } else if is_set("DISPLAY") {
+        SessionType::X11
+    } else {
+        SessionType::Unknown
+    }
+}
+
+/// Whether reminders should be delivered to the terminal instead of the desktop
+///
+/// In `auto` mode szmer runs headless only in a session known to be
+/// text-only, see [`is_text_session`].
+pub fn is_headless(mode: HeadlessMode) -> bool {
+    match mode {
+        HeadlessMode::Always => true,
+        HeadlessMode::Never => false,
+        HeadlessMode::Auto => is_text_session(detect(), |key| env::var(key).ok()),
+    }
+}
+
+/// Whether a session is known to have no desktop to notify
+///
+/// That's a TTY session, or an unknown one reached over SSH. A session that
+/// can't be told apart, e.g. a systemd timer run without `DISPLAY`, still
+/// gets desktop notifications, which reach the desktop through D-Bus.
+///
+/// # Examples
+///
+/// ```
+/// assert!(is_text_session(SessionType::Tty, |_| None));
+/// assert!(!is_text_session(SessionType::Unknown, |_| None));
+/// ```
+pub fn is_text_session(session: SessionType, lookup: impl Fn(&str) -> Option<String>) -> bool {
+    match session {
+        SessionType::Tty => true,
+        SessionType::Unknown => lookup("SSH_CONNECTION").is_some_and(|value| !value.is_empty()),
+        _ => false,
+    }
+}
+
+/// Detect whether szmer runs somewhere a system scheduler can't be used
+///
+/// Returns a human-readable reason when running inside a container or CI job,
+/// or when no systemd user session bus is reachable on Linux.
+pub fn detect_container() -> Option<String> {
+    detect_container_from(|key| env::var(key).ok(), |path| Path::new(path).exists())
+}
+
+/// Detect a container or CI environment from environment variables and marker files
+///
+/// # Examples
+///
+/// ```
+/// let reason = detect_container_from(|_| None, |path| path == "/.dockerenv");
+/// assert_eq!(reason.as_deref(), Some("Docker container"));
+/// ```
+pub fn detect_container_from(
+    lookup: impl Fn(&str) -> Option<String>,
+    exists: impl Fn(&str) -> bool,
+) -> Option<String> {
+    if CI_ENV_VARS.iter().any(|key| lookup(key).is_some()) {
+        return Some("CI environment".to_string());
+    }
+
+    if exists("/.dockerenv") {
+        return Some("Docker container".to_string());
+    }
+
+    if exists("/run/.containerenv") {
+        return Some("Podman container".to_string());
+    }
+
+    // Set by systemd-nspawn, toolbox, distrobox and flatpak
+    if let Some(container) = lookup("container").filter(|value| !value.is_empty()) {
+        return Some(format!("{container} container"));
+    }
+
+    if cfg!(target_os = "linux") && !has_systemd_user_bus(&lookup, &exists) {
+        return Some("No systemd user session bus".to_string());
+    }
+
+    None
+}
+
+fn has_systemd_user_bus(
+    lookup: &impl Fn(&str) -> Option<String>,
+    exists: &impl Fn(&str) -> bool,
+) -> bool {
+    let Some(runtime_dir) = lookup("XDG_RUNTIME_DIR") else {
+        return false;
+    };
+
+    exists(&format!("{runtime_dir}/systemd/private")) || exists(&format!("{runtime_dir}/bus"))
+}
+
+/// Whether szmer runs inside the ChromeOS Linux container (Crostini)
+pub fn is_crostini() -> bool {
+    cfg!(target_os = "linux")
+        && (Path::new("/dev/.cros_milestone").exists()
+            || Path::new("/opt/google/cros-containers").exists())
+}
+
+/// Whether the Crostini notification bridge is running
+///
+/// Crostini forwards freedesktop notifications to ChromeOS through the
+/// `cros-notificationd` user service shipped with cros-guest-tools.
+pub fn is_crostini_notification_bridge_active(runner: &dyn SystemCommandRunner) -> bool {
+    runner
+        .run(
+            "systemctl",
+            &["--user", "is-active", "--quiet", "cros-notificationd"],
+        )
+        .is_ok_and(|output| output.success)
+}
+
+/// GNOME's own break reminder settings (Settings → Wellbeing, GNOME 48+)
+pub const GNOME_BREAKS_SCHEMA: &str = "org.gnome.desktop.break-reminders";
+
+/// Break reminders GNOME itself shows, such as "movement" or "eyesight"
+///
+/// Empty when they're off or the GNOME version doesn't have them.
+pub fn gnome_break_reminders(runner: &dyn SystemCommandRunner) -> Vec<String> {
+    gsettings::get_string_list(runner, GNOME_BREAKS_SCHEMA, "selected-breaks").unwrap_or_default()
+}
+
+/// Whether the GNOME Break Timer app is running
+pub fn is_gnome_break_timer_running(runner: &dyn SystemCommandRunner) -> bool {
+    runner
+        .run("pgrep", &["-f", "gnome-break-timer"])
+        .is_ok_and(|output| output.success)
+}
+
+/// A login of the current user from another machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSession {
+    /// Terminal device under `/dev`, e.g. `pts/3`
+    pub tty: String,
+    /// Host the user connected from
+    pub host: String,
+}
+
+/// SSH logins of the current user, as listed by `who`
+pub fn ssh_sessions(runner: &dyn SystemCommandRunner) -> Vec<RemoteSession> {
+    let Some(user) = env::var("USER").ok().filter(|user| !user.is_empty()) else {
+        return Vec::new();
+    };
+
+    runner
+        .run("who", &[])
+        .ok()
+        .filter(|output| output.success)
+        .map(|output| parse_who(&output.stdout, &user))
+        .unwrap_or_default()
+}
+
+/// Remote logins of `user` in `who` output
+///
+/// Only pseudo-terminals (`pts/N` on Linux, `ttysNNN` on macOS) can be SSH
+/// logins. Local ones either have no host or show an X display like
+/// `(:0)`, and terminals inside tmux show `(tmux(1234).%0)`.
+fn parse_who(output: &str, user: &str) -> Vec<RemoteSession> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            if fields.next()? != user {
+                return None;
+            }
+            let tty = fields.next()?;
+            if !tty.starts_with("pts/") && !tty.starts_with("ttys") {
+                return None;
+            }
+            let host = line.trim_end().strip_suffix(')')?.split_once('(')?.1;
+            let is_remote =
+                !host.is_empty() && !host.starts_with(':') && !host.starts_with("tmux(");
+            is_remote.then(|| RemoteSession {
+                tty: tty.to_string(),
+                host: host.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Name of the desktop environment, if advertised by the session
+pub fn desktop_name() -> Option<String> {
+    env::var("XDG_CURRENT_DESKTOP")
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+
+    fn env_from<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_parse_who_keeps_remote_logins_of_user() {
+        let output = "\
+michal   seat0        2025-01-06 08:55 (login screen)
+michal   tty2         2025-01-06 08:55 (tty2)
+michal   pts/0        2025-01-06 08:56 (:0)
+michal   pts/3        2025-01-06 09:12 (192.168.1.5)
+michal   pts/4        2025-01-06 09:20 (tmux(4242).%0)
+guest    pts/5        2025-01-06 09:30 (10.0.0.7)
+";
+        assert_eq!(
+            parse_who(output, "michal"),
+            vec![RemoteSession {
+                tty: "pts/3".to_string(),
+                host: "192.168.1.5".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_who_macos() {
+        let output =
+            "michal   console  Jan  6 08:55\nmichal   ttys001  Jan  6 09:12 (laptop.lan)\n";
+        assert_eq!(parse_who(output, "michal")[0].host, "laptop.lan");
+        assert_eq!(parse_who(output, "michal").len(), 1);
+    }
+
+    #[test]
+    fn test_detect_from_env_prefers_xdg_session_type() {
+        let vars = [
+            ("XDG_SESSION_TYPE", "x11"),
+            ("WAYLAND_DISPLAY", "wayland-0"),
+        ];
+        assert_eq!(detect_from_env(env_from(&vars)), SessionType::X11);
+    }
+
+    #[test]
+    fn test_detect_from_env_display_variables() {
+        let wayland = [("WAYLAND_DISPLAY", "wayland-0"), ("DISPLAY", ":0")];
+        assert_eq!(detect_from_env(env_from(&wayland)), SessionType::Wayland);
+
+        let x11 = [("DISPLAY", ":0")];
+        assert_eq!(detect_from_env(env_from(&x11)), SessionType::X11);
+    }
+
+    #[test]
+    fn test_detect_container_from_ci_and_markers() {
+        let ci = [("GITHUB_ACTIONS", "true")];
+        assert_eq!(
+            detect_container_from(env_from(&ci), |_| false).as_deref(),
+            Some("CI environment")
+        );
+
+        let podman = detect_container_from(env_from(&[]), |path| path == "/run/.containerenv");
+        assert_eq!(podman.as_deref(), Some("Podman container"));
+
+        let toolbox = [("container", "oci")];
+        assert_eq!(
+            detect_container_from(env_from(&toolbox), |_| false).as_deref(),
+            Some("oci container")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_detect_container_from_user_bus() {
+        let vars = [("XDG_RUNTIME_DIR", "/run/user/1000")];
+        let with_bus = detect_container_from(env_from(&vars), |path| {
+            path == "/run/user/1000/systemd/private"
+        });
+        assert_eq!(with_bus, None);
+
+        let without_bus = detect_container_from(env_from(&vars), |_| false);
+        assert_eq!(without_bus.as_deref(), Some("No systemd user session bus"));
+    }
+
+    #[test]
+    fn test_gnome_break_reminders() {
+        let get = format!("gsettings get {GNOME_BREAKS_SCHEMA} selected-breaks");
+        let runner = RecordingRunner::default().reply(&get, true, "['movement', 'eyesight']\n");
+        assert_eq!(gnome_break_reminders(&runner), vec!["movement", "eyesight"]);
+
+        // Before GNOME 48 the schema doesn't exist
+        let runner = RecordingRunner::default().reply(&get, false, "");
+        assert!(gnome_break_reminders(&runner).is_empty());
+    }
+
+    #[test]
+    fn test_is_text_session_only_when_known() {
+        assert!(is_text_session(SessionType::Tty, env_from(&[])));
+        assert!(!is_text_session(SessionType::Unknown, env_from(&[])));
+        assert!(is_text_session(
+            SessionType::Unknown,
+            env_from(&[("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.1 22")])
+        ));
+        assert!(!is_text_session(
+            SessionType::Wayland,
+            env_from(&[("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.1 22")])
+        ));
+    }
+
+    #[test]
+    fn test_detect_from_env_nothing_set() {
+        assert_eq!(detect_from_env(env_from(&[])), SessionType::Unknown);
+        assert_eq!(
+            detect_from_env(env_from(&[("DISPLAY", "")])),
+            SessionType::Unknown
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::control::{Envelope, Request};
+use std::sync::mpsc::Sender;
+
+#[cfg(unix)]
+use crate::control::Response;
+#[cfg(unix)]
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM, SIGUSR1, SIGUSR2};
+#[cfg(unix)]
+use signal_hook::iterator::Signals;
+#[cfg(unix)]
+use std::sync::mpsc;
+#[cfg(unix)]
+use std::thread;
+
+/// Translate a Unix signal into the daemon request it stands for
+///
+/// - `SIGUSR1` triggers a break right now
+/// - `SIGUSR2` toggles pause
+/// - `SIGHUP` reloads the configuration
+/// - `SIGTERM` and `SIGINT` save state and shut down
+#[cfg(unix)]
+fn request_for(signal: i32) -> Option<Request> {
+    match signal {
+        SIGUSR1 => Some(Request::TriggerBreak),
+        SIGUSR2 => Some(Request::TogglePause),
+        SIGHUP => Some(Request::Reload),
+        SIGTERM | SIGINT => Some(Request::Shutdown),
+        _ => None,
+    }
+}
+
+/// Forward control signals to the daemon loop
+///
+/// Lets window-manager keybindings control the daemon with e.g.
+/// `pkill -USR1 szmer`.
+#[cfg(unix)]
+pub fn spawn_handler(events: Sender<Envelope>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut signals = Signals::new([SIGUSR1, SIGUSR2, SIGHUP, SIGTERM, SIGINT])?;
+
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            let Some(request) = request_for(signal) else {
+                continue;
+            };
+
+            let (reply_tx, reply_rx) = mpsc::channel();
+            if events.send((request, reply_tx)).is_err() {
+                break;
+            }
+
+            // The daemon logs the outcome, only failures are worth repeating
+            if let Ok(Response::Error { message }) = reply_rx.recv() {
+                eprintln!("Warning: {message}");
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn spawn_handler(_events: Sender<Envelope>) -> Result<(), Box<dyn std::error::Error>> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_for_known_signals() {
+        assert!(matches!(request_for(SIGUSR1), Some(Request::TriggerBreak)));
+        assert!(matches!(request_for(SIGUSR2), Some(Request::TogglePause)));
+        assert!(matches!(request_for(SIGHUP), Some(Request::Reload)));
+        assert!(matches!(request_for(SIGTERM), Some(Request::Shutdown)));
+    }
+
+    #[test]
+    fn test_request_for_unknown_signal() {
+        assert!(request_for(signal_hook::consts::SIGALRM).is_none());
+    }
+}
@@ -1,25 +1,62 @@
 use std::fs;
 use std::path::Path;
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+use std::path::PathBuf;
 
 #[cfg(target_os = "macos")]
 const SYSTEM_SOUNDS_DIR: &str = "/System/Library/Sounds";
 
-#[cfg(target_os = "linux")]
-const LINUX_SOUNDS_DIRS: &[&str] = &[
-    "/usr/share/sounds/freedesktop/stereo",
-    "/usr/share/sounds/gnome/default/alerts",
-    "/usr/share/sounds/ubuntu/stereo",
-];
+/// Theme every freedesktop sound theme falls back to
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+const FALLBACK_THEME: &str = "freedesktop";
+
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+const FREEDESKTOP_EXTENSIONS: &[&str] = &[".oga", ".ogg", ".wav"];
 
 pub fn get_available_sounds() -> Result<Vec<String>, Box<dyn std::error::Error>> {
     #[cfg(target_os = "macos")]
     return get_macos_sounds();
 
-    #[cfg(target_os = "linux")]
-    return get_linux_sounds();
+    #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+    return get_freedesktop_sounds();
+
+    #[cfg(not(any(
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "openbsd"
+    )))]
+    return Err(crate::error::SzmerError::Unsupported {
+        feature: "Sound selection",
+    }
+    .into());
+}
+
+/// Whether `name` can be played on this machine
+///
+/// On Linux and the BSDs sounds are freedesktop sound event names such as
+/// `alarm-clock-elapsed`, looked up in the desktop's sound theme the same
+/// way the notification server does. Platforms without sound selection
+/// have nothing to check.
+pub fn is_available(name: &str) -> bool {
+    #[cfg(target_os = "macos")]
+    return Path::new(SYSTEM_SOUNDS_DIR)
+        .join(format!("{name}.aiff"))
+        .is_file();
+
+    #[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+    return resolve(name).is_some();
 
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-    return Err("Sound selection not supported on this platform".into());
+    #[cfg(not(any(
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "openbsd"
+    )))]
+    {
+        let _ = name;
+        true
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -34,28 +71,138 @@ fn get_macos_sounds() -> Result<Vec<String>, Box<dyn std::error::Error>> {
     Ok(sounds)
 }
 
-#[cfg(target_os = "linux")]
-fn get_linux_sounds() -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let extensions = [".oga", ".ogg", ".wav"];
+/// Sound event names offered by the current theme and the themes it inherits
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+fn get_freedesktop_sounds() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let dirs = sound_dirs();
+    let themes = theme_chain(&dirs, &theme_name(&crate::command::ProcessRunner));
 
-    let mut sounds: Vec<String> = LINUX_SOUNDS_DIRS
+    let mut sounds: Vec<String> = themes
         .iter()
+        .flat_map(|theme| dirs.iter().map(move |dir| dir.join(theme).join("stereo")))
         .filter_map(|dir| fs::read_dir(dir).ok())
         .flat_map(|entries| entries.filter_map(Result::ok))
         .filter(|entry| entry.path().is_file())
-        .filter_map(|entry| extract_sound_name(&entry.path(), &extensions))
+        .filter_map(|entry| extract_sound_name(&entry.path(), FREEDESKTOP_EXTENSIONS))
         .collect();
 
     sounds.sort();
     sounds.dedup();
 
     if sounds.is_empty() {
-        return Err("No sounds found in Linux sound directories".into());
+        return Err("No sounds found in system sound directories".into());
     }
 
     Ok(sounds)
 }
 
+/// File the sound event `name` plays in the current sound theme
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+pub fn resolve(name: &str) -> Option<PathBuf> {
+    let dirs = sound_dirs();
+    let themes = theme_chain(&dirs, &theme_name(&crate::command::ProcessRunner));
+    lookup(&dirs, &themes, name)
+}
+
+/// The desktop's sound theme, `freedesktop` when it doesn't set one
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+pub fn theme_name(runner: &dyn crate::command::SystemCommandRunner) -> String {
+    crate::gsettings::get_string(runner, "org.gnome.desktop.sound", "theme-name")
+        .filter(|theme| !theme.is_empty())
+        .unwrap_or_else(|| FALLBACK_THEME.to_string())
+}
+
+/// `sounds` directories under the XDG data directories, most specific first
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+fn sound_dirs() -> Vec<PathBuf> {
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var("HOME")
+                .ok()
+                .map(|home| Path::new(&home).join(".local/share"))
+        });
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    data_home
+        .into_iter()
+        .chain(data_dirs.split(':').map(PathBuf::from))
+        .map(|dir| dir.join("sounds"))
+        .collect()
+}
+
+/// `theme` followed by the themes it inherits from, ending with `freedesktop`
+///
+/// Parents come from the `Inherits` key of each theme's `index.theme`.
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+fn theme_chain(dirs: &[PathBuf], theme: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut pending = vec![theme.to_string()];
+
+    while let Some(theme) = pending.pop() {
+        if chain.contains(&theme) {
+            continue;
+        }
+        let parents = dirs
+            .iter()
+            .find_map(|dir| fs::read_to_string(dir.join(&theme).join("index.theme")).ok())
+            .map(|index| parse_inherits(&index))
+            .unwrap_or_default();
+        pending.extend(parents.into_iter().rev());
+        chain.push(theme);
+    }
+
+    if !chain.iter().any(|theme| theme == FALLBACK_THEME) {
+        chain.push(FALLBACK_THEME.to_string());
+    }
+    chain
+}
+
+/// Themes listed under `Inherits=` in an `index.theme` file
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+fn parse_inherits(index: &str) -> Vec<String> {
+    index
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Inherits="))
+        .map(|themes| {
+            themes
+                .split(',')
+                .map(str::trim)
+                .filter(|theme| !theme.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Find `name` in `themes`, dropping `-suffix` parts until something matches
+///
+/// `message-new-instant` falls back to `message-new` and then `message`,
+/// as the sound naming spec asks.
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"))]
+fn lookup(dirs: &[PathBuf], themes: &[String], name: &str) -> Option<PathBuf> {
+    let mut name = name;
+    loop {
+        let found = themes.iter().find_map(|theme| {
+            dirs.iter().find_map(|dir| {
+                FREEDESKTOP_EXTENSIONS
+                    .iter()
+                    .map(|ext| dir.join(theme).join("stereo").join(format!("{name}{ext}")))
+                    .find(|path| path.is_file())
+            })
+        });
+        if found.is_some() {
+            return found;
+        }
+        name = name.rsplit_once('-')?.0;
+    }
+}
+
 fn extract_sound_name(path: &Path, extensions: &[&str]) -> Option<String> {
     let file_name = path.file_name()?.to_str()?;
 
@@ -64,3 +211,41 @@ fn extract_sound_name(path: &Path, extensions: &[&str]) -> Option<String> {
         .find_map(|ext| file_name.strip_suffix(ext))
         .map(String::from)
 }
+
+#[cfg(all(
+    test,
+    any(target_os = "linux", target_os = "freebsd", target_os = "openbsd")
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_follows_inherits_and_name_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let sounds = dir.path().to_path_buf();
+        for (file, content) in [
+            ("ocean/index.theme", "[Sound Theme]\nInherits=freedesktop\n"),
+            ("ocean/stereo/bell.oga", ""),
+            ("freedesktop/stereo/message.oga", ""),
+            ("freedesktop/stereo/bell.oga", ""),
+        ] {
+            let path = sounds.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let dirs = vec![sounds.clone()];
+
+        let themes = theme_chain(&dirs, "ocean");
+        assert_eq!(themes, vec!["ocean", "freedesktop"]);
+
+        assert_eq!(
+            lookup(&dirs, &themes, "bell"),
+            Some(sounds.join("ocean/stereo/bell.oga"))
+        );
+        assert_eq!(
+            lookup(&dirs, &themes, "message-new-instant"),
+            Some(sounds.join("freedesktop/stereo/message.oga"))
+        );
+        assert_eq!(lookup(&dirs, &themes, "drip"), None);
+    }
+}
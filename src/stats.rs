@@ -0,0 +1,152 @@
+use crate::clock::Clock;
+use crate::compliance::Compliance;
+use crate::time::{start_of_month, start_of_today, start_of_week};
+use crate::timestamp::AnswerRecord;
+use chrono::{DateTime, Local, NaiveDate};
+
+/// Period `szmer stats` covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Range {
+    Today,
+    Week,
+    Month,
+}
+
+impl Range {
+    /// Local midnight the range starts at
+    pub fn start(self, clock: &dyn Clock) -> DateTime<Local> {
+        match self {
+            Self::Today => start_of_today(clock),
+            Self::Week => start_of_week(clock),
+            Self::Month => start_of_month(clock),
+        }
+    }
+
+    /// Name in JSON output
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Today => "today",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Today => "today",
+            Self::Week => "this week",
+            Self::Month => "this month",
+        }
+    }
+}
+
+/// Reminder history summed up over a range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    /// Reminders per day, days without any included
+    pub per_day: Vec<(NaiveDate, usize)>,
+    /// Mean time between two reminders on the same day
+    pub average_gap_seconds: Option<u64>,
+    /// Longest time between two reminders on the same day, and that day
+    pub longest_gap: Option<(NaiveDate, u64)>,
+    pub compliance: Compliance,
+    /// Commits over the range in the configured git repos, `None` without any
+    pub commits: Option<usize>,
+}
+
+impl Stats {
+    /// Sum up `reminders` and `answers` from `since` through `today`
+    ///
+    /// Gaps are only measured within a day, so nights and weekends don't
+    /// count as going without a break.
+    pub fn compute(
+        reminders: &[DateTime<Local>],
+        answers: &[AnswerRecord],
+        since: DateTime<Local>,
+        today: NaiveDate,
+    ) -> Self {
+        let mut times: Vec<DateTime<Local>> = reminders
+            .iter()
+            .copied()
+            .filter(|at| *at >= since)
+            .collect();
+        times.sort();
+
+        let per_day = since
+            .date_naive()
+            .iter_days()
+            .take_while(|day| *day <= today)
+            .map(|day| {
+                let count = times.iter().filter(|at| at.date_naive() == day).count();
+                (day, count)
+            })
+            .collect();
+
+        let gaps: Vec<(NaiveDate, u64)> = times
+            .windows(2)
+            .filter(|pair| pair[0].date_naive() == pair[1].date_naive())
+            .map(|pair| {
+                let seconds = (pair[1] - pair[0]).num_seconds().max(0) as u64;
+                (pair[1].date_naive(), seconds)
+            })
+            .collect();
+        let average_gap_seconds = (!gaps.is_empty())
+            .then(|| gaps.iter().map(|(_, seconds)| seconds).sum::<u64>() / gaps.len() as u64);
+        let longest_gap = gaps.iter().copied().max_by_key(|(_, seconds)| *seconds);
+
+        Self {
+            per_day,
+            average_gap_seconds,
+            longest_gap,
+            compliance: Compliance::since(reminders, answers, since),
+            commits: None,
+        }
+    }
+
+    /// Reminders over the whole range
+    pub fn total(&self) -> usize {
+        self.per_day.iter().map(|(_, count)| count).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::timestamp::Answer;
+
+    #[test]
+    fn test_compute_counts_days_and_gaps_within_a_day() {
+        // A Wednesday
+        let clock = MockClock::at("2025-01-08 12:00:00");
+        let at = |time: &str| MockClock::at(time).now();
+        let reminders = [
+            at("2025-01-06 09:00:00"),
+            at("2025-01-06 10:00:00"),
+            at("2025-01-06 12:00:00"),
+            // The night in between isn't a gap
+            at("2025-01-08 09:00:00"),
+            at("2025-01-08 09:30:00"),
+            // Last week
+            at("2025-01-03 09:00:00"),
+        ];
+        let answers = [(at("2025-01-08 09:31:00"), Answer::Taken)];
+
+        let stats = Stats::compute(
+            &reminders,
+            &answers,
+            Range::Week.start(&clock),
+            clock.now().date_naive(),
+        );
+
+        let counts: Vec<usize> = stats.per_day.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, [3, 0, 2]);
+        assert_eq!(stats.total(), 5);
+        assert_eq!(stats.average_gap_seconds, Some((3600 + 7200 + 1800) / 3));
+        assert_eq!(
+            stats.longest_gap,
+            Some((NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(), 7200))
+        );
+        assert_eq!(stats.compliance.taken, 1);
+    }
+}
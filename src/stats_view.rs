@@ -0,0 +1,310 @@
+use crate::timestamp::{Answer, AnswerRecord};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike};
+use console::{style, Key, Term};
+use std::io;
+
+/// Weeks the streak calendar shows, ending with the week on screen
+const CALENDAR_WEEKS: i64 = 8;
+
+/// Widest a bar of the weekly chart gets, longer ones are scaled down
+const MAX_BAR: usize = 30;
+
+/// How far back a streak is followed
+const MAX_STREAK_DAYS: i64 = 366;
+
+/// Levels of the hour-of-day histogram, from none to the busiest hour
+const LEVELS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Reminders and how they were answered on one day
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Day {
+    reminders: usize,
+    taken: usize,
+    skipped: usize,
+}
+
+/// The recorded reminders and answers the view is drawn from
+pub struct History<'a> {
+    pub reminders: &'a [DateTime<Local>],
+    pub answers: &'a [AnswerRecord],
+}
+
+impl History<'_> {
+    fn day(&self, date: NaiveDate) -> Day {
+        let answered = |wanted: Answer| {
+            self.answers
+                .iter()
+                .filter(|(at, answer)| at.date_naive() == date && *answer == wanted)
+                .count()
+        };
+        Day {
+            reminders: self
+                .reminders
+                .iter()
+                .filter(|at| at.date_naive() == date)
+                .count(),
+            taken: answered(Answer::Taken),
+            skipped: answered(Answer::Skip),
+        }
+    }
+
+    /// Breaks taken in each hour of the day, from `first` through `last`
+    fn taken_by_hour(&self, first: NaiveDate, last: NaiveDate) -> [usize; 24] {
+        let mut hours = [0; 24];
+        for (at, answer) in self.answers {
+            if *answer == Answer::Taken && (first..=last).contains(&at.date_naive()) {
+                hours[at.hour() as usize] += 1;
+            }
+        }
+        hours
+    }
+
+    /// Days in a row up to `today` with a break taken
+    ///
+    /// Days without reminders, like weekends, don't break the streak, and
+    /// neither does today before its first break.
+    fn streak(&self, today: NaiveDate) -> usize {
+        let mut streak = 0;
+        for back in 0..MAX_STREAK_DAYS {
+            let day = self.day(today - Duration::days(back));
+            if day.taken > 0 {
+                streak += 1;
+            } else if day.reminders > 0 && back > 0 {
+                break;
+            }
+        }
+        streak
+    }
+}
+
+/// Browse the break history week by week until the user quits
+///
+/// Left and right (or h and l) move a week, t goes back to this week and
+/// q or Esc quits.
+pub fn run(history: &History, today: NaiveDate) -> io::Result<()> {
+    let term = Term::stdout();
+    let this_week = monday_of(today);
+    let mut monday = this_week;
+
+    term.hide_cursor()?;
+    let result = loop {
+        term.clear_screen()?;
+        for line in render(history, monday, today) {
+            term.write_line(&line)?;
+        }
+
+        match term.read_key() {
+            Ok(Key::ArrowLeft | Key::Char('h')) => monday -= Duration::weeks(1),
+            Ok(Key::ArrowRight | Key::Char('l')) => {
+                monday = (monday + Duration::weeks(1)).min(this_week)
+            }
+            Ok(Key::Char('t') | Key::Home) => monday = this_week,
+            Ok(Key::Char('q') | Key::Escape) => break Ok(()),
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    term.clear_screen()?;
+    term.show_cursor()?;
+    result
+}
+
+fn monday_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// The screen for the week starting on `monday`
+fn render(history: &History, monday: NaiveDate, today: NaiveDate) -> Vec<String> {
+    let sunday = monday + Duration::days(6);
+    let title = format!("Break Stats, week of {}", monday.format("%a %d %b %Y"));
+    let mut lines = vec![
+        String::new(),
+        title.clone(),
+        "━".repeat(title.chars().count()),
+        String::new(),
+    ];
+
+    lines.push(format!(
+        "Breaks {} and {}",
+        style("taken").green(),
+        style("skipped").yellow()
+    ));
+    lines.extend(week_chart(history, monday, today));
+
+    lines.push(String::new());
+    lines.push("Breaks taken by hour".to_string());
+    lines.extend(hour_histogram(history.taken_by_hour(monday, sunday)));
+
+    lines.push(String::new());
+    let streak = history.streak(today);
+    lines.push(format!(
+        "Streak: {streak} {}",
+        if streak == 1 { "day" } else { "days" }
+    ));
+    lines.extend(streak_calendar(history, monday, today));
+
+    lines.push(String::new());
+    lines.push(
+        style("← → change week   t this week   q quit")
+            .dim()
+            .to_string(),
+    );
+    lines
+}
+
+/// A row per day with bars for breaks taken and skipped
+fn week_chart(history: &History, monday: NaiveDate, today: NaiveDate) -> Vec<String> {
+    let days: Vec<(NaiveDate, Day)> = monday
+        .iter_days()
+        .take(7)
+        .map(|date| (date, history.day(date)))
+        .collect();
+    let most = days
+        .iter()
+        .map(|(_, day)| day.taken.max(day.skipped))
+        .max()
+        .unwrap_or(0);
+    let bar = |count: usize| {
+        let width = if most > MAX_BAR {
+            (count * MAX_BAR).div_ceil(most)
+        } else {
+            count
+        };
+        format!("{:<width$}", "▇".repeat(width), width = most.min(MAX_BAR))
+    };
+
+    days.iter()
+        .map(|(date, day)| {
+            let label = date.format("%a %d %b").to_string();
+            if *date > today {
+                return label;
+            }
+            let line = format!(
+                "{label}  {} {:>3}  {} {:>3}",
+                style(bar(day.taken)).green(),
+                day.taken,
+                style(bar(day.skipped)).yellow(),
+                day.skipped
+            );
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Two columns per hour, each as high as its share of the busiest hour
+fn hour_histogram(hours: [usize; 24]) -> Vec<String> {
+    let most = hours.iter().copied().max().unwrap_or(0);
+    if most == 0 {
+        return vec!["  none this week".to_string()];
+    }
+
+    let bars: String = hours
+        .iter()
+        .map(|count| {
+            let level = LEVELS[(count * (LEVELS.len() - 1)).div_ceil(most)];
+            format!("{level}{level}")
+        })
+        .collect();
+    let axis: String = (0..24)
+        .step_by(6)
+        .map(|hour| format!("{hour:<12}"))
+        .collect();
+    vec![
+        format!("  {}", style(bars.trim_end()).green()),
+        format!("  {}", axis.trim_end()),
+    ]
+}
+
+/// A column per week and a row per weekday, ending with the week on screen
+///
+/// ● marks a day with a break taken, ○ one with reminders but no break.
+fn streak_calendar(history: &History, monday: NaiveDate, today: NaiveDate) -> Vec<String> {
+    let first = monday - Duration::weeks(CALENDAR_WEEKS - 1);
+
+    let mut lines = vec![format!(
+        "     {}",
+        (0..CALENDAR_WEEKS)
+            .map(|week| {
+                let start = first + Duration::weeks(week);
+                // Name the month where it starts
+                if week == 0 || start.day() <= 7 {
+                    format!("{:<3}", start.format("%b"))
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect::<String>()
+            .trim_end()
+    )];
+
+    for weekday in 0..7 {
+        let cells: Vec<String> = (0..CALENDAR_WEEKS)
+            .map(|week| {
+                let date = first + Duration::weeks(week) + Duration::days(weekday);
+                let day = history.day(date);
+                if date > today {
+                    " ".to_string()
+                } else if day.taken > 0 {
+                    style("●").green().to_string()
+                } else if day.reminders > 0 {
+                    style("○").yellow().to_string()
+                } else {
+                    "·".to_string()
+                }
+            })
+            .collect();
+        let name = (first + Duration::days(weekday)).format("%a");
+        lines.push(
+            format!("{name}  {}", cells.join("  "))
+                .trim_end()
+                .to_string(),
+        );
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+
+    #[test]
+    fn test_render_week_with_streak() {
+        let at = |time: &str| MockClock::at(time).now();
+        let reminders = [
+            at("2025-01-03 10:00:00"),
+            at("2025-01-06 09:00:00"),
+            at("2025-01-06 10:00:00"),
+            at("2025-01-07 09:00:00"),
+            at("2025-01-08 09:00:00"),
+        ];
+        let answers = [
+            (at("2025-01-03 10:05:00"), Answer::Taken),
+            (at("2025-01-06 09:05:00"), Answer::Taken),
+            (at("2025-01-06 10:05:00"), Answer::Skip),
+            (at("2025-01-07 09:05:00"), Answer::Taken),
+        ];
+        let history = History {
+            reminders: &reminders,
+            answers: &answers,
+        };
+        // A Wednesday, nothing taken yet today
+        let today = at("2025-01-08 12:00:00").date_naive();
+
+        // The weekend without reminders doesn't break the streak
+        assert_eq!(history.streak(today), 3);
+        assert_eq!(history.taken_by_hour(monday_of(today), today)[9], 2);
+
+        let lines: Vec<String> = render(&history, monday_of(today), today)
+            .iter()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect();
+        assert!(lines.contains(&"Break Stats, week of Mon 06 Jan 2025".to_string()));
+        assert!(lines.contains(&"Mon 06 Jan  ▇   1  ▇   1".to_string()));
+        assert!(lines.contains(&"Wed 08 Jan      0      0".to_string()));
+        assert!(lines.contains(&"Thu 09 Jan".to_string()));
+        assert!(lines.contains(&"Streak: 3 days".to_string()));
+        assert!(lines.contains(&"     Nov   Dec            Jan".to_string()));
+        assert!(lines.contains(&"Fri  ·  ·  ·  ·  ·  ·  ●".to_string()));
+    }
+}
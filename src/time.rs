@@ -1,30 +1,215 @@
-use chrono::{DateTime, Local};
+use crate::clock::Clock;
+use crate::i18n::{tr, tr_with};
+use chrono::{DateTime, Datelike, Local, NaiveTime, Weekday};
+
+/// Format a time as "15:00" when it's today, "Sat 09:00" on another day
+pub fn format_time_of_day(clock: &dyn Clock, at: DateTime<Local>) -> String {
+    if at.date_naive() == clock.now().date_naive() {
+        at.format("%H:%M").to_string()
+    } else {
+        at.format("%a %H:%M").to_string()
+    }
+}
+
+/// `at` plus `seconds`, `None` when the result can't be represented
+pub fn checked_add_seconds(at: DateTime<Local>, seconds: u64) -> Option<DateTime<Local>> {
+    let seconds = i64::try_from(seconds).ok()?;
+    at.checked_add_signed(chrono::Duration::try_seconds(seconds)?)
+}
+
+/// The next time the clock shows `time`, later today or tomorrow
+pub fn next_time_of_day(clock: &dyn Clock, time: NaiveTime) -> DateTime<Local> {
+    let now = clock.now();
+    [now.date_naive(), now.date_naive() + chrono::Days::new(1)]
+        .into_iter()
+        .filter_map(|day| day.and_time(time).and_local_timezone(Local).earliest())
+        .find(|at| *at > now)
+        // Only when a DST gap swallows the time two days running
+        .unwrap_or(now)
+}
 
 /// Format a duration until a future time in a human-readable way
 ///
 /// # Examples
 ///
 /// ```
-/// use chrono::Local;
 /// use std::time::Duration;
 ///
-/// let future = Local::now() + Duration::from_secs(3700);
-/// let formatted = format_time_until(future);
+/// let future = SystemClock.now() + Duration::from_secs(3700);
+/// let formatted = format_time_until(&SystemClock, future);
 /// // Returns something like "in 1 hour 1 minute"
 /// ```
-pub fn format_time_until(next_run: DateTime<Local>) -> String {
-    let duration = next_run.signed_duration_since(Local::now());
-    let hours = duration.num_hours().max(0) as u64;
-    let minutes = (duration.num_minutes() % 60).max(0) as u64;
+pub fn format_time_until(clock: &dyn Clock, next_run: DateTime<Local>) -> String {
+    let seconds = next_run.signed_duration_since(clock.now()).num_seconds();
+    if seconds < 60 {
+        return tr("time-very-soon");
+    }
 
-    match (hours, minutes) {
-        (0, m) if m > 0 => format!("in {m}{}", pluralize_unit(" minute", m)),
-        (h, m) if h > 0 => format!(
-            "in {h}{} {m}{}",
-            pluralize_unit(" hour", h),
-            pluralize_unit(" minute", m)
-        ),
-        _ => "very soon".to_string(),
+    tr_with(
+        "time-until",
+        &[(
+            "duration",
+            format_duration(seconds as u64, Case::Accusative).into(),
+        )],
+    )
+}
+
+/// Format how often something repeats, e.g. "every 30 minutes"
+pub fn format_every(seconds: u64) -> String {
+    tr_with(
+        "time-every",
+        &[(
+            "duration",
+            format_duration(seconds, Case::Accusative).into(),
+        )],
+    )
+}
+
+/// Local midnight at the start of the current day
+///
+/// Falls back to 24 hours ago on the rare days where midnight doesn't exist,
+/// such as a DST change at 00:00.
+pub fn start_of_today(clock: &dyn Clock) -> DateTime<Local> {
+    let now = clock.now();
+    now.date_naive()
+        .and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or(now - chrono::Duration::hours(24))
+}
+
+/// Local midnight at the start of the current week, on Monday
+pub fn start_of_week(clock: &dyn Clock) -> DateTime<Local> {
+    let days = clock.now().weekday().num_days_from_monday();
+    start_of_today(clock) - chrono::Duration::days(i64::from(days))
+}
+
+/// Local midnight at the start of the current month
+pub fn start_of_month(clock: &dyn Clock) -> DateTime<Local> {
+    let days = clock.now().day0();
+    start_of_today(clock) - chrono::Duration::days(i64::from(days))
+}
+
+/// Longest duration `parse_duration` accepts, a year
+///
+/// Anything longer is a typo, and would overflow time arithmetic further on.
+pub const MAX_DURATION_SECONDS: u64 = 366 * 86_400;
+
+/// Parse a duration like "90s", "10m" or "1h" into seconds, for clap
+///
+/// A bare number is taken as minutes. Durations over a year are rejected.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(parse_duration("10m"), Ok(600));
+/// assert_eq!(parse_duration("15"), Ok(900));
+/// ```
+pub fn parse_duration(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (number, unit_seconds) = match value.char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1),
+        Some((i, 'm')) => (&value[..i], 60),
+        Some((i, 'h')) => (&value[..i], 3600),
+        _ => (value, 60),
+    };
+
+    let count = match number.trim().parse::<u64>() {
+        Ok(count) if count > 0 => count,
+        _ => {
+            return Err(format!(
+                "Invalid duration: '{value}'. Use e.g. 90s, 10m or 1h"
+            ))
+        }
+    };
+    count
+        .checked_mul(unit_seconds)
+        .filter(|seconds| *seconds <= MAX_DURATION_SECONDS)
+        .ok_or_else(|| format!("Duration too long: '{value}'. Use at most a year"))
+}
+
+/// Parse weekdays like "mon-fri" or "mon,wed,fri" for `schedule.days`
+///
+/// Ranges may wrap around the week, like "fri-mon". The days come back in
+/// week order without duplicates.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(parse_days("sat-sun"), Ok(vec![Weekday::Sat, Weekday::Sun]));
+/// ```
+pub fn parse_days(value: &str) -> Result<Vec<Weekday>, String> {
+    let parse_day = |day: &str| {
+        day.trim().parse::<Weekday>().map_err(|_| {
+            format!(
+                "Invalid day: '{}'. Use e.g. mon-fri or mon,wed,fri",
+                day.trim()
+            )
+        })
+    };
+
+    let mut days = Vec::new();
+    for part in value.split(',').filter(|part| !part.trim().is_empty()) {
+        let (first, last) = part.split_once('-').unwrap_or((part, part));
+        let (mut day, last) = (parse_day(first)?, parse_day(last)?);
+        loop {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+            if day == last {
+                break;
+            }
+            day = day.succ();
+        }
+    }
+
+    days.sort_by_key(Weekday::num_days_from_monday);
+    Ok(days)
+}
+
+/// Format weekdays the way `parse_days` reads them, with runs as ranges
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Weekday::*;
+/// assert_eq!(format_days(&[Mon, Tue, Wed, Thu, Fri]), "mon-fri");
+/// assert_eq!(format_days(&[Mon, Wed, Sat, Sun]), "mon,wed,sat,sun");
+/// ```
+pub fn format_days(days: &[Weekday]) -> String {
+    let name = |day: &Weekday| day.to_string().to_lowercase();
+
+    let mut runs: Vec<Vec<Weekday>> = Vec::new();
+    for day in days {
+        match runs.last_mut() {
+            Some(run) if run.last().map(|last| last.succ()) == Some(*day) => run.push(*day),
+            _ => runs.push(vec![*day]),
+        }
+    }
+
+    runs.iter()
+        .map(|run| match run.as_slice() {
+            [first, .., last] if run.len() > 2 => format!("{}-{}", name(first), name(last)),
+            _ => run.iter().map(name).collect::<Vec<_>>().join(","),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Format how long ago something started, e.g. a daemon's uptime
+///
+/// # Examples
+///
+/// ```
+/// let started_at = SystemClock.now() - chrono::Duration::minutes(90);
+/// assert_eq!(format_elapsed_since(&SystemClock, started_at), "1 hour 30 minutes");
+/// ```
+pub fn format_elapsed_since(clock: &dyn Clock, started_at: DateTime<Local>) -> String {
+    let seconds = (clock.now() - started_at).num_seconds().max(0) as u64;
+    if seconds < 60 {
+        tr("duration-less-than-a-minute")
+    } else {
+        format_interval(seconds)
     }
 }
 
@@ -39,46 +224,87 @@ pub fn format_time_until(next_run: DateTime<Local>) -> String {
 /// assert_eq!(format_interval(7200), "2 hours");
 /// ```
 pub fn format_interval(seconds: u64) -> String {
+    format_duration(seconds, Case::Nominative)
+}
+
+/// Grammatical case a duration is used in
+///
+/// Polish inflects units after "za" and "co": "1 minuta" on its own, but
+/// "za 1 minutę". English ignores it.
+#[derive(Debug, Clone, Copy)]
+enum Case {
+    Nominative,
+    Accusative,
+}
+
+impl Case {
+    fn as_str(self) -> &'static str {
+        match self {
+            Case::Nominative => "nominative",
+            Case::Accusative => "accusative",
+        }
+    }
+}
+
+fn format_duration(seconds: u64, case: Case) -> String {
     let hours = seconds / 3600;
     let minutes = (seconds % 3600) / 60;
 
     match (hours, minutes) {
-        (0, m) => format!("{m}{}", pluralize_unit(" minute", m)),
-        (h, 0) => format!("{h}{}", pluralize_unit(" hour", h)),
+        (0, m) => format_count("duration-minutes", m, case),
+        (h, 0) => format_count("duration-hours", h, case),
         (h, m) => format!(
-            "{h}{} {m}{}",
-            pluralize_unit(" hour", h),
-            pluralize_unit(" minute", m)
+            "{} {}",
+            format_count("duration-hours", h, case),
+            format_count("duration-minutes", m, case)
         ),
     }
 }
 
-/// Pluralize time unit words based on count
+/// Format a long stretch of time off, like a pause, as in "off for 3 days"
+///
+/// Counts whole days from a day on, hours and minutes below that.
+pub fn format_time_off(seconds: u64) -> String {
+    match seconds / 86_400 {
+        0 => format_duration(seconds, Case::Accusative),
+        days => format_count("duration-days", days, Case::Accusative),
+    }
+}
+
+/// Format a duration tersely for `szmer status --short`, always in English
 ///
 /// # Examples
 ///
 /// ```
-/// assert_eq!(pluralize_unit(" minute", 1), " minute");
-/// assert_eq!(pluralize_unit(" minute", 2), " minutes");
-/// assert_eq!(pluralize_unit(" hour", 1), " hour");
-/// assert_eq!(pluralize_unit(" hour", 5), " hours");
+/// assert_eq!(format_compact(45), "45s");
+/// assert_eq!(format_compact(1500), "25m");
+/// assert_eq!(format_compact(5400), "1h30m");
 /// ```
-pub fn pluralize_unit(word: &str, count: u64) -> &'static str {
-    if count == 1 {
-        match word {
-            " minute" => " minute",
-            " hour" => " hour",
-            _ => "",
-        }
-    } else {
-        match word {
-            " minute" => " minutes",
-            " hour" => " hours",
-            _ => "s",
-        }
+pub fn format_compact(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h{m}m"),
     }
 }
 
+/// Format a number of seconds, e.g. "1 second" or "5 seconds"
+pub fn format_seconds(seconds: u64) -> String {
+    format_count("duration-seconds", seconds, Case::Nominative)
+}
+
+/// Format `count` of a unit with the locale's plural rules, in `case`
+fn format_count(id: &str, count: u64, case: Case) -> String {
+    tr_with(
+        id,
+        &[("count", count.into()), ("case", case.as_str().into())],
+    )
+}
+
 /// Parse systemd timestamp from NextElapseUSecRealtime output
 ///
 /// # Examples
@@ -107,9 +333,85 @@ pub fn parse_next_run_timestamp(
     Ok(Local.timestamp_opt(timestamp_sec, timestamp_nsec).single())
 }
 
+/// Build the crontab schedules that run a job every interval in seconds
+///
+/// The interval is cut to whole minutes. One `*/N` step schedule covers an
+/// interval that evenly divides an hour or, as whole hours, a day. Any other
+/// interval gets explicit minute and hour lists counted from midnight, so
+/// every gap is exact except the last one of the day, which ends at
+/// midnight when the count starts over.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(cron_schedules(1200), ["*/20 * * * *"]);
+/// assert_eq!(cron_schedules(7200), ["0 */2 * * *"]);
+/// assert_eq!(cron_schedules(2700).len(), 4);
+/// ```
+#[cfg_attr(
+    not(any(target_os = "freebsd", target_os = "openbsd")),
+    allow(dead_code)
+)]
+pub fn cron_schedules(interval_seconds: u64) -> Vec<String> {
+    const MINUTES_PER_DAY: u64 = 24 * 60;
+    let minutes = (interval_seconds / 60).clamp(1, MINUTES_PER_DAY);
+
+    if 60 % minutes == 0 {
+        return vec![match minutes {
+            60 => "0 * * * *".to_string(),
+            m => format!("*/{m} * * * *"),
+        }];
+    }
+    if minutes.is_multiple_of(60) && MINUTES_PER_DAY.is_multiple_of(minutes) {
+        return vec![match minutes / 60 {
+            24 => "0 0 * * *".to_string(),
+            h => format!("0 */{h} * * *"),
+        }];
+    }
+
+    // Group the hours that share the same minutes into one schedule
+    let mut by_hour: Vec<(u64, Vec<u64>)> = Vec::new();
+    for at in (0..MINUTES_PER_DAY).step_by(minutes as usize) {
+        match by_hour.last_mut() {
+            Some((hour, marks)) if *hour == at / 60 => marks.push(at % 60),
+            _ => by_hour.push((at / 60, vec![at % 60])),
+        }
+    }
+    let mut schedules: Vec<(Vec<u64>, Vec<u64>)> = Vec::new();
+    for (hour, marks) in by_hour {
+        match schedules.iter_mut().find(|(m, _)| *m == marks) {
+            Some((_, hours)) => hours.push(hour),
+            None => schedules.push((marks, vec![hour])),
+        }
+    }
+
+    let join = |values: &[u64]| {
+        values
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    schedules
+        .iter()
+        .map(|(marks, hours)| format!("{} {} * * *", join(marks), join(hours)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_format_compact() {
+        assert_eq!(format_compact(0), "0s");
+        assert_eq!(format_compact(45), "45s");
+        assert_eq!(format_compact(1500), "25m");
+        assert_eq!(format_compact(3600), "1h");
+        assert_eq!(format_compact(5400), "1h30m");
+    }
 
     #[test]
     fn test_format_interval_minutes_only() {
@@ -139,62 +441,94 @@ mod tests {
     }
 
     #[test]
-    fn test_pluralize_unit_minutes() {
-        assert_eq!(pluralize_unit(" minute", 0), " minutes");
-        assert_eq!(pluralize_unit(" minute", 1), " minute");
-        assert_eq!(pluralize_unit(" minute", 2), " minutes");
-        assert_eq!(pluralize_unit(" minute", 100), " minutes");
+    fn test_format_time_until_minutes() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let future = clock.now() + chrono::Duration::minutes(5);
+        assert_eq!(format_time_until(&clock, future), "in 5 minutes");
     }
 
     #[test]
-    fn test_pluralize_unit_hours() {
-        assert_eq!(pluralize_unit(" hour", 0), " hours");
-        assert_eq!(pluralize_unit(" hour", 1), " hour");
-        assert_eq!(pluralize_unit(" hour", 2), " hours");
-        assert_eq!(pluralize_unit(" hour", 24), " hours");
+    fn test_format_time_until_hours_and_minutes() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let future = clock.now() + chrono::Duration::hours(2) + chrono::Duration::minutes(30);
+        assert_eq!(format_time_until(&clock, future), "in 2 hours 30 minutes");
     }
 
     #[test]
-    fn test_pluralize_unit_unknown() {
-        assert_eq!(pluralize_unit("unknown", 1), "");
-        assert_eq!(pluralize_unit("unknown", 2), "s");
+    fn test_format_time_until_very_soon() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let future = clock.now() + chrono::Duration::seconds(30);
+        assert_eq!(format_time_until(&clock, future), "very soon");
     }
 
     #[test]
-    fn test_format_time_until_minutes() {
-        let now = Local::now();
-        let future = now + chrono::Duration::minutes(5);
-        let result = format_time_until(future);
-        // Check for the general format, allowing for minor time variations
-        assert!(result.starts_with("in "));
-        assert!(result.contains("minute"));
+    fn test_format_time_until_past() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let past = clock.now() - chrono::Duration::minutes(10);
+        assert_eq!(format_time_until(&clock, past), "very soon");
     }
 
     #[test]
-    fn test_format_time_until_hours_and_minutes() {
-        let now = Local::now();
-        let future = now + chrono::Duration::hours(2) + chrono::Duration::minutes(30);
-        let result = format_time_until(future);
-        // Check for the general format
-        assert!(result.starts_with("in "));
-        assert!(result.contains("hour"));
-        assert!(result.contains("minute"));
+    fn test_format_time_until_counts_down_as_clock_advances() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let next_run = clock.now() + chrono::Duration::minutes(25);
+
+        clock.advance(chrono::Duration::minutes(20));
+        assert_eq!(format_time_until(&clock, next_run), "in 5 minutes");
     }
 
     #[test]
-    fn test_format_time_until_very_soon() {
-        let now = Local::now();
-        let future = now + chrono::Duration::seconds(30);
-        let result = format_time_until(future);
-        assert_eq!(result, "very soon");
+    fn test_checked_add_seconds_rejects_overflow() {
+        let now = MockClock::at("2025-01-06 09:00:00").now();
+        assert_eq!(
+            checked_add_seconds(now, 3600),
+            Some(now + chrono::Duration::hours(1))
+        );
+        assert_eq!(checked_add_seconds(now, 999_999_999_999 * 3600), None);
+        assert_eq!(checked_add_seconds(now, u64::MAX), None);
     }
 
     #[test]
-    fn test_format_time_until_past() {
-        let now = Local::now();
-        let past = now - chrono::Duration::minutes(10);
-        let result = format_time_until(past);
-        assert_eq!(result, "very soon");
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("90s"), Ok(90));
+        assert_eq!(parse_duration("10m"), Ok(600));
+        assert_eq!(parse_duration("2h"), Ok(7200));
+        assert_eq!(parse_duration("25"), Ok(1500));
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("99999999999999999h").is_err());
+        assert!(parse_duration("8785h").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn test_parse_days_round_trips() {
+        use chrono::Weekday::*;
+        assert_eq!(parse_days("mon-fri"), Ok(vec![Mon, Tue, Wed, Thu, Fri]));
+        assert_eq!(parse_days("fri-mon"), Ok(vec![Mon, Fri, Sat, Sun]));
+        assert_eq!(parse_days("Sat, sun,sat"), Ok(vec![Sat, Sun]));
+        assert_eq!(parse_days(""), Ok(vec![]));
+        assert!(parse_days("mon-someday").is_err());
+
+        for value in ["mon-fri", "mon,wed,fri", "mon,tue,sat,sun", "tue-sun"] {
+            assert_eq!(format_days(&parse_days(value).unwrap()), value);
+        }
+    }
+
+    #[test]
+    fn test_format_elapsed_since() {
+        let clock = MockClock::at("2025-01-06 09:00:00");
+        let started_at = clock.now();
+
+        assert_eq!(
+            format_elapsed_since(&clock, started_at),
+            "less than a minute"
+        );
+        clock.advance(chrono::Duration::minutes(90));
+        assert_eq!(
+            format_elapsed_since(&clock, started_at),
+            "1 hour 30 minutes"
+        );
     }
 
     #[cfg(target_os = "linux")]
@@ -232,4 +566,108 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
+
+    /// Read `format_interval` output back into seconds
+    fn parse_formatted_interval(text: &str) -> u64 {
+        let words: Vec<&str> = text.split(' ').collect();
+        words
+            .chunks(2)
+            .map(|pair| {
+                let count: u64 = pair[0].parse().unwrap();
+                match pair[1] {
+                    "hour" | "hours" => count * 3600,
+                    "minute" | "minutes" => count * 60,
+                    unit => panic!("Unexpected unit '{unit}' in '{text}'"),
+                }
+            })
+            .sum()
+    }
+
+    proptest! {
+        #[test]
+        fn test_format_interval_round_trips_whole_minutes(seconds in 0u64..10 * 86_400) {
+            let formatted = format_interval(seconds);
+            prop_assert_eq!(parse_formatted_interval(&formatted), seconds / 60 * 60);
+        }
+
+        #[test]
+        fn test_format_interval_uses_singular_only_for_one(seconds in 0u64..10 * 86_400) {
+            let formatted = format_interval(seconds);
+            let words: Vec<&str> = formatted.split(' ').collect();
+            for pair in words.chunks(2) {
+                let singular = !pair[1].ends_with('s');
+                prop_assert_eq!(singular, pair[0] == "1", "{}", formatted);
+            }
+        }
+
+        #[cfg(target_os = "linux")]
+        #[test]
+        fn test_parse_next_run_timestamp_round_trips(usec in 0i64..4_102_444_800_000_000) {
+            let output = format!("NextElapseUSecRealtime={usec}\n");
+            let parsed = parse_next_run_timestamp(&output).unwrap().unwrap();
+            prop_assert_eq!(parsed.timestamp_micros(), usec);
+        }
+    }
+
+    #[test]
+    fn test_cron_schedules_steps() {
+        assert_eq!(cron_schedules(30), ["*/1 * * * *"]);
+        assert_eq!(cron_schedules(60), ["*/1 * * * *"]);
+        assert_eq!(cron_schedules(1200), ["*/20 * * * *"]);
+        assert_eq!(cron_schedules(3600), ["0 * * * *"]);
+        assert_eq!(cron_schedules(7200), ["0 */2 * * *"]);
+        assert_eq!(cron_schedules(86400), ["0 0 * * *"]);
+    }
+
+    #[test]
+    fn test_cron_schedules_lists_uneven_intervals() {
+        assert_eq!(
+            cron_schedules(5400),
+            [
+                "0 0,3,6,9,12,15,18,21 * * *",
+                "30 1,4,7,10,13,16,19,22 * * *",
+            ]
+        );
+        assert_eq!(
+            cron_schedules(1500),
+            [
+                "0,25,50 0,5,10,15,20 * * *",
+                "15,40 1,6,11,16,21 * * *",
+                "5,30,55 2,7,12,17,22 * * *",
+                "20,45 3,8,13,18,23 * * *",
+                "10,35 4,9,14,19 * * *",
+            ]
+        );
+        assert_eq!(cron_schedules(18 * 3600), ["0 0,18 * * *"]);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_cron_schedules_fire_every_interval(interval_seconds in 60u64..=86_400) {
+            let minutes = interval_seconds / 60;
+            let mut fired: Vec<u64> = Vec::new();
+            for schedule in cron_schedules(interval_seconds) {
+                let fields: Vec<&str> = schedule.split(' ').collect();
+                let expand = |field: &str, limit: u64| -> Vec<u64> {
+                    match field {
+                        "*" => (0..limit).collect(),
+                        f if f.starts_with("*/") => {
+                            (0..limit).step_by(f[2..].parse().unwrap()).collect()
+                        }
+                        f => f.split(',').map(|v| v.parse().unwrap()).collect(),
+                    }
+                };
+                for hour in expand(fields[1], 24) {
+                    for minute in expand(fields[0], 60) {
+                        fired.push(hour * 60 + minute);
+                    }
+                }
+            }
+            fired.sort_unstable();
+            fired.dedup();
+
+            let expected: Vec<u64> = (0..24 * 60).step_by(minutes as usize).collect();
+            prop_assert_eq!(fired, expected);
+        }
+    }
 }
@@ -1,28 +1,81 @@
+use crate::clock::Clock;
 use chrono::{DateTime, Local};
 use std::env;
+use std::ffi::OsString;
 use std::fs::{self, OpenOptions};
 use std::io::Write as IoWrite;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get the path to the cache directory for szmer
-fn get_cache_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+pub fn get_cache_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
     let home = env::var("HOME")?;
     Ok(PathBuf::from(home).join(".cache").join("szmer"))
 }
 
+/// Get the per-user directory for the daemon's socket and pidfile
+///
+/// Uses `$XDG_RUNTIME_DIR/szmer` when set, which systemd creates per UID
+/// and cleans up at logout, and falls back to the cache directory.
+pub fn get_runtime_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    runtime_dir_from(env::var_os("XDG_RUNTIME_DIR"), get_cache_dir)
+}
+
+fn runtime_dir_from(
+    xdg_runtime_dir: Option<OsString>,
+    fallback: impl FnOnce() -> Result<PathBuf, Box<dyn std::error::Error>>,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match xdg_runtime_dir.filter(|dir| Path::new(dir).is_absolute()) {
+        Some(dir) => Ok(PathBuf::from(dir).join("szmer")),
+        None => fallback(),
+    }
+}
+
+/// Create a directory only the current user can access
+///
+/// Refuses directories owned by someone else, so another user on a shared
+/// machine can't pre-create it and intercept the control socket.
+#[cfg(unix)]
+pub fn create_private_dir(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    fs::create_dir_all(path)?;
+
+    let metadata = fs::metadata(path)?;
+    // SAFETY: getuid has no preconditions and cannot fail
+    let uid = unsafe { libc::getuid() };
+    if metadata.uid() != uid {
+        return Err(format!(
+            "{} is owned by another user, refusing to use it",
+            path.display()
+        )
+        .into());
+    }
+
+    if metadata.permissions().mode() & 0o777 != 0o700 {
+        fs::set_permissions(path, fs::Permissions::from_mode(0o700))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn create_private_dir(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(path)?;
+    Ok(())
+}
+
 /// Get the path to the timestamp file
 fn get_timestamp_file() -> Result<PathBuf, Box<dyn std::error::Error>> {
     Ok(get_cache_dir()?.join("last_notification"))
 }
 
 /// Record the current timestamp as the last notification time
-pub fn record_notification() -> Result<(), Box<dyn std::error::Error>> {
+pub fn record_notification(clock: &dyn Clock) -> Result<(), Box<dyn std::error::Error>> {
     let cache_dir = get_cache_dir()?;
     fs::create_dir_all(&cache_dir)?;
 
     let timestamp_file = get_timestamp_file()?;
-    let now = Local::now();
-    let timestamp = now.timestamp();
+    let timestamp = clock.now().timestamp();
 
     let mut file = OpenOptions::new()
         .create(true)
@@ -34,25 +87,187 @@ pub fn record_notification() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Every recorded notification time, oldest first
+///
+/// Lines that don't parse are skipped, so a damaged entry doesn't hide
+/// the rest of the history.
+pub fn notification_history() -> Result<Vec<DateTime<Local>>, Box<dyn std::error::Error>> {
+    let content = match fs::read_to_string(get_timestamp_file()?) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(parse_history(&content))
+}
+
+fn parse_history(content: &str) -> Vec<DateTime<Local>> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().parse::<i64>().ok())
+        .filter_map(|timestamp| DateTime::from_timestamp(timestamp, 0))
+        .map(|dt| dt.with_timezone(&Local))
+        .collect()
+}
+
+fn get_snooze_file() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(get_cache_dir()?.join("snoozed_until"))
+}
+
+/// Hold reminders back until `until`, for `szmer snooze` without a daemon
+pub fn record_snooze(until: DateTime<Local>) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(get_cache_dir()?)?;
+    fs::write(get_snooze_file()?, format!("{}\n", until.timestamp()))?;
+    Ok(())
+}
+
+/// When the current snooze ends, `None` when reminders aren't snoozed
+pub fn snoozed_until(clock: &dyn Clock) -> Option<DateTime<Local>> {
+    let content = fs::read_to_string(get_snooze_file().ok()?).ok()?;
+    parse_history(&content)
+        .last()
+        .copied()
+        .filter(|until| *until > clock.now())
+}
+
+/// How the user answered a reminder, through its buttons or `szmer respond`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Answer {
+    /// Remind me again in 5 minutes
+    Snooze,
+    /// Not taking this break
+    Skip,
+    /// The break was taken
+    Taken,
+}
+
+impl Answer {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Snooze => "snooze",
+            Self::Skip => "skip",
+            Self::Taken => "taken",
+        }
+    }
+}
+
+fn get_answers_file() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(get_cache_dir()?.join("break_answers"))
+}
+
+/// Append `answer` to the answer history, one `<timestamp> <answer>` line each
+pub fn record_answer(clock: &dyn Clock, answer: Answer) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(get_cache_dir()?)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_answers_file()?)?;
+    writeln!(file, "{} {}", clock.now().timestamp(), answer.name())?;
+
+    Ok(())
+}
+
+/// When a reminder was answered, and how
+pub type AnswerRecord = (DateTime<Local>, Answer);
+
+/// Every recorded answer, oldest first
+pub fn answer_history() -> Result<Vec<AnswerRecord>, Box<dyn std::error::Error>> {
+    match fs::read_to_string(get_answers_file()?) {
+        Ok(content) => Ok(parse_answers(&content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn parse_answers(content: &str) -> Vec<AnswerRecord> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (timestamp, name) = line.trim().split_once(' ')?;
+            let at = DateTime::from_timestamp(timestamp.parse().ok()?, 0)?;
+            let answer = [Answer::Snooze, Answer::Skip, Answer::Taken]
+                .into_iter()
+                .find(|answer| answer.name() == name)?;
+            Some((at.with_timezone(&Local), answer))
+        })
+        .collect()
+}
+
 /// Get the last notification timestamp
 pub fn get_last_notification() -> Result<Option<DateTime<Local>>, Box<dyn std::error::Error>> {
-    let timestamp_file = get_timestamp_file()?;
+    Ok(notification_history()?.last().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if !timestamp_file.exists() {
-        return Ok(None);
+    fn fallback() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(PathBuf::from("/home/me/.cache/szmer"))
     }
 
-    let content = fs::read_to_string(timestamp_file)?;
-    let last_line = content.lines().last();
+    #[test]
+    fn test_parse_history_skips_damaged_lines() {
+        let history = parse_history("1736150400\ngarbage\n\n1736154000\n");
+        let seconds: Vec<i64> = history.iter().map(|dt| dt.timestamp()).collect();
+        assert_eq!(seconds, [1736150400, 1736154000]);
+    }
+
+    #[test]
+    fn test_parse_answers_skips_damaged_lines() {
+        let answers =
+            parse_answers("1736150400 taken\n1736150500 dance\ngarbage\n1736154000 skip\n");
+        let answers: Vec<(i64, Answer)> = answers
+            .iter()
+            .map(|(at, answer)| (at.timestamp(), *answer))
+            .collect();
+        assert_eq!(
+            answers,
+            [(1736150400, Answer::Taken), (1736154000, Answer::Skip)]
+        );
+    }
 
-    match last_line {
-        Some(line) => {
-            let timestamp = line.trim().parse::<i64>()?;
-            let dt = DateTime::from_timestamp(timestamp, 0)
-                .ok_or("Invalid timestamp")?
-                .with_timezone(&Local);
-            Ok(Some(dt))
+    #[test]
+    fn test_answer_names_are_respond_arguments() {
+        use clap::ValueEnum;
+        // Notification buttons run `szmer respond <name>`
+        for answer in Answer::value_variants() {
+            let value = answer.to_possible_value().unwrap();
+            assert_eq!(value.get_name(), answer.name());
         }
-        None => Ok(None),
+    }
+
+    #[test]
+    fn test_runtime_dir_prefers_xdg_runtime_dir() {
+        let dir = runtime_dir_from(Some("/run/user/1000".into()), fallback).unwrap();
+        assert_eq!(dir, PathBuf::from("/run/user/1000/szmer"));
+    }
+
+    #[test]
+    fn test_runtime_dir_falls_back_to_cache() {
+        let expected = PathBuf::from("/home/me/.cache/szmer");
+        assert_eq!(runtime_dir_from(None, fallback).unwrap(), expected);
+        // Relative values are invalid per the XDG spec
+        assert_eq!(
+            runtime_dir_from(Some("run/user".into()), fallback).unwrap(),
+            expected
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_private_dir_restricts_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = env::temp_dir().join(format!("szmer-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+
+        create_private_dir(&dir).unwrap();
+        let mode = fs::metadata(&dir).unwrap().permissions().mode();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(mode & 0o777, 0o700);
     }
 }
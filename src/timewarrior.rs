@@ -1,7 +1,7 @@
+use crate::command::SystemCommandRunner;
 use crate::config::TimewarriorConfig;
 use dialoguer::Confirm;
 use std::path::PathBuf;
-use std::process::Command;
 
 /// Status information about timewarrior integration
 #[derive(Debug)]
@@ -20,12 +20,12 @@ pub struct Status {
 /// # Examples
 ///
 /// ```
-/// if timewarrior::is_installed() {
+/// if timewarrior::is_installed(&ProcessRunner) {
 ///     println!("Timewarrior is available");
 /// }
 /// ```
-pub fn is_installed() -> bool {
-    get_binary_path().is_some()
+pub fn is_installed(runner: &dyn SystemCommandRunner) -> bool {
+    get_binary_path(runner).is_some()
 }
 
 /// Get the absolute path to the timewarrior binary
@@ -38,24 +38,16 @@ pub fn is_installed() -> bool {
 /// # Examples
 ///
 /// ```
-/// if let Some(path) = timewarrior::get_binary_path() {
+/// if let Some(path) = timewarrior::get_binary_path(&ProcessRunner) {
 ///     println!("Timewarrior found at: {}", path.display());
 /// }
 /// ```
-pub fn get_binary_path() -> Option<PathBuf> {
-    Command::new("which")
-        .arg("timew")
-        .output()
+pub fn get_binary_path(runner: &dyn SystemCommandRunner) -> Option<PathBuf> {
+    runner
+        .run("which", &["timew"])
         .ok()
-        .and_then(|output| {
-            if output.status.success() {
-                String::from_utf8(output.stdout)
-                    .ok()
-                    .map(|s| PathBuf::from(s.trim()))
-            } else {
-                None
-            }
-        })
+        .filter(|output| output.success)
+        .map(|output| PathBuf::from(output.stdout.trim()))
 }
 
 /// Check if there's currently an active time tracking session
@@ -70,23 +62,22 @@ pub fn get_binary_path() -> Option<PathBuf> {
 /// # Examples
 ///
 /// ```
-/// match timewarrior::is_tracking_active() {
+/// match timewarrior::is_tracking_active(&ProcessRunner) {
 ///     Ok(true) => println!("Currently tracking time"),
 ///     Ok(false) => println!("Not tracking"),
 ///     Err(e) => eprintln!("Error checking status: {}", e),
 /// }
 /// ```
-pub fn is_tracking_active() -> Result<bool, Box<dyn std::error::Error>> {
-    let output = Command::new("timew")
-        .arg("get")
-        .arg("dom.active")
-        .output()?;
+pub fn is_tracking_active(
+    runner: &dyn SystemCommandRunner,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let output = runner.run("timew", &["get", "dom.active"])?;
 
-    if !output.status.success() {
+    if !output.success {
         return Err("Failed to query timewarrior status".into());
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim() == "1")
+    Ok(output.stdout.trim() == "1")
 }
 
 /// Determine if a notification should be sent based on timewarrior status
@@ -110,23 +101,26 @@ pub fn is_tracking_active() -> Result<bool, Box<dyn std::error::Error>> {
 ///
 /// ```
 /// let config = TimewarriorConfig { enabled: true };
-/// if timewarrior::should_send_notification(&config) {
+/// if timewarrior::should_send_notification(&config, &ProcessRunner) {
 ///     notification::send(...);
 /// }
 /// ```
-pub fn should_send_notification(config: &TimewarriorConfig) -> bool {
+pub fn should_send_notification(
+    config: &TimewarriorConfig,
+    runner: &dyn SystemCommandRunner,
+) -> bool {
     // Integration disabled - always notify
     if !config.enabled {
         return true;
     }
 
     // Timewarrior no longer available - fall back to always notifying
-    if !is_installed() {
+    if !is_installed(runner) {
         return true;
     }
 
     // Check active status - on error, assume active (fail-safe: send notification)
-    is_tracking_active().unwrap_or(true)
+    is_tracking_active(runner).unwrap_or(true)
 }
 
 /// Interactively prompt the user to configure timewarrior integration
@@ -143,18 +137,20 @@ pub fn should_send_notification(config: &TimewarriorConfig) -> bool {
 /// # Examples
 ///
 /// ```
-/// let config = timewarrior::prompt_for_configuration()?;
+/// let config = timewarrior::prompt_for_configuration(&ProcessRunner)?;
 /// println!("Timewarrior enabled: {}", config.enabled);
 /// ```
-pub fn prompt_for_configuration() -> Result<TimewarriorConfig, Box<dyn std::error::Error>> {
+pub fn prompt_for_configuration(
+    runner: &dyn SystemCommandRunner,
+) -> Result<TimewarriorConfig, Box<dyn std::error::Error>> {
     // Check if timewarrior is installed
-    if !is_installed() {
+    if !is_installed(runner) {
         // Not found, return default (disabled)
         return Ok(TimewarriorConfig::default());
     }
 
     // Display detection info
-    if let Some(path) = get_binary_path() {
+    if let Some(path) = get_binary_path(runner) {
         println!("\n✓ Timewarrior detected at {}", path.display());
     }
 
@@ -190,7 +186,7 @@ pub fn prompt_for_configuration() -> Result<TimewarriorConfig, Box<dyn std::erro
 /// # Examples
 ///
 /// ```
-/// let status = timewarrior::get_status();
+/// let status = timewarrior::get_status(&ProcessRunner);
 /// if status.is_installed {
 ///     println!("Installed at: {}", status.binary_path.unwrap().display());
 ///     match status.is_tracking {
@@ -200,11 +196,11 @@ pub fn prompt_for_configuration() -> Result<TimewarriorConfig, Box<dyn std::erro
 ///     }
 /// }
 /// ```
-pub fn get_status() -> Status {
-    let is_installed = is_installed();
-    let binary_path = get_binary_path();
+pub fn get_status(runner: &dyn SystemCommandRunner) -> Status {
+    let is_installed = is_installed(runner);
+    let binary_path = get_binary_path(runner);
     let is_tracking = if is_installed {
-        is_tracking_active().ok()
+        is_tracking_active(runner).ok()
     } else {
         None
     };
@@ -215,3 +211,50 @@ pub fn get_status() -> Status {
         is_tracking,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+
+    fn installed() -> RecordingRunner {
+        RecordingRunner::default().reply("which timew", true, "/usr/bin/timew\n")
+    }
+
+    #[test]
+    fn test_get_binary_path() {
+        assert_eq!(
+            get_binary_path(&installed()),
+            Some(PathBuf::from("/usr/bin/timew"))
+        );
+        assert_eq!(get_binary_path(&RecordingRunner::default()), None);
+    }
+
+    #[test]
+    fn test_should_send_only_while_tracking() {
+        let config = TimewarriorConfig { enabled: true };
+
+        let tracking = installed().reply("timew get dom.active", true, "1\n");
+        assert!(should_send_notification(&config, &tracking));
+
+        let idle = installed().reply("timew get dom.active", true, "0\n");
+        assert!(!should_send_notification(&config, &idle));
+    }
+
+    #[test]
+    fn test_should_send_when_disabled_without_running_anything() {
+        let runner = RecordingRunner::default();
+        assert!(should_send_notification(
+            &TimewarriorConfig::default(),
+            &runner
+        ));
+        assert!(runner.calls().is_empty());
+    }
+
+    #[test]
+    fn test_should_send_when_status_unknown() {
+        // Installed, but `timew get` fails: fail safe and notify
+        let config = TimewarriorConfig { enabled: true };
+        assert!(should_send_notification(&config, &installed()));
+    }
+}
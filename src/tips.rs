@@ -0,0 +1,719 @@
+use crate::command::{ProcessRunner, SystemCommandRunner};
+use crate::config::{Config, TipSource};
+use crate::i18n::{self, tr};
+use crate::plugin::{self, Role};
+use crate::random;
+use crate::timestamp;
+use clap::ValueEnum;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Message ids of the wellness tips by category, translated in `locales/*/szmer.ftl`
+const WELLNESS_TIPS: &[(TipCategory, &[&str])] = &[
+    (
+        TipCategory::Movement,
+        &["tip-walk", "tip-squats", "tip-shake", "tip-arm-circles"],
+    ),
+    (
+        TipCategory::Stretching,
+        &[
+            "tip-shoulder-rolls",
+            "tip-stretch-arms",
+            "tip-neck",
+            "tip-wrists",
+            "tip-twist",
+        ],
+    ),
+    (TipCategory::Eyes, &["tip-look-far", "tip-face"]),
+    (TipCategory::Breathing, &["tip-breathe", "tip-temples"]),
+    (TipCategory::Posture, &["tip-posture", "tip-screen-height"]),
+    (TipCategory::Hydration, &["tip-water", "tip-tea"]),
+    (TipCategory::Refresh, &["tip-snack", "tip-fresh-air"]),
+];
+
+/// Tips of your own next to the config file, one per line
+const CUSTOM_TIPS_FILE: &str = "tips.txt";
+
+/// Key of the link used for tips without a category or a link of their own
+pub const DEFAULT_LINK: &str = "default";
+
+/// What a tip asks you to do, used to pick the link a reminder opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TipCategory {
+    Movement,
+    Stretching,
+    Eyes,
+    Breathing,
+    Posture,
+    Hydration,
+    Refresh,
+}
+
+impl TipCategory {
+    pub const ALL: &[TipCategory] = &[
+        Self::Movement,
+        Self::Stretching,
+        Self::Eyes,
+        Self::Breathing,
+        Self::Posture,
+        Self::Hydration,
+        Self::Refresh,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Movement => "movement",
+            Self::Stretching => "stretching",
+            Self::Eyes => "eyes",
+            Self::Breathing => "breathing",
+            Self::Posture => "posture",
+            Self::Hydration => "hydration",
+            Self::Refresh => "refresh",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|category| category.as_str() == name)
+    }
+}
+
+/// A tip to show, with its category when the source knows it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tip {
+    pub text: String,
+    pub category: Option<TipCategory>,
+}
+
+impl Tip {
+    /// Parse a line of a tip list, which may start with a `[category]` tag
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tip = Tip::parse("[eyes] Look out of the window");
+    /// assert_eq!(tip.category, Some(TipCategory::Eyes));
+    /// assert_eq!(tip.text, "Look out of the window");
+    /// ```
+    pub fn parse(line: &str) -> Self {
+        let tagged = line.strip_prefix('[').and_then(|rest| {
+            let (name, text) = rest.split_once(']')?;
+            Some((TipCategory::from_name(name.trim())?, text.trim()))
+        });
+
+        match tagged {
+            Some((category, text)) => Self {
+                text: text.to_string(),
+                category: Some(category),
+            },
+            None => Self {
+                text: line.to_string(),
+                category: None,
+            },
+        }
+    }
+}
+
+/// How long a downloaded tip list is used before fetching it again
+const URL_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A source of the tip shown in a break reminder
+pub trait TipProvider {
+    /// Short name used in warnings
+    fn name(&self) -> String;
+
+    /// Pick a tip with `rng`, of `category` when given, or explain why there's none
+    fn tip(
+        &self,
+        runner: &dyn SystemCommandRunner,
+        rng: &mut dyn RngCore,
+        category: Option<TipCategory>,
+    ) -> Result<Tip, Box<dyn std::error::Error>>;
+}
+
+/// The translated wellness tips that ship with szmer
+///
+/// Categories come up as often as their `tips.weights` say, a tip within
+/// a category is picked at random.
+#[derive(Default)]
+pub struct BuiltinTips {
+    pub weights: BTreeMap<TipCategory, u32>,
+}
+
+impl BuiltinTips {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            weights: config.tip_weights.clone(),
+        }
+    }
+
+    /// Category of the next tip, drawn by weight
+    ///
+    /// Every category counts once by default and a weight of 0 turns it
+    /// off. With all of them off, each comes up as often as the others.
+    fn category(&self, rng: &mut dyn RngCore) -> TipCategory {
+        TipCategory::ALL
+            .choose_weighted(rng, |category| weight(&self.weights, *category))
+            .or_else(|_| TipCategory::ALL.choose_weighted(rng, |_| 1))
+            .copied()
+            .expect("there are tip categories")
+    }
+}
+
+impl TipProvider for BuiltinTips {
+    fn name(&self) -> String {
+        "builtin".to_string()
+    }
+
+    fn tip(
+        &self,
+        _runner: &dyn SystemCommandRunner,
+        rng: &mut dyn RngCore,
+        category: Option<TipCategory>,
+    ) -> Result<Tip, Box<dyn std::error::Error>> {
+        let category = category.unwrap_or_else(|| self.category(rng));
+        let id = builtin_tips(category)
+            .choose(rng)
+            .expect("every category has built-in tips");
+        Ok(Tip {
+            text: tr(id),
+            category: Some(category),
+        })
+    }
+}
+
+/// How often tips of `category` come up next to the others, see `tips.weights`
+pub fn weight(weights: &BTreeMap<TipCategory, u32>, category: TipCategory) -> u32 {
+    weights.get(&category).copied().unwrap_or(1)
+}
+
+/// Message ids of the built-in tips of `category`
+fn builtin_tips(category: TipCategory) -> &'static [&'static str] {
+    WELLNESS_TIPS
+        .iter()
+        .find(|(tip_category, _)| *tip_category == category)
+        .map_or(&[], |(_, ids)| ids)
+}
+
+/// A random line of a local file
+pub struct FileTips {
+    pub path: PathBuf,
+}
+
+impl TipProvider for FileTips {
+    fn name(&self) -> String {
+        format!("file {}", self.path.display())
+    }
+
+    fn tip(
+        &self,
+        _runner: &dyn SystemCommandRunner,
+        rng: &mut dyn RngCore,
+        category: Option<TipCategory>,
+    ) -> Result<Tip, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read {}: {e}", self.path.display()))?;
+        pick_tip(&content, category, rng)
+            .ok_or_else(|| format!("{} has no {}", self.path.display(), describe(category)).into())
+    }
+}
+
+/// Whatever a shell command prints
+pub struct CommandTips {
+    pub command: String,
+}
+
+impl TipProvider for CommandTips {
+    fn name(&self) -> String {
+        format!("command '{}'", self.command)
+    }
+
+    fn tip(
+        &self,
+        runner: &dyn SystemCommandRunner,
+        _rng: &mut dyn RngCore,
+        category: Option<TipCategory>,
+    ) -> Result<Tip, Box<dyn std::error::Error>> {
+        let output = runner
+            .run("sh", &["-c", &self.command])
+            .map_err(|e| format!("Failed to run '{}': {e}", self.command))?;
+
+        if !output.success {
+            return Err(format!("'{}' failed: {}", self.command, output.stderr.trim()).into());
+        }
+
+        let tip = output.stdout.trim();
+        if tip.is_empty() {
+            return Err(format!("'{}' printed no tip", self.command).into());
+        }
+        // A command prints one tip, so it either fits the category or not
+        let tip = Tip::parse(tip);
+        if category.is_some() && tip.category != category {
+            return Err(format!("'{}' printed no {}", self.command, describe(category)).into());
+        }
+        Ok(tip)
+    }
+}
+
+/// A random line of a remote list, cached so reminders work offline
+///
+/// The list is fetched at most once a day. When fetching fails the last
+/// downloaded copy is used, however old.
+pub struct UrlTips {
+    pub url: String,
+    pub cache_path: PathBuf,
+}
+
+impl UrlTips {
+    /// Cache the list under `~/.cache/szmer/`, one file per URL
+    pub fn new(url: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        let cache_path =
+            timestamp::get_cache_dir()?.join(format!("tips-{:016x}.txt", hasher.finish()));
+
+        Ok(Self {
+            url: url.to_string(),
+            cache_path,
+        })
+    }
+
+    fn fetch(
+        &self,
+        runner: &dyn SystemCommandRunner,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let output = runner
+            .run(
+                "curl",
+                &[
+                    "--silent",
+                    "--show-error",
+                    "--fail",
+                    "--location",
+                    "--max-time",
+                    "5",
+                    &self.url,
+                ],
+            )
+            .map_err(|e| format!("Failed to run curl: {e}"))?;
+
+        if !output.success {
+            return Err(format!("Failed to fetch {}: {}", self.url, output.stderr.trim()).into());
+        }
+        if pick_tip(&output.stdout, None, &mut random::rng()).is_none() {
+            return Err(format!("{} has no tips", self.url).into());
+        }
+
+        if let Some(parent) = self.cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.cache_path, &output.stdout)?;
+        Ok(output.stdout)
+    }
+}
+
+impl TipProvider for UrlTips {
+    fn name(&self) -> String {
+        self.url.clone()
+    }
+
+    fn tip(
+        &self,
+        runner: &dyn SystemCommandRunner,
+        rng: &mut dyn RngCore,
+        category: Option<TipCategory>,
+    ) -> Result<Tip, Box<dyn std::error::Error>> {
+        let content = if is_fresh(&self.cache_path, SystemTime::now()) {
+            fs::read_to_string(&self.cache_path)?
+        } else {
+            match self.fetch(runner) {
+                Ok(content) => content,
+                Err(e) => match fs::read_to_string(&self.cache_path) {
+                    Ok(stale) => {
+                        eprintln!("Warning: {e}, using the cached tips");
+                        stale
+                    }
+                    Err(_) => return Err(e),
+                },
+            }
+        };
+
+        pick_tip(&content, category, rng)
+            .ok_or_else(|| format!("{} has no {}", self.url, describe(category)).into())
+    }
+}
+
+/// Provider for the configured tip source
+pub fn provider(config: &Config) -> Result<Box<dyn TipProvider>, Box<dyn std::error::Error>> {
+    Ok(match &config.tips {
+        TipSource::Builtin => Box::new(BuiltinTips::new(config)),
+        TipSource::File(path) => Box::new(FileTips { path: path.clone() }),
+        TipSource::Command(command) => Box::new(CommandTips {
+            command: command.clone(),
+        }),
+        TipSource::Url(url) => Box::new(UrlTips::new(url)?),
+    })
+}
+
+/// Tip for the next reminder, or of `category` when given
+///
+/// Tip plugins answer first, then the configured source. The built-in
+/// tips are the last resort, so a broken source never leaves a reminder
+/// without a tip. `rng` picks among the source's tips.
+pub fn tip(config: &Config, rng: &mut dyn RngCore, category: Option<TipCategory>) -> Tip {
+    let runner = ProcessRunner;
+
+    if let Some(tip) = plugin_tip(config, &runner)
+        .map(|tip| Tip::parse(&tip))
+        .filter(|tip| category.is_none() || tip.category == category)
+    {
+        return tip;
+    }
+
+    if config.tips.is_builtin() {
+        if let Some(tip) = custom_tip(config, &custom_tips(config), rng, category) {
+            return tip;
+        }
+    } else {
+        match provider(config) {
+            Ok(provider) => match provider.tip(&runner, rng, category) {
+                Ok(tip) => return tip,
+                Err(e) => eprintln!("Warning: tips from {}: {e}", provider.name()),
+            },
+            Err(e) => eprintln!("Warning: {e}"),
+        }
+    }
+
+    BuiltinTips::new(config)
+        .tip(&runner, rng, category)
+        .expect("built-in tips are always available")
+}
+
+/// Tips from `~/.config/szmer/tips.txt` and `custom_tips` in the config
+///
+/// The file reads like a `file:` tip source, with `#` comments and
+/// `[category]` tags, and the config's tips may be tagged too. A list for
+/// the language szmer speaks, like `tips.pl.txt`, comes first.
+pub fn custom_tips(config: &Config) -> Vec<Tip> {
+    let file = Config::get_config_path()
+        .ok()
+        .and_then(|path| {
+            let translated = path.with_file_name(custom_tips_file(&i18n::language()));
+            fs::read_to_string(translated)
+                .or_else(|_| fs::read_to_string(path.with_file_name(CUSTOM_TIPS_FILE)))
+                .ok()
+        })
+        .unwrap_or_default();
+    parse_tips(&file)
+        .into_iter()
+        .chain(parse_tips(&config.custom_tips.join("\n")))
+        .collect()
+}
+
+/// `tips.txt` for `language`, e.g. `tips.pl.txt`
+fn custom_tips_file(language: &str) -> String {
+    format!("tips.{language}.txt")
+}
+
+/// One of `custom`, `None` to show a built-in tip instead
+///
+/// Mixed with the built-in tips, each tip is as likely as any other, so
+/// a few custom tips don't crowd out the rest. With
+/// `replace_builtin_tips` only custom tips are shown, and the built-in
+/// ones only stand in when none has the category asked for. Tagged tips
+/// of a category turned off in `tips.weights` are left out.
+fn custom_tip(
+    config: &Config,
+    custom: &[Tip],
+    rng: &mut dyn RngCore,
+    category: Option<TipCategory>,
+) -> Option<Tip> {
+    // Categories with a weight of 0 are off unless asked for
+    let matches = |tip_category: Option<TipCategory>| match category {
+        Some(category) => tip_category == Some(category),
+        None => {
+            tip_category.is_none_or(|tip_category| weight(&config.tip_weights, tip_category) > 0)
+        }
+    };
+    let custom: Vec<&Tip> = custom.iter().filter(|tip| matches(tip.category)).collect();
+    if custom.is_empty() {
+        return None;
+    }
+
+    if !config.replace_builtin_tips {
+        let builtin: usize = WELLNESS_TIPS
+            .iter()
+            .filter(|(tip_category, _)| matches(Some(*tip_category)))
+            .map(|(_, ids)| ids.len())
+            .sum();
+        let pick = rng.next_u64() % (custom.len() + builtin) as u64;
+        if pick >= custom.len() as u64 {
+            return None;
+        }
+    }
+    custom.choose(rng).map(|tip| (*tip).clone())
+}
+
+/// Link a reminder showing a tip of `category` opens
+///
+/// Falls back to the `default` link when the category has none.
+pub fn link(config: &Config, category: Option<TipCategory>) -> Option<String> {
+    category
+        .and_then(|category| config.links.get(category.as_str()))
+        .or_else(|| config.links.get(DEFAULT_LINK))
+        .cloned()
+}
+
+/// Tip from the first enabled tip plugin that answers
+fn plugin_tip(config: &Config, runner: &dyn SystemCommandRunner) -> Option<String> {
+    plugin::enabled_with_role(config, Role::Tip, runner)
+        .iter()
+        .find_map(|plugin| match plugin.tip(runner) {
+            Ok(tip) => Some(tip),
+            Err(e) => {
+                eprintln!("Warning: {e}");
+                None
+            }
+        })
+}
+
+/// A random tip from a list with one tip per line, of `category` when given
+///
+/// Blank lines and lines starting with `#` are skipped, so lists can
+/// carry comments.
+fn pick_tip(content: &str, category: Option<TipCategory>, rng: &mut dyn RngCore) -> Option<Tip> {
+    let tips: Vec<Tip> = parse_tips(content)
+        .into_iter()
+        .filter(|tip| category.is_none() || tip.category == category)
+        .collect();
+
+    tips.choose(rng).cloned()
+}
+
+/// Every tip in a list with one tip per line, without blanks and comments
+fn parse_tips(content: &str) -> Vec<Tip> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(Tip::parse)
+        .collect()
+}
+
+/// "tips", or "eyes tips" when looking for a category
+fn describe(category: Option<TipCategory>) -> String {
+    match category {
+        Some(category) => format!("{} tips", category.as_str()),
+        None => "tips".to_string(),
+    }
+}
+
+fn is_fresh(path: &Path, now: SystemTime) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age < URL_CACHE_TTL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::RecordingRunner;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const URL: &str = "https://example.com/tips.txt";
+    const CURL: &str =
+        "curl --silent --show-error --fail --location --max-time 5 https://example.com/tips.txt";
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(0)
+    }
+
+    fn url_tips(dir: &tempfile::TempDir) -> UrlTips {
+        UrlTips {
+            url: URL.to_string(),
+            cache_path: dir.path().join("tips.txt"),
+        }
+    }
+
+    #[test]
+    fn test_pick_tip_skips_comments_and_blanks() {
+        assert_eq!(
+            pick_tip("# curated by HR\n\n  Drink water  \n", None, &mut rng()).map(|tip| tip.text),
+            Some("Drink water".to_string())
+        );
+        assert_eq!(pick_tip("# only a comment\n\n", None, &mut rng()), None);
+    }
+
+    #[test]
+    fn test_pick_tip_filters_by_category() {
+        let list = "[eyes] Look out of the window\n[movement] Take the stairs\nDrink water\n";
+        let tip = pick_tip(list, Some(TipCategory::Movement), &mut rng()).unwrap();
+        assert_eq!(tip.text, "Take the stairs");
+        assert_eq!(
+            pick_tip(list, Some(TipCategory::Breathing), &mut rng()),
+            None
+        );
+
+        let builtin = BuiltinTips::default()
+            .tip(
+                &RecordingRunner::default(),
+                &mut rng(),
+                Some(TipCategory::Eyes),
+            )
+            .unwrap();
+        assert_eq!(builtin.category, Some(TipCategory::Eyes));
+    }
+
+    #[test]
+    fn test_custom_tips_mix_in_or_replace_the_builtin_ones() {
+        let custom = vec![
+            Tip::parse("[eyes] Blink slowly ten times"),
+            Tip::parse("Water the plants"),
+        ];
+        let mut config = Config::default();
+        let mut rng = rng();
+
+        let shown = (0..200)
+            .filter(|_| custom_tip(&config, &custom, &mut rng, None).is_some())
+            .count();
+        // 2 of the 21 tips are custom
+        assert!((5..60).contains(&shown), "{shown}");
+
+        config.replace_builtin_tips = true;
+        assert!((0..20).all(|_| custom_tip(&config, &custom, &mut rng, None).is_some()));
+        assert_eq!(
+            custom_tip(&config, &custom, &mut rng, Some(TipCategory::Eyes)).map(|tip| tip.text),
+            Some("Blink slowly ten times".to_string())
+        );
+        // The built-in tips stand in for categories the custom ones lack
+        assert_eq!(
+            custom_tip(&config, &custom, &mut rng, Some(TipCategory::Movement)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tip_weights_pick_categories() {
+        let runner = RecordingRunner::default();
+        let mut rng = rng();
+        let mut weights: BTreeMap<_, _> = TipCategory::ALL
+            .iter()
+            .map(|category| (*category, 0))
+            .collect();
+        weights.insert(TipCategory::Eyes, 3);
+        weights.insert(TipCategory::Posture, 1);
+        let builtin = BuiltinTips { weights };
+
+        let eyes = (0..200)
+            .map(|_| builtin.tip(&runner, &mut rng, None).unwrap().category)
+            .filter(|category| *category == Some(TipCategory::Eyes))
+            .count();
+        assert!((120..190).contains(&eyes), "{eyes}");
+        // Asking for a category overrides its weight
+        let movement = builtin.tip(&runner, &mut rng, Some(TipCategory::Movement));
+        assert_eq!(movement.unwrap().category, Some(TipCategory::Movement));
+
+        // Custom tips of a category that's off are left out
+        let config = Config {
+            replace_builtin_tips: true,
+            tip_weights: BTreeMap::from([(TipCategory::Movement, 0)]),
+            ..Config::default()
+        };
+        let custom = vec![Tip::parse("[movement] Do 20 squats")];
+        assert_eq!(custom_tip(&config, &custom, &mut rng, None), None);
+    }
+
+    #[test]
+    fn test_tip_category_tags() {
+        let tip = Tip::parse("[stretching] Roll your shoulders");
+        assert_eq!(tip.category, Some(TipCategory::Stretching));
+        assert_eq!(tip.text, "Roll your shoulders");
+
+        // Unknown tags are part of the tip
+        assert_eq!(Tip::parse("[wip] Drink water").text, "[wip] Drink water");
+        assert_eq!(Tip::parse("Drink water").category, None);
+    }
+
+    #[test]
+    fn test_every_category_has_a_link_key() {
+        for category in TipCategory::ALL {
+            let key = format!("links.{}", category.as_str());
+            assert!(crate::config::is_config_key(&key), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_link_falls_back_to_default() {
+        let mut config = Config::default();
+        assert_eq!(link(&config, Some(TipCategory::Eyes)), None);
+
+        config.links.insert(
+            DEFAULT_LINK.to_string(),
+            "https://wiki.example.com/wellness".to_string(),
+        );
+        config.links.insert(
+            "eyes".to_string(),
+            "https://example.com/20-20-20".to_string(),
+        );
+        assert_eq!(
+            link(&config, Some(TipCategory::Eyes)).as_deref(),
+            Some("https://example.com/20-20-20")
+        );
+        assert_eq!(
+            link(&config, Some(TipCategory::Movement)).as_deref(),
+            Some("https://wiki.example.com/wellness")
+        );
+        assert_eq!(
+            link(&config, None).as_deref(),
+            Some("https://wiki.example.com/wellness")
+        );
+    }
+
+    #[test]
+    fn test_url_tips_are_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let tips = url_tips(&dir);
+
+        let runner = RecordingRunner::default().reply(CURL, true, "Stretch\n");
+        assert_eq!(tips.tip(&runner, &mut rng(), None).unwrap().text, "Stretch");
+        assert_eq!(tips.tip(&runner, &mut rng(), None).unwrap().text, "Stretch");
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[test]
+    fn test_url_tips_fall_back_to_stale_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let tips = url_tips(&dir);
+        let offline = RecordingRunner::default().reply(CURL, false, "");
+
+        assert!(tips.tip(&offline, &mut rng(), None).is_err());
+
+        fs::write(&tips.cache_path, "Look away from the screen\n").unwrap();
+        let stale = SystemTime::now() - URL_CACHE_TTL - Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&tips.cache_path)
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+
+        assert_eq!(
+            tips.tip(&offline, &mut rng(), None).unwrap().text,
+            "Look away from the screen"
+        );
+        assert_eq!(offline.calls(), vec![CURL, CURL]);
+    }
+}
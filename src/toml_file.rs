@@ -0,0 +1,363 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt::Write;
+use toml_edit::{Document, Item};
+
+/// Parse a TOML document into the JSON value serde reads the config from
+pub fn parse(text: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let document = Document::parse(text)?;
+    Ok(table_value(document.as_table().iter()))
+}
+
+fn table_value<'a>(entries: impl Iterator<Item = (&'a str, &'a Item)>) -> Value {
+    let map = entries
+        .filter_map(|(key, item)| Some((key.to_string(), item_value(item)?)))
+        .collect();
+    Value::Object(map)
+}
+
+fn item_value(item: &Item) -> Option<Value> {
+    match item {
+        Item::None => None,
+        Item::Value(value) => Some(toml_value(value)),
+        Item::Table(table) => Some(table_value(table.iter())),
+        Item::ArrayOfTables(tables) => Some(Value::Array(
+            tables
+                .iter()
+                .map(|table| table_value(table.iter()))
+                .collect(),
+        )),
+    }
+}
+
+fn toml_value(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => Value::from(*f.value()),
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::String(d.value().to_string()),
+        toml_edit::Value::Array(array) => Value::Array(array.iter().map(toml_value).collect()),
+        toml_edit::Value::InlineTable(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.to_string(), toml_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Write `value` as a TOML document, keeping the comments of `previous`
+///
+/// Comments on their own lines stay above the key or table header they
+/// were above in `previous`, so hand-written notes survive `szmer config
+/// set`. Comments at the end of a line are dropped. TOML has no null, so
+/// keys without a value are left out.
+pub fn to_string(value: &Value, previous: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let Value::Object(root) = value else {
+        return Err("Only a table can be written as a TOML document".into());
+    };
+    let mut comments = comments_by_path(previous);
+    let mut out = String::new();
+
+    write_comments(&mut out, &mut comments, "");
+    start_section(&mut out);
+    write_table(&mut out, &mut comments, "", root)?;
+    write_comments(&mut out, &mut comments, END);
+    Ok(out)
+}
+
+/// Where comments after the last key go
+const END: &str = "\0end";
+
+fn write_table(
+    out: &mut String,
+    comments: &mut HashMap<String, Vec<String>>,
+    path: &str,
+    table: &Map<String, Value>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Keys of a table come before its sub-tables, or they'd belong to those
+    for (key, value) in table {
+        if value.is_null() || value.is_object() || is_array_of_tables(value) {
+            continue;
+        }
+        write_comments(out, comments, &join(path, key));
+        writeln!(out, "{} = {}", format_key(key), inline(value)?)?;
+    }
+
+    for (key, value) in table {
+        let child = join(path, key);
+        match value {
+            Value::Object(map) => {
+                start_section(out);
+                write_comments(out, comments, &child);
+                writeln!(out, "[{}]", dotted(path, key))?;
+                write_table(out, comments, &child, map)?;
+            }
+            Value::Array(items) if is_array_of_tables(value) => {
+                for (index, item) in items.iter().enumerate() {
+                    let Value::Object(map) = item else { continue };
+                    start_section(out);
+                    write_comments(out, comments, &format!("{child}[{index}]"));
+                    writeln!(out, "[[{}]]", dotted(path, key))?;
+                    write_table(out, comments, &child, map)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn is_array_of_tables(value: &Value) -> bool {
+    value
+        .as_array()
+        .is_some_and(|items| !items.is_empty() && items.iter().all(Value::is_object))
+}
+
+/// A blank line between sections
+fn start_section(out: &mut String) {
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn write_comments(out: &mut String, comments: &mut HashMap<String, Vec<String>>, path: &str) {
+    for comment in comments.remove(path).unwrap_or_default() {
+        out.push_str(&comment);
+        out.push('\n');
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Table header for `key` under `path`, with keys quoted where needed
+fn dotted(path: &str, key: &str) -> String {
+    path.split('.')
+        .filter(|part| !part.is_empty())
+        .chain(std::iter::once(key))
+        .map(format_key)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn format_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04X}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// `value` on a single line, arrays and tables inline
+fn inline(value: &Value) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(match value {
+        Value::Null => return Err("TOML has no null values".into()),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => i.to_string(),
+            (None, Some(_)) if n.is_u64() => {
+                return Err(format!("{n} is too large for TOML").into())
+            }
+            (None, Some(f)) => format!("{f:?}"),
+            (None, None) => n.to_string(),
+        },
+        Value::String(s) => quote(s),
+        Value::Array(items) => {
+            let items: Result<Vec<String>, _> = items.iter().map(inline).collect();
+            format!("[{}]", items?.join(", "))
+        }
+        Value::Object(map) => {
+            let entries: Result<Vec<String>, Box<dyn std::error::Error>> = map
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| Ok(format!("{} = {}", format_key(key), inline(value)?)))
+                .collect();
+            let entries = entries?;
+            if entries.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", entries.join(", "))
+            }
+        }
+    })
+}
+
+/// Comment lines of a TOML document, by the key or table header below them
+///
+/// Keys are dotted paths like `notification.tone`; tables of an array
+/// are numbered, e.g. `profiles[0]`. Comments above the first key belong
+/// to the file and are keyed by an empty path.
+fn comments_by_path(text: &str) -> HashMap<String, Vec<String>> {
+    let mut comments: HashMap<String, Vec<String>> = HashMap::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut table = String::new();
+    let mut array_tables: HashMap<String, usize> = HashMap::new();
+    let mut seen_key = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            pending.push(line.to_string());
+            continue;
+        }
+        if line.is_empty() {
+            // Comments at the top, set off by a blank line, describe the file
+            if !seen_key && !pending.is_empty() {
+                comments
+                    .entry(String::new())
+                    .or_default()
+                    .append(&mut pending);
+            }
+            continue;
+        }
+
+        let path = if let Some(header) = line
+            .strip_prefix("[[")
+            .and_then(|rest| rest.split_once("]]"))
+        {
+            table = normalize(header.0);
+            let index = array_tables.entry(table.clone()).or_insert(0);
+            let path = format!("{table}[{index}]");
+            *index += 1;
+            Some(path)
+        } else if let Some(header) = line.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            table = normalize(header.0);
+            Some(table.clone())
+        } else {
+            line.split_once('=')
+                .map(|(key, _)| join(&table, &normalize(key)))
+        };
+
+        if let Some(path) = path {
+            seen_key = true;
+            if !pending.is_empty() {
+                comments.entry(path).or_default().append(&mut pending);
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        // A file of only comments, like a fresh one, keeps them at the top
+        let path = if seen_key { END } else { "" };
+        comments
+            .entry(path.to_string())
+            .or_default()
+            .append(&mut pending);
+    }
+    comments
+}
+
+/// A dotted key or table header without quotes and spaces
+fn normalize(key: &str) -> String {
+    key.split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\''))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip_keeps_comments() {
+        let previous = "\
+# My szmer settings
+
+# Half an hour works best
+interval_seconds = 3600
+paused = false
+
+# Be nice
+[notification]
+tone = \"gentle\"
+
+[[profiles]]
+# Every hour
+name = \"stand\"
+";
+        let value = json!({
+            "interval_seconds": 1800,
+            "notification_sound": null,
+            "paused": false,
+            "notification": { "tone": "humorous" },
+            "links": { "default": "https://example.com/a \"b\"" },
+            "profiles": [
+                { "name": "stand", "interval_seconds": 3600 },
+                { "name": "eyes", "category": "eyes" }
+            ],
+            "delivery": { "chain": ["desktop", "terminal"] }
+        });
+
+        let text = to_string(&value, previous).unwrap();
+        // serde_json sorts keys, so szmer's own keys come out alphabetically
+        assert_eq!(
+            text,
+            "\
+# My szmer settings
+
+# Half an hour works best
+interval_seconds = 1800
+paused = false
+
+[delivery]
+chain = [\"desktop\", \"terminal\"]
+
+[links]
+default = \"https://example.com/a \\\"b\\\"\"
+
+# Be nice
+[notification]
+tone = \"humorous\"
+
+[[profiles]]
+interval_seconds = 3600
+# Every hour
+name = \"stand\"
+
+[[profiles]]
+category = \"eyes\"
+name = \"eyes\"
+"
+        );
+
+        let mut expected = value.clone();
+        expected
+            .as_object_mut()
+            .unwrap()
+            .remove("notification_sound");
+        assert_eq!(parse(&text).unwrap(), expected);
+    }
+}
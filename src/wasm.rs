@@ -0,0 +1,172 @@
+use std::path::Path;
+use wasmtime::{Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Instructions a single call may run, so a runaway loop can't hang szmer
+const FUEL: u64 = 100_000_000;
+
+/// Largest linear memory a plugin may grow to
+const MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Check that `path` is a module szmer can run
+pub fn validate(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let engine = engine()?;
+    let module = Module::from_file(&engine, path)?;
+    if let Some(import) = module.imports().next() {
+        return Err(format!(
+            "Plugin imports {}::{}, WASM plugins can't import anything",
+            import.module(),
+            import.name()
+        )
+        .into());
+    }
+    for export in ["memory", "alloc", "describe"] {
+        if module.get_export(export).is_none() {
+            return Err(format!("Plugin doesn't export {export}").into());
+        }
+    }
+    Ok(())
+}
+
+/// Run `action` of the module at `path`, returning its JSON reply
+///
+/// Plugins run inside wasmtime with nothing to import, so they can't start
+/// processes, open files or use the network. A module exports its `memory`,
+/// `alloc(len: i32) -> i32` making room for a request, and the actions
+/// `describe`, `gate` and `tip` as `(ptr: i32, len: i32) -> i64`. An action
+/// gets the request JSON at `ptr`, with `len` 0 when there is none, and
+/// returns where its JSON reply is, packed as `ptr << 32 | len`.
+pub fn call(
+    path: &Path,
+    action: &str,
+    request: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let engine = engine()?;
+    let module = Module::from_file(&engine, path)?;
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(MEMORY_BYTES)
+        .instances(1)
+        .build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits: &mut StoreLimits| limits);
+    store.set_fuel(FUEL)?;
+
+    // Nothing is linked in, a module with imports fails to instantiate
+    let instance = Linker::new(&engine).instantiate(&mut store, &module)?;
+    let (ptr, len) = match request {
+        Some(request) => write_request(&instance, &mut store, request)?,
+        None => (0, 0),
+    };
+
+    let function = instance
+        .get_typed_func::<(i32, i32), i64>(&mut store, action)
+        .map_err(|_| format!("Plugin doesn't export {action}"))?;
+    let reply = function.call(&mut store, (ptr, len))?;
+    read_reply(&instance, &mut store, reply)
+}
+
+fn engine() -> Result<Engine, Box<dyn std::error::Error>> {
+    let mut config = wasmtime::Config::new();
+    config.consume_fuel(true);
+    Ok(Engine::new(&config)?)
+}
+
+fn write_request(
+    instance: &Instance,
+    store: &mut Store<StoreLimits>,
+    request: &str,
+) -> Result<(i32, i32), Box<dyn std::error::Error>> {
+    let len = i32::try_from(request.len())?;
+    let alloc = instance.get_typed_func::<i32, i32>(&mut *store, "alloc")?;
+    let ptr = alloc.call(&mut *store, len)?;
+    memory(instance, store)?.write(&mut *store, usize::try_from(ptr)?, request.as_bytes())?;
+    Ok((ptr, len))
+}
+
+fn read_reply(
+    instance: &Instance,
+    store: &mut Store<StoreLimits>,
+    reply: i64,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let (ptr, len) = unpack(reply);
+    let mut bytes = vec![0; len];
+    memory(instance, store)?
+        .read(&*store, ptr, &mut bytes)
+        .map_err(|_| "Plugin replied with memory outside its own")?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+fn memory(
+    instance: &Instance,
+    store: &mut Store<StoreLimits>,
+) -> Result<wasmtime::Memory, Box<dyn std::error::Error>> {
+    Ok(instance
+        .get_memory(&mut *store, "memory")
+        .ok_or("Plugin doesn't export memory")?)
+}
+
+/// Split an action's return value into the reply's pointer and length
+fn unpack(reply: i64) -> (usize, usize) {
+    let reply = reply as u64;
+    ((reply >> 32) as usize, (reply & 0xffff_ffff) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const EXAMPLE: &str = include_str!("../examples/plugins/stretch.wat");
+
+    fn module(source: &str) -> tempfile::TempPath {
+        let file = tempfile::Builder::new().suffix(".wat").tempfile().unwrap();
+        fs::write(file.path(), source).unwrap();
+        file.into_temp_path()
+    }
+
+    #[test]
+    fn test_example_plugin_replies() {
+        let path = module(EXAMPLE);
+        validate(&path).unwrap();
+
+        let describe = call(&path, "describe", None).unwrap();
+        assert!(describe.contains(r#""roles":["gate","tip"]"#));
+        let verdict = call(&path, "gate", Some(r#"{"interval_seconds":60}"#)).unwrap();
+        assert!(verdict.contains(r#""open":false"#));
+        let verdict = call(&path, "gate", Some(r#"{"interval_seconds":3600}"#)).unwrap();
+        assert!(verdict.contains(r#""open":true"#));
+        assert!(call(&path, "tip", None).unwrap().starts_with(r#"{"tip":"#));
+    }
+
+    #[test]
+    fn test_imports_are_refused() {
+        let path = module(
+            r#"(module
+                 (import "env" "system" (func $system (param i32) (result i32)))
+                 (memory (export "memory") 1))"#,
+        );
+
+        let error = validate(&path).unwrap_err().to_string();
+        assert!(error.contains("can't import anything"), "{error}");
+        assert!(call(&path, "describe", None).is_err());
+    }
+
+    #[test]
+    fn test_runaway_plugin_runs_out_of_fuel() {
+        let path = module(
+            r#"(module
+                 (memory (export "memory") 1)
+                 (func (export "alloc") (param i32) (result i32) i32.const 0)
+                 (func (export "describe") (param i32 i32) (result i64)
+                   (loop $forever (br $forever))
+                   i64.const 0))"#,
+        );
+
+        assert!(call(&path, "describe", None).is_err());
+    }
+
+    #[test]
+    fn test_unpack() {
+        assert_eq!(unpack((1024 << 32) | 17), (1024, 17));
+        assert_eq!(unpack(0), (0, 0));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::control::Envelope;
+use std::sync::mpsc::Sender;
+
+#[cfg(feature = "watch")]
+use crate::config::Config;
+#[cfg(feature = "watch")]
+use crate::control::Request;
+#[cfg(feature = "watch")]
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+#[cfg(feature = "watch")]
+use std::path::PathBuf;
+#[cfg(feature = "watch")]
+use std::sync::mpsc;
+
+/// Watch the config file and ask the daemon to reload it whenever it changes
+///
+/// Watches the config directory rather than the file itself, so editors
+/// that save by replacing the file are picked up too. The returned watcher
+/// stops watching when dropped.
+#[cfg(feature = "watch")]
+pub fn watch_config(
+    events: Sender<Envelope>,
+) -> Result<RecommendedWatcher, Box<dyn std::error::Error>> {
+    let [config_path, legacy_path] = Config::config_files()?;
+    let config_dir = config_path
+        .parent()
+        .ok_or("Config path has no parent directory")?
+        .to_path_buf();
+    std::fs::create_dir_all(&config_dir)?;
+
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        let is_config = |path: &PathBuf| *path == config_path || *path == legacy_path;
+        if event.kind.is_access() || !event.paths.iter().any(is_config) {
+            return;
+        }
+
+        // The daemon logs what the reload applied, the reply isn't needed
+        let (reply_tx, _reply_rx) = mpsc::channel();
+        let _ = events.send((Request::Reload, reply_tx));
+    })?;
+
+    watcher.watch(&config_dir, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+#[cfg(not(feature = "watch"))]
+pub fn watch_config(_events: Sender<Envelope>) -> Result<(), Box<dyn std::error::Error>> {
+    Err("szmer was built without the 'watch' feature, send SIGHUP to reload".into())
+}
@@ -0,0 +1,959 @@
+use crate::config::Countdown;
+use crate::overlay::Look;
+use crate::timestamp;
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::os::fd::{AsFd, AsRawFd};
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::{
+    wl_buffer, wl_compositor, wl_keyboard, wl_output, wl_pointer, wl_registry, wl_seat, wl_shm,
+    wl_shm_pool, wl_surface,
+};
+use wayland_client::{delegate_noop, Connection, Dispatch, EventQueue, Proxy, QueueHandle, WEnum};
+use wayland_protocols::ext::idle_notify::v1::client::{
+    ext_idle_notification_v1, ext_idle_notifier_v1,
+};
+use wayland_protocols_wlr::layer_shell::v1::client::{zwlr_layer_shell_v1, zwlr_layer_surface_v1};
+
+/// Global of ext-idle-notify-v1, which reports when the seat goes idle
+pub const IDLE_NOTIFY: &str = "ext_idle_notifier_v1";
+
+/// Global of wlr-layer-shell, which lets the overlay cover whole outputs
+pub const LAYER_SHELL: &str = "zwlr_layer_shell_v1";
+
+/// Idle time the compositor waits for before it reports the seat idle
+const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Linux input event code of the Esc key
+const KEY_ESC: u32 = 1;
+
+/// Size of the grace countdown banner, at the top of the focused output
+const BANNER_SIZE: (u32, u32) = (460, 150);
+
+/// Interfaces the compositor offers, `None` outside a Wayland session
+pub fn globals() -> Option<Vec<String>> {
+    let connection = Connection::connect_to_env().ok()?;
+    let (globals, _queue) = registry_queue_init::<Globals>(&connection).ok()?;
+    Some(
+        globals
+            .contents()
+            .clone_list()
+            .into_iter()
+            .map(|global| global.interface)
+            .collect(),
+    )
+}
+
+struct Globals;
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for Globals {
+    fn event(
+        _: &mut Self,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+/// Follows ext-idle-notify-v1 from a thread of its own
+///
+/// The compositor only says when the seat goes idle and when it's back, so
+/// idle time is known to a client that stays connected, like the daemon.
+pub struct IdleMonitor {
+    idle: Arc<Mutex<Idle>>,
+}
+
+#[derive(Debug, Default)]
+struct Idle {
+    /// When the last input was, while the seat is idle
+    since: Option<Instant>,
+    /// Cleared when the compositor goes away
+    connected: bool,
+}
+
+impl IdleMonitor {
+    /// Start following the seat, `None` when the compositor can't report idleness
+    pub fn start() -> Option<Self> {
+        let connection = Connection::connect_to_env().ok()?;
+        let (globals, mut queue) = registry_queue_init::<IdleState>(&connection).ok()?;
+        let qh = queue.handle();
+        let seat: wl_seat::WlSeat = globals.bind(&qh, 1..=1, ()).ok()?;
+        let notifier: ext_idle_notifier_v1::ExtIdleNotifierV1 =
+            globals.bind(&qh, 1..=1, ()).ok()?;
+        notifier.get_idle_notification(IDLE_TIMEOUT.as_millis() as u32, &seat, &qh, ());
+
+        let idle = Arc::new(Mutex::new(Idle {
+            since: None,
+            connected: true,
+        }));
+        let mut state = IdleState { idle: idle.clone() };
+        thread::spawn(move || {
+            while queue.blocking_dispatch(&mut state).is_ok() {}
+            if let Ok(mut idle) = state.idle.lock() {
+                *idle = Idle::default();
+            }
+        });
+        Some(Self { idle })
+    }
+
+    /// Seconds since the last input, `None` once the compositor is gone
+    pub fn idle_seconds(&self) -> Option<u64> {
+        let idle = self.idle.lock().ok()?;
+        if !idle.connected {
+            return None;
+        }
+        Some(idle.since.map_or(0, |since| since.elapsed().as_secs()))
+    }
+}
+
+struct IdleState {
+    idle: Arc<Mutex<Idle>>,
+}
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for IdleState {
+    fn event(
+        _: &mut Self,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ext_idle_notification_v1::ExtIdleNotificationV1, ()> for IdleState {
+    fn event(
+        state: &mut Self,
+        _: &ext_idle_notification_v1::ExtIdleNotificationV1,
+        event: ext_idle_notification_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let Ok(mut idle) = state.idle.lock() else {
+            return;
+        };
+        match event {
+            // The seat has been idle for the timeout already
+            ext_idle_notification_v1::Event::Idled => {
+                idle.since = Instant::now().checked_sub(IDLE_TIMEOUT);
+            }
+            ext_idle_notification_v1::Event::Resumed => idle.since = None,
+            _ => {}
+        }
+    }
+}
+
+delegate_noop!(IdleState: ignore wl_seat::WlSeat);
+delegate_noop!(IdleState: ext_idle_notifier_v1::ExtIdleNotifierV1);
+
+/// Where the overlay goes on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Surface {
+    /// A banner at the top of the focused output, clicked to postpone
+    Banner,
+    /// Every output, covered edge to edge, with Esc or the button postponing
+    Cover,
+}
+
+/// Draws the overlay with wlr-layer-shell, above every window
+pub struct LayerShell {
+    font: FontVec,
+}
+
+impl LayerShell {
+    /// `None` without a Wayland compositor offering layer-shell, or a font to draw with
+    pub fn connect(font: &Path) -> Option<Self> {
+        globals()?.iter().find(|global| *global == LAYER_SHELL)?;
+        let font = FontVec::try_from_vec(fs::read(font).ok()?).ok()?;
+        Some(Self { font })
+    }
+
+    /// Count `seconds` down with the text `text(left)`, like `overlay::count_down`
+    ///
+    /// With `Surface::Cover`, outputs plugged in during the countdown are
+    /// covered too. Returns whether the overlay was postponed.
+    pub fn count_down(
+        &self,
+        look: &Look,
+        title: &str,
+        surface: Surface,
+        postpone_label: Option<&str>,
+        seconds: u64,
+        text: impl Fn(u64) -> String,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let connection = Connection::connect_to_env()?;
+        let (globals, mut queue) = registry_queue_init::<Overlay>(&connection)?;
+        let qh = queue.handle();
+
+        let layer_shell: zwlr_layer_shell_v1::ZwlrLayerShellV1 = globals.bind(&qh, 1..=4, ())?;
+        let mut overlay = Overlay {
+            compositor: globals.bind(&qh, 1..=4, ())?,
+            shm: globals.bind(&qh, 1..=1, ())?,
+            layer_shell,
+            surface,
+            screens: Vec::new(),
+            new_outputs: Vec::new(),
+            pointer: None,
+            postpone: postpone_label.is_some(),
+            postponed: false,
+        };
+        // Without a seat there's no postponing, the overlay still counts down
+        let _seat = globals.bind::<wl_seat::WlSeat, _, _>(&qh, 1..=5, ()).ok();
+
+        match surface {
+            Surface::Banner => overlay.add_screen(None, &qh),
+            Surface::Cover => {
+                for global in globals.contents().clone_list() {
+                    if global.interface == wl_output::WlOutput::interface().name {
+                        let output = globals.registry().bind::<wl_output::WlOutput, _, _>(
+                            global.name,
+                            global.version.min(4),
+                            &qh,
+                            global.name,
+                        );
+                        overlay.new_outputs.push((global.name, output));
+                    }
+                }
+            }
+        }
+
+        let started = Instant::now();
+        let seconds = seconds.max(1);
+        let canvas = Canvas {
+            font: &self.font,
+            look,
+            title,
+            postpone_label,
+        };
+        loop {
+            let elapsed = started.elapsed().as_secs();
+            if elapsed >= seconds || overlay.postponed {
+                break;
+            }
+            for (name, output) in std::mem::take(&mut overlay.new_outputs) {
+                overlay.add_screen(Some((name, output)), &qh);
+            }
+
+            let left = look.shown_left(seconds - elapsed);
+            let progress = seconds.saturating_sub(left) as f32 / seconds as f32;
+            for screen in &mut overlay.screens {
+                screen.draw(&canvas, &text(left), progress, &overlay.shm, &qh)?;
+            }
+            let next = started + Duration::from_secs(elapsed + 1);
+            dispatch_until(&mut queue, &mut overlay, next)?;
+        }
+
+        for screen in overlay.screens.drain(..) {
+            screen.destroy(overlay.layer_shell.version());
+        }
+        queue.flush()?;
+        Ok(overlay.postponed)
+    }
+}
+
+/// Handle events until `deadline`, or until the overlay is postponed
+fn dispatch_until(
+    queue: &mut EventQueue<Overlay>,
+    overlay: &mut Overlay,
+    deadline: Instant,
+) -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        queue.dispatch_pending(overlay)?;
+        queue.flush()?;
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() || overlay.postponed {
+            return Ok(());
+        }
+        let Some(guard) = queue.prepare_read() else {
+            continue;
+        };
+        let mut poll = libc::pollfd {
+            fd: guard.connection_fd().as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `poll` points at one valid pollfd for the duration of the call
+        let ready = unsafe { libc::poll(&mut poll, 1, left.as_millis().max(1) as i32) };
+        if ready > 0 {
+            guard.read()?;
+        }
+    }
+}
+
+struct Overlay {
+    compositor: wl_compositor::WlCompositor,
+    shm: wl_shm::WlShm,
+    layer_shell: zwlr_layer_shell_v1::ZwlrLayerShellV1,
+    surface: Surface,
+    screens: Vec<Screen>,
+    /// Outputs to cover at the next frame, by their global name
+    new_outputs: Vec<(u32, wl_output::WlOutput)>,
+    /// Surface under the pointer and where on it
+    pointer: Option<(wl_surface::WlSurface, f64, f64)>,
+    postpone: bool,
+    postponed: bool,
+}
+
+impl Overlay {
+    fn add_screen(&mut self, output: Option<(u32, wl_output::WlOutput)>, qh: &QueueHandle<Self>) {
+        use zwlr_layer_surface_v1::{Anchor, KeyboardInteractivity};
+
+        let surface = self.compositor.create_surface(qh, ());
+        let layer = self.layer_shell.get_layer_surface(
+            &surface,
+            output.as_ref().map(|(_, output)| output),
+            zwlr_layer_shell_v1::Layer::Overlay,
+            "szmer".to_string(),
+            qh,
+            (),
+        );
+        match self.surface {
+            Surface::Banner => {
+                let (width, height) = BANNER_SIZE;
+                layer.set_anchor(Anchor::Top);
+                layer.set_size(width, height);
+                layer.set_margin(48, 0, 0, 0);
+                // Typing goes on while the banner counts down
+                if self.layer_shell.version() >= 4 {
+                    layer.set_keyboard_interactivity(KeyboardInteractivity::OnDemand);
+                }
+            }
+            Surface::Cover => {
+                layer.set_anchor(Anchor::Top | Anchor::Bottom | Anchor::Left | Anchor::Right);
+                layer.set_size(0, 0);
+                layer.set_exclusive_zone(-1);
+                if self.postpone {
+                    layer.set_keyboard_interactivity(KeyboardInteractivity::Exclusive);
+                }
+            }
+        }
+        surface.commit();
+
+        self.screens.push(Screen {
+            output,
+            surface,
+            layer,
+            size: None,
+            buffers: Vec::new(),
+            button: None,
+        });
+    }
+
+    fn remove_output(&mut self, name: u32) {
+        let version = self.layer_shell.version();
+        let (gone, kept) = std::mem::take(&mut self.screens)
+            .into_iter()
+            .partition(|screen| screen.output.as_ref().is_some_and(|(n, _)| *n == name));
+        self.screens = kept;
+        for screen in gone {
+            screen.destroy(version);
+        }
+        self.new_outputs.retain(|(n, _)| *n != name);
+    }
+}
+
+/// One layer surface with the buffers drawn into it
+struct Screen {
+    output: Option<(u32, wl_output::WlOutput)>,
+    surface: wl_surface::WlSurface,
+    layer: zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+    /// Set by the compositor, nothing is drawn before it
+    size: Option<(u32, u32)>,
+    buffers: Vec<Buffer>,
+    /// Where the Postpone button was drawn
+    button: Option<Rect>,
+}
+
+impl Screen {
+    fn draw(
+        &mut self,
+        canvas: &Canvas,
+        text: &str,
+        progress: f32,
+        shm: &wl_shm::WlShm,
+        qh: &QueueHandle<Overlay>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some((width, height)) = self.size else {
+            return Ok(());
+        };
+
+        // Buffers of an older size go once the compositor is done with them
+        self.buffers.retain(|buffer| {
+            let keep = buffer.size == (width, height) || buffer.busy.load(Ordering::Relaxed);
+            if !keep {
+                buffer.wl.destroy();
+            }
+            keep
+        });
+        let free = self.buffers.iter().position(|buffer| {
+            buffer.size == (width, height) && !buffer.busy.load(Ordering::Relaxed)
+        });
+        let index = match free {
+            Some(index) => index,
+            // Two are enough, a frame is drawn once a second
+            None if self.buffers.len() < 2 => {
+                self.buffers.push(Buffer::new(shm, width, height, qh)?);
+                self.buffers.len() - 1
+            }
+            None => return Ok(()),
+        };
+
+        let (pixels, button) = canvas.render(width, height, text, progress);
+        self.button = button;
+        let buffer = &self.buffers[index];
+        buffer.file.write_all_at(&pixels, 0)?;
+        buffer.busy.store(true, Ordering::Relaxed);
+        self.surface.attach(Some(&buffer.wl), 0, 0);
+        self.surface.damage(0, 0, width as i32, height as i32);
+        self.surface.commit();
+        Ok(())
+    }
+
+    fn destroy(self, layer_shell_version: u32) {
+        for buffer in &self.buffers {
+            buffer.wl.destroy();
+        }
+        if layer_shell_version >= 3 {
+            self.layer.destroy();
+        }
+        self.surface.destroy();
+        if let Some((_, output)) = self.output {
+            if output.version() >= 3 {
+                output.release();
+            }
+        }
+    }
+}
+
+/// Shared memory the compositor reads a frame from
+struct Buffer {
+    wl: wl_buffer::WlBuffer,
+    file: File,
+    size: (u32, u32),
+    /// Set while the compositor may still read it
+    busy: Arc<AtomicBool>,
+}
+
+impl Buffer {
+    fn new(
+        shm: &wl_shm::WlShm,
+        width: u32,
+        height: u32,
+        qh: &QueueHandle<Overlay>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let len = width as usize * height as usize * 4;
+        let file = shm_file(len)?;
+        let pool = shm.create_pool(file.as_fd(), i32::try_from(len)?, qh, ());
+        let busy = Arc::new(AtomicBool::new(false));
+        let wl = pool.create_buffer(
+            0,
+            width as i32,
+            height as i32,
+            width as i32 * 4,
+            wl_shm::Format::Argb8888,
+            qh,
+            busy.clone(),
+        );
+        pool.destroy();
+        Ok(Self {
+            wl,
+            file,
+            size: (width, height),
+            busy,
+        })
+    }
+}
+
+/// An unlinked file of `len` bytes in the runtime directory, shared with the compositor
+fn shm_file(len: usize) -> Result<File, Box<dyn std::error::Error>> {
+    static COUNT: AtomicU32 = AtomicU32::new(0);
+
+    let dir = timestamp::get_runtime_dir()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "overlay-{}-{}.shm",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    fs::remove_file(&path)?;
+    file.set_len(len as u64)?;
+    file.flush()?;
+    Ok(file)
+}
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for Overlay {
+    fn event(
+        state: &mut Self,
+        registry: &wl_registry::WlRegistry,
+        event: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        if state.surface != Surface::Cover {
+            return;
+        }
+        match event {
+            wl_registry::Event::Global {
+                name,
+                interface,
+                version,
+            } if interface == wl_output::WlOutput::interface().name => {
+                let output =
+                    registry.bind::<wl_output::WlOutput, _, _>(name, version.min(4), qh, name);
+                state.new_outputs.push((name, output));
+            }
+            wl_registry::Event::GlobalRemove { name } => state.remove_output(name),
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, ()> for Overlay {
+    fn event(
+        state: &mut Self,
+        layer: &zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+        event: zwlr_layer_surface_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_layer_surface_v1::Event::Configure {
+                serial,
+                width,
+                height,
+            } => {
+                layer.ack_configure(serial);
+                if let Some(screen) = state.screens.iter_mut().find(|s| s.layer == *layer) {
+                    let (banner_width, banner_height) = BANNER_SIZE;
+                    screen.size = Some((
+                        if width == 0 { banner_width } else { width },
+                        if height == 0 { banner_height } else { height },
+                    ));
+                }
+            }
+            // The output went away, or the compositor wants the surface gone
+            zwlr_layer_surface_v1::Event::Closed => {
+                let version = state.layer_shell.version();
+                if let Some(index) = state.screens.iter().position(|s| s.layer == *layer) {
+                    state.screens.remove(index).destroy(version);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<wl_buffer::WlBuffer, Arc<AtomicBool>> for Overlay {
+    fn event(
+        _: &mut Self,
+        _: &wl_buffer::WlBuffer,
+        event: wl_buffer::Event,
+        busy: &Arc<AtomicBool>,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wl_buffer::Event::Release = event {
+            busy.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Dispatch<wl_seat::WlSeat, ()> for Overlay {
+    fn event(
+        _: &mut Self,
+        seat: &wl_seat::WlSeat,
+        event: wl_seat::Event,
+        _: &(),
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        if let wl_seat::Event::Capabilities {
+            capabilities: WEnum::Value(capabilities),
+        } = event
+        {
+            if capabilities.contains(wl_seat::Capability::Keyboard) {
+                seat.get_keyboard(qh, ());
+            }
+            if capabilities.contains(wl_seat::Capability::Pointer) {
+                seat.get_pointer(qh, ());
+            }
+        }
+    }
+}
+
+impl Dispatch<wl_keyboard::WlKeyboard, ()> for Overlay {
+    fn event(
+        state: &mut Self,
+        _: &wl_keyboard::WlKeyboard,
+        event: wl_keyboard::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wl_keyboard::Event::Key {
+            key: KEY_ESC,
+            state: WEnum::Value(wl_keyboard::KeyState::Pressed),
+            ..
+        } = event
+        {
+            state.postponed |= state.postpone;
+        }
+    }
+}
+
+impl Dispatch<wl_pointer::WlPointer, ()> for Overlay {
+    fn event(
+        state: &mut Self,
+        _: &wl_pointer::WlPointer,
+        event: wl_pointer::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            wl_pointer::Event::Enter {
+                surface,
+                surface_x,
+                surface_y,
+                ..
+            } => state.pointer = Some((surface, surface_x, surface_y)),
+            wl_pointer::Event::Motion {
+                surface_x,
+                surface_y,
+                ..
+            } => {
+                if let Some((_, x, y)) = &mut state.pointer {
+                    (*x, *y) = (surface_x, surface_y);
+                }
+            }
+            wl_pointer::Event::Leave { .. } => state.pointer = None,
+            wl_pointer::Event::Button {
+                state: WEnum::Value(wl_pointer::ButtonState::Pressed),
+                ..
+            } => {
+                let Some((surface, x, y)) = &state.pointer else {
+                    return;
+                };
+                let clicked = state
+                    .screens
+                    .iter()
+                    .filter(|screen| screen.surface == *surface)
+                    .filter_map(|screen| screen.button)
+                    .any(|button| button.contains(*x as i32, *y as i32));
+                state.postponed |= clicked && state.postpone;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<wl_output::WlOutput, u32> for Overlay {
+    fn event(
+        _: &mut Self,
+        _: &wl_output::WlOutput,
+        _: wl_output::Event,
+        _: &u32,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(Overlay: wl_compositor::WlCompositor);
+delegate_noop!(Overlay: wl_shm_pool::WlShmPool);
+delegate_noop!(Overlay: zwlr_layer_shell_v1::ZwlrLayerShellV1);
+delegate_noop!(Overlay: ignore wl_shm::WlShm);
+delegate_noop!(Overlay: ignore wl_surface::WlSurface);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Rect {
+    fn contains(&self, x: i32, y: i32) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+}
+
+/// What every frame shows, drawn at the size of each surface
+struct Canvas<'a> {
+    font: &'a FontVec,
+    look: &'a Look,
+    title: &'a str,
+    postpone_label: Option<&'a str>,
+}
+
+impl Canvas<'_> {
+    /// ARGB pixels of a frame, and where its Postpone button is
+    fn render(
+        &self,
+        width: u32,
+        height: u32,
+        text: &str,
+        progress: f32,
+    ) -> (Vec<u8>, Option<Rect>) {
+        let background = rgb(self.look.background);
+        let color = rgb(&self.look.text_color);
+        let mut pixels = Pixels {
+            width: width as i32,
+            height: height as i32,
+            data: [background[2], background[1], background[0], 0xff]
+                .repeat((width * height) as usize),
+        };
+
+        // Points to pixels, smaller on the banner so the text fits
+        let mut size = self.look.font_size as f32 * 4.0 / 3.0;
+        if (width, height) == BANNER_SIZE {
+            size = size.min(22.0);
+        }
+        let max_width = width as f32 * 0.8;
+        let title_lines = wrap(self.font, size * 1.25, self.title, max_width);
+        let text_lines = wrap(self.font, size, text, max_width);
+        let bar = self.look.countdown == Countdown::Bar;
+
+        let line_height = |size: f32| self.font.as_scaled(PxScale::from(size)).height() * 1.2;
+        let mut block = title_lines.len() as f32 * line_height(size * 1.25)
+            + text_lines.len() as f32 * line_height(size);
+        if bar {
+            block += size;
+        }
+        let mut y = (height as f32 - block) / 2.0;
+        for line in &title_lines {
+            pixels.text(self.font, size * 1.25, line, y, color);
+            y += line_height(size * 1.25);
+        }
+        for line in &text_lines {
+            pixels.text(self.font, size, line, y, color);
+            y += line_height(size);
+        }
+        if bar {
+            let track = Rect {
+                x: (width as f32 * 0.2) as i32,
+                y: (y + size / 2.0) as i32,
+                width: (width as f32 * 0.6) as i32,
+                height: (size / 4.0).max(4.0) as i32,
+            };
+            pixels.fill(track, color, 0.25);
+            pixels.fill(
+                Rect {
+                    width: (track.width as f32 * progress) as i32,
+                    ..track
+                },
+                color,
+                1.0,
+            );
+        }
+
+        let button = self.postpone_label.map(|label| {
+            let label_size = size.min(24.0);
+            let label_width = text_width(self.font, label_size, label);
+            let button = Rect {
+                width: label_width as i32 + (label_size * 2.0) as i32,
+                height: (label_size * 2.0) as i32,
+                x: (width as i32 - (label_width + label_size * 2.0) as i32) / 2,
+                y: height as i32 - (label_size * 3.0) as i32,
+            };
+            pixels.fill(button, color, 0.15);
+            let label_top =
+                button.y as f32 + (button.height as f32 - line_height(label_size)) / 2.0;
+            pixels.text(self.font, label_size, label, label_top, color);
+            button
+        });
+        (pixels.data, button)
+    }
+}
+
+/// A frame being drawn, 4 bytes per pixel as `Argb8888` has them in memory
+struct Pixels {
+    width: i32,
+    height: i32,
+    data: Vec<u8>,
+}
+
+impl Pixels {
+    /// Blend `color` into one pixel, `alpha` from 0 to 1
+    fn blend(&mut self, x: i32, y: i32, color: [u8; 3], alpha: f32) {
+        if !(0..self.width).contains(&x) || !(0..self.height).contains(&y) {
+            return;
+        }
+        let at = ((y * self.width + x) * 4) as usize;
+        // Bytes are blue, green, red, alpha
+        for (channel, value) in [color[2], color[1], color[0]].into_iter().enumerate() {
+            let old = self.data[at + channel] as f32;
+            self.data[at + channel] = (old + (value as f32 - old) * alpha.clamp(0.0, 1.0)) as u8;
+        }
+    }
+
+    fn fill(&mut self, rect: Rect, color: [u8; 3], alpha: f32) {
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                self.blend(x, y, color, alpha);
+            }
+        }
+    }
+
+    /// Draw one line of text centered across the frame, its top at `top`
+    fn text(&mut self, font: &FontVec, size: f32, line: &str, top: f32, color: [u8; 3]) {
+        let scaled = font.as_scaled(PxScale::from(size));
+        let mut x = (self.width as f32 - text_width(font, size, line)) / 2.0;
+        let baseline = top + scaled.ascent();
+        let mut previous = None;
+        for c in line.chars() {
+            let id = scaled.glyph_id(c);
+            if let Some(previous) = previous {
+                x += scaled.kern(previous, id);
+            }
+            let glyph = id.with_scale_and_position(size, ab_glyph::point(x, baseline));
+            x += scaled.h_advance(id);
+            previous = Some(id);
+            let Some(outline) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outline.px_bounds();
+            outline.draw(|gx, gy, coverage| {
+                self.blend(
+                    bounds.min.x as i32 + gx as i32,
+                    bounds.min.y as i32 + gy as i32,
+                    color,
+                    coverage,
+                );
+            });
+        }
+    }
+}
+
+fn text_width(font: &FontVec, size: f32, text: &str) -> f32 {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut previous = None;
+    let mut width = 0.0;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            width += scaled.kern(previous, id);
+        }
+        width += scaled.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+/// Break `text` into lines no wider than `max_width`, at spaces
+fn wrap(font: &FontVec, size: f32, text: &str, max_width: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if text_width(font, size, &format!("{line} {word}")) <= max_width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+/// Channels of a `#rrggbb` or `#rgb` color, black when it isn't one
+fn rgb(hex: &str) -> [u8; 3] {
+    let hex = hex.trim_start_matches('#');
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).unwrap_or(0);
+    match hex.len() {
+        3 => {
+            let short: Vec<u8> = hex.chars().map(|c| channel(&c.to_string()) * 17).collect();
+            [short[0], short[1], short[2]]
+        }
+        6 if hex.is_ascii() => [
+            channel(&hex[0..2]),
+            channel(&hex[2..4]),
+            channel(&hex[4..6]),
+        ],
+        _ => [0, 0, 0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OverlayConfig;
+
+    /// A font the test machine has, the test is skipped without one
+    fn font() -> Option<FontVec> {
+        let output = std::process::Command::new("fc-match")
+            .args(["--format=%{file}", "sans-serif"])
+            .output()
+            .ok()?;
+        let path = String::from_utf8(output.stdout).ok()?;
+        FontVec::try_from_vec(fs::read(path).ok()?).ok()
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(rgb("#ffcc00"), [0xff, 0xcc, 0x00]);
+        assert_eq!(rgb("#fc0"), [0xff, 0xcc, 0x00]);
+        assert_eq!(rgb("blue"), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_render_draws_text_and_button() {
+        let Some(font) = font() else {
+            return;
+        };
+        let look = Look::new(&OverlayConfig::default());
+        let canvas = Canvas {
+            font: &font,
+            look: &look,
+            title: "Time for a Break!",
+            postpone_label: Some("Postpone"),
+        };
+
+        let (pixels, button) = canvas.render(800, 600, "Look away from the screen (4:59)", 0.0);
+        assert_eq!(pixels.len(), 800 * 600 * 4);
+        // The corner is background, Adwaita dark's, and fully opaque
+        assert_eq!(pixels[..4], [0x24, 0x24, 0x24, 0xff]);
+        // The middle of the screen has white text on it
+        let middle = &pixels[250 * 800 * 4..350 * 800 * 4];
+        assert!(middle
+            .chunks(4)
+            .any(|pixel| pixel[..3] == [0xff, 0xff, 0xff]));
+
+        let button = button.unwrap();
+        assert!(button.contains(400, button.y + 1));
+        assert!(button.y + button.height < 600);
+    }
+
+    #[test]
+    fn test_wrap_keeps_lines_in_width() {
+        let Some(font) = font() else {
+            return;
+        };
+        let text = "Stand up, lace your fingers and reach for the ceiling for ten seconds";
+        let lines = wrap(&font, 20.0, text, 200.0);
+        assert!(lines.len() > 1);
+        assert!(lines
+            .iter()
+            .all(|line| text_width(&font, 20.0, line) <= 200.0));
+        assert_eq!(lines.join(" "), text);
+    }
+}
@@ -0,0 +1,538 @@
+use crate::clock::{Clock, SystemClock};
+use crate::command::ProcessRunner;
+use crate::config::Config;
+use crate::control::{self, Request, Response};
+use crate::git;
+use crate::time::{self, start_of_today};
+use crate::timestamp;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::time::Duration;
+
+/// Largest request body accepted, control requests are tiny
+const MAX_BODY_BYTES: usize = 4096;
+
+/// How long a client may take to send its request before it's dropped
+///
+/// Requests are handled one at a time, so an idle connection would hold up
+/// everyone else.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Commands `POST /api/<command>` relays, the ones the page needs
+///
+/// Shutting down, reloading or putting the daemon to sleep stays with the
+/// control socket.
+const API_COMMANDS: &[&str] = &["status", "stop", "resume", "snooze", "trigger_break"];
+
+const INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>szmer</title>
+<style>
+  body { font-family: system-ui, sans-serif; max-width: 28rem; margin: 3rem auto; padding: 0 1rem; text-align: center; }
+  #countdown { font-size: 3rem; font-variant-numeric: tabular-nums; margin: 1rem 0; }
+  button { font-size: 1rem; padding: .5rem 1rem; margin: .25rem; }
+  #message { min-height: 1.5rem; color: #666; }
+  @media (prefers-contrast: more) {
+    body { background: #fff; color: #000; }
+    #message { color: #000; }
+    button { background: #fff; color: #000; border: 2px solid #000; }
+  }
+</style>
+</head>
+<body>
+<h1>szmer</h1>
+<div id="state">Loading…</div>
+<div id="countdown">--:--</div>
+<div>
+  <button onclick="send('stop')">Pause</button>
+  <button onclick="send('resume')">Resume</button>
+  <button onclick="send('snooze', {seconds: 600})">Snooze 10 min</button>
+  <button onclick="send('trigger_break')">Break now</button>
+</div>
+<p id="message"></p>
+<script>
+let nextFire = null;
+
+async function refresh() {
+  const res = await fetch('/api/status');
+  const body = await res.json();
+  if (!res.ok) {
+    document.getElementById('state').textContent = body.message;
+    nextFire = null;
+    return;
+  }
+  document.getElementById('state').textContent = body.paused ? '⏸ Paused' : '▶ Active';
+  nextFire = body.paused || !body.next_fire ? null : new Date(body.next_fire);
+}
+
+// A countdown changing every second is constant motion, so show minutes instead
+const reducedMotion = matchMedia('(prefers-reduced-motion: reduce)').matches;
+
+function tick() {
+  const el = document.getElementById('countdown');
+  if (!nextFire) { el.textContent = '--:--'; return; }
+  const left = Math.max(0, Math.round((nextFire - Date.now()) / 1000));
+  const m = Math.floor(left / 60), s = left % 60;
+  el.textContent = reducedMotion ? Math.ceil(left / 60) + ' min' : m + ':' + String(s).padStart(2, '0');
+}
+
+async function send(command, body) {
+  const res = await fetch('/api/' + command, { method: 'POST', body: JSON.stringify(body || {}) });
+  document.getElementById('message').textContent = (await res.json()).message;
+  refresh();
+}
+
+refresh();
+setInterval(refresh, 15000);
+setInterval(tick, 1000);
+</script>
+</body>
+</html>
+"#;
+
+/// Today's breaks as shown to whoever has the share link
+#[derive(Debug, Serialize)]
+struct ShareSummary {
+    date: NaiveDate,
+    reminders_today: usize,
+    last_reminder: Option<DateTime<Local>>,
+    paused: bool,
+    /// Only present when git repos are configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commits_today: Option<usize>,
+}
+
+/// Serve a small status page and a REST API mirroring the control socket
+///
+/// `GET /` shows status, a countdown and pause/snooze buttons.
+/// `GET /api/status` and `POST /api/<command>` accept the same JSON as the
+/// control socket, with the command taken from the path, for the commands
+/// in `API_COMMANDS`. Control requests are relayed to the running daemon and
+/// only accepted from localhost, addressed to a loopback host and, when a
+/// browser sends an `Origin`, from the page itself. That keeps other web
+/// pages, and DNS rebinding, from driving the daemon through the browser.
+///
+/// `POST /notify`, `POST /pause` and `POST /resume` let other tools drive
+/// reminders with the token from `szmer api-token`, sent as a bearer token.
+///
+/// With `public` the server listens on every interface so the read-only
+/// `GET /share/<token>` page and the token-protected endpoints can be
+/// reached from other machines.
+///
+/// # Arguments
+/// * `port` - Port to listen on
+/// * `public` - Listen on all interfaces instead of localhost only
+pub fn serve(port: u16, public: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let host = if public { "0.0.0.0" } else { "127.0.0.1" };
+    let listener = TcpListener::bind((host, port))?;
+    println!("✓ Serving szmer on http://{host}:{port}");
+    if public {
+        println!("  Only the share page and the token-protected endpoints are reachable from other machines");
+    }
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Warning: Failed to accept connection: {e}");
+                continue;
+            }
+        };
+        if let Err(e) = handle(stream) {
+            eprintln!("Warning: Failed to handle request: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream) -> Result<(), Box<dyn std::error::Error>> {
+    let local = stream.peer_addr()?.ip().is_loopback();
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    let mut host = None;
+    let mut origin = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("host") {
+                host = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("origin") {
+                origin = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return respond_json(
+            &mut stream,
+            413,
+            &json!({ "message": "Request body too large" }),
+        );
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    match (method.as_str(), path.as_str()) {
+        ("GET", path) if path.starts_with("/share/") => {
+            share(&mut stream, &path["/share/".len()..])
+        }
+        ("POST", "/notify" | "/pause" | "/resume") => {
+            trigger(&mut stream, &path[1..], authorization.as_deref(), &body)
+        }
+        _ if !local || !same_site(host.as_deref(), origin.as_deref()) => {
+            respond_json(&mut stream, 403, &json!({ "message": "Forbidden" }))
+        }
+        ("GET", "/") => respond(&mut stream, 200, "text/html; charset=utf-8", INDEX_HTML),
+        ("GET", "/api/status") => relay(&mut stream, Ok(Request::Status)),
+        ("POST", path) if path.starts_with("/api/") => {
+            relay(&mut stream, build_request(&path["/api/".len()..], &body))
+        }
+        _ => respond_json(&mut stream, 404, &json!({ "message": "Not found" })),
+    }
+}
+
+/// Whether a request is addressed to this machine by a loopback name
+///
+/// `origin`, which browsers send with every `POST`, must be this page.
+fn same_site(host: Option<&str>, origin: Option<&str>) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    let loopback = name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback());
+
+    loopback && origin.is_none_or(|origin| origin == format!("http://{host}"))
+}
+
+/// Turn `POST /api/<command>` and its JSON body into a control request
+fn build_request(command: &str, body: &[u8]) -> Result<Request, String> {
+    if !API_COMMANDS.contains(&command) {
+        return Err(format!("Unknown command: {command}"));
+    }
+
+    let mut value = if body.iter().all(u8::is_ascii_whitespace) {
+        json!({})
+    } else {
+        serde_json::from_slice(body).map_err(|e| format!("Invalid JSON body: {e}"))?
+    };
+
+    let Value::Object(fields) = &mut value else {
+        return Err("Request body must be a JSON object".to_string());
+    };
+    fields.insert("command".to_string(), Value::String(command.to_string()));
+
+    serde_json::from_value(value).map_err(|e| format!("Invalid request: {e}"))
+}
+
+/// Body of `POST /pause`, a pause without `seconds` lasts until resumed
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PauseBody {
+    seconds: Option<u64>,
+}
+
+/// Relay a trigger endpoint to the daemon once its bearer token checks out
+fn trigger(
+    stream: &mut TcpStream,
+    action: &str,
+    authorization: Option<&str>,
+    body: &[u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let given = authorization.and_then(|value| value.strip_prefix("Bearer "));
+    let authorized = match (config.api.token.as_deref(), given) {
+        (Some(expected), Some(given)) => tokens_match(expected, given.trim()),
+        _ => false,
+    };
+    if !authorized {
+        return respond_json(
+            stream,
+            401,
+            &json!({ "message": "Missing or wrong token, see 'szmer api-token'" }),
+        );
+    }
+
+    relay(stream, trigger_request(action, body, Local::now()))
+}
+
+fn trigger_request(action: &str, body: &[u8], now: DateTime<Local>) -> Result<Request, String> {
+    match action {
+        "notify" => Ok(Request::TriggerBreak),
+        "resume" => Ok(Request::Resume),
+        "pause" => {
+            let pause: PauseBody = if body.iter().all(u8::is_ascii_whitespace) {
+                PauseBody::default()
+            } else {
+                serde_json::from_slice(body).map_err(|e| format!("Invalid JSON body: {e}"))?
+            };
+            let until = match pause.seconds {
+                Some(seconds) => Some(
+                    time::checked_add_seconds(now, seconds)
+                        .ok_or_else(|| format!("Can't pause for {seconds} seconds"))?,
+                ),
+                None => None,
+            };
+            Ok(Request::Stop { until })
+        }
+        _ => Err(format!("Unknown action: {action}")),
+    }
+}
+
+fn relay(
+    stream: &mut TcpStream,
+    request: Result<Request, String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let request = match request {
+        Ok(request) => request,
+        Err(message) => return respond_json(stream, 400, &json!({ "message": message })),
+    };
+
+    match control::send(&request)? {
+        Some(Response::Ok { message }) => respond_json(stream, 200, &json!({ "message": message })),
+        Some(Response::Status(status)) => respond_json(stream, 200, &serde_json::to_value(status)?),
+        Some(Response::Error { message }) => {
+            respond_json(stream, 409, &json!({ "message": message }))
+        }
+        None => respond_json(
+            stream,
+            503,
+            &json!({ "message": "szmer daemon is not running. Start it with 'szmer daemon'." }),
+        ),
+    }
+}
+
+/// Serve the read-only summary, as HTML or as JSON with a `.json` suffix
+fn share(stream: &mut TcpStream, token: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (token, as_json) = match token.strip_suffix(".json") {
+        Some(token) => (token, true),
+        None => (token, false),
+    };
+
+    let config = Config::load()?;
+    if !config
+        .share
+        .token
+        .as_deref()
+        .is_some_and(|expected| tokens_match(expected, token))
+    {
+        return respond_json(stream, 404, &json!({ "message": "Not found" }));
+    }
+
+    let summary = share_summary(&config, &SystemClock)?;
+    if as_json {
+        return respond_json(stream, 200, &serde_json::to_value(&summary)?);
+    }
+    respond(
+        stream,
+        200,
+        "text/html; charset=utf-8",
+        &render_share_page(&summary),
+    )
+}
+
+fn share_summary(
+    config: &Config,
+    clock: &dyn Clock,
+) -> Result<ShareSummary, Box<dyn std::error::Error>> {
+    let since = start_of_today(clock);
+    let history = timestamp::notification_history()?;
+    let repos = &config.integrations.git.repos;
+
+    Ok(ShareSummary {
+        date: since.date_naive(),
+        reminders_today: history.iter().filter(|at| **at >= since).count(),
+        last_reminder: history.last().copied(),
+        paused: config.paused,
+        commits_today: (!repos.is_empty())
+            .then(|| git::total_commits_since(&ProcessRunner, repos, since)),
+    })
+}
+
+fn render_share_page(summary: &ShareSummary) -> String {
+    let last = summary
+        .last_reminder
+        .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "never".to_string());
+    let commits = summary
+        .commits_today
+        .map(|count| format!("<br>Commits today: {count}"))
+        .unwrap_or_default();
+    let state = if summary.paused {
+        "⏸ Paused"
+    } else {
+        "▶ Active"
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex">
+<title>szmer breaks</title>
+<style>body {{ font-family: system-ui, sans-serif; max-width: 28rem; margin: 3rem auto; padding: 0 1rem; text-align: center; }}</style>
+</head>
+<body>
+<h1>Breaks on {date}</h1>
+<p style="font-size: 3rem; margin: 1rem 0">{count}</p>
+<p>break reminders today</p>
+<p>Reminders: {state}<br>Last reminder: {last}{commits}</p>
+</body>
+</html>
+"#,
+        date = summary.date,
+        count = summary.reminders_today,
+    )
+}
+
+/// Generate a random share or API token, 128 bits as hex
+pub fn new_token() -> String {
+    rand::random::<[u8; 16]>()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Compare tokens without bailing out at the first differing byte
+fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn respond_json(
+    stream: &mut TcpStream,
+    status: u16,
+    body: &Value,
+) -> Result<(), Box<dyn std::error::Error>> {
+    respond(stream, status, "application/json", &body.to_string())
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        _ => "Service Unavailable",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_request_without_body() {
+        assert!(matches!(
+            build_request("stop", b""),
+            Ok(Request::Stop { until: None })
+        ));
+    }
+
+    #[test]
+    fn test_build_request_with_fields() {
+        assert!(matches!(
+            build_request("snooze", br#"{"seconds": 600}"#),
+            Ok(Request::Snooze { seconds: 600 })
+        ));
+    }
+
+    #[test]
+    fn test_trigger_request_pauses_for_seconds() {
+        let now = Local::now();
+        assert!(matches!(
+            trigger_request("notify", b"", now),
+            Ok(Request::TriggerBreak)
+        ));
+        assert!(matches!(
+            trigger_request("pause", b"", now),
+            Ok(Request::Stop { until: None })
+        ));
+        assert!(matches!(
+            trigger_request("pause", br#"{"seconds": 1800}"#, now),
+            Ok(Request::Stop { until: Some(until) }) if until == now + chrono::Duration::seconds(1800)
+        ));
+        assert!(trigger_request("pause", br#"{"minutes": 30}"#, now).is_err());
+        assert!(trigger_request("pause", br#"{"seconds": 18446744073709551615}"#, now).is_err());
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc123", "abc124"));
+        assert!(!tokens_match("abc123", "abc"));
+    }
+
+    #[test]
+    fn test_build_request_rejects_unknown_and_streams() {
+        assert!(build_request("explode", b"").is_err());
+        assert!(build_request("events", b"").is_err());
+        assert!(build_request("shutdown", b"").is_err());
+        assert!(build_request("stop", b"[1]").is_err());
+    }
+
+    #[test]
+    fn test_same_site_only_allows_the_page_on_loopback() {
+        assert!(same_site(Some("127.0.0.1:7878"), None));
+        assert!(same_site(
+            Some("localhost:7878"),
+            Some("http://localhost:7878")
+        ));
+        assert!(same_site(Some("[::1]:7878"), Some("http://[::1]:7878")));
+        // Another page POSTing to the API
+        assert!(!same_site(
+            Some("127.0.0.1:7878"),
+            Some("https://example.com")
+        ));
+        // DNS rebinding keeps the attacker's host name
+        assert!(!same_site(Some("evil.example:7878"), None));
+        assert!(!same_site(None, None));
+    }
+}
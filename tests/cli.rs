@@ -0,0 +1,534 @@
+#![cfg(target_os = "linux")]
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Fake `systemctl` that logs its arguments and answers status queries
+///
+/// Set `FAKE_SYSTEMCTL_FAIL` to a subcommand such as `enable` to make it fail.
+const FAKE_SYSTEMCTL: &str = r#"#!/bin/sh
+echo "systemctl $*" >> "$HOME/commands.log"
+case "$*" in
+  *"$FAKE_SYSTEMCTL_FAIL"*)
+    if [ -n "$FAKE_SYSTEMCTL_FAIL" ]; then
+      echo "Failed to $FAKE_SYSTEMCTL_FAIL unit: Access denied" >&2
+      exit 1
+    fi ;;
+esac
+case "$*" in
+  *is-active*) echo active ;;
+  *NextElapseUSecRealtime*) echo "NextElapseUSecRealtime=1760970600000000" ;;
+esac
+"#;
+
+/// A throwaway HOME with a fake systemctl first on PATH
+struct Sandbox {
+    home: TempDir,
+}
+
+impl Sandbox {
+    fn new() -> Self {
+        let home = TempDir::new().unwrap();
+        let bin = home.path().join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::create_dir_all(home.path().join("run")).unwrap();
+
+        let systemctl = bin.join("systemctl");
+        fs::write(&systemctl, FAKE_SYSTEMCTL).unwrap();
+        fs::set_permissions(&systemctl, fs::Permissions::from_mode(0o755)).unwrap();
+
+        Self { home }
+    }
+
+    fn szmer(&self) -> Command {
+        let home = self.home.path();
+        let mut command = Command::cargo_bin("szmer").unwrap();
+        command
+            .env_clear()
+            .env("HOME", home)
+            .env("XDG_RUNTIME_DIR", home.join("run"))
+            .env(
+                "PATH",
+                format!("{}:/usr/bin:/bin", home.join("bin").display()),
+            )
+            .env("NO_COLOR", "1");
+        command
+    }
+
+    fn install(&self) {
+        self.szmer()
+            .args(["install", "--interval", "25", "--force"])
+            .assert()
+            .success();
+    }
+
+    /// Install a shell script as a plugin in the plugins directory
+    fn plugin(&self, name: &str, script: &str) {
+        let dir = self.home.path().join(".config/szmer/plugins");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn unit_path(&self, name: &str) -> PathBuf {
+        self.home.path().join(".config/systemd/user").join(name)
+    }
+
+    fn config(&self) -> toml_edit::Document<String> {
+        let path = self.home.path().join(".config/szmer/config.toml");
+        toml_edit::Document::parse(fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Every systemctl invocation so far, in order
+    fn commands(&self) -> Vec<String> {
+        fs::read_to_string(self.home.path().join("commands.log"))
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+}
+
+#[test]
+fn test_install_writes_units_and_enables_timer() {
+    let sandbox = Sandbox::new();
+
+    sandbox
+        .szmer()
+        .args(["install", "--interval", "25", "--force"])
+        .assert()
+        .success()
+        .stdout(contains("✓ Break reminder installed successfully!"));
+
+    let service = fs::read_to_string(sandbox.unit_path("szmer.service")).unwrap();
+    assert!(service.contains(" notify"));
+    let timer = fs::read_to_string(sandbox.unit_path("szmer.timer")).unwrap();
+    assert!(timer.contains("OnUnitActiveSec=1500"));
+
+    assert_eq!(
+        sandbox.config()["interval_seconds"].as_integer(),
+        Some(1500)
+    );
+    assert_eq!(
+        sandbox.config()["timewarrior"]["enabled"].as_bool(),
+        Some(false)
+    );
+    assert_eq!(
+        sandbox.commands(),
+        [
+            "systemctl --user daemon-reload",
+            "systemctl --user enable --now szmer.timer",
+        ]
+    );
+}
+
+#[test]
+fn test_install_twice_is_refused() {
+    let sandbox = Sandbox::new();
+    sandbox.install();
+
+    sandbox
+        .szmer()
+        .args(["install", "--interval", "25", "--force"])
+        .assert()
+        .code(1)
+        .stderr(contains("✗ Break reminder is already installed"))
+        .stderr(contains("hint: Run 'szmer uninstall' first"));
+}
+
+#[test]
+fn test_install_reports_systemctl_failure() {
+    let sandbox = Sandbox::new();
+
+    sandbox
+        .szmer()
+        .args(["install", "--interval", "25", "--force"])
+        .env("FAKE_SYSTEMCTL_FAIL", "enable")
+        .assert()
+        .code(1)
+        .stderr(contains(
+            "Failed to enable systemd timer: Failed to enable unit",
+        ))
+        .stderr(contains("hint: Run 'szmer doctor'"));
+}
+
+#[test]
+fn test_stop_and_resume_toggle_paused() {
+    let sandbox = Sandbox::new();
+    sandbox.install();
+
+    sandbox
+        .szmer()
+        .arg("stop")
+        .assert()
+        .success()
+        .stdout(contains("✓ Break reminders stopped."));
+    assert_eq!(sandbox.config()["paused"].as_bool(), Some(true));
+
+    sandbox
+        .szmer()
+        .arg("stop")
+        .assert()
+        .success()
+        .stdout(contains("already stopped"));
+
+    sandbox
+        .szmer()
+        .arg("resume")
+        .assert()
+        .success()
+        .stdout(contains("✓ Break reminders resumed."));
+    assert_eq!(sandbox.config()["paused"].as_bool(), Some(false));
+}
+
+#[test]
+fn test_json_config_moves_to_toml_on_save() {
+    let sandbox = Sandbox::new();
+    sandbox.install();
+    let dir = sandbox.home.path().join(".config/szmer");
+    fs::remove_file(dir.join("config.toml")).unwrap();
+    fs::write(
+        dir.join("config.json"),
+        r#"{"paused": false, "interval_seconds": 1200, "notification_sound": "bell"}"#,
+    )
+    .unwrap();
+
+    sandbox
+        .szmer()
+        .arg("stop")
+        .assert()
+        .success()
+        .stderr(contains("the old file is kept as"));
+
+    let config = sandbox.config();
+    assert_eq!(config["paused"].as_bool(), Some(true));
+    assert_eq!(config["interval_seconds"].as_integer(), Some(1200));
+    assert_eq!(config["notification_sound"].as_str(), Some("bell"));
+    assert!(!dir.join("config.json").exists());
+    assert!(dir.join("config.json.bak").exists());
+}
+
+#[test]
+fn test_stop_without_install_is_unavailable() {
+    let sandbox = Sandbox::new();
+
+    sandbox
+        .szmer()
+        .arg("stop")
+        .assert()
+        .code(69)
+        .stderr(contains("✗ Break reminder is not installed"));
+}
+
+#[test]
+fn test_status_reads_scheduler_state() {
+    let sandbox = Sandbox::new();
+
+    sandbox
+        .szmer()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(contains("Scheduler:    ✗ Not installed"));
+
+    sandbox.install();
+    sandbox.szmer().arg("stop").assert().success();
+
+    sandbox
+        .szmer()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(contains("Scheduler:    ✓ Running"))
+        .stdout(contains("Interval:     25 minutes"))
+        .stdout(contains("Paused"));
+    assert!(sandbox
+        .commands()
+        .contains(&"systemctl --user is-active szmer.timer".to_string()));
+}
+
+#[test]
+fn test_stats_counts_commits_in_configured_repos() {
+    let sandbox = Sandbox::new();
+    // Fake git with a reflog of two commits made just now
+    let git = sandbox.home.path().join("bin/git");
+    fs::write(
+        &git,
+        "#!/bin/sh\nnow=$(date +%s)\nprintf 'HEAD@{%s} commit: One\\nHEAD@{%s} commit: Two\\n' $now $now\n",
+    )
+    .unwrap();
+    fs::set_permissions(&git, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let output = sandbox.szmer().args(["stats", "--json"]).output().unwrap();
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["commits"], serde_json::Value::Null);
+
+    let repo = sandbox.home.path().join("src/app");
+    fs::create_dir_all(&repo).unwrap();
+    sandbox
+        .szmer()
+        .args(["config", "set", "integrations.git.repos"])
+        .arg(&repo)
+        .assert()
+        .success();
+
+    let output = sandbox.szmer().args(["stats", "--json"]).output().unwrap();
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["commits"], 2);
+    sandbox
+        .szmer()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(contains("Commits:         2"));
+}
+
+#[test]
+fn test_status_json() {
+    let sandbox = Sandbox::new();
+
+    sandbox
+        .szmer()
+        .args(["status", "--json"])
+        .assert()
+        .success()
+        .stdout(contains(r#""installed": false"#));
+
+    sandbox.install();
+    sandbox.szmer().arg("stop").assert().success();
+
+    let output = sandbox.szmer().args(["--json", "status"]).output().unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["state"], "paused");
+    assert_eq!(status["interval_seconds"], 1500);
+    assert_eq!(status["scheduler"], "running");
+}
+
+#[test]
+fn test_uninstall_disables_timer_and_removes_units() {
+    let sandbox = Sandbox::new();
+    sandbox.install();
+
+    sandbox
+        .szmer()
+        .arg("uninstall")
+        .assert()
+        .success()
+        .stdout(contains("✓ Break reminder uninstalled successfully!"));
+
+    assert!(!sandbox.unit_path("szmer.service").exists());
+    assert!(!sandbox.unit_path("szmer.timer").exists());
+    assert!(sandbox.commands().ends_with(&[
+        "systemctl --user disable --now szmer.timer".to_string(),
+        "systemctl --user daemon-reload".to_string(),
+    ]));
+
+    sandbox
+        .szmer()
+        .arg("uninstall")
+        .assert()
+        .success()
+        .stdout(contains("not currently installed"));
+}
+
+#[test]
+fn test_closed_gate_skips_notify() {
+    let sandbox = Sandbox::new();
+    sandbox.plugin(
+        "focus",
+        r#"case "$1" in
+  describe) echo '{"roles":["gate"]}' ;;
+  gate) echo '{"open":false,"detail":"in a meeting"}' ;;
+esac"#,
+    );
+    sandbox
+        .szmer()
+        .args(["config", "set", "plugins.enabled", "focus"])
+        .assert()
+        .success();
+
+    sandbox
+        .szmer()
+        .arg("notify")
+        .assert()
+        .code(75)
+        .stderr(contains(
+            "Skipping notification: plugin:focus: in a meeting",
+        ));
+}
+
+#[test]
+fn test_slow_gate_does_not_delay_notify() {
+    let sandbox = Sandbox::new();
+    sandbox.plugin(
+        "slow",
+        r#"case "$1" in
+  describe) echo '{"roles":["gate"]}' ;;
+  gate) sleep 30; echo '{"open":false,"detail":"too late"}' ;;
+esac"#,
+    );
+    sandbox
+        .szmer()
+        .args(["config", "set", "plugins.enabled", "slow"])
+        .assert()
+        .success();
+    sandbox
+        .szmer()
+        .args(["config", "set", "delivery.deadline", "1"])
+        .assert()
+        .success();
+
+    sandbox
+        .szmer()
+        .args(["config", "set", "headless.mode", "always"])
+        .assert()
+        .success();
+
+    let started = Instant::now();
+    sandbox
+        .szmer()
+        .arg("notify")
+        .assert()
+        .success()
+        .stderr(contains("Gate plugin:slow ignored: no answer within 1s"));
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_invalid_config_lists_every_problem_until_fixed() {
+    let sandbox = Sandbox::new();
+    let dir = sandbox.home.path().join(".config/szmer");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("config.toml"),
+        "interval_seconds = 0\n\n[schedule]\nwork_hours = \"9-5\"\n",
+    )
+    .unwrap();
+
+    sandbox
+        .szmer()
+        .args(["config", "show"])
+        .assert()
+        .code(78)
+        .stderr(contains("has 2 values szmer can't use"))
+        .stderr(contains(
+            "interval: 0 seconds is out of range, it must be from 1 minute to 24 hours",
+        ))
+        .stderr(contains(
+            "Fix: szmer config set schedule.work_hours 09:00-17:30",
+        ));
+
+    // Setting a value still works, and drops the one that couldn't be read
+    sandbox
+        .szmer()
+        .args(["config", "set", "interval", "30m"])
+        .assert()
+        .success();
+    sandbox
+        .szmer()
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout(contains("is valid"));
+}
+
+#[test]
+fn test_config_unset_and_reset() {
+    let sandbox = Sandbox::new();
+    for (key, value) in [("interval", "45m"), ("schedule.days", "mon-fri")] {
+        sandbox
+            .szmer()
+            .args(["config", "set", key, value])
+            .assert()
+            .success();
+    }
+
+    sandbox
+        .szmer()
+        .args(["config", "unset", "interval"])
+        .assert()
+        .success()
+        .stdout(contains("interval is back to its default"));
+    let config = sandbox.config();
+    assert_eq!(config["interval_seconds"].as_integer(), Some(3600));
+    assert!(config["schedule"].get("days").is_some());
+
+    // Without a terminal to ask on, a reset has to be confirmed up front
+    sandbox
+        .szmer()
+        .args(["config", "reset"])
+        .assert()
+        .failure()
+        .stderr(contains("--yes"));
+    sandbox
+        .szmer()
+        .args(["config", "reset", "--yes"])
+        .assert()
+        .success();
+    assert!(sandbox.config().get("schedule").is_none());
+    assert!(sandbox
+        .home
+        .path()
+        .join(".config/szmer/config.toml.reset.bak")
+        .exists());
+}
+
+#[test]
+fn test_config_set_overlay_accessibility() {
+    let sandbox = Sandbox::new();
+    for (key, value) in [
+        ("overlay.theme", "high-contrast"),
+        ("overlay.motion", "reduced"),
+    ] {
+        sandbox
+            .szmer()
+            .args(["config", "set", key, value])
+            .assert()
+            .success();
+    }
+    let config = sandbox.config();
+    assert_eq!(config["overlay"]["theme"].as_str(), Some("high-contrast"));
+    assert_eq!(config["overlay"]["motion"].as_str(), Some("reduced"));
+
+    sandbox
+        .szmer()
+        .args(["config", "set", "overlay.motion", "slow"])
+        .assert()
+        .failure()
+        .stderr(contains("'auto', 'full' or 'reduced'"));
+}
+
+#[test]
+fn test_config_keys_lists_what_set_takes() {
+    let sandbox = Sandbox::new();
+    sandbox
+        .szmer()
+        .args(["config", "keys"])
+        .assert()
+        .success()
+        .stdout(contains("break_duration"))
+        .stdout(contains("pomodoro.enabled"));
+
+    sandbox
+        .szmer()
+        .args(["config", "set", "break_duration", "10m"])
+        .assert()
+        .success();
+    assert_eq!(
+        sandbox.config()["break_duration_seconds"].as_integer(),
+        Some(600)
+    );
+
+    sandbox
+        .szmer()
+        .args(["config", "set", "volume", "11"])
+        .assert()
+        .code(64)
+        .stderr(contains("szmer config keys"));
+}
@@ -0,0 +1,42 @@
+# Generated by `szmer generate --format home-manager`
+{ pkgs, ... }:
+{
+  systemd.user.services.szmer = {
+    Unit.Description = "Szmer break reminder";
+    Service = {
+      Type = "oneshot";
+      ExecStart = "/usr/local/bin/szmer notify";
+    };
+  };
+
+  systemd.user.timers.szmer = {
+    Unit.Description = "Szmer break reminder timer";
+    Timer = {
+      OnBootSec = 1500;
+      OnUnitActiveSec = 1500;
+      Persistent = true;
+    };
+    Install.WantedBy = [ "timers.target" ];
+  };
+
+  xdg.configFile."szmer/config.toml".source = (pkgs.formats.toml { }).generate "szmer-config.toml" {
+    break_duration_seconds = 300;
+    delivery = {
+      chain = [
+        "desktop"
+      ];
+      gate_deadline_seconds = 5;
+    };
+    headless = {
+      method = "bell";
+      mirror_ssh = false;
+      mode = "auto";
+    };
+    interval_seconds = 3600;
+    paused = false;
+    timewarrior = {
+      enabled = false;
+    };
+    version = 1;
+  };
+}
@@ -0,0 +1,47 @@
+# Generated by `szmer generate --format nix`
+{ pkgs, ... }:
+let
+  szmerConfig = (pkgs.formats.toml { }).generate "szmer-config.toml" {
+    break_duration_seconds = 300;
+    delivery = {
+      chain = [
+        "desktop"
+      ];
+      gate_deadline_seconds = 5;
+    };
+    headless = {
+      method = "bell";
+      mirror_ssh = false;
+      mode = "auto";
+    };
+    interval_seconds = 3600;
+    paused = false;
+    timewarrior = {
+      enabled = false;
+    };
+    version = 1;
+  };
+in
+{
+  systemd.user.services.szmer = {
+    description = "Szmer break reminder";
+    serviceConfig = {
+      Type = "oneshot";
+      ExecStart = "/usr/local/bin/szmer notify";
+    };
+  };
+
+  systemd.user.timers.szmer = {
+    description = "Szmer break reminder timer";
+    wantedBy = [ "timers.target" ];
+    timerConfig = {
+      OnBootSec = "1500";
+      OnUnitActiveSec = "1500";
+      Persistent = true;
+    };
+  };
+
+  systemd.user.tmpfiles.rules = [
+    "L+ %h/.config/szmer/config.toml - - - - ${szmerConfig}"
+  ];
+}